@@ -0,0 +1,370 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::utils::{format_units, parse_base_units};
+
+/// Decimals of ETH, and of every `Amount`.
+pub const DECIMALS: usize = 18;
+
+/// Fixed-point parts the weights of [`Amount::split`] are scaled to; equal
+/// weights scale to equal integers, so they still split exactly evenly.
+const WEIGHT_SCALE: f64 = 1e18;
+
+/// An ETH amount held exactly as an integer number of wei, so sizing and
+/// slicing never drift the way `f64` sums do. `u128` covers about 3.4e20 ETH,
+/// the same range volatility configs accept for their limits. Serializes as a
+/// wei integer string, like the config's execution sizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(u128);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    pub const fn from_wei(wei: u128) -> Self {
+        Amount(wei)
+    }
+
+    pub const fn wei(self) -> u128 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Parses a decimal amount of a token with `decimals` decimals, e.g.
+    /// `"2500.5"` of a 6-decimal token, into the same quantity at 18 decimals.
+    pub fn parse_units(text: &str, decimals: usize) -> Result<Self, String> {
+        if decimals > DECIMALS {
+            return Err(format!("at most {} decimals are supported, got {}", DECIMALS, decimals));
+        }
+        let units = parse_base_units(text, decimals).map_err(|e| e.to_string())?;
+        let units: u128 = units.parse().map_err(|_| format!("amount is too large: {}", text))?;
+        units.checked_mul(10u128.pow((DECIMALS - decimals) as u32))
+            .map(Amount)
+            .ok_or_else(|| format!("amount is too large: {}", text))
+    }
+
+    /// Takes a float by way of its shortest decimal representation, so `0.1`
+    /// is exactly 10^17 wei; digits past 18 decimals are truncated. `None` for
+    /// negative, non-finite or out-of-range values.
+    pub fn from_eth_f64(eth: f64) -> Option<Self> {
+        if !eth.is_finite() || eth < 0.0 {
+            return None;
+        }
+        let decimal = eth.to_string();
+        let truncated = match decimal.split_once('.') {
+            Some((whole, fraction)) if fraction.len() > DECIMALS => format!("{}.{}", whole, &fraction[..DECIMALS]),
+            _ => decimal,
+        };
+        truncated.parse().ok()
+    }
+
+    /// The amount in ETH, for display and float-based models only.
+    pub fn as_eth_f64(self) -> f64 {
+        self.0 as f64 / 1e18
+    }
+
+    /// Base units of a token with `decimals` decimals, truncating any excess
+    /// precision (e.g. 1.23456789 at 6 decimals is `"1234567"`).
+    pub fn to_base_units(self, decimals: usize) -> String {
+        if decimals <= DECIMALS {
+            (self.0 / 10u128.pow((DECIMALS - decimals) as u32)).to_string()
+        } else if self.0 == 0 {
+            "0".to_string()
+        } else {
+            format!("{}{}", self.0, "0".repeat(decimals - DECIMALS))
+        }
+    }
+
+    /// `self * numerator / denominator`, rounded down, without overflowing
+    /// on the intermediate product. Saturates if the result does not fit.
+    pub fn mul_div(self, numerator: u64, denominator: u64) -> Amount {
+        let (numerator, denominator) = (numerator as u128, denominator as u128);
+        let (quotient, remainder) = (self.0 / denominator, self.0 % denominator);
+        // remainder < denominator, so both factors fit in 64 bits
+        let part = remainder * numerator / denominator;
+        Amount(quotient.saturating_mul(numerator).saturating_add(part))
+    }
+
+    /// Fixed-point product of two 18-decimal quantities, rounded down, e.g. an
+    /// ETH amount times a quote price. `None` if the result does not fit.
+    pub fn checked_mul(self, other: Amount) -> Option<Amount> {
+        let unit = 10u128.pow(DECIMALS as u32);
+        let ((a1, a0), (b1, b0)) = ((self.0 / unit, self.0 % unit), (other.0 / unit, other.0 % unit));
+        // a * b / unit = a1 * b1 * unit + a1 * b0 + a0 * b1 + a0 * b0 / unit, where
+        // a0, b0 < unit keeps every partial product within 128 bits
+        a1.checked_mul(b1)?.checked_mul(unit)?
+            .checked_add(a1.checked_mul(b0)?)?
+            .checked_add(a0.checked_mul(b1)?)?
+            .checked_add(a0 * b0 / unit)
+            .map(Amount)
+    }
+
+    /// Scales by a percentage and rounds down, as `amount * factor / 100` does on-chain.
+    pub fn scale_pct(self, pct: u64) -> Amount {
+        self.mul_div(pct, 100)
+    }
+
+    pub fn saturating_sub(self, other: Amount) -> Amount {
+        Amount(self.0.saturating_sub(other.0))
+    }
+
+    /// Splits the amount in proportion to `weights`. Each boundary is taken
+    /// from the cumulative weight, and the last part is whatever is left, so the
+    /// parts always sum to the amount exactly.
+    pub fn split(self, weights: &[f64]) -> Vec<Amount> {
+        let total: f64 = weights.iter().sum();
+        if weights.is_empty() || !total.is_finite() || total <= 0.0 {
+            return vec![Amount::ZERO; weights.len()];
+        }
+        let scaled: Vec<u64> = weights.iter()
+            .map(|weight| (weight.max(0.0) / total * WEIGHT_SCALE).round() as u64)
+            .collect();
+        let scaled_total: u64 = scaled.iter().sum();
+        if scaled_total == 0 {
+            return vec![Amount::ZERO; weights.len()];
+        }
+        let mut parts = Vec::with_capacity(weights.len());
+        let mut cumulative = 0;
+        let mut allocated = Amount::ZERO;
+        for weight in &scaled[..scaled.len() - 1] {
+            cumulative += weight;
+            let boundary = self.mul_div(cumulative, scaled_total);
+            parts.push(boundary - allocated);
+            allocated = boundary;
+        }
+        parts.push(self - allocated);
+        parts
+    }
+
+    /// ETH with exactly `precision` decimals, rounding half up, e.g. `"2.500000"`.
+    pub fn format_eth(self, precision: u8) -> String {
+        let precision = (precision as usize).min(DECIMALS);
+        let unit = 10u128.pow((DECIMALS - precision) as u32);
+        let rounded = match self.0 % unit {
+            remainder if remainder > 0 && remainder * 2 >= unit => (self.0 / unit).saturating_add(1),
+            _ => self.0 / unit,
+        };
+        let digits = format!("{:0>width$}", rounded, width = precision + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - precision);
+        if precision == 0 {
+            whole.to_string()
+        } else {
+            format!("{}.{}", whole, fraction)
+        }
+    }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+
+    fn add(self, other: Amount) -> Amount {
+        Amount(self.0 + other.0)
+    }
+}
+
+impl Sub for Amount {
+    type Output = Amount;
+
+    fn sub(self, other: Amount) -> Amount {
+        Amount(self.0 - other.0)
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Amount> for Amount {
+    fn sum<I: Iterator<Item = &'a Amount>>(iter: I) -> Amount {
+        iter.copied().sum()
+    }
+}
+
+/// Exact ETH without trailing zeros, e.g. `1.5`.
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = format_units(&self.0.to_string(), DECIMALS).map_err(|_| fmt::Error)?;
+        f.write_str(&text)
+    }
+}
+
+/// Parses an ETH decimal such as `2.5` or `.1`, with at most 18 decimals.
+impl FromStr for Amount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Amount::parse_units(s.trim(), DECIMALS)
+            .map_err(|e| format!("invalid ETH amount '{}': {}", s, e))
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let wei = String::deserialize(deserializer)?;
+        if wei.is_empty() || !wei.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(serde::de::Error::custom(format!("invalid wei amount '{}': expected an integer string", wei)));
+        }
+        wei.parse()
+            .map(Amount)
+            .map_err(|_| serde::de::Error::custom(format!("wei amount is too large: {}", wei)))
+    }
+}
+
+/// Serializes as a JSON number of ETH, for output that has always carried
+/// float amounts; only the display is a float, never the arithmetic.
+pub fn serialize_eth<S: Serializer>(amount: &Amount, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(amount.as_eth_f64())
+}
+
+/// Deserializes ETH written as a JSON number (`2.5`) or a decimal string (`"2.5"`).
+pub fn deserialize_eth<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Amount, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(f64),
+        Text(String),
+    }
+
+    match Raw::deserialize(deserializer)? {
+        Raw::Number(eth) => Amount::from_eth_f64(eth)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid ETH amount: {}", eth))),
+        Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn eth(text: &str) -> Amount {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn converts_between_decimals() {
+        assert_eq!(eth("1.5").wei(), 1_500_000_000_000_000_000);
+        assert_eq!(eth(".1").wei(), 100_000_000_000_000_000);
+        assert_eq!(eth("0.000000000000000001").wei(), 1);
+        assert_eq!(Amount::parse_units("2500.5", 6).unwrap(), eth("2500.5"));
+        assert_eq!(eth("2500.5").to_base_units(6), "2500500000");
+        assert_eq!(eth("1.23456789").to_base_units(6), "1234567");
+        assert_eq!(eth("12.9").to_base_units(0), "12");
+        assert_eq!(Amount::from_eth_f64(0.00000001).unwrap().to_base_units(8), "1");
+        assert_eq!(eth("1").to_base_units(20), "100000000000000000000");
+        assert_eq!(Amount::ZERO.to_base_units(24), "0");
+        assert_eq!(eth("2.500").to_string(), "2.5");
+        assert_eq!(Amount::ZERO.to_string(), "0");
+        for bad in ["", ".", "-1", "1e18", "1.2.3", "0.0000000000000000001", "999999999999999999999999"] {
+            assert!(bad.parse::<Amount>().is_err(), "accepted {:?}", bad);
+        }
+        assert!(Amount::parse_units("1.5", 0).is_err());
+        assert!(Amount::parse_units("1", 19).is_err());
+    }
+
+    #[test]
+    fn multiplies_at_fixed_point() {
+        assert_eq!(eth("2").checked_mul(eth("1500.5")), Some(eth("3001")));
+        assert_eq!(eth("0.1").checked_mul(eth("0.3")), Some(eth("0.03")));
+        assert_eq!(eth("4.999999999999999999").checked_mul(eth("3000.123456")).unwrap().to_base_units(6), "15000617279");
+        // Intermediate products past 128 bits are fine while the result fits
+        assert_eq!(eth("100000000").checked_mul(eth("1000000000")), Some(eth("100000000000000000")));
+        assert_eq!(Amount::from_wei(1).checked_mul(Amount::from_wei(1)), Some(Amount::ZERO));
+        assert_eq!(Amount::from_wei(u128::MAX).checked_mul(eth("2")), None);
+    }
+
+    #[test]
+    fn takes_floats_by_their_shortest_decimal() {
+        assert_eq!(Amount::from_eth_f64(0.1), Some(eth("0.1")));
+        assert_eq!(Amount::from_eth_f64(2.5), Some(eth("2.5")));
+        assert_eq!(Amount::from_eth_f64(0.0), Some(Amount::ZERO));
+        assert_eq!(Amount::from_eth_f64(1e-20), Some(Amount::ZERO));
+        assert_eq!(Amount::from_eth_f64(1e20).unwrap().to_string(), "100000000000000000000");
+        assert_eq!(Amount::from_eth_f64(-1.0), None);
+        assert_eq!(Amount::from_eth_f64(f64::NAN), None);
+        assert_eq!(Amount::from_eth_f64(1e30), None);
+        assert_eq!(eth("0.3").as_eth_f64(), 0.3);
+    }
+
+    #[test]
+    fn formats_with_fixed_precision() {
+        assert_eq!(eth("2.5").format_eth(6), "2.500000");
+        assert_eq!(eth("0.1234565").format_eth(6), "0.123457");
+        assert_eq!(eth("0.1234564").format_eth(6), "0.123456");
+        assert_eq!(eth("9.9999999").format_eth(2), "10.00");
+        assert_eq!(eth("1.5").format_eth(0), "2");
+        assert_eq!(eth("0.000000000000000001").format_eth(18), "0.000000000000000001");
+        assert_eq!(Amount::ZERO.format_eth(3), "0.000");
+    }
+
+    #[test]
+    fn scales_like_the_contract() {
+        // 1 wei short of 3 ETH: the on-chain floor drops the fraction of a wei
+        let amount = Amount::from_wei(2_999_999_999_999_999_999);
+        assert_eq!(amount.scale_pct(150).wei(), 4_499_999_999_999_999_998);
+        assert_eq!(eth("2").scale_pct(90), eth("1.8"));
+        assert_eq!(Amount::from_wei(u128::MAX).scale_pct(50).wei(), u128::MAX / 2);
+        assert_eq!(Amount::from_wei(u128::MAX).scale_pct(150).wei(), u128::MAX);
+    }
+
+    #[test]
+    fn splits_conserve_the_amount() {
+        let parts = eth("10").split(&[1.0, 1.0, 1.0]);
+        assert_eq!(parts, vec![Amount::from_wei(3_333_333_333_333_333_333), Amount::from_wei(3_333_333_333_333_333_333), Amount::from_wei(3_333_333_333_333_333_334)]);
+        assert_eq!(eth("1").split(&[3.0, 2.0, 1.0]).iter().sum::<Amount>(), eth("1"));
+        assert_eq!(eth("1").split(&[0.0, 1.0]), vec![Amount::ZERO, eth("1")]);
+        assert!(eth("1").split(&[]).is_empty());
+    }
+
+    #[test]
+    fn serializes_as_a_wei_string() {
+        assert_eq!(serde_json::to_string(&eth("1.5")).unwrap(), r#""1500000000000000000""#);
+        assert_eq!(serde_json::from_str::<Amount>(r#""100""#).unwrap().wei(), 100);
+        for bad in [r#""1.5""#, r#""-1""#, "100", r#""""#] {
+            assert!(serde_json::from_str::<Amount>(bad).is_err(), "accepted {}", bad);
+        }
+    }
+
+    #[test]
+    fn reads_eth_numbers_and_strings() {
+        #[derive(Deserialize, Serialize)]
+        struct Position {
+            #[serde(deserialize_with = "deserialize_eth", serialize_with = "serialize_eth")]
+            amount: Amount,
+        }
+        let parse = |json: &str| serde_json::from_str::<Position>(json).map(|p| p.amount);
+        assert_eq!(parse(r#"{"amount": 0.1}"#).unwrap(), eth("0.1"));
+        assert_eq!(parse(r#"{"amount": "2.000000000000000001"}"#).unwrap().wei(), 2_000_000_000_000_000_001);
+        assert!(parse(r#"{"amount": -1}"#).is_err());
+        let json = serde_json::to_string(&Position { amount: eth("2.5") }).unwrap();
+        assert_eq!(json, r#"{"amount":2.5}"#);
+    }
+
+    proptest! {
+        #[test]
+        fn splits_sum_exactly(wei in 0u128..u128::MAX / 2, weights in prop::collection::vec(0.0f64..1000.0, 1..50)) {
+            prop_assume!(weights.iter().sum::<f64>() > 0.0);
+            let amount = Amount::from_wei(wei);
+            prop_assert_eq!(amount.split(&weights).iter().sum::<Amount>(), amount);
+        }
+
+        #[test]
+        fn display_round_trips(wei in any::<u128>()) {
+            let amount = Amount::from_wei(wei);
+            prop_assert_eq!(amount.to_string().parse::<Amount>().unwrap(), amount);
+        }
+    }
+}
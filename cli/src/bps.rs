@@ -0,0 +1,167 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+/// Hundredths of a basis point in one basis point.
+const SCALE: u64 = 100;
+
+/// Basis points with two decimal places, stored as hundredths of a basis
+/// point so arithmetic stays exact. Whole values serialize as JSON integers,
+/// keeping configs written before fractional bps readable by older tools.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bps(u64);
+
+impl Bps {
+    pub const ZERO: Bps = Bps(0);
+
+    pub const fn from_bps(bps: u64) -> Self {
+        Bps(bps * SCALE)
+    }
+
+    pub const fn hundredths(self) -> u64 {
+        self.0
+    }
+
+    /// Rounds a float number of basis points to the nearest hundredth.
+    pub fn from_f64(bps: f64) -> Option<Self> {
+        let hundredths = (bps * SCALE as f64).round();
+        (hundredths.is_finite() && hundredths >= 0.0 && hundredths <= u64::MAX as f64)
+            .then_some(Bps(hundredths as u64))
+    }
+
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    /// The value as a plain fraction, e.g. 250bps is 0.025.
+    pub fn as_fraction(self) -> f64 {
+        self.as_f64() / 10_000.0
+    }
+
+    pub fn is_whole(self) -> bool {
+        self.0.is_multiple_of(SCALE)
+    }
+}
+
+impl Add for Bps {
+    type Output = Bps;
+
+    fn add(self, other: Bps) -> Bps {
+        Bps(self.0 + other.0)
+    }
+}
+
+impl Sub for Bps {
+    type Output = Bps;
+
+    fn sub(self, other: Bps) -> Bps {
+        Bps(self.0 - other.0)
+    }
+}
+
+impl Mul<u64> for Bps {
+    type Output = Bps;
+
+    fn mul(self, factor: u64) -> Bps {
+        Bps(self.0 * factor)
+    }
+}
+
+impl fmt::Display for Bps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (whole, fraction) = (self.0 / SCALE, self.0 % SCALE);
+        match fraction {
+            0 => write!(f, "{}", whole),
+            f_ if f_.is_multiple_of(10) => write!(f, "{}.{}", whole, f_ / 10),
+            _ => write!(f, "{}.{:02}", whole, fraction),
+        }
+    }
+}
+
+/// Parses `300`, `3.25` or `3.25bps`, with at most two decimal places.
+impl FromStr for Bps {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.trim();
+        let digits = digits.strip_suffix("bps").unwrap_or(digits).trim_end();
+        let invalid = || format!("invalid basis points '{}': expected e.g. 300 or 3.25bps", s);
+
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if fraction.len() > 2 {
+            return Err(format!("invalid basis points '{}': at most two decimal places", s));
+        }
+
+        let whole: u64 = whole.parse().map_err(|_| invalid())?;
+        let fraction: u64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+        whole.checked_mul(SCALE)
+            .and_then(|h| h.checked_add(fraction))
+            .map(Bps)
+            .ok_or_else(invalid)
+    }
+}
+
+impl Serialize for Bps {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_whole() {
+            serializer.serialize_u64(self.0 / SCALE)
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Bps {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Integer(u64),
+            Float(f64),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Integer(bps) => bps.checked_mul(SCALE)
+                .map(Bps)
+                .ok_or_else(|| serde::de::Error::custom("basis points out of range")),
+            Raw::Float(bps) => Bps::from_f64(bps)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid basis points: {}", bps))),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_displays() {
+        assert_eq!("300".parse::<Bps>().unwrap(), Bps::from_bps(300));
+        assert_eq!("3.25bps".parse::<Bps>().unwrap().hundredths(), 325);
+        assert_eq!("0.5".parse::<Bps>().unwrap().to_string(), "0.5");
+        assert_eq!("3.05".parse::<Bps>().unwrap().to_string(), "3.05");
+        assert_eq!(Bps::from_bps(300).to_string(), "300");
+        for bad in ["", "-1", "3.255", "1e3", ".5", "bps"] {
+            assert!(bad.parse::<Bps>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn stays_compatible_with_integer_json() {
+        let whole: Bps = serde_json::from_str("300").unwrap();
+        assert_eq!(whole, Bps::from_bps(300));
+        assert_eq!(serde_json::to_string(&whole).unwrap(), "300");
+
+        let fractional: Bps = serde_json::from_str("3.25").unwrap();
+        assert_eq!(fractional.hundredths(), 325);
+        assert_eq!(serde_json::to_string(&fractional).unwrap(), "3.25");
+        assert_eq!(serde_json::from_str::<Bps>(r#""0.07bps""#).unwrap().hundredths(), 7);
+        assert!(serde_json::from_str::<Bps>("-1").is_err());
+    }
+}
@@ -0,0 +1,27 @@
+/// Metadata captured by `build.rs` when the binary was compiled.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const GIT_COMMIT: &str = env!("VECTOR_PLUS_GIT_COMMIT");
+const RUSTC_VERSION: &str = env!("VECTOR_PLUS_RUSTC_VERSION");
+const BUILD_TIMESTAMP: &str = env!("VECTOR_PLUS_BUILD_TIMESTAMP");
+const FEATURES: &str = env!("VECTOR_PLUS_FEATURES");
+
+/// Full build details, for bug reports.
+pub fn describe() -> String {
+    let build_date = BUILD_TIMESTAMP
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|date| date.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let features = if FEATURES.is_empty() { "none" } else { FEATURES };
+
+    [
+        format!("vector-plus {}", VERSION),
+        format!("commit:     {}", GIT_COMMIT),
+        format!("built:      {}", build_date),
+        format!("rustc:      {}", RUSTC_VERSION),
+        format!("features:   {}", features),
+        format!("target:     {}-{}", std::env::consts::ARCH, std::env::consts::OS),
+    ]
+    .join("\n")
+}
@@ -0,0 +1,259 @@
+use clap::Subcommand;
+use colored::*;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use super::strategy::StrategyKind;
+use crate::eip712::keccak256;
+use crate::utils::confirm_overwrite;
+
+/// Layout version of the bundle manifest, bumped when its format changes.
+const BUNDLE_VERSION: u32 = 1;
+const MANIFEST_NAME: &str = "manifest.json";
+
+#[derive(Subcommand)]
+pub enum BundleCommands {
+    /// Package config files and a manifest into a zip
+    Export {
+        /// Bundle file to write
+        #[arg(long, default_value = "bundle.zip")]
+        out: String,
+
+        /// Config files to include
+        #[arg(required = true)]
+        files: Vec<String>,
+    },
+
+    /// Verify a bundle's checksums and unpack its configs
+    Import {
+        /// Bundle file to read
+        bundle: String,
+
+        /// Directory to unpack into
+        #[arg(long, default_value = ".")]
+        into: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    bundle_version: u32,
+    tool_version: String,
+    created_at: u64,
+    networks: Vec<String>,
+    files: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    name: String,
+    /// Strategy type, when the file is a recognized strategy config
+    kind: Option<String>,
+    keccak256: String,
+}
+
+pub async fn handle_command(command: &BundleCommands, cli: &crate::Cli) -> Result<()> {
+    match command {
+        BundleCommands::Export { out, files } => export_bundle(out, files, cli),
+        BundleCommands::Import { bundle, into } => import_bundle(bundle, into, cli.assume_yes),
+    }
+}
+
+fn export_bundle(out: &str, files: &[String], cli: &crate::Cli) -> Result<()> {
+    println!("{} {}", "📦 Exporting strategy bundle:".cyan(), out.yellow());
+
+    let mut networks = BTreeSet::from([cli.network.clone()]);
+    let mut names = BTreeSet::new();
+    let mut entries = Vec::with_capacity(files.len());
+    let mut contents = Vec::with_capacity(files.len());
+
+    for file in files {
+        let content = fs::read(file).map_err(|_| eyre::eyre!("Could not read file: {}", file))?;
+        let value: serde_json::Value = serde_json::from_slice(&content)
+            .map_err(|e| eyre::eyre!("Invalid JSON format in {}: {}", file, e))?;
+
+        // Bundles are flat, so two files may not share a name
+        let name = entry_name(file)?;
+        if !names.insert(name.clone()) {
+            return Err(eyre::eyre!("Two files are named {} - bundles cannot hold both", name));
+        }
+
+        if let Some(network) = value.get("network").and_then(|n| n.as_str()) {
+            networks.insert(network.to_string());
+        }
+        let kind = StrategyKind::detect(&value).map(|kind| kind.name().to_string());
+        println!("  • {} ({})", name, kind.as_deref().unwrap_or("unrecognized config"));
+
+        entries.push(ManifestEntry {
+            name,
+            kind,
+            keccak256: format!("0x{}", hex::encode(keccak256(&content))),
+        });
+        contents.push(content);
+    }
+
+    let manifest = Manifest {
+        bundle_version: BUNDLE_VERSION,
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().timestamp() as u64,
+        networks: networks.into_iter().collect(),
+        files: entries,
+    };
+
+    confirm_overwrite(out, cli.assume_yes)?;
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(fs::File::create(out)?);
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    for (entry, content) in manifest.files.iter().zip(&contents) {
+        zip.start_file(entry.name.as_str(), options)?;
+        zip.write_all(content)?;
+    }
+    zip.finish()?;
+
+    println!();
+    println!("{} {} files for {}", "✅ Bundled".green(), manifest.files.len(), manifest.networks.join(", "));
+    Ok(())
+}
+
+fn import_bundle(bundle: &str, into: &str, assume_yes: bool) -> Result<()> {
+    println!("{} {}", "📦 Importing strategy bundle:".cyan(), bundle.yellow());
+
+    let file = fs::File::open(bundle).map_err(|_| eyre::eyre!("Could not read file: {}", bundle))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| eyre::eyre!("Not a valid bundle: {}", e))?;
+
+    let manifest: Manifest = {
+        let entry = archive.by_name(MANIFEST_NAME)
+            .map_err(|_| eyre::eyre!("Bundle has no {}", MANIFEST_NAME))?;
+        serde_json::from_reader(entry).map_err(|e| eyre::eyre!("Invalid bundle manifest: {}", e))?
+    };
+
+    println!("  • Created by vector-plus {}", manifest.tool_version);
+    println!("  • Networks: {}", manifest.networks.join(", "));
+    if manifest.bundle_version != BUNDLE_VERSION {
+        println!(
+            "{} bundle version {} differs from this tool's version {}; configs are unpacked as-is",
+            "⚠️ ".yellow(), manifest.bundle_version, BUNDLE_VERSION
+        );
+    }
+
+    // Verify every checksum before writing anything, so a corrupt bundle leaves no partial import
+    let mut verified = Vec::with_capacity(manifest.files.len());
+    for entry in &manifest.files {
+        if entry_name(&entry.name).ok().as_deref() != Some(entry.name.as_str()) {
+            return Err(eyre::eyre!("Unsafe file name in bundle: {}", entry.name));
+        }
+        let mut content = Vec::new();
+        archive.by_name(&entry.name)
+            .map_err(|_| eyre::eyre!("Bundle is missing {}", entry.name))?
+            .read_to_end(&mut content)?;
+        let checksum = format!("0x{}", hex::encode(keccak256(&content)));
+        if !checksum.eq_ignore_ascii_case(&entry.keccak256) {
+            return Err(eyre::eyre!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                entry.name, entry.keccak256, checksum
+            ));
+        }
+        verified.push((entry, content));
+    }
+
+    let listed: BTreeSet<&str> = manifest.files.iter().map(|e| e.name.as_str()).collect();
+    for name in archive.file_names() {
+        if name != MANIFEST_NAME && !listed.contains(name) {
+            println!("{} {} is not in the manifest and was skipped", "⚠️ ".yellow(), name);
+        }
+    }
+
+    fs::create_dir_all(into).map_err(|_| eyre::eyre!("Could not create directory: {}", into))?;
+    for (entry, content) in &verified {
+        let path = Path::new(into).join(&entry.name);
+        let display = path.display().to_string();
+        confirm_overwrite(&display, assume_yes)?;
+        fs::write(&path, content)?;
+        println!("  • {} ({})", display, entry.kind.as_deref().unwrap_or("unrecognized config"));
+    }
+
+    println!();
+    println!("{} {} files into {}", "✅ Imported".green(), verified.len(), into);
+    Ok(())
+}
+
+/// The bare file name a config is stored under in a bundle.
+fn entry_name(path: &str) -> Result<String> {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+        .filter(|name| name != MANIFEST_NAME)
+        .ok_or_else(|| eyre::eyre!("Invalid bundle file name: {}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a bundle holding `files` under a manifest that lists each with
+    /// the given checksum, or the real one when it is `None`.
+    fn write_bundle(path: &Path, files: &[(&str, &str, Option<&str>)]) {
+        let manifest = Manifest {
+            bundle_version: BUNDLE_VERSION,
+            tool_version: "0.0.0".to_string(),
+            created_at: 0,
+            networks: vec!["local".to_string()],
+            files: files
+                .iter()
+                .map(|(name, content, checksum)| ManifestEntry {
+                    name: name.to_string(),
+                    kind: None,
+                    keccak256: checksum
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("0x{}", hex::encode(keccak256(content.as_bytes())))),
+                })
+                .collect(),
+        };
+        let options = zip::write::SimpleFileOptions::default();
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        zip.start_file(MANIFEST_NAME, options).unwrap();
+        zip.write_all(serde_json::to_string(&manifest).unwrap().as_bytes()).unwrap();
+        for (name, content, _) in files.iter().filter(|(name, ..)| *name != MANIFEST_NAME) {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn imports_only_verified_bundles() {
+        let dir = std::env::temp_dir().join(format!("vector-plus-bundle-{}", std::process::id()));
+        let into = dir.join("into");
+        fs::create_dir_all(&dir).unwrap();
+        let bundle = dir.join("bundle.zip");
+        let import = || import_bundle(bundle.to_str().unwrap(), into.to_str().unwrap(), true);
+
+        write_bundle(&bundle, &[("vol.json", "{}", None), ("twap.json", r#"{"intervals":4}"#, None)]);
+        import().unwrap();
+        assert_eq!(fs::read_to_string(into.join("twap.json")).unwrap(), r#"{"intervals":4}"#);
+        fs::remove_dir_all(&into).unwrap();
+
+        // One bad checksum stops the whole import before anything is written
+        let wrong = format!("0x{}", "00".repeat(32));
+        write_bundle(&bundle, &[("vol.json", "{}", None), ("twap.json", "{}", Some(&wrong))]);
+        let err = import().unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch for twap.json"), "{}", err);
+        assert!(!into.exists());
+
+        for name in ["../escape.json", "nested/vol.json", "/tmp/vol.json", MANIFEST_NAME] {
+            write_bundle(&bundle, &[(name, "{}", None)]);
+            let err = import().unwrap_err();
+            assert!(err.to_string().contains(&format!("Unsafe file name in bundle: {}", name)), "{}", err);
+        }
+        assert!(!into.exists() && !dir.join("escape.json").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,17 @@
 use clap::Subcommand;
 use colored::*;
 use eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Findings, ValidationSummary};
+use super::twap::{check_twap_config, fill_gas_cost, gas_token_eth, TwapConfig, DEFAULT_GAS_PER_FILL, DEFAULT_MAX_GAS_PCT};
+use super::volatility::{check_volatility_config, size_execution, volatility_adjustment_factor, wei_format_errors, Regime, VolatilityConfig};
+use crate::amount::Amount;
+use crate::bps::Bps;
+use crate::error::VectorPlusError;
+use crate::utils::table::{Align, Cell, Table};
+use crate::utils::timeline::{Mark, Timeline};
+use crate::utils::{add_key_findings, format_amount, output_name, read_config, status, write_created_config};
 
 #[derive(Subcommand)]
 pub enum CombinedCommands {
@@ -9,30 +20,590 @@ pub enum CombinedCommands {
         /// TWAP duration in minutes
         #[arg(long)]
         twap_duration: u64,
-        
+
         /// TWAP intervals
         #[arg(long)]
         twap_intervals: u32,
-        
-        /// Volatility threshold
+
+        /// Volatility threshold in basis points, e.g. 600 or 6.5bps
         #[arg(long)]
-        volatility_threshold: u64,
-        
+        volatility_threshold: Bps,
+
         /// Output file
         #[arg(short, long, default_value = "combined-strategy.json")]
         output: String,
     },
+
+    /// Compare the gas of volatility-sized fills with a flat TWAP over a volatility series
+    Simulate {
+        /// Combined strategy file
+        #[arg(long, default_value = "combined-strategy.json")]
+        config: String,
+
+        /// Order size in ETH
+        #[arg(long)]
+        order_size: Amount,
+
+        /// File with one volatility in basis points per TWAP interval (one per line); extra values cover intervals added by pauses
+        #[arg(long)]
+        volatility_series: String,
+
+        /// Chart each interval's sized fill and pauses on a time axis, or list them when stdout is not a terminal
+        #[arg(long)]
+        show_timeline: bool,
+
+        /// Consecutive intervals out of EMERGENCY before a paused TWAP fills again
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        resume_after_calm_intervals: u32,
+
+        /// Gas price in gwei
+        #[arg(long, value_parser = crate::utils::parse_non_negative_f64)]
+        gas_price_gwei: f64,
+
+        /// Gas used by one fill
+        #[arg(long, default_value_t = DEFAULT_GAS_PER_FILL)]
+        gas_per_fill: u64,
+
+        /// Price of the gas token in ETH, needed where gas isn't paid in ETH
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+        gas_token_price_eth: Option<f64>,
+
+        /// Most gas a fill may cost, as a percentage of its value, to count as worthwhile
+        #[arg(long, default_value_t = DEFAULT_MAX_GAS_PCT, value_parser = crate::utils::parse_non_negative_f64)]
+        max_gas_pct: f64,
+    },
+
+    /// Validate combined strategy
+    Validate {
+        /// Strategy file to validate
+        file: String,
+
+        /// Fail on unknown fields and duplicate keys instead of warning
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+/// Shortest TWAP that `volatility watch` can react within: its default 30-price
+/// window at the default 60s poll.
+const VOLATILITY_RESPONSE_SECS: u64 = 30 * 60;
+/// The compatibility scenario ramps volatility from baseline to this multiple of it.
+const SCENARIO_PEAK_MULTIPLE: f64 = 2.0;
+/// Adjustment factors at or below this percentage count as heavily reduced.
+const HEAVY_REDUCTION_FACTOR: u64 = 80;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CombinedStrategy {
+    pub(crate) twap: TwapConfig,
+    pub(crate) volatility: VolatilityConfig,
 }
 
-pub async fn handle_command(command: &CombinedCommands, _cli: &crate::Cli) -> Result<()> {
+pub async fn handle_command(command: &CombinedCommands, cli: &crate::Cli) -> Result<()> {
     match command {
         CombinedCommands::Create { twap_duration, twap_intervals, volatility_threshold, output } => {
-            println!("{}", "🚀 Creating combined strategy...".cyan());
-            println!("  • TWAP duration: {} minutes", twap_duration);
-            println!("  • TWAP intervals: {}", twap_intervals);
-            println!("  • Volatility threshold: {}bps", volatility_threshold);
-            println!("{} {}", "✅ Combined strategy created:".green(), output);
-            Ok(())
+            create_combined_strategy(*twap_duration, *twap_intervals, *volatility_threshold, output, &cli.config).await
+        }
+        CombinedCommands::Simulate {
+            config,
+            order_size,
+            volatility_series,
+            show_timeline,
+            resume_after_calm_intervals,
+            gas_price_gwei,
+            gas_per_fill,
+            gas_token_price_eth,
+            max_gas_pct,
+        } => {
+            let gas = FillGas {
+                fill_cost_eth: fill_gas_cost(*gas_per_fill, *gas_price_gwei) * gas_token_eth(&cli.network, *gas_token_price_eth)?,
+                max_gas_pct: *max_gas_pct,
+            };
+            let gate = PauseGate::new(*resume_after_calm_intervals);
+            simulate_gas_budget(config, *order_size, volatility_series, gate, &gas, *show_timeline, cli.precision)
+        }
+        CombinedCommands::Validate { file, strict } => {
+            ValidationSummary::single(file, validate_combined_strategy(file, *strict).await)
+        }
+    }
+}
+
+async fn create_combined_strategy(
+    twap_duration: u64,
+    twap_intervals: u32,
+    volatility_threshold: Bps,
+    output: &str,
+    config_file: &str,
+) -> Result<()> {
+    status!("{}", "🚀 Creating combined strategy...".cyan());
+
+    if twap_intervals == 0 {
+        return Err(eyre::eyre!("TWAP intervals must be greater than zero"));
+    }
+
+    let defaults = crate::config::load_or_default(config_file)?.defaults.volatility;
+    if volatility_threshold <= defaults.baseline_volatility {
+        return Err(VectorPlusError::Validation(format!(
+            "Volatility threshold ({}bps) must be above the baseline volatility ({}bps)",
+            volatility_threshold, defaults.baseline_volatility
+        )).into());
+    }
+    let mut volatility = VolatilityConfig::new(
+        defaults.baseline_volatility,
+        defaults.baseline_volatility,
+        Amount::ZERO,
+        Amount::ZERO,
+        defaults.conservative_mode,
+    );
+    volatility.max_execution_size = defaults.max_execution_size;
+    volatility.min_execution_size = defaults.min_execution_size;
+    volatility.volatility_threshold = volatility_threshold;
+    volatility.emergency_threshold = volatility.emergency_threshold.max(volatility_threshold * 2);
+
+    let strategy = CombinedStrategy {
+        twap: TwapConfig::new(twap_duration, twap_intervals, true),
+        volatility,
+    };
+    let warnings = check_compatibility(&strategy);
+
+    write_created_config(output, &strategy)?;
+
+    status!("  • TWAP duration: {} minutes", twap_duration);
+    status!("  • TWAP intervals: {}", twap_intervals);
+    status!("  • Volatility threshold: {}bps", volatility_threshold);
+    for warning in &warnings {
+        status!("{}", warning.yellow());
+    }
+    status!("{} {}", "✅ Combined strategy created:".green(), output_name(output));
+    Ok(())
+}
+
+/// Warns about TWAP and volatility settings that fight each other. Sizes each
+/// interval while volatility climbs linearly from baseline to
+/// `SCENARIO_PEAK_MULTIPLE` times baseline over the TWAP, and flags
+/// strategies that would halt or heavily cut most intervals, or that finish
+/// before volatility monitoring could respond.
+pub(crate) fn check_compatibility(strategy: &CombinedStrategy) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let twap = &strategy.twap;
+    let volatility = &strategy.volatility;
+
+    if twap.duration < VOLATILITY_RESPONSE_SECS {
+        warnings.push(format!(
+            "⚠️  The TWAP finishes in {} minutes, before `volatility watch` fills its default {}-minute window - sizing will not react to volatility within it",
+            twap.duration / 60,
+            VOLATILITY_RESPONSE_SECS / 60
+        ));
+    }
+
+    let intervals = twap.intervals.max(1);
+    let baseline = volatility.baseline_volatility.as_f64();
+    let (mut halted, mut reduced) = (0, 0);
+    for i in 0..intervals {
+        let progress = if intervals == 1 { 1.0 } else { i as f64 / (intervals - 1) as f64 };
+        let mut scenario = volatility.clone();
+        scenario.current_volatility = Bps::from_f64(baseline * (1.0 + (SCENARIO_PEAK_MULTIPLE - 1.0) * progress))
+            .unwrap_or(volatility.baseline_volatility);
+        if Regime::classify(&scenario) == Regime::Emergency {
+            halted += 1;
+        } else if volatility_adjustment_factor(&scenario) <= HEAVY_REDUCTION_FACTOR {
+            reduced += 1;
+        }
+    }
+
+    let scenario = format!(
+        "as volatility climbs from {}bps to {:.0}bps over the TWAP",
+        volatility.baseline_volatility, baseline * SCENARIO_PEAK_MULTIPLE
+    );
+    if halted * 2 > intervals {
+        warnings.push(format!(
+            "⚠️  The emergency threshold ({}bps) would halt {} of {} intervals {} - raise it above the volatility threshold's range",
+            volatility.emergency_threshold, halted, intervals, scenario
+        ));
+    } else if (halted + reduced) * 2 > intervals {
+        warnings.push(format!(
+            "⚠️  {} of {} intervals would be halted or cut by {}% or more {} - the volatility threshold ({}bps) is close to baseline",
+            halted + reduced, intervals, 100 - HEAVY_REDUCTION_FACTOR, scenario, volatility.volatility_threshold
+        ));
+    }
+
+    warnings
+}
+
+/// Runs the TWAP, volatility and compatibility checks without printing,
+/// returning `(warnings, errors)` tagged with the part they concern.
+pub(crate) fn check_combined_strategy(strategy: &CombinedStrategy) -> (Vec<String>, Vec<String>) {
+    let (twap_warnings, twap_errors) = check_twap_config(&strategy.twap);
+    let (vol_warnings, vol_errors) = check_volatility_config(&strategy.volatility);
+
+    let warnings = twap_warnings.iter().map(|w| format!("[twap] {}", w))
+        .chain(vol_warnings.iter().map(|w| format!("[volatility] {}", w)))
+        .chain(check_compatibility(strategy).iter().map(|w| format!("[combined] {}", w)))
+        .collect();
+    let errors = twap_errors.iter().map(|e| format!("[twap] {}", e))
+        .chain(vol_errors.iter().map(|e| format!("[volatility] {}", e)))
+        .collect();
+    (warnings, errors)
+}
+
+pub(crate) async fn validate_combined_strategy(file: &str, strict: bool) -> Result<Findings> {
+    println!("{} {}", "🔍 Validating combined strategy:".cyan(), file.yellow());
+
+    let strategy: CombinedStrategy = read_config(file)?;
+
+    let (mut warnings, mut errors) = check_combined_strategy(&strategy);
+    errors.extend(wei_format_errors(&strategy.volatility).iter().map(|e| format!("[volatility] {}", e)));
+    add_key_findings::<CombinedStrategy>(file, strict, &mut warnings, &mut errors);
+
+    if errors.is_empty() && warnings.is_empty() {
+        println!("{}", "✅ Combined strategy is valid!".green());
+        println!("📊 Strategy summary:");
+        println!("  • TWAP duration: {} minutes", strategy.twap.duration / 60);
+        println!("  • TWAP intervals: {}", strategy.twap.intervals);
+        println!("  • Volatility threshold: {}bps", strategy.volatility.volatility_threshold);
+        println!("  • Emergency threshold: {}bps", strategy.volatility.emergency_threshold);
+    } else {
+        for warning in &warnings {
+            println!("{}", warning.yellow());
+        }
+        for error in &errors {
+            println!("{}", error.red());
+        }
+    }
+
+    Ok(Findings::of(&warnings, &errors))
+}
+
+/// Gas of one fill, and how much of a fill's value it may take.
+struct FillGas {
+    /// Gas cost of one fill, converted to ETH
+    fill_cost_eth: f64,
+    max_gas_pct: f64,
+}
+
+impl FillGas {
+    /// Whether a fill of `amount` is worth its gas.
+    fn worthwhile(&self, amount: Amount) -> bool {
+        !amount.is_zero() && self.fill_cost_eth <= amount.as_eth_f64() * self.max_gas_pct / 100.0
+    }
+}
+
+/// Pauses a TWAP when volatility enters EMERGENCY and resumes it once
+/// volatility has stayed out for `resume_after` consecutive intervals.
+#[derive(Debug, Clone, Copy)]
+struct PauseGate {
+    resume_after: u32,
+    /// Calm intervals seen since the last EMERGENCY one, while paused
+    paused: Option<u32>,
+}
+
+impl PauseGate {
+    fn new(resume_after: u32) -> Self {
+        Self { resume_after, paused: None }
+    }
+
+    /// Advances one interval, returning whether it may fill.
+    fn step(&mut self, regime: Regime) -> bool {
+        self.paused = match (regime, self.paused) {
+            (Regime::Emergency, _) => Some(0),
+            (_, Some(calm)) if calm + 1 < self.resume_after => Some(calm + 1),
+            _ => None,
+        };
+        self.paused.is_none()
+    }
+}
+
+/// Whether a TWAP interval filled, waited out volatility or had nothing left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FillState {
+    Running,
+    Paused,
+    Done,
+}
+
+impl FillState {
+    fn name(self) -> &'static str {
+        match self {
+            FillState::Running => "running",
+            FillState::Paused => "paused",
+            FillState::Done => "done",
         }
     }
-}
\ No newline at end of file
+}
+
+/// One TWAP interval under the flat schedule and under volatility sizing.
+#[derive(Debug, PartialEq)]
+struct IntervalFill {
+    volatility: Bps,
+    regime: Regime,
+    state: FillState,
+    flat: Amount,
+    sized: Amount,
+}
+
+/// Fills per interval: the flat TWAP's chunks, and the same chunks sized at
+/// each interval's volatility and the strategy's execution limits, until the
+/// order is filled. `gate` pauses the sized TWAP through EMERGENCY, pushing
+/// its remaining chunks back an interval each time; the series runs on past
+/// the TWAP's intervals only while chunks are still waiting.
+fn plan_fills(strategy: &CombinedStrategy, order_size: Amount, series: &[Bps], mut gate: PauseGate) -> Result<Vec<IntervalFill>> {
+    let twap = &strategy.twap;
+    let weights = twap.weighting.weights(twap.weights.as_deref(), twap.intervals)?;
+    let chunks = order_size.split(&weights);
+    let mut pending = chunks.iter().copied();
+    let mut remaining = order_size;
+    let mut fills = Vec::with_capacity(series.len());
+    for (i, &volatility) in series.iter().enumerate() {
+        let flat = chunks.get(i).copied().unwrap_or(Amount::ZERO);
+        let waiting = !remaining.is_zero() && pending.len() > 0;
+        if i >= chunks.len() && !waiting {
+            break;
+        }
+        let mut current = strategy.volatility.clone();
+        current.current_volatility = volatility;
+        let regime = Regime::classify(&current);
+        let (state, sized) = match (waiting, gate.step(regime)) {
+            (false, _) => (FillState::Done, Amount::ZERO),
+            (true, false) => (FillState::Paused, Amount::ZERO),
+            (true, true) => {
+                let chunk = pending.next().unwrap_or_default();
+                let sized = if chunk.is_zero() { Amount::ZERO } else { size_execution(&current, chunk).1.min(remaining) };
+                (FillState::Running, sized)
+            }
+        };
+        remaining = remaining.saturating_sub(sized);
+        fills.push(IntervalFill { volatility, regime, state, flat, sized });
+    }
+    Ok(fills)
+}
+
+/// Reads one volatility in basis points per line (blank lines and `#`
+/// comments are skipped), requiring at least one per TWAP interval.
+fn load_volatility_series(file: &str, intervals: u32) -> Result<Vec<Bps>> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|_| eyre::eyre!("Could not read file: {}", file))?;
+
+    let mut series = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let volatility: Bps = line.parse()
+            .map_err(|e| eyre::eyre!("Invalid volatility on line {} of {}: {} ({})", i + 1, file, line, e))?;
+        series.push(volatility);
+    }
+
+    if series.len() < intervals as usize {
+        return Err(eyre::eyre!(
+            "Volatility series has {} values but the TWAP has {} intervals",
+            series.len(),
+            intervals
+        ));
+    }
+    Ok(series)
+}
+
+fn simulate_gas_budget(
+    config_file: &str,
+    order_size: Amount,
+    series_file: &str,
+    gate: PauseGate,
+    gas: &FillGas,
+    show_timeline: bool,
+    precision: u8,
+) -> Result<()> {
+    let strategy: CombinedStrategy = read_config(config_file)?;
+    if strategy.twap.intervals == 0 {
+        return Err(eyre::eyre!("TWAP must have at least one interval"));
+    }
+    let series = load_volatility_series(series_file, strategy.twap.intervals)?;
+    let fills = plan_fills(&strategy, order_size, &series, gate)?;
+
+    println!("{}", "⛽ Simulating volatility-adjusted gas budget...".cyan());
+    println!("  • Strategy: {}", config_file);
+    println!("  • Order size: {} ETH over {} intervals", order_size.format_eth(precision), strategy.twap.intervals);
+    println!("  • Gas per fill: {:.6} ETH (worthwhile at ≤ {}% of the fill)", gas.fill_cost_eth, gas.max_gas_pct);
+    println!("  • Pauses in EMERGENCY, resuming after {} calm intervals", gate.resume_after);
+    println!();
+
+    let mut table = Table::new()
+        .column("interval", "Interval", Align::Right)
+        .column("volatility", "Volatility", Align::Right)
+        .column("regime", "Regime", Align::Left)
+        .column("state", "TWAP", Align::Left)
+        .column("flat", "Flat (ETH)", Align::Right)
+        .column("sized", "Sized (ETH)", Align::Right)
+        .column("gas_pct", "Gas %", Align::Right);
+    for (i, fill) in fills.iter().enumerate() {
+        let gas_pct = match fill.sized.is_zero() {
+            true => Cell::new("-", serde_json::Value::Null),
+            false => {
+                let pct = gas.fill_cost_eth / fill.sized.as_eth_f64() * 100.0;
+                Cell::new(format!("{:.3}%", pct), pct)
+            }
+        };
+        let cells = vec![
+            Cell::new((i + 1).to_string(), i + 1),
+            Cell::new(format!("{}bps", fill.volatility), fill.volatility.as_f64()),
+            Cell::text(fill.regime.name()),
+            Cell::text(fill.state.name()),
+            Cell::new(fill.flat.format_eth(precision), fill.flat.as_eth_f64()),
+            Cell::new(fill.sized.format_eth(precision), fill.sized.as_eth_f64()),
+            gas_pct,
+        ];
+        match (fill.regime, gas.worthwhile(fill.sized)) {
+            (Regime::Emergency, _) => table.colored_row(cells, Color::Red),
+            _ if fill.state == FillState::Paused => table.colored_row(cells, Color::Yellow),
+            (_, false) if !fill.sized.is_zero() => table.colored_row(cells, Color::Yellow),
+            _ => table.row(cells),
+        }
+    }
+    println!("{}", table.render());
+    println!();
+
+    let interval_secs = strategy.twap.duration / strategy.twap.intervals as u64;
+    if show_timeline {
+        println!("{}", "🗓️  Timeline:".bold());
+        let mut timeline = Timeline::new();
+        for (i, fill) in fills.iter().enumerate() {
+            let mark = match fill.state {
+                FillState::Running if fill.sized.is_zero() => Mark::Skipped,
+                FillState::Running => Mark::Fill,
+                FillState::Paused => Mark::Paused,
+                FillState::Done => Mark::Idle,
+            };
+            let start = i as u64 * interval_secs;
+            let size = format!("{} ETH", fill.sized.format_eth(precision));
+            timeline.span((i + 1).to_string(), start, start + interval_secs, fill.sized.as_eth_f64(), size, mark);
+        }
+        println!("{}", timeline.render_for_stdout());
+        println!();
+    }
+
+    let count = |amounts: &mut dyn Iterator<Item = Amount>| -> (usize, usize) {
+        amounts.filter(|amount| !amount.is_zero())
+            .fold((0, 0), |(fills, worthwhile), amount| (fills + 1, worthwhile + gas.worthwhile(amount) as usize))
+    };
+    let (flat_fills, flat_worthwhile) = count(&mut fills.iter().map(|fill| fill.flat));
+    let (sized_fills, sized_worthwhile) = count(&mut fills.iter().map(|fill| fill.sized));
+    let filled: Amount = fills.iter().map(|fill| fill.sized).sum();
+    let flat_gas = gas.fill_cost_eth * flat_fills as f64;
+    let sized_gas = gas.fill_cost_eth * sized_fills as f64;
+
+    println!("📊 Gas Budget:");
+    println!(
+        "  • Flat TWAP: {} fills, {} worthwhile, {} ETH gas",
+        flat_fills, flat_worthwhile, format_amount(flat_gas, precision)
+    );
+    println!(
+        "  • Volatility-sized: {} fills, {} worthwhile, {} ETH gas",
+        sized_fills, sized_worthwhile, format_amount(sized_gas, precision)
+    );
+    let saved = flat_gas - sized_gas;
+    let text = format!("{} fills, {} ETH", flat_fills as i64 - sized_fills as i64, format_amount(saved, precision));
+    if saved >= 0.0 {
+        println!("  • Savings: {}", text.green());
+    } else {
+        println!("  • Savings: {}", text.red());
+    }
+    let paused = fills.iter().filter(|fill| fill.state == FillState::Paused).count();
+    if paused > 0 {
+        println!(
+            "  • Paused: {} intervals, delaying the TWAP by {} minutes",
+            paused,
+            paused as u64 * interval_secs / 60
+        );
+    }
+    let unfilled = order_size.saturating_sub(filled);
+    let executed = fills.iter().filter(|fill| fill.state == FillState::Running).count();
+    if !unfilled.is_zero() && executed < strategy.twap.intervals as usize {
+        println!(
+            "{} {} ETH is still waiting after a pause when the series ends; add volatility values to simulate further",
+            "⚠️ ".yellow(),
+            unfilled.format_eth(precision)
+        );
+    } else if !unfilled.is_zero() {
+        println!(
+            "{} {} ETH is still unfilled after the last interval; high volatility cut or halted fills",
+            "⚠️ ".yellow(),
+            unfilled.format_eth(precision)
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy(duration_mins: u64, threshold: u64, emergency: u64) -> CombinedStrategy {
+        let mut volatility = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(300), "5".parse().unwrap(), "0.1".parse().unwrap(), false);
+        volatility.volatility_threshold = Bps::from_bps(threshold);
+        volatility.emergency_threshold = Bps::from_bps(emergency);
+        CombinedStrategy { twap: TwapConfig::new(duration_mins, 10, false), volatility }
+    }
+
+    #[test]
+    fn calm_intervals_finish_the_order_in_fewer_fills() {
+        let strategy = strategy(60, 600, 1200);
+        // 2 ETH over 10 intervals is 0.2 ETH a fill on a flat schedule
+        let calm = vec![Bps::from_bps(150); 10];
+        let fills = plan_fills(&strategy, "2".parse().unwrap(), &calm, PauseGate::new(1)).unwrap();
+        // 125% of each chunk, so 8 fills cover the order
+        assert_eq!(fills.iter().filter(|fill| !fill.sized.is_zero()).count(), 8);
+        assert_eq!(fills[7].sized, "0.25".parse().unwrap());
+        assert_eq!(fills.iter().map(|fill| fill.sized).sum::<Amount>(), "2".parse().unwrap());
+
+        let mut stormy = calm.clone();
+        stormy[0] = Bps::from_bps(1300);
+        let fills = plan_fills(&strategy, "2".parse().unwrap(), &stormy, PauseGate::new(1)).unwrap();
+        assert_eq!((fills[0].regime, fills[0].state, fills[0].sized), (Regime::Emergency, FillState::Paused, Amount::ZERO));
+
+        let gas = FillGas { fill_cost_eth: 0.002, max_gas_pct: 1.0 };
+        assert!(gas.worthwhile("0.25".parse().unwrap()));
+        assert!(!gas.worthwhile("0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn pauses_through_emergency_and_resumes_after_calm_intervals() {
+        let strategy = strategy(60, 600, 1200);
+        let order: Amount = "2".parse().unwrap();
+        // Volatility at baseline sizes every chunk at 0.2 ETH
+        let mut series = vec![Bps::from_bps(300); 14];
+        series[2] = Bps::from_bps(1300);
+        series[3] = Bps::from_bps(1300);
+
+        let fills = plan_fills(&strategy, order, &series, PauseGate::new(2)).unwrap();
+        let states: Vec<FillState> = fills.iter().map(|fill| fill.state).collect();
+        use FillState::*;
+        // Two EMERGENCY intervals, then one calm interval of debounce
+        assert_eq!(states[..6], [Running, Running, Paused, Paused, Paused, Running]);
+        // Every chunk still fills, three intervals late
+        assert_eq!(fills.len(), 13);
+        assert_eq!(fills.iter().map(|fill| fill.sized).sum::<Amount>(), order);
+        assert_eq!(fills[12].state, Running);
+
+        // A relapse during the debounce restarts it
+        let mut gate = PauseGate::new(2);
+        assert!(!gate.step(Regime::Emergency));
+        assert!(!gate.step(Regime::Normal));
+        assert!(!gate.step(Regime::Emergency));
+        assert!(!gate.step(Regime::Normal));
+        assert!(gate.step(Regime::Elevated));
+    }
+
+    #[test]
+    fn flags_incompatible_combinations() {
+        assert!(check_compatibility(&strategy(60, 600, 1200)).is_empty());
+
+        let short = check_compatibility(&strategy(10, 600, 1200));
+        assert_eq!(short.len(), 1);
+        assert!(short[0].contains("finishes in 10 minutes"), "{}", short[0]);
+
+        let halting = check_compatibility(&strategy(60, 320, 400));
+        assert!(halting[0].contains("would halt"), "{}", halting[0]);
+
+        let reducing = check_compatibility(&strategy(60, 310, 1200));
+        assert!(reducing[0].contains("cut by 20% or more"), "{}", reducing[0]);
+    }
+}
@@ -0,0 +1,43 @@
+use clap::CommandFactory;
+use clap_complete::Shell;
+use eyre::Result;
+use std::io::Write;
+
+/// Writes the completion script for `shell` to `out`. It is generated from the
+/// same clap definitions the parser uses, so new commands and flags complete
+/// without changes here.
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = crate::Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Prints the script for `shell`. It is built in memory first because the
+/// generator panics on a failed write, and a reader that stops early, e.g.
+/// `head`, is not an error.
+pub fn print_completions(shell: Shell) -> Result<()> {
+    let mut script = Vec::new();
+    write_completions(shell, &mut script);
+    let mut stdout = std::io::stdout().lock();
+    match stdout.write_all(&script).and_then(|()| stdout.flush()) {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_nested_subcommands_and_flags() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut script = Vec::new();
+            write_completions(shell, &mut script);
+            let script = String::from_utf8(script).unwrap();
+            assert!(script.contains("vector-plus"), "{} script names the binary", shell);
+            assert!(script.contains("calculate") && script.contains("pct-of"), "{} script covers volatility calculate", shell);
+            assert!(script.contains("completions"), "{} script completes itself", shell);
+        }
+    }
+}
@@ -1,6 +1,20 @@
 use clap::Subcommand;
 use colored::*;
 use eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::combined::CombinedStrategy;
+use super::options::OptionConfig;
+use super::strategy::StrategyKind;
+use super::twap::TwapConfig;
+use super::volatility::VolatilityConfig;
+use crate::bps::Bps;
+use crate::config::{ConfigNotFound, Source, VectorPlusConfig};
+use crate::error::VectorPlusError;
+use crate::utils::table::{Align, Cell, Table};
+use crate::utils::{add_key_findings, backup_existing, backup_path, confirm_overwrite, read_config, write_json_file, write_json_file_atomic};
 
 #[derive(Subcommand)]
 pub enum ConfigCommands {
@@ -9,27 +23,1101 @@ pub enum ConfigCommands {
         /// Force overwrite existing config
         #[arg(long)]
         force: bool,
+
+        /// Add fields missing from an existing config, keeping its values
+        #[arg(long, conflicts_with = "force")]
+        merge: bool,
+    },
+
+    /// Create the configuration from a 1inch SDK settings file
+    Import {
+        /// SDK settings JSON with network, contracts and settings
+        #[arg(long = "from-1inch", value_name = "FILE")]
+        from_oneinch: String,
+
+        /// Force overwrite existing config
+        #[arg(long)]
+        force: bool,
     },
     
     /// Show current configuration
-    Show,
+    Show {
+        /// Print the effective configuration as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// List every resolved setting with where it came from: default, file, env-file, env or flag
+        #[arg(long)]
+        effective: bool,
+    },
+
+    /// Check that a config file exists and parses
+    Validate {
+        /// Config file to check (defaults to --config)
+        file: Option<String>,
+
+        /// Fail on unknown fields and duplicate keys instead of warning
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Show fields where a config file differs from the defaults
+    Diff {
+        /// Config file to compare (defaults to --config)
+        file: Option<String>,
+
+        /// Print the differences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print a stable hash of a config, ignoring key order and timestamps
+    Fingerprint {
+        /// Config file to fingerprint
+        file: String,
+
+        /// Approved fingerprint to compare against; exits non-zero on mismatch
+        #[arg(long)]
+        expect: Option<String>,
+    },
+
+    /// Record that a config's current contents were reviewed, in <FILE>.approval
+    Approve {
+        /// Config file to approve
+        file: String,
+
+        /// Name of the reviewer
+        #[arg(long, env = "VECTOR_PLUS_APPROVER")]
+        approver: String,
+
+        /// Context for the approval, e.g. a ticket or review link
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// Swap a config file with the .bak kept when it was last overwritten
+    Restore {
+        /// Config file to restore
+        file: String,
+    },
+
+    /// Flag risky settings in a strategy config that still passes validation
+    Lint {
+        /// Volatility, TWAP, combined or option config to lint
+        file: String,
+
+        /// Lint id to skip; repeat for several
+        #[arg(long, value_name = "ID")]
+        allow: Vec<String>,
+
+        /// Print the findings as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
+/// Metadata that changes on every write without changing what a config does.
+const VOLATILE_FIELDS: [&str; 4] = ["created_at", "creation_time", "last_update_time", "updated_at"];
+
 pub async fn handle_command(command: &ConfigCommands, cli: &crate::Cli) -> Result<()> {
     match command {
-        ConfigCommands::Init { force: _ } => {
+        ConfigCommands::Init { merge: true, .. } if std::path::Path::new(&cli.config).exists() => merge_defaults(cli),
+        ConfigCommands::Init { force, .. } => {
             println!("{}", "⚙️  Initializing Vector Plus configuration...".cyan());
             println!("  • Network: {}", cli.network);
             println!("  • Config file: {}", cli.config);
+            if !force {
+                confirm_overwrite(&cli.config, cli.assume_yes)?;
+            }
+            let config = VectorPlusConfig {
+                network: cli.network.clone(),
+                ..VectorPlusConfig::default()
+            };
+            if let Some(backup) = backup_existing(&cli.config)? {
+                println!("  • Kept the previous {} as {}", cli.config, backup);
+            }
+            write_json_file(&cli.config, &config)?;
+            crate::history::record(&cli.config, &config);
             println!("{}", "✅ Configuration initialized".green());
             Ok(())
         }
-        ConfigCommands::Show => {
+        ConfigCommands::Import { from_oneinch, force } => import_oneinch(cli, from_oneinch, *force),
+        ConfigCommands::Show { json, effective: true } => show_effective(cli, *json),
+        ConfigCommands::Show { json: true, .. } => {
+            // --network and --rpc-url (or their env vars) override the file
+            let mut config = crate::config::load_or_default(&cli.config)?;
+            config.network = cli.network.clone();
+            if let Some(rpc_url) = &cli.rpc_url {
+                config.rpc_url = Some(rpc_url.clone());
+            }
+            println!("{}", serde_json::to_string_pretty(&config)?);
+            Ok(())
+        }
+        ConfigCommands::Show { json: false, .. } => {
             println!("{}", "📋 Vector Plus Configuration:".cyan());
             println!("  • Network: {}", cli.network.yellow());
             println!("  • Config file: {}", cli.config.yellow());
             println!("  • Verbose: {}", cli.verbose.to_string().yellow());
+            println!("  • Display precision: {} decimals", cli.precision.to_string().yellow());
+            let time_format = clap::ValueEnum::to_possible_value(&cli.time_format).map(|value| value.get_name().to_string());
+            println!("  • Time format: {}", time_format.unwrap_or_default().yellow());
+
+            let config = crate::config::load_or_default(&cli.config)?;
+            let defaults = &config.defaults;
+            println!("  • Baseline volatility: {} bps", defaults.volatility.baseline_volatility);
+            println!("  • TWAP: {} intervals over {} seconds", defaults.twap.intervals, defaults.twap.duration);
+            println!("  • Implied volatility: {} bps", defaults.options.implied_volatility);
             Ok(())
         }
+        ConfigCommands::Validate { file, strict } => {
+            let file = file.as_deref().unwrap_or(&cli.config);
+            println!("{} {}", "🔍 Validating configuration:".cyan(), file.yellow());
+            let config = crate::config::load(file)?;
+            let (mut warnings, mut errors) = (Vec::new(), Vec::new());
+            add_key_findings::<VectorPlusConfig>(file, *strict, &mut warnings, &mut errors);
+            for warning in &warnings {
+                println!("{}", warning.yellow());
+            }
+            for error in &errors {
+                println!("{}", error.red());
+            }
+            if !errors.is_empty() {
+                return Err(VectorPlusError::Validation("Configuration validation failed".to_string()).into());
+            }
+            crate::networks::register(&config.custom_networks);
+            crate::eip712::chain_id(&config.network)?;
+            if !config.custom_networks.is_empty() {
+                let names: Vec<&str> = config.custom_networks.keys().map(String::as_str).collect();
+                println!("  • Custom networks: {}", names.join(", "));
+            }
+            println!("{}", "✅ Configuration is valid".green());
+            Ok(())
+        }
+        ConfigCommands::Diff { file, json } => {
+            diff_against_defaults(file.as_deref().unwrap_or(&cli.config), *json)
+        }
+        ConfigCommands::Fingerprint { file, expect } => fingerprint_config(file, expect.as_deref()),
+        ConfigCommands::Approve { file, approver, note } => approve_config(file, approver, note.as_deref()),
+        ConfigCommands::Restore { file } => restore_backup(file),
+        ConfigCommands::Lint { file, allow, json } => lint_file(file, allow, *json),
+    }
+}
+
+/// 1inch SDK settings fields and the config fields they fill, as dotted
+/// source paths and JSON pointers. Several sources may fill one target.
+const ONEINCH_FIELDS: [(&str, &str); 15] = [
+    ("rpcUrl", "/rpc_url"),
+    ("nodeUrl", "/rpc_url"),
+    ("contracts.volatilityCalculator", "/contracts/volatility_calculator"),
+    ("contracts.twapExecutor", "/contracts/twap_executor"),
+    ("contracts.optionsCalculator", "/contracts/options_calculator"),
+    ("settings.baselineVolatilityBps", "/defaults/volatility/baseline_volatility"),
+    ("settings.maxExecutionSize", "/defaults/volatility/max_execution_size"),
+    ("settings.minExecutionSize", "/defaults/volatility/min_execution_size"),
+    ("settings.conservativeMode", "/defaults/volatility/conservative_mode"),
+    ("settings.twap.duration", "/defaults/twap/duration"),
+    ("settings.twap.intervals", "/defaults/twap/intervals"),
+    ("settings.twap.randomize", "/defaults/twap/randomize_execution"),
+    ("settings.options.expirationHours", "/defaults/options/default_expiration_hours"),
+    ("settings.options.impliedVolatilityBps", "/defaults/options/implied_volatility"),
+    ("settings.options.riskFreeRateBps", "/defaults/options/risk_free_rate"),
+];
+
+/// SDK fields naming the chain, matched against the built-in networks.
+const ONEINCH_CHAIN_FIELDS: [&str; 2] = ["networkId", "chainId"];
+
+/// Maps 1inch SDK settings onto the defaults, returning the config and the
+/// source fields that have no counterpart.
+fn config_from_oneinch(source: &Value) -> Result<(VectorPlusConfig, Vec<String>)> {
+    let mut fields = BTreeMap::new();
+    flatten("", source, &mut fields);
+    let mut config = serde_json::to_value(VectorPlusConfig::default())?;
+    let mut unmapped = Vec::new();
+    for (path, value) in &fields {
+        if ONEINCH_CHAIN_FIELDS.contains(&path.as_str()) {
+            let chain_id = value.as_u64()
+                .or_else(|| value.as_str().and_then(|id| id.parse().ok()))
+                .ok_or_else(|| VectorPlusError::Validation(format!("{} is not a chain id: {}", path, value)))?;
+            let network = crate::networks::NETWORKS.iter()
+                .find(|network| network.chain_id == chain_id)
+                .ok_or_else(|| VectorPlusError::Validation(format!(
+                    "Chain id {} is not a built-in network; add it under custom_networks after importing",
+                    chain_id
+                )))?;
+            config["network"] = Value::from(network.name);
+        } else if let Some((_, target)) = ONEINCH_FIELDS.iter().find(|(from, _)| from == path) {
+            if let Some(slot) = config.pointer_mut(target) {
+                *slot = value.clone();
+            }
+        } else {
+            unmapped.push(path.clone());
+        }
+    }
+    let config: VectorPlusConfig = serde_json::from_value(config)
+        .map_err(|e| VectorPlusError::Validation(format!("1inch settings do not fit the config: {}", e)))?;
+    Ok((config, unmapped))
+}
+
+/// Problems with an imported config that would otherwise surface only on use.
+fn check_imported(config: &VectorPlusConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+    if let Some(url) = &config.rpc_url {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            errors.push(format!("rpc_url must be an http(s) URL, got {}", url));
+        }
+    }
+    for (contract, address) in [
+        ("volatility_calculator", &config.contracts.volatility_calculator),
+        ("twap_executor", &config.contracts.twap_executor),
+        ("options_calculator", &config.contracts.options_calculator),
+    ] {
+        if let Some(address) = address {
+            if crate::eip712::parse_address(address).is_err() {
+                errors.push(format!("contracts.{} is not a 0x-prefixed 20-byte address: {}", contract, address));
+            }
+        }
+    }
+    let volatility = &config.defaults.volatility;
+    for (field, size) in [("max_execution_size", &volatility.max_execution_size), ("min_execution_size", &volatility.min_execution_size)] {
+        if size.parse::<u128>().is_err() {
+            errors.push(format!("defaults.volatility.{} must be a whole number of wei, got {}", field, size));
+        }
+    }
+    if config.defaults.twap.duration == 0 || config.defaults.twap.intervals == 0 {
+        errors.push("defaults.twap duration and intervals must be greater than zero".to_string());
+    }
+    errors
+}
+
+fn import_oneinch(cli: &crate::Cli, file: &str, force: bool) -> Result<()> {
+    println!("{}", "📥 Importing 1inch SDK settings...".cyan());
+    println!("  • Source: {}", file);
+    println!("  • Config file: {}", cli.config);
+    let source: Value = read_config(file)?;
+    if !source.is_object() {
+        return Err(VectorPlusError::Validation(format!("{} is not a JSON object", file)).into());
+    }
+    let (config, unmapped) = config_from_oneinch(&source)?;
+    let errors = check_imported(&config);
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{}", format!("❌ {}", error).red());
+        }
+        return Err(VectorPlusError::Validation("Imported configuration is invalid".to_string()).into());
+    }
+    for path in &unmapped {
+        println!("{}", format!("⚠️  {} has no Vector Plus equivalent and was skipped", path).yellow());
+    }
+
+    if !force {
+        confirm_overwrite(&cli.config, cli.assume_yes)?;
+    }
+    if let Some(backup) = backup_existing(&cli.config)? {
+        println!("  • Kept the previous {} as {}", cli.config, backup);
+    }
+    write_json_file(&cli.config, &config)?;
+    crate::history::record(&cli.config, &config);
+    println!("  • Network: {}", config.network);
+    println!("{}", "✅ Configuration imported".green());
+    Ok(())
+}
+
+/// Fills in fields the existing config lacks, e.g. ones added by an upgrade,
+/// without touching the values it already has.
+fn merge_defaults(cli: &crate::Cli) -> Result<()> {
+    println!("{}", "⚙️  Merging defaults into the existing configuration...".cyan());
+    println!("  • Config file: {}", cli.config);
+    let mut existing: Value = read_config(&cli.config)?;
+    if !existing.is_object() {
+        return Err(VectorPlusError::Validation(format!("{} is not a JSON object", cli.config)).into());
+    }
+    let defaults = serde_json::to_value(VectorPlusConfig {
+        network: cli.network.clone(),
+        ..VectorPlusConfig::default()
+    })?;
+    let mut added = Vec::new();
+    add_missing("", &mut existing, &defaults, &mut added);
+    if added.is_empty() {
+        println!("{}", "✅ Configuration already has every field".green());
+        return Ok(());
+    }
+    serde_json::from_value::<VectorPlusConfig>(existing.clone())
+        .map_err(|e| VectorPlusError::Validation(format!("{} would not be a valid config after merging: {}", cli.config, e)))?;
+
+    if let Some(backup) = backup_existing(&cli.config)? {
+        println!("  • Kept the previous {} as {}", cli.config, backup);
+    }
+    write_json_file(&cli.config, &existing)?;
+    crate::history::record(&cli.config, &existing);
+    println!("📝 Added fields:");
+    for (field, value) in &added {
+        println!("  • {}: {}", field, render(value).yellow());
+    }
+    println!("{}", format!("✅ Added {} missing fields", added.len()).green());
+    Ok(())
+}
+
+/// Copies each field of `defaults` that `target` lacks, recording its dotted
+/// path. Objects present in both are merged rather than replaced.
+fn add_missing(prefix: &str, target: &mut Value, defaults: &Value, added: &mut Vec<(String, Value)>) {
+    let (Value::Object(target), Value::Object(defaults)) = (target, defaults) else {
+        return;
+    };
+    for (key, default) in defaults {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match target.get_mut(key) {
+            Some(value) => add_missing(&path, value, default, added),
+            None if default.is_null() => {}
+            None => {
+                target.insert(key.clone(), default.clone());
+                added.push((path, default.clone()));
+            }
+        }
+    }
+}
+
+/// One review of a config, kept in its `.approval` sidecar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Approval {
+    pub fingerprint: String,
+    pub approver: String,
+    pub approved_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+pub(crate) fn approval_path(file: &str) -> String {
+    format!("{}.approval", file)
+}
+
+fn read_approvals(file: &str) -> Result<Vec<Approval>> {
+    let path = approval_path(file);
+    if !std::path::Path::new(&path).exists() {
+        return Ok(Vec::new());
+    }
+    read_config(&path)
+}
+
+fn approve_config(file: &str, approver: &str, note: Option<&str>) -> Result<()> {
+    if approver.trim().is_empty() {
+        return Err(VectorPlusError::Validation("--approver must not be empty".to_string()).into());
+    }
+    let fingerprint = fingerprint(&read_config::<Value>(file)?);
+    let mut approvals = read_approvals(file)?;
+    approvals.push(Approval {
+        fingerprint: fingerprint.clone(),
+        approver: approver.to_string(),
+        approved_at: chrono::Utc::now(),
+        note: note.map(str::to_string),
+    });
+    let path = approval_path(file);
+    write_json_file_atomic(&path, &approvals)?;
+
+    println!("{} {}", "🖊️  Approved".cyan(), file.yellow());
+    println!("  • Fingerprint: {}", fingerprint.bold());
+    println!("  • Approver: {}", approver);
+    println!("  • Recorded in {} ({} approvals)", path, approvals.len());
+    Ok(())
+}
+
+/// The latest approval of `file`'s current contents, or a validation error
+/// when it has none, e.g. because it changed after it was approved. Backs
+/// `--require-approval` on the sizing commands.
+pub(crate) fn require_approval(file: &str) -> Result<Approval> {
+    let fingerprint = fingerprint(&read_config::<Value>(file)?);
+    let approvals = read_approvals(file)?;
+    if let Some(approval) = approvals.iter().rev().find(|approval| approval.fingerprint == fingerprint) {
+        return Ok(approval.clone());
+    }
+    let message = match approvals.last() {
+        None => format!("{} has not been approved; review it and run `vector-plus config approve {}`", file, file),
+        Some(last) => format!(
+            "{} changed since it was last approved by {} at {} (approved {}, now {}); review it and run `vector-plus config approve {}`",
+            file, last.approver, last.approved_at.to_rfc3339(), last.fingerprint, fingerprint, file
+        ),
+    };
+    Err(VectorPlusError::Validation(message).into())
+}
+
+/// Swaps `file` with its backup, so running it again undoes the restore.
+fn restore_backup(file: &str) -> Result<()> {
+    let backup = backup_path(file);
+    let content = std::fs::read_to_string(&backup)
+        .map_err(|_| eyre::eyre!("No backup of {} found (expected {})", file, backup))?;
+    serde_json::from_str::<Value>(&content)
+        .map_err(|e| eyre::eyre!("Backup {} is not valid JSON either, so it was left in place: {}", backup, e))?;
+
+    println!("{} {} from {}", "♻️  Restoring".cyan(), file.yellow(), backup);
+    if std::path::Path::new(file).exists() {
+        let swap = format!("{}.restore", file);
+        std::fs::rename(file, &swap)?;
+        std::fs::rename(&backup, file)?;
+        std::fs::rename(&swap, &backup)?;
+        println!("  • The replaced version is now {} (run restore again to undo)", backup);
+    } else {
+        std::fs::rename(&backup, file)?;
+    }
+    println!("{}", "✅ Restored".green());
+    Ok(())
+}
+/// A field `config diff` reports, with its default and configured values.
+#[derive(Debug)]
+struct Difference {
+    field: String,
+    default: Value,
+    value: Value,
+}
+
+/// Fields of `config` that differ from the defaults for its network, and how
+/// many default fields it matches. Walks the
+/// fields of both, since optional ones such as `price_command` or
+/// `custom_networks` are left out when unset; a missing field reads as null.
+fn differences_from_defaults(config: &VectorPlusConfig) -> Result<(Vec<Difference>, usize)> {
+    // Compare against the defaults for the same network, so the network itself never shows up
+    let defaults = VectorPlusConfig {
+        network: config.network.clone(),
+        ..VectorPlusConfig::default()
+    };
+
+    let mut actual = BTreeMap::new();
+    let mut expected = BTreeMap::new();
+    flatten("", &serde_json::to_value(config)?, &mut actual);
+    flatten("", &serde_json::to_value(&defaults)?, &mut expected);
+
+    let fields: std::collections::BTreeSet<&String> = expected.keys().chain(actual.keys()).collect();
+    let differences: Vec<Difference> = fields
+        .into_iter()
+        .filter_map(|field| {
+            let default = expected.get(field).unwrap_or(&Value::Null);
+            let value = actual.get(field).unwrap_or(&Value::Null);
+            (value != default).then(|| Difference { field: field.clone(), default: default.clone(), value: value.clone() })
+        })
+        .collect();
+    let matching = expected
+        .iter()
+        .filter(|(field, default)| actual.get(*field).unwrap_or(&Value::Null) == *default)
+        .count();
+    Ok((differences, matching))
+}
+
+fn diff_against_defaults(file: &str, json: bool) -> Result<()> {
+    let config = crate::config::load(file)?;
+    let (differences, matching) = differences_from_defaults(&config)?;
+
+    if json {
+        let output = serde_json::json!({
+            "file": file,
+            "network": config.network,
+            "differences": differences.iter().map(|difference| serde_json::json!({
+                "field": difference.field,
+                "default": difference.default,
+                "value": difference.value,
+            })).collect::<Vec<_>>(),
+            "matching_fields": matching,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("{} {} (network: {})", "🔍 Comparing against defaults:".cyan(), file.yellow(), config.network);
+    println!();
+
+    if differences.is_empty() {
+        println!("{}", "✅ Configuration matches the defaults".green());
+        return Ok(());
+    }
+
+    println!("📝 Overridden values:");
+    for Difference { field, default, value } in &differences {
+        println!(
+            "  • {}: {} → {}",
+            field,
+            render(default).dimmed(),
+            render(value).yellow().bold()
+        );
+    }
+    println!();
+    println!("  {} overridden, {} matching defaults", differences.len(), matching);
+
+    Ok(())
+}
+
+/// One resolved setting for `config show --effective`.
+struct EffectiveSetting {
+    name: String,
+    value: Value,
+    source: Source,
+}
+
+impl EffectiveSetting {
+    fn new(name: impl Into<String>, value: impl Into<Value>, source: Source) -> Self {
+        Self { name: name.into(), value: value.into(), source }
     }
-}
\ No newline at end of file
+}
+
+/// Every setting a command would use: the global flags as clap resolved them
+/// in [`crate::Cli::resolve`], then the config file's fields, or the built-in
+/// defaults when `--config` does not exist. The RPC URL falls back from the
+/// flag to the network's `custom_networks` entry to the file's `rpc_url`, as
+/// RPC commands do.
+fn effective_settings(cli: &crate::Cli, config: &VectorPlusConfig, from_file: bool) -> Result<Vec<EffectiveSetting>> {
+    let file_source = if from_file { Source::File } else { Source::Default };
+    let mut settings = vec![
+        EffectiveSetting::new("network", cli.network.as_str(), cli.source("network")),
+        EffectiveSetting::new("config", cli.config.as_str(), cli.source("config")),
+    ];
+
+    let custom_rpc_url = config.custom_networks.get(&cli.network).and_then(|network| network.rpc_url.as_deref());
+    settings.push(match (&cli.rpc_url, custom_rpc_url, &config.rpc_url) {
+        (Some(url), _, _) => EffectiveSetting::new("rpc_url", url.as_str(), cli.source("rpc_url")),
+        (None, Some(url), _) => EffectiveSetting::new(format!("rpc_url (custom_networks.{})", cli.network), url, Source::File),
+        (None, None, Some(url)) => EffectiveSetting::new("rpc_url", url.as_str(), file_source),
+        (None, None, None) => EffectiveSetting::new("rpc_url", Value::Null, Source::Default),
+    });
+
+    settings.push(EffectiveSetting::new("precision", cli.precision, cli.source("precision")));
+    let time_format = clap::ValueEnum::to_possible_value(&cli.time_format).map(|value| value.get_name().to_string());
+    settings.push(EffectiveSetting::new("time_format", time_format, cli.source("time_format")));
+    settings.push(EffectiveSetting::new("verbose", cli.verbose, cli.source("verbose")));
+    settings.push(EffectiveSetting::new("assume_yes", cli.assume_yes, cli.source("assume_yes")));
+    settings.push(if cli.no_color {
+        EffectiveSetting::new("color", "never", cli.source("no_color"))
+    } else {
+        let color = clap::ValueEnum::to_possible_value(&cli.color).map(|value| value.get_name().to_string());
+        EffectiveSetting::new("color", color, cli.source("color"))
+    });
+
+    // The file's own network and rpc_url are covered above
+    let mut fields = BTreeMap::new();
+    flatten("", &serde_json::to_value(config)?, &mut fields);
+    for (name, value) in fields {
+        if name != "network" && name != "rpc_url" {
+            settings.push(EffectiveSetting::new(name, value, file_source));
+        }
+    }
+    Ok(settings)
+}
+
+fn show_effective(cli: &crate::Cli, json: bool) -> Result<()> {
+    let (config, from_file) = match crate::config::load(&cli.config) {
+        Ok(config) => (config, true),
+        Err(e) if e.is::<ConfigNotFound>() => {
+            if !json {
+                eprintln!("{} {}; using built-in defaults", "⚠️ ".yellow(), e);
+            }
+            (VectorPlusConfig::default(), false)
+        }
+        Err(e) => return Err(e),
+    };
+    let settings = effective_settings(cli, &config, from_file)?;
+
+    let mut table = Table::new()
+        .column("setting", "Setting", Align::Left)
+        .column("value", "Value", Align::Left)
+        .column("source", "Source", Align::Left);
+    for setting in &settings {
+        let cells = vec![
+            Cell::text(setting.name.as_str()),
+            Cell::new(render(&setting.value), setting.value.clone()),
+            Cell::text(setting.source.name()),
+        ];
+        match setting.source {
+            Source::Default => table.row(cells),
+            _ => table.colored_row(cells, Color::Yellow),
+        }
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&table.to_json())?);
+        return Ok(());
+    }
+
+    println!("{}", "📋 Effective Vector Plus Configuration:".cyan());
+    println!();
+    println!("{}", table.render());
+    println!();
+    println!("  Precedence, highest first: flag, env, env-file, file, default");
+    if from_file && config.network != cli.network && cli.source("network") == Source::Default {
+        println!(
+            "  {} {} sets network {}, which is not applied; pass --network or set VECTOR_PLUS_NETWORK",
+            "ℹ️ ".blue(), cli.config, config.network
+        );
+    }
+    Ok(())
+}
+
+fn fingerprint_config(file: &str, expect: Option<&str>) -> Result<()> {
+    let value: Value = read_config(file)?;
+    let fingerprint = fingerprint(&value);
+
+    println!("{} {}", "🔏 Fingerprint of".cyan(), file.yellow());
+    println!("  {}", fingerprint.bold());
+
+    if let Some(expected) = expect {
+        if !expected.trim_start_matches("0x").eq_ignore_ascii_case(fingerprint.trim_start_matches("0x")) {
+            println!("{}", "❌ Does not match the expected fingerprint".red());
+            return Err(VectorPlusError::Validation(format!("Fingerprint mismatch: expected {}, got {}", expected, fingerprint)).into());
+        }
+        println!("{}", "✅ Matches the expected fingerprint".green());
+    }
+    Ok(())
+}
+
+/// Keccak-256 of the normalized config, as `0x` hex.
+pub(crate) fn fingerprint(value: &Value) -> String {
+    let canonical = normalize(value).to_string();
+    format!("0x{}", hex::encode(crate::eip712::keccak256(canonical.as_bytes())))
+}
+
+/// Canonical form of a config: keys sorted, volatile metadata removed and
+/// whole-number floats written as integers, so `5.0` and `5` compare equal.
+fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<&String, Value> = map
+                .iter()
+                .filter(|(key, _)| !VOLATILE_FIELDS.contains(&key.as_str()))
+                .map(|(key, child)| (key, normalize(child)))
+                .collect();
+            Value::Object(sorted.into_iter().map(|(key, child)| (key.clone(), child)).collect())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(normalize).collect()),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() < 9.0e15 => Value::from(f as i64),
+            _ => value.clone(),
+        },
+        other => other.clone(),
+    }
+}
+
+/// Flattens nested objects into dotted field paths, e.g. `defaults.twap.intervals`.
+pub(crate) fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&path, child, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+fn render(value: &Value) -> String {
+    match value {
+        Value::Null => "(unset)".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// How much a lint matters; neither fails the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum LintSeverity {
+    Warning,
+    Info,
+}
+
+/// A best-practice check on a strategy config that passes validation.
+struct LintRule {
+    id: &'static str,
+    severity: LintSeverity,
+    explanation: &'static str,
+}
+
+const LINT_RULES: [LintRule; 9] = [
+    LintRule {
+        id: "aggressive-high-baseline",
+        severity: LintSeverity::Warning,
+        explanation: "Conservative mode is off although the baseline is already volatile, so sizes are only cut after a further rise",
+    },
+    LintRule {
+        id: "emergency-near-current",
+        severity: LintSeverity::Warning,
+        explanation: "Current volatility is within 10% of the emergency threshold, so a small move halts execution",
+    },
+    LintRule {
+        id: "threshold-near-baseline",
+        severity: LintSeverity::Info,
+        explanation: "The volatility threshold is within 10% of baseline, so ordinary noise flips the regime to ELEVATED",
+    },
+    LintRule {
+        id: "wide-execution-range",
+        severity: LintSeverity::Info,
+        explanation: "Max execution size is over 100x the min, so the clamps rarely bound the adjusted size",
+    },
+    LintRule {
+        id: "single-interval",
+        severity: LintSeverity::Warning,
+        explanation: "A TWAP with one interval fills the whole order at once, with no time averaging",
+    },
+    LintRule {
+        id: "predictable-schedule",
+        severity: LintSeverity::Info,
+        explanation: "Fills land at fixed times without randomize_execution, which makes the schedule easy to front-run",
+    },
+    LintRule {
+        id: "loose-slippage",
+        severity: LintSeverity::Warning,
+        explanation: "Max slippage above 3% lets each fill trade far from the quoted price",
+    },
+    LintRule {
+        id: "option-expires-soon",
+        severity: LintSeverity::Warning,
+        explanation: "The option expires within an hour, leaving little time for it to be filled",
+    },
+    LintRule {
+        id: "zero-premium",
+        severity: LintSeverity::Warning,
+        explanation: "A premium of zero gives the option away",
+    },
+];
+
+const LINT_SLIPPAGE_BPS: u64 = 300;
+const LINT_HIGH_BASELINE: Bps = Bps::from_bps(500);
+const LINT_EXECUTION_RANGE_MULTIPLE: u128 = 100;
+
+/// One rule that fired on a config.
+#[derive(Debug, Serialize)]
+struct LintFinding {
+    id: &'static str,
+    severity: LintSeverity,
+    /// Where in the file it applies, for combined strategies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    section: Option<&'static str>,
+    message: String,
+    explanation: &'static str,
+}
+
+/// Collects findings for one file, dropping those `--allow`ed.
+struct Linter<'a> {
+    allow: &'a [String],
+    section: Option<&'static str>,
+    findings: Vec<LintFinding>,
+    suppressed: usize,
+}
+
+impl Linter<'_> {
+    fn flag(&mut self, id: &'static str, message: String) {
+        if self.allow.iter().any(|allowed| allowed == id) {
+            self.suppressed += 1;
+            return;
+        }
+        let rule = LINT_RULES.iter().find(|rule| rule.id == id).expect("lint ids come from LINT_RULES");
+        self.findings.push(LintFinding {
+            id,
+            severity: rule.severity,
+            section: self.section,
+            message,
+            explanation: rule.explanation,
+        });
+    }
+
+    fn volatility(&mut self, config: &VolatilityConfig) {
+        if !config.conservative_mode && config.baseline_volatility >= LINT_HIGH_BASELINE {
+            self.flag(
+                "aggressive-high-baseline",
+                format!("conservative_mode is off with baseline volatility {}bps", config.baseline_volatility),
+            );
+        }
+        let (current, emergency) = (config.current_volatility.as_f64(), config.emergency_threshold.as_f64());
+        if current <= emergency && emergency - current < emergency * 0.1 {
+            self.flag(
+                "emergency-near-current",
+                format!("current volatility {}bps is close to the emergency threshold {}bps", config.current_volatility, config.emergency_threshold),
+            );
+        }
+        if config.volatility_threshold.as_f64() <= config.baseline_volatility.as_f64() * 1.1 {
+            self.flag(
+                "threshold-near-baseline",
+                format!("volatility threshold {}bps vs baseline {}bps", config.volatility_threshold, config.baseline_volatility),
+            );
+        }
+        if let Ok((min_size, max_size)) = config.checked_execution_limits() {
+            if min_size.wei() > 0 && max_size.wei() / min_size.wei() > LINT_EXECUTION_RANGE_MULTIPLE {
+                self.flag(
+                    "wide-execution-range",
+                    format!("execution sizes range from {} to {} ETH", min_size.format_eth(4), max_size.format_eth(4)),
+                );
+            }
+        }
+    }
+
+    fn twap(&mut self, config: &TwapConfig) {
+        if config.intervals == 1 {
+            self.flag("single-interval", "intervals is 1".to_string());
+        }
+        if config.intervals > 1 && !config.randomize_execution {
+            self.flag("predictable-schedule", format!("{} intervals at fixed times", config.intervals));
+        }
+        if config.max_slippage_bps > LINT_SLIPPAGE_BPS {
+            self.flag("loose-slippage", format!("max slippage is {}bps", config.max_slippage_bps));
+        }
+    }
+
+    fn option(&mut self, config: &OptionConfig) {
+        let now = chrono::Utc::now().timestamp() as u64;
+        if config.expiration > now && config.expiration - now < 3600 {
+            self.flag("option-expires-soon", format!("expires in {} minutes", (config.expiration - now) / 60));
+        }
+        if config.premium == 0.0 {
+            self.flag("zero-premium", "premium is 0".to_string());
+        }
+    }
+}
+
+/// Runs every lint that applies to the file's kind of config.
+fn lint_config(value: Value, allow: &[String]) -> Result<(StrategyKind, Linter<'_>)> {
+    let kind = StrategyKind::detect(&value).ok_or_else(|| {
+        VectorPlusError::Validation("Not a volatility, TWAP, combined or option config".to_string())
+    })?;
+    let mut linter = Linter { allow, section: None, findings: Vec::new(), suppressed: 0 };
+    match kind {
+        StrategyKind::Volatility => linter.volatility(&serde_json::from_value(value)?),
+        StrategyKind::Twap => linter.twap(&serde_json::from_value(value)?),
+        StrategyKind::Option => linter.option(&serde_json::from_value(value)?),
+        StrategyKind::Combined => {
+            let strategy: CombinedStrategy = serde_json::from_value(value)?;
+            linter.section = Some("twap");
+            linter.twap(&strategy.twap);
+            linter.section = Some("volatility");
+            linter.volatility(&strategy.volatility);
+        }
+    }
+    Ok((kind, linter))
+}
+
+fn lint_file(file: &str, allow: &[String], json: bool) -> Result<()> {
+    let unknown: Vec<&str> = allow.iter()
+        .map(String::as_str)
+        .filter(|id| !LINT_RULES.iter().any(|rule| rule.id == *id))
+        .collect();
+    if !unknown.is_empty() {
+        let known: Vec<&str> = LINT_RULES.iter().map(|rule| rule.id).collect();
+        return Err(VectorPlusError::Validation(format!(
+            "Unknown lint id: {} (known: {})", unknown.join(", "), known.join(", ")
+        )).into());
+    }
+
+    let (kind, linter) = lint_config(read_config(file)?, allow)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&linter.findings)?);
+        return Ok(());
+    }
+
+    println!("{} {} ({} config)", "🧹 Linting:".cyan(), file.yellow(), kind.name());
+    for finding in &linter.findings {
+        let location = finding.section.map(|section| format!("{}: ", section)).unwrap_or_default();
+        let line = format!("[{}] {}{}", finding.id, location, finding.message);
+        match finding.severity {
+            LintSeverity::Warning => println!("  {} {}", "⚠️ ".yellow(), line.yellow()),
+            LintSeverity::Info => println!("  {} {}", "ℹ️ ".blue(), line),
+        }
+        println!("      {}", finding.explanation.dimmed());
+    }
+    if !linter.findings.is_empty() {
+        println!();
+    }
+    let warnings = linter.findings.iter().filter(|finding| finding.severity == LintSeverity::Warning).count();
+    println!(
+        "📋 Lint: {} warnings, {} info{}",
+        warnings,
+        linter.findings.len() - warnings,
+        if linter.suppressed > 0 { format!(", {} allowed", linter.suppressed) } else { String::new() }
+    );
+    if !linter.findings.is_empty() {
+        println!("  {} Silence a lint with --allow <id>", "ℹ️ ".blue());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_ignores_key_order_and_timestamps() {
+        let a: Value = serde_json::from_str(
+            r#"{"baseline_volatility":300,"max_execution_size":"5","nested":{"x":1,"y":[2.0,3]},"last_update_time":1}"#,
+        ).unwrap();
+        let b: Value = serde_json::from_str(
+            r#"{"nested":{"y":[2,3],"x":1.0},"last_update_time":999,"max_execution_size":"5","baseline_volatility":300}"#,
+        ).unwrap();
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+
+        let changed: Value = serde_json::from_str(
+            r#"{"baseline_volatility":301,"max_execution_size":"5","nested":{"x":1,"y":[2,3]}}"#,
+        ).unwrap();
+        assert_ne!(fingerprint(&a), fingerprint(&changed));
+    }
+
+    #[test]
+    fn diff_reports_fields_the_defaults_leave_out() {
+        let mut config = VectorPlusConfig::default();
+        let (differences, matching) = differences_from_defaults(&config).unwrap();
+        assert!(differences.is_empty(), "{:?}", differences);
+        assert!(matching > 0);
+
+        config.custom_networks.insert("devnet".to_string(), crate::config::CustomNetwork {
+            chain_id: 31337,
+            rpc_url: Some("http://127.0.0.1:8545".to_string()),
+            contracts: Default::default(),
+            gas_token: "ETH".to_string(),
+            block_time_ms: 2_000,
+        });
+        let (differences, after) = differences_from_defaults(&config).unwrap();
+        let fields: Vec<&str> = differences.iter().map(|difference| difference.field.as_str()).collect();
+        assert_eq!(fields, [
+            "custom_networks.devnet.block_time_ms",
+            "custom_networks.devnet.chain_id",
+            "custom_networks.devnet.gas_token",
+            "custom_networks.devnet.rpc_url",
+        ]);
+        assert_eq!(differences[1].default, Value::Null);
+        assert_eq!(differences[1].value, 31337);
+        assert_eq!(after, matching);
+    }
+
+    #[test]
+    fn diff_reports_a_configured_price_command() {
+        let config = VectorPlusConfig {
+            price_command: Some("./fetch-price.sh {symbol}".to_string()),
+            ..VectorPlusConfig::default()
+        };
+        let (differences, _) = differences_from_defaults(&config).unwrap();
+        assert_eq!(differences.len(), 1, "{:?}", differences);
+        assert_eq!(differences[0].field, "price_command");
+        assert_eq!(differences[0].default, Value::Null);
+        assert_eq!(differences[0].value, "./fetch-price.sh {symbol}");
+    }
+
+    #[test]
+    fn merging_adds_only_missing_fields() {
+        let mut existing = serde_json::json!({
+            "network": "polygon",
+            "defaults": { "twap": { "intervals": 6 } },
+            "legacy": true
+        });
+        let defaults = serde_json::json!({
+            "network": "mainnet",
+            "rpc_url": null,
+            "defaults": { "twap": { "intervals": 12, "duration": 3600 }, "volatility": { "baseline_volatility": 300 } }
+        });
+        let mut added = Vec::new();
+        add_missing("", &mut existing, &defaults, &mut added);
+        let fields: Vec<&str> = added.iter().map(|(field, _)| field.as_str()).collect();
+        assert_eq!(fields, ["defaults.twap.duration", "defaults.volatility"]);
+        assert_eq!(existing["network"], "polygon");
+        assert_eq!(existing["defaults"]["twap"]["intervals"], 6);
+        assert_eq!(existing["legacy"], true);
+
+        // A second merge has nothing left to add
+        added.clear();
+        add_missing("", &mut existing, &defaults, &mut added);
+        assert!(added.is_empty());
+    }
+
+    #[test]
+    fn imports_oneinch_settings() {
+        let source: Value = serde_json::from_str(r#"{
+            "networkId": 137,
+            "nodeUrl": "https://polygon-rpc.com",
+            "contracts": {"twapExecutor": "0x1111111254eeb25477b68fb85ed929f73a960582", "limitOrderProtocol": "0x11"},
+            "settings": {"baselineVolatilityBps": 450, "twap": {"intervals": 6}}
+        }"#).unwrap();
+        let (config, unmapped) = config_from_oneinch(&source).unwrap();
+        assert_eq!(config.network, "polygon");
+        assert_eq!(config.rpc_url.as_deref(), Some("https://polygon-rpc.com"));
+        assert_eq!(config.defaults.volatility.baseline_volatility, Bps::from_bps(450));
+        assert_eq!(config.defaults.twap.intervals, 6);
+        // Fields the source lacks keep their defaults
+        assert_eq!(config.defaults.twap.duration, VectorPlusConfig::default().defaults.twap.duration);
+        assert_eq!(unmapped, vec!["contracts.limitOrderProtocol"]);
+        assert!(check_imported(&config).is_empty());
+
+        let unknown: Value = serde_json::from_str(r#"{"chainId": 56}"#).unwrap();
+        assert!(config_from_oneinch(&unknown).is_err());
+        let bad: Value = serde_json::from_str(r#"{"contracts": {"optionsCalculator": "0x1234"}, "settings": {"twap": {"intervals": 0}}}"#).unwrap();
+        assert_eq!(check_imported(&config_from_oneinch(&bad).unwrap().0).len(), 2);
+    }
+
+    #[test]
+    fn effective_settings_name_their_source() {
+        use clap::CommandFactory;
+        let matches = crate::Cli::command()
+            .try_get_matches_from(["vector-plus", "--network", "mybase", "--precision", "4", "config", "show", "--effective"])
+            .unwrap();
+        let cli = crate::Cli::from_matches(&matches, &[]).unwrap();
+        let mut config = VectorPlusConfig { rpc_url: Some("http://file.example".to_string()), ..VectorPlusConfig::default() };
+        let settings = effective_settings(&cli, &config, true).unwrap();
+        let find = |settings: &[EffectiveSetting], name: &str| {
+            let setting = settings.iter().find(|setting| setting.name == name).unwrap();
+            (setting.value.clone(), setting.source)
+        };
+        assert_eq!(find(&settings, "network"), (Value::from("mybase"), Source::Flag));
+        assert_eq!(find(&settings, "precision"), (Value::from(4), Source::Flag));
+        assert_eq!(find(&settings, "verbose"), (Value::from(false), Source::Default));
+        assert_eq!(find(&settings, "rpc_url"), (Value::from("http://file.example"), Source::File));
+        assert_eq!(find(&settings, "defaults.twap.intervals").1, Source::File);
+
+        // The selected custom network's RPC URL wins over the file's
+        config.custom_networks.insert("mybase".to_string(), crate::config::CustomNetwork {
+            chain_id: 84532,
+            rpc_url: Some("http://custom.example".to_string()),
+            contracts: Default::default(),
+            gas_token: "ETH".to_string(),
+            block_time_ms: 2000,
+        });
+        let settings = effective_settings(&cli, &config, true).unwrap();
+        assert_eq!(find(&settings, "rpc_url (custom_networks.mybase)"), (Value::from("http://custom.example"), Source::File));
+
+        // Without a file, its fields are the built-in defaults
+        let settings = effective_settings(&cli, &VectorPlusConfig::default(), false).unwrap();
+        assert_eq!(find(&settings, "rpc_url"), (Value::Null, Source::Default));
+        assert_eq!(find(&settings, "defaults.twap.intervals").1, Source::Default);
+    }
+
+    #[test]
+    fn lints_each_section_of_a_combined_strategy() {
+        let strategy = serde_json::json!({
+            "twap": {
+                "start_time": 0, "duration": 3600, "intervals": 1, "executed_intervals": 0,
+                "randomize_execution": false, "min_execution_gap": 60, "max_slippage_bps": 50
+            },
+            "volatility": {
+                "baseline_volatility": 600, "current_volatility": 800, "volatility_threshold": 900,
+                "emergency_threshold": 850, "conservative_mode": false,
+                "max_execution_size": "5000000000000000000", "min_execution_size": "100000000000000000",
+                "last_update_time": 0
+            }
+        });
+        let allow = ["emergency-near-current".to_string()];
+        let (kind, linter) = lint_config(strategy, &allow).unwrap();
+        assert_eq!(kind, StrategyKind::Combined);
+        let ids: Vec<(&str, Option<&str>)> = linter.findings.iter().map(|finding| (finding.id, finding.section)).collect();
+        assert_eq!(ids, [("single-interval", Some("twap")), ("aggressive-high-baseline", Some("volatility"))]);
+        assert_eq!(linter.suppressed, 1);
+    }
+
+    #[test]
+    fn approval_follows_the_fingerprint() {
+        let dir = std::env::temp_dir().join(format!("vector-plus-approval-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("strategy.json");
+        let file = file.to_str().unwrap();
+        std::fs::write(file, r#"{"baseline_volatility":300,"last_update_time":1}"#).unwrap();
+        assert!(require_approval(file).is_err());
+
+        approve_config(file, "alice", None).unwrap();
+        // Timestamps are outside the fingerprint, so touching them keeps the approval
+        std::fs::write(file, r#"{"last_update_time":2,"baseline_volatility":300.0}"#).unwrap();
+        assert_eq!(require_approval(file).unwrap().approver, "alice");
+
+        std::fs::write(file, r#"{"baseline_volatility":900}"#).unwrap();
+        let err = require_approval(file).unwrap_err().to_string();
+        assert!(err.contains("changed since it was last approved by alice"), "{}", err);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
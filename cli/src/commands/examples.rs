@@ -1,5 +1,33 @@
+use clap::Subcommand;
 use colored::*;
 use eyre::Result;
+use std::fs;
+use std::path::Path;
+
+use super::combined::{check_combined_strategy, CombinedStrategy};
+use super::options::{check_option_config, OptionConfig};
+use super::twap::{check_twap_config, TwapConfig};
+use super::volatility::{check_volatility_config, VolatilityConfig};
+use crate::amount::Amount;
+use crate::bps::Bps;
+use crate::utils::{confirm_overwrite, write_json_file};
+
+#[derive(Subcommand)]
+pub enum ExamplesCommands {
+    /// Write a working example config of each strategy type into a directory
+    Export {
+        /// Directory to write the example configs into
+        #[arg(long)]
+        out: String,
+    },
+}
+
+pub async fn handle_command(command: Option<&ExamplesCommands>, cli: &crate::Cli) -> Result<()> {
+    match command {
+        None => show_examples().await,
+        Some(ExamplesCommands::Export { out }) => export_examples(out, cli.assume_yes),
+    }
+}
 
 pub async fn show_examples() -> Result<()> {
     println!("{}", "📚 Vector Plus Examples".cyan().bold());
@@ -25,7 +53,12 @@ pub async fn show_examples() -> Result<()> {
     println!("  {} vector-plus combined create --twap-duration 180 --twap-intervals 18 --volatility-threshold 600", "•".blue());
     println!();
     
+    println!("{}", "🔎 Strategy Inspection Examples:".yellow().bold());
+    println!("  {} vector-plus strategy inspect combined-strategy.json", "•".blue());
+    println!();
+    
     println!("{}", "⚙️  Configuration Examples:".yellow().bold());
+    println!("  {} vector-plus examples export --out examples", "•".blue());
     println!("  {} vector-plus config init --force", "•".blue());
     println!("  {} vector-plus config show", "•".blue());
     println!("  {} vector-plus --network polygon --verbose volatility create-config", "•".blue());
@@ -37,4 +70,130 @@ pub async fn show_examples() -> Result<()> {
     println!("  {} Run 'vector-plus interactive' for guided setup", "•".cyan());
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// An example config, serialized, with the validator findings it raises.
+struct Example {
+    file: &'static str,
+    kind: &'static str,
+    json: serde_json::Value,
+    warnings: Vec<String>,
+    errors: Vec<String>,
+}
+
+/// Builds one config of each strategy type from the values the printed
+/// examples use, paired with what its `validate` command reports.
+fn example_configs() -> Result<Vec<Example>> {
+    let mut volatility = VolatilityConfig::new(
+        Bps::from_bps(300),
+        Bps::from_bps(500),
+        Amount::from_wei(5_000_000_000_000_000_000),
+        Amount::from_wei(100_000_000_000_000_000),
+        true,
+    );
+    volatility.hysteresis_margin = Bps::from_bps(50);
+    let twap = TwapConfig::new(120, 12, true);
+
+    let mut combined_volatility = volatility.clone();
+    combined_volatility.conservative_mode = false;
+    combined_volatility.hysteresis_margin = Bps::ZERO;
+    let combined = CombinedStrategy { twap: TwapConfig::new(180, 18, true), volatility: combined_volatility };
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    let option = OptionConfig {
+        is_call: true,
+        strike_price: 2100.0,
+        expiration: now + 168 * 3600,
+        premium: 50.0,
+        implied_volatility: 8000,
+        creation_time: now,
+        quote_symbol: "USDC".to_string(),
+        quote_decimals: 6,
+    };
+
+    let (combined_warnings, combined_errors) = check_combined_strategy(&combined);
+    let (volatility_warnings, volatility_errors) = check_volatility_config(&volatility);
+    let (twap_warnings, twap_errors) = check_twap_config(&twap);
+    let (option_warnings, option_errors) = check_option_config(&option);
+    Ok(vec![
+        Example {
+            file: "volatility-config.json",
+            kind: "volatility",
+            json: serde_json::to_value(&volatility)?,
+            warnings: volatility_warnings,
+            errors: volatility_errors,
+        },
+        Example {
+            file: "twap-config.json",
+            kind: "twap",
+            json: serde_json::to_value(&twap)?,
+            warnings: twap_warnings,
+            errors: twap_errors,
+        },
+        Example {
+            file: "combined-strategy.json",
+            kind: "combined",
+            json: serde_json::to_value(&combined)?,
+            warnings: combined_warnings,
+            errors: combined_errors,
+        },
+        Example {
+            file: "option-config.json",
+            kind: "options",
+            json: serde_json::to_value(&option)?,
+            warnings: option_warnings,
+            errors: option_errors,
+        },
+    ])
+}
+
+/// Writes the example configs into `out`, after checking every one against
+/// its validator so nothing is written if any would fail.
+fn export_examples(out: &str, assume_yes: bool) -> Result<()> {
+    println!("{} {}", "📚 Exporting example configs to:".cyan(), out.yellow());
+
+    let examples = example_configs()?;
+    if let Some(example) = examples.iter().find(|example| !example.errors.is_empty()) {
+        return Err(eyre::eyre!(
+            "Example {} does not validate: {}",
+            example.file,
+            example.errors.join("; ")
+        ));
+    }
+
+    fs::create_dir_all(out).map_err(|_| eyre::eyre!("Could not create directory: {}", out))?;
+    for example in &examples {
+        let path = Path::new(out).join(example.file).display().to_string();
+        confirm_overwrite(&path, assume_yes)?;
+        write_json_file(&path, &example.json)?;
+        crate::history::record(&path, &example.json);
+        println!("  • {}", path);
+        for warning in &example.warnings {
+            println!("    {}", warning.yellow());
+        }
+    }
+
+    println!();
+    println!("{} {} example configs", "✅ Exported".green(), examples.len());
+    println!();
+    println!("{}", "🚀 Next steps:".bold());
+    for example in &examples {
+        let path = Path::new(out).join(example.file);
+        println!("  {} vector-plus {} validate {}", "•".blue(), example.kind, path.display());
+    }
+    println!("  {} vector-plus volatility calculate --amount 2.5 --config {}", "•".blue(), Path::new(out).join("volatility-config.json").display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_configs_pass_their_validators() {
+        for example in example_configs().unwrap() {
+            assert!(example.errors.is_empty(), "{}: {:?}", example.file, example.errors);
+            assert!(example.warnings.is_empty(), "{}: {:?}", example.file, example.warnings);
+        }
+    }
+}
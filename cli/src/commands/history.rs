@@ -0,0 +1,73 @@
+use clap::Subcommand;
+use colored::*;
+use eyre::Result;
+use std::path::Path;
+
+use crate::utils::table::{Align, Cell, Table};
+use crate::utils::{confirm, format_time, TimeFormat};
+
+#[derive(Subcommand)]
+pub enum HistoryCommands {
+    /// Delete the history log
+    Clear,
+}
+
+pub fn handle_command(command: Option<&HistoryCommands>, limit: usize, cli: &crate::Cli) -> Result<()> {
+    let path = cli.history_path().ok_or_else(|| {
+        eyre::eyre!("No history location: set HOME or XDG_CONFIG_HOME, or pass --history-file")
+    })?;
+    match command {
+        None => show_history(&path, limit, cli.time_format),
+        Some(HistoryCommands::Clear) => clear_history(&path, cli.assume_yes),
+    }
+}
+
+fn show_history(path: &Path, limit: usize, time_format: TimeFormat) -> Result<()> {
+    let (entries, unreadable) = crate::history::read(path)?;
+    if entries.is_empty() {
+        println!("No history recorded yet in {}", path.display());
+        return Ok(());
+    }
+
+    let shown = &entries[entries.len().saturating_sub(limit)..];
+    println!(
+        "{} {} (last {} of {})",
+        "📜 History in".cyan(),
+        path.display().to_string().yellow(),
+        shown.len(),
+        entries.len()
+    );
+    println!();
+    let mut table = Table::new()
+        .column("time", "Time", Align::Left)
+        .column("command", "Command", Align::Left)
+        .column("output", "Output", Align::Left)
+        .column("fingerprint", "Fingerprint", Align::Left);
+    for entry in shown {
+        table.row(vec![
+            Cell::text(format_time(entry.timestamp.timestamp() as u64, time_format)),
+            Cell::text(entry.command.as_str()),
+            Cell::text(entry.output.as_str()),
+            Cell::text(entry.fingerprint.as_str()),
+        ]);
+    }
+    println!("{}", table.render());
+    if unreadable > 0 {
+        println!("{}", format!("⚠️  Skipped {} unreadable lines", unreadable).yellow());
+    }
+    Ok(())
+}
+
+fn clear_history(path: &Path, assume_yes: bool) -> Result<()> {
+    let (entries, _) = crate::history::read(path)?;
+    if !path.exists() {
+        println!("No history to clear in {}", path.display());
+        return Ok(());
+    }
+    if !confirm(&format!("Delete {} history entries in {}?", entries.len(), path.display()), false, assume_yes)? {
+        return Err(eyre::eyre!("History not cleared"));
+    }
+    std::fs::remove_file(path).map_err(|e| eyre::eyre!("Could not delete {}: {}", path.display(), e))?;
+    println!("{} {} entries", "🗑️  Cleared".green(), entries.len());
+    Ok(())
+}
@@ -2,7 +2,9 @@ use dialoguer::{theme::ColorfulTheme, Select, Input, Confirm};
 use colored::*;
 use eyre::Result;
 
-pub async fn run_interactive_mode(_cli: &crate::Cli) -> Result<()> {
+use super::volatility::{create_volatility_config, VolatilityConfigInputs};
+
+pub async fn run_interactive_mode(cli: &crate::Cli) -> Result<()> {
     println!("{}", "🎯 Vector Plus Interactive Mode".cyan().bold());
     println!();
 
@@ -22,7 +24,7 @@ pub async fn run_interactive_mode(_cli: &crate::Cli) -> Result<()> {
         .interact()?;
 
     match selection {
-        0 => build_volatility_strategy().await,
+        0 => build_volatility_strategy(cli).await,
         1 => build_twap_strategy().await,
         2 => build_options_strategy().await,
         3 => build_combined_strategy().await,
@@ -34,41 +36,12 @@ pub async fn run_interactive_mode(_cli: &crate::Cli) -> Result<()> {
     }
 }
 
-async fn build_volatility_strategy() -> Result<()> {
+async fn build_volatility_strategy(cli: &crate::Cli) -> Result<()> {
     println!("{}", "🌊 Building Volatility Strategy".blue().bold());
     println!();
     
-    let baseline: u64 = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("Baseline volatility (basis points)")
-        .default(300)
-        .interact()?;
-    
-    let current: u64 = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("Current volatility (basis points)")
-        .default(350)
-        .interact()?;
-    
-    let max_size: f64 = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("Maximum execution size (ETH)")
-        .default(5.0)
-        .interact()?;
-    
-    let conservative = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enable conservative mode?")
-        .default(false)
-        .interact()?;
-    
-    println!();
-    println!("{}", "✅ Volatility strategy configured!".green());
-    println!("📁 Run: vector-plus volatility create-config \\");
-    println!("       --baseline-volatility {} \\", baseline);
-    println!("       --current-volatility {} \\", current);
-    println!("       --max-execution-size {} {}", max_size, if conservative { "\\" } else { "" });
-    if conservative {
-        println!("       --conservative-mode");
-    }
-    
-    Ok(())
+    let inputs = VolatilityConfigInputs::default().prompt(cli.assume_yes)?;
+    create_volatility_config(&inputs, cli.precision, cli.verbose).await
 }
 
 async fn build_twap_strategy() -> Result<()> {
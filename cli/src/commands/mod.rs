@@ -3,11 +3,22 @@ pub mod twap;
 pub mod options;
 pub mod combined;
 pub mod config;
+pub mod strategy;
+pub mod bundle;
+pub mod order;
+pub mod report;
+pub mod simulate;
 pub mod examples;
 pub mod interactive;
+pub mod completions;
+pub mod history;
 
 pub use volatility::VolatilityCommands;
 pub use twap::TwapCommands;
 pub use options::OptionsCommands;
 pub use combined::CombinedCommands;
-pub use config::ConfigCommands;
\ No newline at end of file
+pub use config::ConfigCommands;
+pub use strategy::StrategyCommands;
+pub use order::OrderCommands;
+pub use examples::ExamplesCommands;
+pub use history::HistoryCommands;
\ No newline at end of file
@@ -1,58 +1,979 @@
-use clap::Subcommand;
+use clap::{Args, Subcommand, ValueEnum};
 use colored::*;
 use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::strategy::{Findings, ValidationSummary};
+use super::volatility::VolatilityConfig;
+use crate::bps::Bps;
+use crate::config::OptionsDefaults;
+use crate::error::VectorPlusError;
+use crate::utils::{add_key_findings, format_amount, format_time, output_name, read_config, status, verbose, write_created_config, TimeFormat, STDOUT_PATH};
+use crate::utils::table::{Align, Cell, OutputFormat, Table};
+
+const DEFAULT_QUOTE_SYMBOL: &str = "USDC";
+const DEFAULT_QUOTE_DECIMALS: u32 = 6;
+/// Beyond this, f64 strikes and premiums cannot carry the precision anyway
+const MAX_QUOTE_DECIMALS: u32 = 18;
 
 #[derive(Subcommand)]
 pub enum OptionsCommands {
     /// Create call option configuration
     CreateCall {
-        /// Strike price in USDC
-        #[arg(long)]
-        strike_price: f64,
-        
-        /// Expiration in hours
-        #[arg(long)]
-        expiration_hours: u64,
-        
-        /// Premium in USDC
-        #[arg(long)]
-        premium: f64,
+        #[command(flatten)]
+        option: OptionArgs,
+
+        /// Output file
+        #[arg(short, long, default_value = "option-config.json")]
+        output: String,
+    },
+
+    /// Create put option configuration
+    CreatePut {
+        #[command(flatten)]
+        option: OptionArgs,
+
+        /// Output file
+        #[arg(short, long, default_value = "option-config.json")]
+        output: String,
     },
-    
+
     /// Calculate option premium
     Premium {
         /// Current price
+        #[arg(long, required_unless_present = "batch", value_parser = crate::utils::parse_positive_f64)]
+        current_price: Option<f64>,
+
+        /// Strike price
+        #[arg(long, required_unless_present = "batch", value_parser = crate::utils::parse_positive_f64)]
+        strike_price: Option<f64>,
+
+        /// Time to expiration (hours)
+        #[arg(long, required_unless_present = "batch", value_parser = crate::utils::parse_positive_f64)]
+        time_to_expiration: Option<f64>,
+
+        /// Whether the option is a call or a put
+        #[arg(long, value_enum, default_value = "call", conflicts_with = "batch")]
+        option_type: OptionType,
+
+        /// Implied volatility in bps (default: the config's)
+        #[arg(long, conflicts_with = "batch")]
+        volatility: Option<u64>,
+
+        /// JSON array of options to price with Black-Scholes, reporting premium and Greeks
+        #[arg(long, conflicts_with_all = ["current_price", "strike_price", "time_to_expiration"])]
+        batch: Option<String>,
+
+        /// Print the batch results as JSON, the same as --format json
+        #[arg(long, requires = "batch")]
+        json: bool,
+
+        /// Output format of the batch results
+        #[arg(long, value_enum, requires = "batch", conflicts_with = "json")]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        quote: QuoteArgs,
+    },
+
+    /// Validate option configuration
+    Validate {
+        /// Configuration file to validate
+        file: String,
+
+        /// Fail on unknown fields and duplicate keys instead of warning
         #[arg(long)]
+        strict: bool,
+    },
+
+    /// Suggest stop-loss prices from a volatility config
+    SuggestStop {
+        /// Current price
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
         current_price: f64,
-        
-        /// Strike price
+
+        /// Volatility config whose current volatility is used as the daily sigma
+        #[arg(long, default_value = "volatility-config.json")]
+        config: String,
+
+        /// Confidence (percent) that a one-day move stays within the stop
+        #[arg(long, default_value = "95", value_parser = crate::utils::parse_finite_f64)]
+        confidence: f64,
+    },
+
+    /// Price calls and puts across a grid of strikes with Black-Scholes
+    Chain {
+        /// Current price of the underlying
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+        current_price: f64,
+
+        /// Hours until expiration
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+        expiration_hours: f64,
+
+        /// Strike grid as min:max:step, e.g. 1800:2200:50
         #[arg(long)]
-        strike_price: f64,
-        
-        /// Time to expiration (hours)
+        strikes: String,
+
+        /// Implied volatility in basis points (default: from --config)
+        #[arg(long)]
+        implied_volatility: Option<u64>,
+
+        /// Annual risk-free rate in basis points (default: from --config)
         #[arg(long)]
-        time_to_expiration: f64,
+        risk_free_rate: Option<u64>,
+
+        /// Print the chain as CSV, the same as --format csv
+        #[arg(long, conflicts_with = "json")]
+        csv: bool,
+
+        /// Print the chain as JSON, the same as --format json
+        #[arg(long)]
+        json: bool,
+
+        /// Output format of the chain
+        #[arg(long, value_enum, conflicts_with_all = ["csv", "json"])]
+        format: Option<OutputFormat>,
+
+        #[command(flatten)]
+        quote: QuoteArgs,
     },
 }
 
-pub async fn handle_command(command: &OptionsCommands, _cli: &crate::Cli) -> Result<()> {
+/// The asset strikes and premiums are denominated in.
+#[derive(Args)]
+pub struct QuoteArgs {
+    /// Decimals of the quote asset
+    #[arg(long, default_value_t = DEFAULT_QUOTE_DECIMALS)]
+    quote_decimals: u32,
+
+    /// Symbol of the quote asset
+    #[arg(long, default_value = DEFAULT_QUOTE_SYMBOL)]
+    quote_symbol: String,
+}
+
+impl QuoteArgs {
+    fn resolve(&self) -> Result<QuoteAsset> {
+        if self.quote_decimals > MAX_QUOTE_DECIMALS {
+            return Err(eyre::eyre!("--quote-decimals must be at most {}, got {}", MAX_QUOTE_DECIMALS, self.quote_decimals));
+        }
+        if self.quote_symbol.trim().is_empty() {
+            return Err(eyre::eyre!("--quote-symbol must not be empty"));
+        }
+        Ok(QuoteAsset { symbol: self.quote_symbol.clone(), decimals: self.quote_decimals })
+    }
+}
+
+/// Quote asset of an option, e.g. 6-decimal USDC.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct QuoteAsset {
+    pub(crate) symbol: String,
+    pub(crate) decimals: u32,
+}
+
+impl QuoteAsset {
+    fn scale(&self) -> f64 {
+        10f64.powi(self.decimals as i32)
+    }
+
+    /// `value` rounded to the asset's smallest unit.
+    fn round(&self, value: f64) -> f64 {
+        (value * self.scale()).round() / self.scale()
+    }
+
+    /// Whether `value` is a whole number of the asset's smallest unit.
+    fn is_representable(&self, value: f64) -> bool {
+        let units = value * self.scale();
+        (units - units.round()).abs() <= units.abs() * 1e-12
+    }
+
+    /// `value` with the symbol, at no more decimals than the asset has.
+    fn format(&self, value: f64, precision: u8) -> String {
+        format!("{} {}", format_amount(value, precision.min(self.decimals as u8)), self.symbol)
+    }
+}
+
+/// Flags shared by `create-call` and `create-put`.
+#[derive(Args)]
+pub struct OptionArgs {
+    /// Strike price in the quote asset
+    #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+    strike_price: f64,
+
+    /// Expiration in hours
+    #[arg(long)]
+    expiration_hours: u64,
+
+    /// Premium in the quote asset (overrides --price-from-volatility)
+    #[arg(long, required_unless_present = "price_from_volatility", value_parser = crate::utils::parse_non_negative_f64)]
+    premium: Option<f64>,
+
+    /// Price the premium with Black-Scholes, using this volatility config's current volatility
+    #[arg(long, requires = "current_price")]
+    price_from_volatility: Option<String>,
+
+    /// Current price of the underlying, for --price-from-volatility
+    #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+    current_price: Option<f64>,
+
+    #[command(flatten)]
+    quote: QuoteArgs,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct OptionConfig {
+    pub(crate) is_call: bool,
+    /// In the quote asset, rounded to its decimals
+    pub(crate) strike_price: f64,
+    pub(crate) expiration: u64,
+    /// In the quote asset, rounded to its decimals
+    pub(crate) premium: f64,
+    pub(crate) implied_volatility: u64,
+    pub(crate) creation_time: u64,
+    // Configs written before these fields were USDC
+    #[serde(default = "default_quote_symbol")]
+    pub(crate) quote_symbol: String,
+    #[serde(default = "default_quote_decimals")]
+    pub(crate) quote_decimals: u32,
+}
+
+fn default_quote_symbol() -> String {
+    DEFAULT_QUOTE_SYMBOL.to_string()
+}
+
+fn default_quote_decimals() -> u32 {
+    DEFAULT_QUOTE_DECIMALS
+}
+
+impl OptionConfig {
+    pub(crate) fn quote(&self) -> QuoteAsset {
+        QuoteAsset { symbol: self.quote_symbol.clone(), decimals: self.quote_decimals }
+    }
+}
+
+pub async fn handle_command(command: &OptionsCommands, cli: &crate::Cli) -> Result<()> {
     match command {
-        OptionsCommands::CreateCall { strike_price, expiration_hours, premium } => {
-            println!("{}", "📞 Creating call option configuration...".cyan());
-            println!("  • Strike price: ${}", strike_price);
-            println!("  • Expiration: {} hours", expiration_hours);
-            println!("  • Premium: ${}", premium);
-            println!("{}", "✅ Call option config created".green());
-            Ok(())
-        }
-        OptionsCommands::Premium { current_price, strike_price, time_to_expiration } => {
-            println!("{}", "💰 Calculating option premium...".cyan());
-            let estimated_premium = (current_price - strike_price).max(0.0) + 
-                                  (time_to_expiration * 0.1); // Simple estimation
-            println!("  • Current price: ${}", current_price);
-            println!("  • Strike price: ${}", strike_price);
-            println!("  • Estimated premium: ${:.2}", estimated_premium);
-            Ok(())
-        }
-    }
-}
\ No newline at end of file
+        OptionsCommands::CreateCall { option, output } => create_option(true, option, output, cli).await,
+        OptionsCommands::CreatePut { option, output } => create_option(false, option, output, cli).await,
+        OptionsCommands::Premium { batch: Some(batch), json, format, quote, .. } => {
+            let defaults = crate::config::load_or_default(&cli.config)?.defaults.options;
+            let format = OutputFormat::resolve(*format, *json, false);
+            price_batch_file(batch, &defaults, &quote.resolve()?, format, cli.precision).await
+        }
+        OptionsCommands::Premium { current_price, strike_price, time_to_expiration, option_type, volatility, quote, .. } => {
+            let defaults = crate::config::load_or_default(&cli.config)?.defaults.options;
+            // clap requires the prices and expiration without --batch
+            let option = BatchOption {
+                current_price: current_price.unwrap_or_default(),
+                strike_price: strike_price.unwrap_or_default(),
+                time_to_expiration: time_to_expiration.unwrap_or_default(),
+                option_type: *option_type,
+                volatility: *volatility,
+            };
+            price_single(&option, &defaults, &quote.resolve()?, cli.precision)
+        }
+        OptionsCommands::Validate { file, strict } => {
+            ValidationSummary::single(file, validate_option_config(file, *strict, cli.precision, cli.time_format).await)
+        }
+        OptionsCommands::SuggestStop { current_price, config, confidence } => {
+            suggest_stop(*current_price, config, *confidence, cli.precision).await
+        }
+        OptionsCommands::Chain {
+            current_price,
+            expiration_hours,
+            strikes,
+            implied_volatility,
+            risk_free_rate,
+            csv,
+            json,
+            format,
+            quote,
+        } => {
+            let quote = quote.resolve()?;
+            let defaults = crate::config::load_or_default(&cli.config)?.defaults.options;
+            verbose!(
+                cli.verbose,
+                "defaults from {}: implied volatility {}bps, risk-free rate {}bps",
+                cli.config, defaults.implied_volatility, defaults.risk_free_rate
+            );
+            let inputs = ChainInputs {
+                current_price: *current_price,
+                expiration_hours: *expiration_hours,
+                implied_volatility: implied_volatility.unwrap_or(defaults.implied_volatility),
+                risk_free_rate: risk_free_rate.unwrap_or(defaults.risk_free_rate),
+            };
+            option_chain(&inputs, strikes, &quote, OutputFormat::resolve(*format, *json, *csv), cli.precision).await
+        }
+    }
+}
+
+async fn create_option(is_call: bool, args: &OptionArgs, output: &str, cli: &crate::Cli) -> Result<()> {
+    let (side, title) = if is_call { ("call", "Call") } else { ("put", "Put") };
+    status!("{}", format!("📞 Creating {} option configuration...", side).cyan());
+
+    let precision = cli.precision;
+    let quote = args.quote.resolve()?;
+    let defaults = crate::config::load_or_default(&cli.config)?.defaults.options;
+    verbose!(
+        cli.verbose,
+        "defaults from {}: implied volatility {}bps, risk-free rate {}bps",
+        cli.config, defaults.implied_volatility, defaults.risk_free_rate
+    );
+    let mut implied_volatility = defaults.implied_volatility;
+
+    let premium = match (args.premium, &args.price_from_volatility, args.current_price) {
+        (Some(premium), source, _) => {
+            if source.is_some() {
+                status!("  {} --premium overrides --price-from-volatility", "ℹ️ ".blue());
+            }
+            premium
+        }
+        (None, Some(file), Some(current_price)) => {
+            let pricing = price_from_volatility(
+                is_call, current_price, args.strike_price, args.expiration_hours, file, defaults.risk_free_rate,
+            )?;
+            implied_volatility = pricing.implied_volatility;
+
+            status!("📐 Black-Scholes inputs:");
+            status!("  • Current price: {}", quote.format(current_price, precision));
+            status!(
+                "  • Volatility: {}bps daily from {} → {}bps annualized",
+                pricing.daily_volatility, file, pricing.implied_volatility
+            );
+            status!("  • Risk-free rate: {}bps", defaults.risk_free_rate);
+            status!("  • Delta: {:.4}", pricing.quote.delta);
+            pricing.quote.premium
+        }
+        // clap requires --premium or --price-from-volatility with --current-price
+        _ => return Err(eyre::eyre!("Provide --premium or --price-from-volatility with --current-price")),
+    };
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    if !quote.is_representable(args.strike_price) {
+        status!("  {} Strike rounded to {} decimals of {}", "ℹ️ ".blue(), quote.decimals, quote.symbol);
+    }
+    let config = OptionConfig {
+        is_call,
+        strike_price: quote.round(args.strike_price),
+        expiration: now + args.expiration_hours * 3600,
+        premium: quote.round(premium),
+        implied_volatility,
+        creation_time: now,
+        quote_symbol: quote.symbol.clone(),
+        quote_decimals: quote.decimals,
+    };
+
+    write_created_config(output, &config)?;
+    if output != STDOUT_PATH {
+        verbose!(cli.verbose, "wrote {}", fs::canonicalize(output)?.display());
+    }
+
+    status!("  • Strike price: {}", quote.format(config.strike_price, precision));
+    status!("  • Expiration: {} hours", args.expiration_hours);
+    status!("  • Premium: {}", quote.format(config.premium, precision));
+    status!("{} {}", format!("✅ {} option config created:", title).green(), output_name(output));
+    Ok(())
+}
+
+struct VolatilityPricing {
+    quote: Quote,
+    daily_volatility: Bps,
+    /// Annualized, in basis points, as stored in the option config
+    implied_volatility: u64,
+}
+
+/// Prices an option with Black-Scholes, treating the volatility config's
+/// current volatility as a daily sigma and annualizing it over 365 days.
+fn price_from_volatility(
+    is_call: bool,
+    current_price: f64,
+    strike_price: f64,
+    expiration_hours: u64,
+    config_file: &str,
+    risk_free_rate: u64,
+) -> Result<VolatilityPricing> {
+    if current_price <= 0.0 || strike_price <= 0.0 {
+        return Err(eyre::eyre!("Current and strike prices must be positive"));
+    }
+    if expiration_hours == 0 {
+        return Err(eyre::eyre!("Expiration must be in the future"));
+    }
+
+    let config: VolatilityConfig = read_config(config_file)?;
+    if config.current_volatility == Bps::ZERO {
+        return Err(eyre::eyre!("Current volatility in {} must be greater than zero", config_file));
+    }
+
+    let sigma = config.current_volatility.as_fraction() * 365f64.sqrt();
+    let years = expiration_hours as f64 / (365.0 * 24.0);
+    let (call, put) = black_scholes(current_price, strike_price, years, sigma, risk_free_rate as f64 / 10_000.0);
+
+    Ok(VolatilityPricing {
+        quote: if is_call { call } else { put },
+        daily_volatility: config.current_volatility,
+        implied_volatility: (sigma * 10_000.0).round() as u64,
+    })
+}
+
+pub(crate) async fn validate_option_config(file: &str, strict: bool, precision: u8, time_format: TimeFormat) -> Result<Findings> {
+    println!("{} {}", "🔍 Validating option config:".cyan(), file.yellow());
+
+    let config: OptionConfig = read_config(file)?;
+
+    let (mut warnings, mut errors) = check_option_config(&config);
+    add_key_findings::<OptionConfig>(file, strict, &mut warnings, &mut errors);
+
+    if errors.is_empty() && warnings.is_empty() {
+        println!("{}", "✅ Option configuration is valid!".green());
+        println!("📊 Configuration summary:");
+        println!("  • Type: {}", if config.is_call { "call" } else { "put" });
+        let quote = config.quote();
+        println!("  • Strike price: {}", quote.format(config.strike_price, precision));
+        println!("  • Premium: {}", quote.format(config.premium, precision));
+        println!("  • Implied volatility: {}bps", config.implied_volatility);
+        println!("  • Expires: {}", format_time(config.expiration, time_format));
+    } else {
+        for warning in &warnings {
+            println!("{}", warning.yellow());
+        }
+        for error in &errors {
+            println!("{}", error.red());
+        }
+    }
+
+    Ok(Findings::of(&warnings, &errors))
+}
+
+/// Runs all validation checks without printing, returning `(warnings, errors)`.
+pub(crate) fn check_option_config(config: &OptionConfig) -> (Vec<String>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    if config.strike_price <= 0.0 {
+        errors.push("❌ Strike price must be positive".to_string());
+    }
+
+    if config.premium < 0.0 {
+        errors.push("❌ Premium cannot be negative".to_string());
+    }
+
+    let quote = config.quote();
+    if config.quote_decimals > MAX_QUOTE_DECIMALS {
+        errors.push(format!("❌ Quote decimals must be at most {}", MAX_QUOTE_DECIMALS));
+    } else {
+        for (name, value) in [("Strike price", config.strike_price), ("Premium", config.premium)] {
+            if !quote.is_representable(value) {
+                warnings.push(format!("⚠️  {} {} has more than {}'s {} decimals", name, value, quote.symbol, quote.decimals));
+            }
+        }
+        if config.premium > 0.0 && quote.round(config.premium) == 0.0 {
+            errors.push(format!("❌ Premium is below one unit of {} ({} decimals)", quote.symbol, quote.decimals));
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    if config.expiration <= now {
+        errors.push("❌ Option has already expired".to_string());
+    } else if config.expiration - now < 3600 {
+        warnings.push("⚠️  Option expires within 1 hour".to_string());
+    }
+
+    (warnings, errors)
+}
+
+async fn suggest_stop(current_price: f64, config_file: &str, confidence: f64, precision: u8) -> Result<()> {
+    if current_price <= 0.0 {
+        return Err(eyre::eyre!("Current price must be positive"));
+    }
+    if !(50.0..100.0).contains(&confidence) {
+        return Err(eyre::eyre!("Confidence must be at least 50% and below 100%"));
+    }
+
+    let config: VolatilityConfig = read_config(config_file)?;
+
+    // Log-normal daily move: price * exp(±z * sigma)
+    let sigma = config.current_volatility.as_fraction();
+    let z = normal_quantile(confidence / 100.0);
+    let upper = current_price * (z * sigma).exp();
+    let lower = current_price * (-z * sigma).exp();
+
+    println!("{}", "🛡️  Suggesting volatility-adjusted stops...".cyan());
+    println!("  • Current price: ${}", format_amount(current_price, precision));
+    println!("  • Daily volatility: {}bps ({})", config.current_volatility, config_file);
+    println!("  • Confidence: {}% (z = {:.4})", confidence, z);
+    println!();
+    println!("🎯 Suggested stops:");
+    println!(
+        "  • Long stop (below): ${} ({})",
+        format_amount(lower, precision).yellow(),
+        format!("-{:.2}%", (1.0 - lower / current_price) * 100.0).red()
+    );
+    println!(
+        "  • Short stop (above): ${} ({})",
+        format_amount(upper, precision).yellow(),
+        format!("+{:.2}%", (upper / current_price - 1.0) * 100.0).green()
+    );
+    if config.current_volatility > config.volatility_threshold {
+        println!("{}", "⚠️  Volatility is above threshold - stops are wider than usual".yellow());
+    }
+
+    Ok(())
+}
+
+/// Inverse of the standard normal CDF, using Acklam's rational approximation
+/// (relative error below 1.2e-9). `p` must lie in (0, 1).
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+        1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+        6.680131188771972e+01, -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+        -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+struct ChainInputs {
+    current_price: f64,
+    expiration_hours: f64,
+    /// Basis points, like the config defaults
+    implied_volatility: u64,
+    risk_free_rate: u64,
+}
+
+/// Black-Scholes premium and Greeks for one side of a strike.
+#[derive(Debug, Clone, Copy)]
+struct Quote {
+    premium: f64,
+    delta: f64,
+    gamma: f64,
+    /// Premium change per 1 percentage point of volatility
+    vega: f64,
+    /// Premium change per calendar day
+    theta: f64,
+}
+
+/// Prices a European call and put at `strike`. `years` and `sigma` must be positive.
+fn black_scholes(spot: f64, strike: f64, years: f64, sigma: f64, rate: f64) -> (Quote, Quote) {
+    let vol_sqrt_t = sigma * years.sqrt();
+    let d1 = ((spot / strike).ln() + (rate + sigma * sigma / 2.0) * years) / vol_sqrt_t;
+    let d2 = d1 - vol_sqrt_t;
+    let discounted_strike = strike * (-rate * years).exp();
+    let density = (-d1 * d1 / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt();
+    let gamma = density / (spot * vol_sqrt_t);
+    let vega = spot * density * years.sqrt() / 100.0;
+    let decay = -spot * density * sigma / (2.0 * years.sqrt());
+
+    let call = Quote {
+        premium: spot * normal_cdf(d1) - discounted_strike * normal_cdf(d2),
+        delta: normal_cdf(d1),
+        gamma,
+        vega,
+        theta: (decay - rate * discounted_strike * normal_cdf(d2)) / 365.0,
+    };
+    let put = Quote {
+        premium: discounted_strike * normal_cdf(-d2) - spot * normal_cdf(-d1),
+        delta: normal_cdf(d1) - 1.0,
+        gamma,
+        vega,
+        theta: (decay + rate * discounted_strike * normal_cdf(-d2)) / 365.0,
+    };
+    (call, put)
+}
+
+/// Parses a `min:max:step` strike grid. Strikes are `min + i * step` up to and including `max`.
+fn parse_strike_grid(grid: &str) -> Result<Vec<f64>> {
+    const MAX_STRIKES: usize = 1000;
+
+    let parts: Vec<&str> = grid.split(':').collect();
+    let [min, max, step] = parts.as_slice() else {
+        return Err(eyre::eyre!("Strikes must be min:max:step, got: {}", grid));
+    };
+    let parse = |value: &str| {
+        value.trim().parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| eyre::eyre!("Invalid strike grid value: {}", value))
+    };
+    let (min, max, step) = (parse(min)?, parse(max)?, parse(step)?);
+
+    if min <= 0.0 {
+        return Err(eyre::eyre!("Minimum strike must be positive"));
+    }
+    if max < min {
+        return Err(eyre::eyre!("Maximum strike must not be below the minimum"));
+    }
+    if step <= 0.0 {
+        return Err(eyre::eyre!("Strike step must be positive"));
+    }
+
+    // Tolerate float error so 1800:2200:50 includes 2200
+    let count = ((max - min) / step + 1e-9).floor() as usize + 1;
+    if count > MAX_STRIKES {
+        return Err(eyre::eyre!("Strike grid has {} strikes, at most {} are allowed", count, MAX_STRIKES));
+    }
+    Ok((0..count).map(|i| min + i as f64 * step).collect())
+}
+
+async fn option_chain(inputs: &ChainInputs, strikes: &str, quote: &QuoteAsset, format: OutputFormat, precision: u8) -> Result<()> {
+    let precision = precision.min(quote.decimals as u8);
+    if inputs.current_price <= 0.0 {
+        return Err(eyre::eyre!("Current price must be positive"));
+    }
+    if inputs.expiration_hours <= 0.0 {
+        return Err(eyre::eyre!("Expiration must be in the future"));
+    }
+    if inputs.implied_volatility == 0 {
+        return Err(eyre::eyre!("Implied volatility must be greater than zero"));
+    }
+    let strikes = parse_strike_grid(strikes)?;
+
+    let years = inputs.expiration_hours / (365.0 * 24.0);
+    let sigma = inputs.implied_volatility as f64 / 10_000.0;
+    let rate = inputs.risk_free_rate as f64 / 10_000.0;
+
+    let mut table = Table::new()
+        .column("strike", "Strike", Align::Right)
+        .column("call_premium", "Call", Align::Right)
+        .column("call_delta", "Delta", Align::Right)
+        .column("call_break_even", "Break-even", Align::Right)
+        .column("put_premium", "Put", Align::Right)
+        .column("put_delta", "Delta", Align::Right)
+        .column("put_break_even", "Break-even", Align::Right);
+    for strike in strikes {
+        let (call, put) = black_scholes(inputs.current_price, strike, years, sigma, rate);
+        if !call.premium.is_finite() || !put.premium.is_finite() {
+            return Err(VectorPlusError::Pricing(format!("Strike {} has no finite premium", strike)).into());
+        }
+        let cells = vec![
+            Cell::new(format_amount(strike, precision), strike),
+            Cell::new(format_amount(call.premium, precision), call.premium),
+            Cell::new(format!("{:.4}", call.delta), call.delta),
+            Cell::new(format_amount(strike + call.premium, precision), strike + call.premium),
+            Cell::new(format_amount(put.premium, precision), put.premium),
+            Cell::new(format!("{:.4}", put.delta), put.delta),
+            Cell::new(format_amount(strike - put.premium, precision), strike - put.premium),
+        ];
+        // Highlight the strike nearest the money
+        if (strike - inputs.current_price).abs() * 2.0 < strike.max(inputs.current_price) * 0.01 {
+            table.colored_row(cells, Color::Yellow);
+        } else {
+            table.row(cells);
+        }
+    }
+
+    if format.is_machine() {
+        println!("{}", table.format(format));
+        return Ok(());
+    }
+
+    println!("{}", "⛓️  Building option chain...".cyan());
+    println!("  • Current price: {}", quote.format(inputs.current_price, precision));
+    println!("  • Expiration: {} hours", inputs.expiration_hours);
+    println!(
+        "  • Implied volatility: {}bps, risk-free rate: {}bps",
+        inputs.implied_volatility, inputs.risk_free_rate
+    );
+    println!();
+    println!("{}", table.render());
+
+    Ok(())
+}
+
+/// Batches at least this large are priced across threads.
+const PARALLEL_BATCH: usize = 10_000;
+
+/// One option in a `premium --batch` file.
+#[derive(Debug, Deserialize)]
+struct BatchOption {
+    current_price: f64,
+    strike_price: f64,
+    /// Hours until expiration
+    time_to_expiration: f64,
+    option_type: OptionType,
+    /// Implied volatility in bps (default: the config's)
+    volatility: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+impl BatchOption {
+    fn price(&self, default_volatility: u64, rate: f64) -> std::result::Result<(u64, Quote), String> {
+        if self.current_price <= 0.0 || self.strike_price <= 0.0 {
+            return Err("current_price and strike_price must be positive".to_string());
+        }
+        if self.time_to_expiration <= 0.0 {
+            return Err("time_to_expiration must be in the future".to_string());
+        }
+        let volatility = self.volatility.unwrap_or(default_volatility);
+        if volatility == 0 {
+            return Err("volatility must be greater than zero".to_string());
+        }
+        let years = self.time_to_expiration / (365.0 * 24.0);
+        let (call, put) = black_scholes(self.current_price, self.strike_price, years, volatility as f64 / 10_000.0, rate);
+        Ok((volatility, if self.option_type == OptionType::Call { call } else { put }))
+    }
+}
+
+/// Prices one option with Black-Scholes, the same as a one-entry batch.
+fn quote_option(option: &BatchOption, defaults: &OptionsDefaults) -> Result<(u64, Quote)> {
+    let rate = defaults.risk_free_rate as f64 / 10_000.0;
+    let (volatility, quote) = option.price(defaults.implied_volatility, rate).map_err(VectorPlusError::Validation)?;
+    if !quote.premium.is_finite() {
+        return Err(VectorPlusError::Pricing("The option has no finite premium".to_string()).into());
+    }
+    Ok((volatility, quote))
+}
+
+fn price_single(option: &BatchOption, defaults: &OptionsDefaults, quote_asset: &QuoteAsset, precision: u8) -> Result<()> {
+    let (volatility, quote) = quote_option(option, defaults)?;
+    let precision = precision.min(quote_asset.decimals as u8);
+    let kind = if option.option_type == OptionType::Call { "call" } else { "put" };
+    println!("{} ({})", "💰 Calculating option premium...".cyan(), kind);
+    println!("  • Current price: {}", quote_asset.format(option.current_price, precision));
+    println!("  • Strike price: {}", quote_asset.format(option.strike_price, precision));
+    println!("  • Time to expiration: {} hours", option.time_to_expiration);
+    println!("  • Volatility: {}bps, risk-free rate: {}bps", volatility, defaults.risk_free_rate);
+    println!("  • Premium: {}", quote_asset.format(quote_asset.round(quote.premium), precision));
+    println!("  • Delta: {:.4}, gamma: {:.6}, vega: {:.4}, theta/day: {:.4}", quote.delta, quote.gamma, quote.vega, quote.theta);
+    Ok(())
+}
+
+/// Prices every option, splitting large batches across threads.
+fn price_batch(options: &[BatchOption], default_volatility: u64, rate: f64) -> Vec<std::result::Result<(u64, Quote), String>> {
+    let price_all = |options: &[BatchOption]| -> Vec<_> {
+        options.iter().map(|option| option.price(default_volatility, rate)).collect()
+    };
+    if options.len() < PARALLEL_BATCH {
+        return price_all(options);
+    }
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = options.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = options.chunks(chunk).map(|part| scope.spawn(move || price_all(part))).collect();
+        handles.into_iter().flat_map(|handle| handle.join().expect("pricing thread panicked")).collect()
+    })
+}
+
+async fn price_batch_file(file: &str, defaults: &OptionsDefaults, quote: &QuoteAsset, format: OutputFormat, precision: u8) -> Result<()> {
+    let options: Vec<BatchOption> = read_config(file)?;
+    if options.is_empty() {
+        return Err(eyre::eyre!("{} has no options to price", file));
+    }
+    let rate = defaults.risk_free_rate as f64 / 10_000.0;
+    let priced = price_batch(&options, defaults.implied_volatility, rate);
+    let failures: Vec<String> = priced.iter()
+        .enumerate()
+        .filter_map(|(i, result)| result.as_ref().err().map(|e| format!("  • Entry {}: {}", i, e)))
+        .collect();
+    if !failures.is_empty() {
+        return Err(eyre::eyre!("{} of {} options in {} are invalid:\n{}", failures.len(), options.len(), file, failures.join("\n")));
+    }
+
+    let precision = precision.min(quote.decimals as u8);
+    let mut table = Table::new()
+        .column("type", "Type", Align::Left)
+        .column("current_price", "Spot", Align::Right)
+        .column("strike_price", "Strike", Align::Right)
+        .column("time_to_expiration", "Hours", Align::Right)
+        .column("volatility", "Vol (bps)", Align::Right)
+        .column("premium", "Premium", Align::Right)
+        .column("delta", "Delta", Align::Right)
+        .column("gamma", "Gamma", Align::Right)
+        .column("vega", "Vega", Align::Right)
+        .column("theta", "Theta/day", Align::Right);
+    let mut total_premium = 0.0;
+    for (i, (option, (volatility, quote))) in options.iter().zip(priced.into_iter().flatten()).enumerate() {
+        if !quote.premium.is_finite() {
+            return Err(VectorPlusError::Pricing(format!("Entry {} of {} has no finite premium", i, file)).into());
+        }
+        total_premium += quote.premium;
+        table.row(vec![
+            Cell::text(if option.option_type == OptionType::Call { "call" } else { "put" }),
+            Cell::new(format_amount(option.current_price, precision), option.current_price),
+            Cell::new(format_amount(option.strike_price, precision), option.strike_price),
+            Cell::new(option.time_to_expiration.to_string(), option.time_to_expiration),
+            Cell::new(volatility.to_string(), volatility),
+            Cell::new(format_amount(quote.premium, precision), quote.premium),
+            Cell::new(format!("{:.4}", quote.delta), quote.delta),
+            Cell::new(format!("{:.6}", quote.gamma), quote.gamma),
+            Cell::new(format!("{:.4}", quote.vega), quote.vega),
+            Cell::new(format!("{:.4}", quote.theta), quote.theta),
+        ]);
+    }
+
+    if format.is_machine() {
+        println!("{}", table.format(format));
+        return Ok(());
+    }
+
+    println!("{} {}", "💰 Pricing options from:".cyan(), file.yellow());
+    println!("  • Options: {}", options.len());
+    println!("  • Risk-free rate: {}bps, default volatility: {}bps", defaults.risk_free_rate, defaults.implied_volatility);
+    println!();
+    println!("{}", table.render());
+    println!();
+    println!("  • Total premium: {}", quote.format(total_premium, precision));
+    Ok(())
+}
+
+/// Standard normal CDF via the complementary error function.
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function, Chebyshev fit with fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+        + t * (0.37409196
+        + t * (0.09678418
+        + t * (-0.18628806
+        + t * (0.27886807
+        + t * (-1.13520398
+        + t * (1.48851587
+        + t * (-0.82215223
+        + t * 0.17087277))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_batches_in_parallel_like_serially() {
+        let options: Vec<BatchOption> = serde_json::from_str(
+            r#"[{"current_price":100,"strike_price":100,"time_to_expiration":8760,"option_type":"call","volatility":2000},
+                {"current_price":100,"strike_price":90,"time_to_expiration":24,"option_type":"put"},
+                {"current_price":100,"strike_price":-1,"time_to_expiration":24,"option_type":"put"}]"#,
+        ).unwrap();
+        let priced = price_batch(&options, 8000, 0.05);
+        let (volatility, call) = priced[0].as_ref().unwrap();
+        assert_eq!(*volatility, 2000);
+        assert!((call.premium - 10.4506).abs() < 1e-3);
+        assert_eq!(priced[1].as_ref().unwrap().0, 8000);
+        assert!(priced[2].is_err());
+
+        let large: Vec<BatchOption> = (0..PARALLEL_BATCH + 7)
+            .map(|i| BatchOption {
+                current_price: 100.0,
+                strike_price: 50.0 + (i % 100) as f64,
+                time_to_expiration: 24.0,
+                option_type: if i % 2 == 0 { OptionType::Call } else { OptionType::Put },
+                volatility: None,
+            })
+            .collect();
+        let parallel = price_batch(&large, 8000, 0.05);
+        assert_eq!(parallel.len(), large.len());
+        for (option, result) in large.iter().zip(&parallel).step_by(997) {
+            assert_eq!(result.as_ref().unwrap().1.premium, option.price(8000, 0.05).unwrap().1.premium);
+        }
+    }
+
+    #[test]
+    fn checks_amounts_against_quote_decimals() {
+        let mut config: OptionConfig = serde_json::from_str(
+            r#"{"is_call":true,"strike_price":2000.5,"expiration":0,"premium":12.25,"implied_volatility":8000,"creation_time":0}"#,
+        ).unwrap();
+        assert_eq!(config.quote(), QuoteAsset { symbol: "USDC".to_string(), decimals: 6 });
+        config.expiration = chrono::Utc::now().timestamp() as u64 + 86_400;
+        assert_eq!(check_option_config(&config), (vec![], vec![]));
+
+        config.quote_symbol = "EURS".to_string();
+        config.quote_decimals = 2;
+        config.premium = 0.004;
+        let (warnings, errors) = check_option_config(&config);
+        assert_eq!(warnings, vec!["⚠️  Premium 0.004 has more than EURS's 2 decimals"]);
+        assert_eq!(errors, vec!["❌ Premium is below one unit of EURS (2 decimals)"]);
+
+        let quote = config.quote();
+        assert_eq!(quote.round(1.235), 1.24);
+        assert_eq!(quote.format(1.5, 6), "1.50 EURS");
+    }
+
+    #[test]
+    fn parses_strike_grids() {
+        assert_eq!(parse_strike_grid("1800:2000:50").unwrap(), vec![1800.0, 1850.0, 1900.0, 1950.0, 2000.0]);
+        assert_eq!(parse_strike_grid("0.1:0.3:0.1").unwrap().len(), 3);
+        assert_eq!(parse_strike_grid("100:120:15").unwrap(), vec![100.0, 115.0]);
+        assert!(parse_strike_grid("1800:2000").is_err());
+        assert!(parse_strike_grid("2000:1800:50").is_err());
+        assert!(parse_strike_grid("1800:2000:0").is_err());
+        assert!(parse_strike_grid("0:2000:50").is_err());
+    }
+
+    #[test]
+    fn single_premium_matches_the_batch() {
+        let defaults = crate::config::VectorPlusConfig::default().defaults.options;
+        let entries: Vec<BatchOption> = serde_json::from_str(
+            r#"[{"current_price":2000,"strike_price":2200,"time_to_expiration":168,"option_type":"call"},
+                {"current_price":2000,"strike_price":1900,"time_to_expiration":168,"option_type":"put","volatility":6000}]"#,
+        ).unwrap();
+        let rate = defaults.risk_free_rate as f64 / 10_000.0;
+        for (entry, batch) in entries.iter().zip(price_batch(&entries, defaults.implied_volatility, rate)) {
+            let (volatility, single) = quote_option(entry, &defaults).unwrap();
+            let (batch_volatility, batch) = batch.unwrap();
+            assert_eq!(volatility, batch_volatility);
+            assert_eq!(single.premium, batch.premium);
+            assert_eq!(single.delta, batch.delta);
+        }
+        // Out of the money with a week left still has time value
+        let (_, call) = quote_option(&entries[0], &defaults).unwrap();
+        assert!(call.premium > 0.0 && call.premium < 200.0, "{}", call.premium);
+
+        let expired = BatchOption { time_to_expiration: 0.0, ..entries.into_iter().next().unwrap() };
+        assert!(quote_option(&expired, &defaults).is_err());
+    }
+
+    #[test]
+    fn black_scholes_matches_reference_and_parity() {
+        // S=100, K=100, T=1y, sigma=20%, r=5%: call 10.4506, put 5.5735
+        let (call, put) = black_scholes(100.0, 100.0, 1.0, 0.2, 0.05);
+        assert!((call.premium - 10.4506).abs() < 1e-3);
+        assert!((put.premium - 5.5735).abs() < 1e-3);
+        assert!((call.delta - 0.6368).abs() < 1e-3);
+        assert!((call.delta - put.delta - 1.0).abs() < 1e-12);
+        // Reference Greeks: gamma 0.018762, vega 0.37524 per vol point, call theta -6.414/yr
+        assert!((call.gamma - 0.018762).abs() < 1e-5);
+        assert!((call.vega - 0.37524).abs() < 1e-4);
+        assert!((call.theta * 365.0 + 6.414).abs() < 1e-2);
+        assert!((put.theta * 365.0 + 1.658).abs() < 1e-2);
+
+        // Put-call parity: C - P = S - K e^(-rT)
+        let (call, put) = black_scholes(2000.0, 2200.0, 168.0 / 8760.0, 0.8, 0.03);
+        let parity = 2000.0 - 2200.0 * (-0.03f64 * 168.0 / 8760.0).exp();
+        assert!((call.premium - put.premium - parity).abs() < 1e-3);
+    }
+}
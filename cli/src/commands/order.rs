@@ -0,0 +1,1902 @@
+use clap::Subcommand;
+use colored::*;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::Duration;
+
+use super::simulate::Asset;
+use crate::eip712::{chain_id, domain_separator, format_address, format_uint256, parse_address, parse_uint256, selector, OrderJson, LIMIT_ORDER_PROTOCOL};
+use crate::error::VectorPlusError;
+use crate::networks::{self, Network};
+use crate::rpc::{parse_quantity, CallOutcome, RpcClient};
+use crate::shutdown::Shutdown;
+use crate::utils::table::{Align, Cell, Table};
+use crate::utils::{backup_existing, confirm, confirm_overwrite, format_time, format_units, parse_base_units, read_config, TimeFormat, STDOUT_PATH};
+
+/// ABI tuple of `IOrderMixin.Order`; v4 encodes its `Address` fields as uint256.
+const ORDER_TUPLE: &str = "(uint256,uint256,uint256,uint256,uint256,uint256,uint256,uint256)";
+
+#[derive(Subcommand)]
+pub enum OrderCommands {
+    /// Report orders in a directory that share a salt or an order hash
+    CheckCollisions {
+        /// Directory of order JSON files
+        dir: String,
+    },
+
+    /// Print a readable summary of fillOrder calldata or an order file
+    Decode {
+        /// `fillOrder` or `fillOrderArgs` calldata as hex
+        #[arg(long, required_unless_present = "order", conflicts_with = "order")]
+        calldata: Option<String>,
+
+        /// Order JSON file
+        #[arg(long)]
+        order: Option<String>,
+    },
+
+    /// Build an order JSON with fill behavior flags in its maker traits
+    Build {
+        /// Asset sold: an address or a token symbol known on --network, e.g. WETH
+        #[arg(long, required_unless_present_any = ["template", "batch"])]
+        maker_asset: Option<String>,
+
+        /// Asset bought: an address or a token symbol known on --network, e.g. USDC
+        #[arg(long, required_unless_present_any = ["template", "batch"])]
+        taker_asset: Option<String>,
+
+        /// Amount of the maker asset sold, in whole tokens, e.g. 1.5
+        #[arg(long, required_unless_present_any = ["template", "batch"], requires = "maker_asset")]
+        making_amount: Option<String>,
+
+        /// Amount of the taker asset bought, in whole tokens, e.g. 3000
+        #[arg(long, required_unless_present_any = ["template", "batch"], requires = "taker_asset")]
+        taking_amount: Option<String>,
+
+        /// Order JSON with `${salt}`, `${maker}`, `${makingAmount}`-style placeholders to fill from the flags
+        #[arg(long)]
+        template: Option<String>,
+
+        /// JSON array of per-order fields (makerAsset, makingAmount, salt, ...) overriding the flags; writes every order to --out
+        #[arg(long, conflicts_with = "template", requires = "out")]
+        batch: Option<String>,
+
+        /// Maker address (default: the zero address, for a preview)
+        #[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
+        maker: String,
+
+        /// Address that receives the taker asset (default: the maker)
+        #[arg(long)]
+        receiver: Option<String>,
+
+        /// Decimals of the maker asset, required when it is not a known token
+        #[arg(long)]
+        maker_decimals: Option<u32>,
+
+        /// Decimals of the taker asset, required when it is not a known token
+        #[arg(long)]
+        taker_decimals: Option<u32>,
+
+        /// Order salt, decimal or 0x hex (default: the current time in milliseconds)
+        #[arg(long)]
+        salt: Option<String>,
+
+        /// Expire the order this many seconds from now (default: never)
+        #[arg(long)]
+        expires_in_secs: Option<u64>,
+
+        /// Let takers fill part of the order (default: the whole order in one fill)
+        #[arg(long)]
+        allow_partial_fill: bool,
+
+        /// Let the order be filled more than once, until nothing remains; needs --allow-partial-fill
+        #[arg(long)]
+        allow_multiple_fills: bool,
+
+        /// Pay the maker in the native token by unwrapping the taker asset, which must be WETH
+        #[arg(long)]
+        unwrap_weth: bool,
+
+        /// Nonce of a single-fill order, the bit its fill or cancel invalidates for the maker
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u64).range(..1 << 40))]
+        nonce: u64,
+
+        /// Write the order JSON to this file
+        #[arg(long)]
+        out: Option<String>,
+    },
+
+    /// Print an order's EIP-712 signing hashes, or sign them on a Ledger in builds with the `ledger` feature
+    Sign {
+        /// Order JSON file
+        #[arg(long)]
+        order: String,
+
+        #[command(flatten)]
+        ledger: LedgerArgs,
+    },
+
+    /// Send a signed order's fillOrder transaction to --rpc-url, e.g. a local fork
+    Submit {
+        /// Signed order JSON: `{ "order": {...}, "signature": "0x..." }`
+        #[arg(long)]
+        order: String,
+
+        /// Amount to fill in the taker asset's base units (default: the whole order)
+        #[arg(long)]
+        amount: Option<String>,
+
+        /// Unlocked node account to send from (default: the node's first account)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Seconds to wait for the receipt
+        #[arg(long, default_value_t = 60)]
+        timeout_secs: u64,
+
+        /// Allow sending to a production chain id such as 1 or 42161, e.g. a fork that kept its chain id
+        #[arg(long)]
+        allow_mainnet: bool,
+    },
+
+    /// Check with eth_call whether fillOrder would succeed, without sending a transaction
+    Preflight {
+        /// Order JSON, or a signed order `{ "order": {...}, "signature": "0x..." }`
+        #[arg(long)]
+        order: String,
+
+        /// Maker's signature as hex (default: the signed order's)
+        #[arg(long)]
+        signature: Option<String>,
+
+        /// Amount to fill in the taker asset's base units (default: the whole order)
+        #[arg(long)]
+        amount: Option<String>,
+
+        /// Taker the call is made from (default: the node's first account)
+        #[arg(long)]
+        from: Option<String>,
+    },
+
+    /// Check the maker's balance and allowance of the maker asset cover the order
+    CheckMaker {
+        /// Order JSON, or a signed order `{ "order": {...}, "signature": "0x..." }`
+        #[arg(long)]
+        order: String,
+    },
+
+    /// Build the cancelOrder calldata for an order, optionally sending it from the maker
+    Cancel {
+        /// Order JSON, or a signed order `{ "order": {...}, "signature": "0x..." }`
+        #[arg(long)]
+        order: String,
+
+        /// Send the cancellation to --rpc-url from the maker, which the node must have unlocked
+        #[arg(long)]
+        submit: bool,
+
+        /// Seconds to wait for the receipt
+        #[arg(long, default_value_t = 60, requires = "submit")]
+        timeout_secs: u64,
+
+        /// Allow sending to a production chain id such as 1 or 42161, e.g. a fork that kept its chain id
+        #[arg(long, requires = "submit")]
+        allow_mainnet: bool,
+    },
+}
+
+/// First account of the standard Ethereum derivation, as Ledger Live uses it.
+pub(crate) const DEFAULT_HD_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// Hardware wallet signing for `order sign`, never handling a private key.
+/// Declared in every build so `--ledger` without the `ledger` feature gets a
+/// clear error rather than an unknown flag.
+#[derive(clap::Args)]
+#[cfg_attr(not(feature = "ledger"), allow(dead_code))]
+pub struct LedgerArgs {
+    /// Sign on a connected Ledger, after showing the address it derives
+    #[arg(long)]
+    ledger: bool,
+
+    /// BIP-32 derivation path of the signing account
+    #[arg(long, default_value = DEFAULT_HD_PATH)]
+    hd_path: String,
+
+    /// Write the signed order `{ "order": {...}, "signature": "0x..." }` to this file
+    #[arg(long, requires = "ledger")]
+    out: Option<String>,
+}
+
+/// An order with the maker's EIP-712 signature, as passed to `order submit`.
+#[derive(Debug, Serialize, Deserialize)]
+struct SignedOrder {
+    order: OrderJson,
+    /// 64-byte compact `r || vs` or 65-byte `r || s || v` signature
+    signature: String,
+}
+
+/// Inputs of `order build`; the assets and amounts are only optional with a template.
+struct BuildInputs<'a> {
+    maker_asset: Option<&'a str>,
+    taker_asset: Option<&'a str>,
+    making_amount: Option<&'a str>,
+    taking_amount: Option<&'a str>,
+    template: Option<&'a str>,
+    maker: &'a str,
+    receiver: Option<&'a str>,
+    maker_decimals: Option<u32>,
+    taker_decimals: Option<u32>,
+    salt: Option<&'a str>,
+    expires_in_secs: Option<u64>,
+    traits: MakerTraits,
+    out: Option<&'a str>,
+}
+
+/// Options for `order submit` beyond the signed order itself; `order cancel
+/// --submit` uses the timeout and mainnet guard.
+struct SubmitOptions<'a> {
+    amount: Option<&'a str>,
+    from: Option<&'a str>,
+    timeout_secs: u64,
+    allow_mainnet: bool,
+}
+
+pub async fn handle_command(command: &OrderCommands, cli: &crate::Cli) -> Result<()> {
+    match command {
+        OrderCommands::CheckCollisions { dir } => check_collisions(dir, &cli.network).await,
+        OrderCommands::Decode { calldata, order } => {
+            let network = networks::lookup(&cli.network)?;
+            match (calldata, order) {
+                (Some(calldata), _) => decode_calldata(calldata, network, cli.time_format),
+                (None, Some(file)) => decode_order_file(file, network, cli.time_format),
+                (None, None) => unreachable!("clap requires --calldata or --order"),
+            }
+        }
+        OrderCommands::Build {
+            maker_asset,
+            taker_asset,
+            making_amount,
+            taking_amount,
+            template,
+            batch,
+            maker,
+            receiver,
+            maker_decimals,
+            taker_decimals,
+            salt,
+            expires_in_secs,
+            allow_partial_fill,
+            allow_multiple_fills,
+            unwrap_weth,
+            nonce,
+            out,
+        } => {
+            let inputs = BuildInputs {
+                maker_asset: maker_asset.as_deref(),
+                taker_asset: taker_asset.as_deref(),
+                making_amount: making_amount.as_deref(),
+                taking_amount: taking_amount.as_deref(),
+                template: template.as_deref(),
+                maker,
+                receiver: receiver.as_deref(),
+                maker_decimals: *maker_decimals,
+                taker_decimals: *taker_decimals,
+                salt: salt.as_deref(),
+                expires_in_secs: *expires_in_secs,
+                traits: MakerTraits {
+                    allow_partial_fill: *allow_partial_fill,
+                    allow_multiple_fills: *allow_multiple_fills,
+                    unwrap_weth: *unwrap_weth,
+                    expiry: None,
+                    nonce: *nonce,
+                },
+                out: out.as_deref(),
+            };
+            match batch {
+                Some(file) => build_batch(file, &inputs, cli),
+                None => build_order(&inputs, cli),
+            }
+        }
+        #[cfg(feature = "ledger")]
+        OrderCommands::Sign { order, ledger } if ledger.ledger => {
+            sign_on_ledger(order, ledger, networks::lookup(&cli.network)?, cli.assume_yes)
+        }
+        #[cfg(not(feature = "ledger"))]
+        OrderCommands::Sign { order, ledger } if ledger.ledger => Err(VectorPlusError::Validation(format!(
+            "This build has no Ledger support: rebuild with `cargo build --release --features ledger`, \
+             or run `order sign --order {}` without --ledger and sign the digest it prints with \
+             `cast wallet sign --no-hash <DIGEST> --keystore <FILE>`",
+            order
+        )).into()),
+        OrderCommands::Sign { order, .. } => {
+            let order_json: OrderJson = read_config(order)?;
+            let network = networks::lookup(&cli.network)?;
+            let digest = print_signing_hashes(order, &order_json, network)?;
+            println!();
+            println!("Sign the digest with a keystore, e.g.");
+            println!("  cast wallet sign --no-hash 0x{} --keystore <FILE>", hex::encode(digest));
+            println!("and pass {{\"order\": ..., \"signature\": ...}} to `order submit`.");
+            Ok(())
+        }
+        OrderCommands::Submit { order, amount, from, timeout_secs, allow_mainnet } => {
+            let options = SubmitOptions {
+                amount: amount.as_deref(),
+                from: from.as_deref(),
+                timeout_secs: *timeout_secs,
+                allow_mainnet: *allow_mainnet,
+            };
+            submit_order(order, &options, cli).await
+        }
+        OrderCommands::Preflight { order, signature, amount, from } => {
+            let signed = load_signed_order(order, signature.as_deref())?;
+            preflight_order(order, &signed, amount.as_deref(), from.as_deref(), cli).await
+        }
+        OrderCommands::CheckMaker { order } => {
+            let (parsed, _) = read_order_file(order)?;
+            check_maker(order, &parsed, cli).await
+        }
+        OrderCommands::Cancel { order, submit, timeout_secs, allow_mainnet } => {
+            let (parsed, _) = read_order_file(order)?;
+            let options = SubmitOptions {
+                amount: None,
+                from: None,
+                timeout_secs: *timeout_secs,
+                allow_mainnet: *allow_mainnet,
+            };
+            cancel_order(order, &parsed, submit.then_some(&options), cli).await
+        }
+    }
+}
+
+async fn check_collisions(dir: &str, network: &str) -> Result<()> {
+    let chain = chain_id(network)?;
+    println!("{} {} (chain {})", "🔍 Checking orders in:".cyan(), dir.yellow(), chain);
+
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .map_err(|_| eyre::eyre!("Could not read directory: {}", dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    let mut orders = Vec::new();
+    let mut skipped = 0;
+
+    for path in &files {
+        let name = path.display().to_string();
+        let order = fs::read_to_string(path)
+            .map_err(eyre::Report::from)
+            .and_then(|content| serde_json::from_str::<OrderJson>(&content).map_err(eyre::Report::from));
+        let order = match order {
+            Ok(order) => order,
+            Err(_) => {
+                skipped += 1;
+                continue;
+            }
+        };
+
+        orders.push((name, order));
+    }
+
+    println!("  • Orders: {}", orders.len());
+    if skipped > 0 {
+        println!("  • Skipped {} JSON files that are not orders", skipped);
+    }
+    println!();
+
+    let collisions = Collisions::find(&orders, chain)?;
+    if collisions.is_empty() {
+        println!("{}", "✅ No duplicate salts, order hashes or single-fill nonces".green());
+    }
+    collisions.report()
+}
+
+/// Orders sharing a salt or an order hash, or single-fill orders of one maker
+/// sharing a nonce (filling one invalidates the rest), each group listing the
+/// orders' names.
+struct Collisions {
+    salts: Vec<(String, Vec<String>)>,
+    hashes: Vec<(String, Vec<String>)>,
+    nonces: Vec<(String, Vec<String>)>,
+}
+
+impl Collisions {
+    fn find(orders: &[(String, OrderJson)], chain: u64) -> Result<Self> {
+        // Keyed by hex so the report order is stable
+        let mut by_salt: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut by_hash: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut by_nonce: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (name, order) in orders {
+            let salt = parse_uint256(&order.salt)
+                .map_err(|e| eyre::eyre!("{}: invalid order field salt: {}", name, e))?;
+            let hash = order.hash(chain).map_err(|e| eyre::eyre!("{}: {}", name, e))?;
+            by_salt.entry(hex::encode(salt)).or_default().push(name.clone());
+            by_hash.entry(hex::encode(hash)).or_default().push(name.clone());
+
+            let traits = MakerTraits::decode(&parse_uint256(&order.maker_traits)
+                .map_err(|e| eyre::eyre!("{}: invalid order field makerTraits: {}", name, e))?);
+            if traits.uses_bit_invalidator() {
+                let maker = parse_address(&order.maker)
+                    .map_err(|e| eyre::eyre!("{}: invalid order field maker: {}", name, e))?;
+                by_nonce.entry(format!("{} nonce {}", format_address(&maker), traits.nonce)).or_default().push(name.clone());
+            }
+        }
+        let shared = |groups: BTreeMap<String, Vec<String>>| groups.into_iter().filter(|(_, names)| names.len() > 1).collect();
+        Ok(Self { salts: shared(by_salt), hashes: shared(by_hash), nonces: shared(by_nonce) })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.salts.is_empty() && self.hashes.is_empty() && self.nonces.is_empty()
+    }
+
+    /// Prints each group and fails if there are any.
+    fn report(&self) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        for (label, groups, prefix) in [
+            ("❌ Duplicate salt", &self.salts, "0x"),
+            ("❌ Duplicate order hash", &self.hashes, "0x"),
+            ("❌ Single-fill orders sharing maker", &self.nonces, ""),
+        ] {
+            for (key, names) in groups {
+                println!("{} {}{}", label.red(), prefix, key);
+                for name in names {
+                    println!("  • {}", name);
+                }
+            }
+        }
+        Err(VectorPlusError::Validation(format!(
+            "Found {} duplicate salts, {} duplicate order hashes and {} shared single-fill nonces",
+            self.salts.len(),
+            self.hashes.len(),
+            self.nonces.len()
+        )).into())
+    }
+}
+
+/// Which `OrderMixin` fill function a calldata blob calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FillFunction {
+    FillOrder,
+    FillOrderArgs,
+}
+
+impl FillFunction {
+    fn signature(self) -> String {
+        match self {
+            FillFunction::FillOrder => format!("fillOrder({},bytes32,bytes32,uint256,uint256)", ORDER_TUPLE),
+            FillFunction::FillOrderArgs => format!("fillOrderArgs({},bytes32,bytes32,uint256,uint256,bytes)", ORDER_TUPLE),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            FillFunction::FillOrder => "fillOrder",
+            FillFunction::FillOrderArgs => "fillOrderArgs",
+        }
+    }
+}
+
+/// A decoded fill call: the order plus the taker's side of the call.
+#[derive(Debug)]
+struct DecodedFill {
+    function: FillFunction,
+    order: OrderJson,
+    amount: [u8; 32],
+    taker_traits: [u8; 32],
+    /// Order extension carried in `fillOrderArgs` args, if any
+    extension: Option<Vec<u8>>,
+}
+
+fn decode_fill_calldata(calldata: &str) -> Result<DecodedFill> {
+    let digits = calldata.trim();
+    let digits = digits.strip_prefix("0x").unwrap_or(digits);
+    let data = hex::decode(digits).map_err(|e| eyre::eyre!("Calldata is not valid hex: {}", e))?;
+    if data.len() < 4 {
+        return Err(eyre::eyre!("Calldata is too short to hold a function selector"));
+    }
+
+    let (prefix, body) = data.split_at(4);
+    let function = [FillFunction::FillOrder, FillFunction::FillOrderArgs]
+        .into_iter()
+        .find(|function| selector(&function.signature()) == prefix)
+        .ok_or_else(|| eyre::eyre!(
+            "Unsupported function selector 0x{}: expected fillOrder (0x{}) or fillOrderArgs (0x{})",
+            hex::encode(prefix),
+            hex::encode(selector(&FillFunction::FillOrder.signature())),
+            hex::encode(selector(&FillFunction::FillOrderArgs.signature())),
+        ))?;
+
+    let head_words = match function {
+        FillFunction::FillOrder => 12,
+        FillFunction::FillOrderArgs => 13,
+    };
+    if body.len() < head_words * 32 || body.len() % 32 != 0 {
+        return Err(eyre::eyre!(
+            "Malformed {} calldata: expected at least {} 32-byte words after the selector, got {} bytes",
+            function.name(), head_words, body.len()
+        ));
+    }
+    let word = |index: usize| -> [u8; 32] {
+        body[index * 32..(index + 1) * 32].try_into().expect("slice is 32 bytes")
+    };
+
+    let order = OrderJson {
+        salt: format!("0x{}", hex::encode(word(0))),
+        maker: format_address(&word(1)),
+        receiver: format_address(&word(2)),
+        maker_asset: format_address(&word(3)),
+        taker_asset: format_address(&word(4)),
+        making_amount: format_uint256(&word(5)),
+        taking_amount: format_uint256(&word(6)),
+        maker_traits: format!("0x{}", hex::encode(word(7))),
+    };
+    let taker_traits = word(11);
+
+    let extension = match function {
+        FillFunction::FillOrder => None,
+        FillFunction::FillOrderArgs => {
+            let args = abi_bytes(body, &word(12))
+                .map_err(|e| eyre::eyre!("Malformed fillOrderArgs args: {}", e))?;
+            extension_from_args(args, &taker_traits)?
+        }
+    };
+
+    Ok(DecodedFill { function, order, amount: word(10), taker_traits, extension })
+}
+
+/// Reads a dynamic `bytes` value whose head word holds `offset`.
+fn abi_bytes<'a>(body: &'a [u8], offset: &[u8; 32]) -> Result<&'a [u8]> {
+    let to_usize = |word: &[u8]| -> Option<usize> {
+        word[..24].iter().all(|&b| b == 0)
+            .then(|| usize::try_from(u64::from_be_bytes(word[24..32].try_into().unwrap())).ok())
+            .flatten()
+    };
+    let data_start = to_usize(offset)
+        .and_then(|start| start.checked_add(32))
+        .filter(|&end| end <= body.len())
+        .ok_or_else(|| eyre::eyre!("offset points past the end of the calldata"))?;
+    let data_end = to_usize(&body[data_start - 32..data_start])
+        .and_then(|length| data_start.checked_add(length))
+        .filter(|&end| end <= body.len())
+        .ok_or_else(|| eyre::eyre!("length runs past the end of the calldata"))?;
+    Ok(&body[data_start..data_end])
+}
+
+/// Splits the extension out of `fillOrderArgs` args, which pack an optional
+/// 20-byte target, then the extension, then the interaction, with lengths
+/// taken from the taker traits.
+fn extension_from_args(args: &[u8], taker_traits: &[u8; 32]) -> Result<Option<Vec<u8>>> {
+    let has_target = taker_traits[0] & 0x08 != 0; // bit 251
+    let extension_len = u32::from_be_bytes([0, taker_traits[1], taker_traits[2], taker_traits[3]]) as usize; // bits 224..248
+    let start = if has_target { 20 } else { 0 };
+    if extension_len == 0 {
+        return Ok(None);
+    }
+    args.get(start..start + extension_len)
+        .map(|extension| Some(extension.to_vec()))
+        .ok_or_else(|| eyre::eyre!(
+            "Malformed fillOrderArgs args: taker traits declare a {}-byte extension but args hold {} bytes",
+            extension_len, args.len()
+        ))
+}
+
+/// The predicate field of an order extension, if non-empty. Extensions start
+/// with a word of cumulative uint32 end offsets, field 0 in the lowest bits.
+fn extension_predicate(extension: &[u8]) -> Result<Option<&[u8]>> {
+    const PREDICATE_FIELD: usize = 4;
+    if extension.len() < 32 {
+        return Err(eyre::eyre!("Order extension is shorter than its 32-byte offsets header"));
+    }
+    let (offsets, fields) = extension.split_at(32);
+    let end_of = |field: usize| -> usize {
+        let at = 28 - field * 4;
+        u32::from_be_bytes(offsets[at..at + 4].try_into().unwrap()) as usize
+    };
+    let (begin, end) = (end_of(PREDICATE_FIELD - 1), end_of(PREDICATE_FIELD));
+    if begin > end || end > fields.len() {
+        return Err(eyre::eyre!("Order extension offsets are inconsistent with its length"));
+    }
+    Ok((begin < end).then(|| &fields[begin..end]))
+}
+
+/// Reads `len` (at most 64) bits starting `offset` bits above the lowest bit of a word.
+fn word_bits(word: &[u8; 32], offset: usize, len: usize) -> u64 {
+    (0..len).rev().fold(0u64, |value, i| {
+        let bit = offset + i;
+        (value << 1) | ((word[31 - bit / 8] >> (bit % 8)) & 1) as u64
+    })
+}
+
+fn word_flag(word: &[u8; 32], bit: usize) -> bool {
+    word_bits(word, bit, 1) == 1
+}
+
+fn set_word_bits(word: &mut [u8; 32], offset: usize, len: usize, value: u64) {
+    for i in 0..len {
+        let bit = offset + i;
+        let mask = 1 << (bit % 8);
+        if (value >> i) & 1 == 1 {
+            word[31 - bit / 8] |= mask;
+        } else {
+            word[31 - bit / 8] &= !mask;
+        }
+    }
+}
+
+// makerTraits flags, by bit counted from the lowest (MakerTraitsLib in LOP v4)
+const NO_PARTIAL_FILLS_FLAG: usize = 255;
+const ALLOW_MULTIPLE_FILLS_FLAG: usize = 254;
+const UNWRAP_WETH_FLAG: usize = 247;
+
+/// Fill behavior held in an order's makerTraits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MakerTraits {
+    pub(crate) allow_partial_fill: bool,
+    pub(crate) allow_multiple_fills: bool,
+    pub(crate) unwrap_weth: bool,
+    /// Unix timestamp, in bits 80..120
+    pub(crate) expiry: Option<u64>,
+    /// Nonce or epoch, in bits 120..160
+    pub(crate) nonce: u64,
+}
+
+impl MakerTraits {
+    fn decode(word: &[u8; 32]) -> Self {
+        Self {
+            allow_partial_fill: !word_flag(word, NO_PARTIAL_FILLS_FLAG),
+            allow_multiple_fills: word_flag(word, ALLOW_MULTIPLE_FILLS_FLAG),
+            unwrap_weth: word_flag(word, UNWRAP_WETH_FLAG),
+            expiry: Some(word_bits(word, 80, 40)).filter(|&expiry| expiry != 0),
+            nonce: word_bits(word, 120, 40),
+        }
+    }
+
+    pub(crate) fn encode(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        set_word_bits(&mut word, NO_PARTIAL_FILLS_FLAG, 1, !self.allow_partial_fill as u64);
+        set_word_bits(&mut word, ALLOW_MULTIPLE_FILLS_FLAG, 1, self.allow_multiple_fills as u64);
+        set_word_bits(&mut word, UNWRAP_WETH_FLAG, 1, self.unwrap_weth as u64);
+        set_word_bits(&mut word, 80, 40, self.expiry.unwrap_or(0));
+        set_word_bits(&mut word, 120, 40, self.nonce);
+        word
+    }
+
+    /// Orders that cannot be filled more than once are closed through the
+    /// maker's bit invalidator at their nonce rather than by hash.
+    fn uses_bit_invalidator(&self) -> bool {
+        !self.allow_partial_fill || !self.allow_multiple_fills
+    }
+}
+
+/// Fill flags that contradict each other or the order's taker asset.
+fn check_maker_traits(traits: &MakerTraits, taker_asset: &str, network: &Network) -> (Vec<String>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    if traits.allow_multiple_fills && !traits.allow_partial_fill {
+        errors.push("❌ Multiple fills are allowed but partial fills are not - the first fill takes the whole order, so allow partial fills too".to_string());
+    }
+    if traits.allow_partial_fill && !traits.allow_multiple_fills {
+        warnings.push("⚠️  Partial fills are allowed but multiple fills are not - the first partial fill closes the order".to_string());
+    }
+    if traits.uses_bit_invalidator() && traits.nonce == 0 {
+        warnings.push("⚠️  Single-fill order with nonce 0 - it shares its invalidation bit with every other nonce-0 order of the maker, so filling or cancelling one closes them all; set --nonce".to_string());
+    }
+    if traits.unwrap_weth {
+        match network.wrapped_native() {
+            Some(token) if !token.address.eq_ignore_ascii_case(taker_asset) => errors.push(format!(
+                "❌ Unwrapping WETH needs the taker asset to be {}'s {} ({}), got {}",
+                network.name, token.symbol, token.address, taker_asset
+            )),
+            Some(_) => {}
+            None => warnings.push(format!(
+                "⚠️  Could not check that the taker asset is {}'s wrapped {}, which unwrapping WETH needs",
+                network.name, network.gas_token
+            )),
+        }
+    }
+    (warnings, errors)
+}
+
+/// Placeholders `order build --template` can fill, with what supplies each.
+const TEMPLATE_PLACEHOLDERS: [(&str, &str); 8] = [
+    ("salt", "--salt or the current time"),
+    ("maker", "--maker"),
+    ("receiver", "--receiver or the maker"),
+    ("makerAsset", "--maker-asset"),
+    ("takerAsset", "--taker-asset"),
+    ("makingAmount", "--making-amount"),
+    ("takingAmount", "--taking-amount"),
+    ("makerTraits", "the fill flags"),
+];
+
+/// Replaces each `${name}` in `template` with its value, or lists every
+/// placeholder left unresolved with what would supply it.
+fn fill_template(template: &str, values: &BTreeMap<&str, String>) -> std::result::Result<String, Vec<String>> {
+    let mut filled = String::with_capacity(template.len());
+    let mut unresolved = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + len];
+        filled.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(value) => filled.push_str(value),
+            None => {
+                let source = TEMPLATE_PLACEHOLDERS.iter()
+                    .find(|(placeholder, _)| *placeholder == name)
+                    .map_or("not a known placeholder", |(_, source)| source);
+                let entry = format!("${{{}}} ({})", name, source);
+                if !unresolved.contains(&entry) {
+                    unresolved.push(entry);
+                }
+                filled.push_str(&rest[start..=start + len]);
+            }
+        }
+        rest = &rest[start + len + 1..];
+    }
+    filled.push_str(rest);
+    if !unresolved.is_empty() {
+        return Err(unresolved);
+    }
+    Ok(filled)
+}
+
+/// Builds and checks the order `inputs` describe, returning it with the JSON text to save.
+fn assemble_order(inputs: &BuildInputs<'_>, network: &Network, now: chrono::DateTime<chrono::Utc>) -> Result<(OrderJson, String)> {
+    let maker = parse_address(inputs.maker).map_err(|e| eyre::eyre!("Invalid --maker: {}", e))?;
+    let receiver = match inputs.receiver {
+        Some(receiver) => parse_address(receiver).map_err(|e| eyre::eyre!("Invalid --receiver: {}", e))?,
+        None => [0u8; 32],
+    };
+    let traits = MakerTraits {
+        expiry: inputs.expires_in_secs.map(|secs| now.timestamp() as u64 + secs),
+        ..inputs.traits
+    };
+
+    // Keyed by the order's JSON field names, which templates use as placeholders
+    let mut values = BTreeMap::from([
+        ("salt", inputs.salt.map(str::to_string).unwrap_or_else(|| now.timestamp_millis().to_string())),
+        ("maker", format_address(&maker)),
+        ("receiver", format_address(&receiver)),
+        ("makerTraits", format!("0x{}", hex::encode(traits.encode()))),
+    ]);
+    for (asset, decimals, amount, asset_flag, amount_flag, asset_field, amount_field) in [
+        (inputs.maker_asset, inputs.maker_decimals, inputs.making_amount, "maker-asset", "making-amount", "makerAsset", "makingAmount"),
+        (inputs.taker_asset, inputs.taker_decimals, inputs.taking_amount, "taker-asset", "taking-amount", "takerAsset", "takingAmount"),
+    ] {
+        let Some(asset) = asset else {
+            continue;
+        };
+        let asset = Asset::resolve(asset, decimals, asset_flag, network)?;
+        if let Some(amount) = amount {
+            let units = parse_base_units(amount, asset.decimals as usize)
+                .map_err(|e| eyre::eyre!("--{}: {}", amount_flag, e))?;
+            if units == "0" {
+                return Err(VectorPlusError::Validation(format!("--{} must be greater than zero", amount_flag)).into());
+            }
+            values.insert(amount_field, units);
+        }
+        values.insert(asset_field, asset.address);
+    }
+
+    let (order, text) = match inputs.template {
+        Some(file) => {
+            let template = fs::read_to_string(file).map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => VectorPlusError::FileNotFound(file.to_string()).into(),
+                _ => eyre::eyre!("Could not read template {}: {}", file, e),
+            })?;
+            let filled = fill_template(&template, &values).map_err(|unresolved| {
+                VectorPlusError::Validation(format!("Unresolved placeholders in {}: {}", file, unresolved.join(", ")))
+            })?;
+            let order: OrderJson = serde_json::from_str(&filled).map_err(|e| {
+                VectorPlusError::Validation(format!("Filled template {} is not a complete order: {}", file, e))
+            })?;
+            order.struct_hash().map_err(|e| VectorPlusError::Validation(format!("Filled template {}: {}", file, e)))?;
+            (order, filled)
+        }
+        None => {
+            // clap requires every asset and amount flag without a template
+            let mut field = |name: &str| values.remove(name).unwrap_or_default();
+            let order = OrderJson {
+                salt: field("salt"),
+                maker: field("maker"),
+                receiver: field("receiver"),
+                maker_asset: field("makerAsset"),
+                taker_asset: field("takerAsset"),
+                making_amount: field("makingAmount"),
+                taking_amount: field("takingAmount"),
+                maker_traits: field("makerTraits"),
+            };
+            let text = serde_json::to_string_pretty(&order)?;
+            (order, text)
+        }
+    };
+
+    // print_order repeats any warnings next to the decoded traits
+    let traits = MakerTraits::decode(&parse_uint256(&order.maker_traits)?);
+    let (_, errors) = check_maker_traits(&traits, &order.taker_asset, network);
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{}", error.red());
+        }
+        return Err(VectorPlusError::Validation(format!("{} incompatible fill flags", errors.len())).into());
+    }
+    Ok((order, text))
+}
+
+fn build_order(inputs: &BuildInputs<'_>, cli: &crate::Cli) -> Result<()> {
+    let network = networks::lookup(&cli.network)?;
+    let (order, text) = assemble_order(inputs, network, chrono::Utc::now())?;
+
+    println!("{} (chain {})", "🛠️  Built order".cyan(), network.chain_id);
+    println!();
+    print_order(&order, network, cli.time_format)?;
+    println!();
+    println!("{}", text.trim_end());
+    match inputs.out {
+        Some(out) => {
+            confirm_overwrite(out, cli.assume_yes)?;
+            if let Some(backup) = backup_existing(out)? {
+                println!("  • Kept the previous {} as {}", out, backup);
+            }
+            fs::write(out, text)?;
+            crate::history::record(out, &order);
+            println!("{} {}", "💾 Saved to".green(), out);
+        }
+        None => crate::history::record(STDOUT_PATH, &order),
+    }
+    Ok(())
+}
+
+/// One order of `order build --batch`; unset fields fall back to the flags.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct BatchEntry {
+    maker_asset: Option<String>,
+    taker_asset: Option<String>,
+    #[serde(default, deserialize_with = "number_or_string")]
+    making_amount: Option<String>,
+    #[serde(default, deserialize_with = "number_or_string")]
+    taking_amount: Option<String>,
+    #[serde(default, deserialize_with = "number_or_string")]
+    salt: Option<String>,
+    maker: Option<String>,
+    receiver: Option<String>,
+    maker_decimals: Option<u32>,
+    taker_decimals: Option<u32>,
+    expires_in_secs: Option<u64>,
+    nonce: Option<u64>,
+}
+
+/// Accepts `1.5` as well as `"1.5"`, keeping the text so amounts parse exactly.
+fn number_or_string<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<String>, D::Error> {
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(text)) => Ok(Some(text)),
+        Some(serde_json::Value::Number(number)) => Ok(Some(number.to_string())),
+        Some(other) => Err(serde::de::Error::custom(format!("expected a number or string, got {}", other))),
+    }
+}
+
+/// An entry of the `order build --batch` output file.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuiltOrder<'a> {
+    order: &'a OrderJson,
+    order_hash: String,
+}
+
+fn build_batch(file: &str, base: &BuildInputs<'_>, cli: &crate::Cli) -> Result<()> {
+    let network = networks::lookup(&cli.network)?;
+    let entries: Vec<BatchEntry> = read_config(file)?;
+    if entries.is_empty() {
+        return Err(VectorPlusError::Validation(format!("{} has no orders", file)).into());
+    }
+    let now = chrono::Utc::now();
+
+    let mut orders = Vec::with_capacity(entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        // Consecutive default salts keep orders built in the same millisecond apart
+        let salt = entry.salt.clone().or_else(|| base.salt.map(str::to_string))
+            .unwrap_or_else(|| (now.timestamp_millis() + i as i64).to_string());
+        // Single-fill orders sharing a nonce share an invalidation bit, so
+        // each one counts up from --nonce unless the entry sets its own
+        let nonce = match entry.nonce {
+            Some(nonce) => nonce,
+            None if base.traits.uses_bit_invalidator() => base.traits.nonce + i as u64,
+            None => base.traits.nonce,
+        };
+        if nonce >= 1 << 40 {
+            return Err(VectorPlusError::Validation(format!("Batch order {} has nonce {}, past the 40 bits makerTraits holds", i + 1, nonce)).into());
+        }
+        let inputs = BuildInputs {
+            maker_asset: entry.maker_asset.as_deref().or(base.maker_asset),
+            taker_asset: entry.taker_asset.as_deref().or(base.taker_asset),
+            making_amount: entry.making_amount.as_deref().or(base.making_amount),
+            taking_amount: entry.taking_amount.as_deref().or(base.taking_amount),
+            template: None,
+            maker: entry.maker.as_deref().unwrap_or(base.maker),
+            receiver: entry.receiver.as_deref().or(base.receiver),
+            maker_decimals: entry.maker_decimals.or(base.maker_decimals),
+            taker_decimals: entry.taker_decimals.or(base.taker_decimals),
+            salt: Some(&salt),
+            expires_in_secs: entry.expires_in_secs.or(base.expires_in_secs),
+            traits: MakerTraits { nonce, ..base.traits },
+            out: None,
+        };
+        let missing: Vec<&str> = [
+            ("makerAsset", inputs.maker_asset),
+            ("takerAsset", inputs.taker_asset),
+            ("makingAmount", inputs.making_amount),
+            ("takingAmount", inputs.taking_amount),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| value.is_none().then_some(field))
+        .collect();
+        if !missing.is_empty() {
+            return Err(VectorPlusError::Validation(format!(
+                "Batch order {} has no {} and no matching flag",
+                i + 1,
+                missing.join(", ")
+            )).into());
+        }
+        let (order, _) = assemble_order(&inputs, network, now).map_err(|e| e.wrap_err(format!("Batch order {}", i + 1)))?;
+        orders.push((format!("order {}", i + 1), order));
+    }
+
+    // Same check as `order check-collisions`, so the ladder never reuses a salt
+    // or lets one fill invalidate another
+    let collisions = Collisions::find(&orders, network.chain_id)?;
+    collisions.report()?;
+
+    let mut table = Table::new()
+        .column("order", "#", Align::Right)
+        .column("salt", "Salt", Align::Right)
+        .column("making_amount", "Making", Align::Right)
+        .column("taking_amount", "Taking", Align::Right)
+        .column("hash", "Order hash", Align::Left);
+    let mut built = Vec::with_capacity(orders.len());
+    for (i, (_, order)) in orders.iter().enumerate() {
+        let hash = format!("0x{}", hex::encode(order.hash(network.chain_id)?));
+        table.row(vec![
+            Cell::new((i + 1).to_string(), i + 1),
+            Cell::new(order.salt.clone(), order.salt.clone()),
+            Cell::new(format_token_amount(&order.making_amount, &order.maker_asset, network), order.making_amount.clone()),
+            Cell::new(format_token_amount(&order.taking_amount, &order.taker_asset, network), order.taking_amount.clone()),
+            Cell::new(hash.clone(), hash.clone()),
+        ]);
+        built.push(BuiltOrder { order, order_hash: hash });
+    }
+
+    println!("{} {} orders (chain {})", "🛠️  Built".cyan(), orders.len(), network.chain_id);
+    println!();
+    println!("{}", table.render());
+
+    // clap requires --out with --batch
+    let out = base.out.unwrap_or_default();
+    confirm_overwrite(out, cli.assume_yes)?;
+    if let Some(backup) = backup_existing(out)? {
+        println!("  • Kept the previous {} as {}", out, backup);
+    }
+    fs::write(out, serde_json::to_string_pretty(&built)?)?;
+    crate::history::record(out, &built);
+    println!("{} {}", "💾 Saved to".green(), out);
+    Ok(())
+}
+
+/// Prints the EIP-712 domain separator, struct hash and digest of `order`,
+/// returning the digest.
+fn print_signing_hashes(file: &str, order: &OrderJson, network: &Network) -> Result<[u8; 32]> {
+    let domain = domain_separator(network.chain_id, LIMIT_ORDER_PROTOCOL)?;
+    let struct_hash = order.struct_hash()?;
+    let digest = order.hash(network.chain_id)?;
+
+    println!("{} {} (chain {})", "✍️  Signing order:".cyan(), file.yellow(), network.chain_id);
+    println!("  • Domain separator: 0x{}", hex::encode(domain));
+    println!("  • Order struct hash: 0x{}", hex::encode(struct_hash));
+    println!("  • EIP-712 digest: 0x{}", hex::encode(digest));
+    Ok(digest)
+}
+
+/// Signs on a Ledger, after checking the account at the path is the order's maker.
+#[cfg(feature = "ledger")]
+fn sign_on_ledger(file: &str, args: &LedgerArgs, network: &Network, assume_yes: bool) -> Result<()> {
+    use crate::ledger::{parse_hd_path, Ledger};
+
+    let path = parse_hd_path(&args.hd_path)?;
+    let order: OrderJson = read_config(file)?;
+    print_signing_hashes(file, &order, network)?;
+    println!("  • Derivation path: {}", args.hd_path);
+
+    let ledger = Ledger::open()?;
+    let account = ledger.address(&path, false)?;
+    println!("  • Ledger account: {}", account);
+    if parse_address(&account)? != parse_address(&order.maker)? {
+        return Err(VectorPlusError::Validation(format!(
+            "The order's maker is {}, not the Ledger account at {}; rebuild the order with --maker {}",
+            order.maker, args.hd_path, account
+        )).into());
+    }
+    println!();
+    println!("Check the address on the Ledger matches, then approve it there.");
+    ledger.address(&path, true)?;
+    if !confirm(&format!("Sign the order as {}?", account), true, assume_yes)? {
+        return Err(eyre::eyre!("Signing cancelled"));
+    }
+
+    println!("Approve the domain and message hashes above on the Ledger.");
+    let domain = domain_separator(network.chain_id, LIMIT_ORDER_PROTOCOL)?;
+    let signature = ledger.sign_eip712_hashed(&path, &domain, &order.struct_hash()?)?;
+    let signed = SignedOrder { order, signature };
+    let text = serde_json::to_string_pretty(&signed)?;
+    println!("{}", "✅ Order signed".green());
+    match &args.out {
+        Some(out) => {
+            confirm_overwrite(out, assume_yes)?;
+            if let Some(backup) = backup_existing(out)? {
+                println!("  • Kept the previous {} as {}", out, backup);
+            }
+            fs::write(out, text)?;
+            println!("{} {}", "💾 Saved to".green(), out);
+        }
+        None => println!("{}", text),
+    }
+    Ok(())
+}
+
+fn decode_calldata(calldata: &str, network: &Network, time_format: TimeFormat) -> Result<()> {
+    let fill = decode_fill_calldata(calldata)?;
+    println!("{} {} (chain {})", "🔎 Decoded".cyan(), fill.function.name().yellow(), network.chain_id);
+    println!();
+    print_order(&fill.order, network, time_format)?;
+
+    let traits = &fill.taker_traits;
+    let amount = format_uint256(&fill.amount);
+    let (side, asset) = if word_flag(traits, 255) {
+        ("making", &fill.order.maker_asset)
+    } else {
+        ("taking", &fill.order.taker_asset)
+    };
+    let threshold = threshold_word(traits);
+    println!();
+    println!("{}", "🧾 Fill:".bold());
+    println!("  • Amount: {} ({} amount)", format_token_amount(&amount, asset, network), side);
+    if threshold.iter().any(|&b| b != 0) {
+        println!("  • Threshold: {}", format_uint256(&threshold));
+    }
+    let mut flags = Vec::new();
+    if word_flag(traits, 254) { flags.push("unwrap WETH"); }
+    if word_flag(traits, 253) { flags.push("skip order permit"); }
+    if word_flag(traits, 252) { flags.push("use Permit2"); }
+    if word_flag(traits, 251) { flags.push("args carry a target"); }
+    if !flags.is_empty() {
+        println!("  • Taker flags: {}", flags.join(", "));
+    }
+
+    match &fill.extension {
+        Some(extension) => {
+            println!("  • Extension: {} bytes", extension.len());
+            match extension_predicate(extension)? {
+                Some(predicate) => println!("  • Predicate: 0x{} ({} bytes)", hex::encode(predicate), predicate.len()),
+                None => println!("  • Predicate: none"),
+            }
+        }
+        None if word_flag(&parse_uint256(&fill.order.maker_traits)?, 249) => {
+            println!("  • Extension: required by the order but not included in this call");
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// The threshold held in the low 185 bits of taker traits.
+fn threshold_word(traits: &[u8; 32]) -> [u8; 32] {
+    let mut word = *traits;
+    word[..8].fill(0);
+    word[8] &= 0x01;
+    word
+}
+
+fn decode_order_file(file: &str, network: &Network, time_format: TimeFormat) -> Result<()> {
+    let order: OrderJson = read_config(file)?;
+
+    println!("{} {} (chain {})", "🔎 Decoded order:".cyan(), file.yellow(), network.chain_id);
+    println!();
+    print_order(&order, network, time_format)
+}
+
+fn print_order(order: &OrderJson, network: &Network, time_format: TimeFormat) -> Result<()> {
+    let hash = order.hash(network.chain_id)?;
+    let receiver = parse_address(&order.receiver)?;
+    let making = format_uint256(&parse_uint256(&order.making_amount)?);
+    let taking = format_uint256(&parse_uint256(&order.taking_amount)?);
+    let maker_asset = format_address(&parse_address(&order.maker_asset)?);
+    let taker_asset = format_address(&parse_address(&order.taker_asset)?);
+
+    println!("{}", "📋 Order:".bold());
+    println!("  • Maker: {}", format_address(&parse_address(&order.maker)?));
+    if receiver.iter().all(|&b| b == 0) {
+        println!("  • Receiver: maker");
+    } else {
+        println!("  • Receiver: {}", format_address(&receiver));
+    }
+    println!("  • Selling: {} ({})", format_token_amount(&making, &maker_asset, network).yellow(), maker_asset);
+    println!("  • Buying: {} ({})", format_token_amount(&taking, &taker_asset, network).yellow(), taker_asset);
+    println!("  • Salt: 0x{}", hex::encode(parse_uint256(&order.salt)?));
+    println!("  • Order hash: 0x{}", hex::encode(hash));
+
+    let traits = parse_uint256(&order.maker_traits)?;
+    println!();
+    println!("{}", "⚙️  Maker traits:".bold());
+    match word_bits(&traits, 80, 40) {
+        0 => println!("  • Expires: never"),
+        expiry => {
+            let status = if expiry <= chrono::Utc::now().timestamp() as u64 { " (expired)".red().to_string() } else { String::new() };
+            println!("  • Expires: {}{}", format_time(expiry, time_format), status);
+        }
+    }
+    let fill_traits = MakerTraits::decode(&traits);
+    println!(
+        "  • Partial fills: {}, multiple fills: {}",
+        if fill_traits.allow_partial_fill { "yes" } else { "no" },
+        if fill_traits.allow_multiple_fills { "yes" } else { "no" }
+    );
+    let allowed_sender = word_bits(&traits, 0, 64) | word_bits(&traits, 64, 16);
+    if allowed_sender != 0 {
+        println!("  • Allowed sender: ...{}", hex::encode(&traits[22..]));
+    }
+    let (nonce, series) = (word_bits(&traits, 120, 40), word_bits(&traits, 160, 40));
+    if nonce != 0 || series != 0 {
+        println!("  • Nonce or epoch: {}, series: {}", nonce, series);
+    }
+    let mut flags = Vec::new();
+    if word_flag(&traits, 252) { flags.push("pre-interaction"); }
+    if word_flag(&traits, 251) { flags.push("post-interaction"); }
+    if word_flag(&traits, 250) { flags.push("epoch manager check"); }
+    if word_flag(&traits, 249) { flags.push("has extension"); }
+    if word_flag(&traits, 248) { flags.push("use Permit2"); }
+    if fill_traits.unwrap_weth { flags.push("unwrap WETH"); }
+    if !flags.is_empty() {
+        println!("  • Flags: {}", flags.join(", "));
+    }
+    let (warnings, errors) = check_maker_traits(&fill_traits, &taker_asset, network);
+    for warning in &warnings {
+        println!("  {}", warning.yellow());
+    }
+    for error in &errors {
+        println!("  {}", error.red());
+    }
+    Ok(())
+}
+
+/// `units` of `asset` formatted by the token's decimals when it is known on
+/// `network`, otherwise as raw base units.
+fn format_token_amount(units: &str, asset: &str, network: &Network) -> String {
+    match network.token(asset) {
+        Some(token) => match format_units(units, token.decimals as usize) {
+            Ok(amount) => format!("{} {}", amount, token.symbol),
+            Err(_) => units.to_string(),
+        },
+        None => format!("{} base units of an unknown token", units),
+    }
+}
+
+/// Splits a signature into the `r` and `vs` words `fillOrder` takes.
+fn compact_signature(signature: &str) -> Result<([u8; 32], [u8; 32])> {
+    let bytes = hex::decode(signature.strip_prefix("0x").unwrap_or(signature))
+        .map_err(|e| eyre::eyre!("Signature is not valid hex: {}", e))?;
+    let mut r = [0u8; 32];
+    let mut vs = [0u8; 32];
+    match bytes.len() {
+        64 => {
+            r.copy_from_slice(&bytes[..32]);
+            vs.copy_from_slice(&bytes[32..]);
+        }
+        65 => {
+            r.copy_from_slice(&bytes[..32]);
+            vs.copy_from_slice(&bytes[32..64]);
+            // EIP-2098: the parity of v goes in the top bit of s
+            match bytes[64] {
+                27 | 0 => {}
+                28 | 1 => vs[0] |= 0x80,
+                v => return Err(eyre::eyre!("Invalid signature v value: {}", v)),
+            }
+        }
+        n => return Err(eyre::eyre!("Signature must be 64 or 65 bytes, got {}", n)),
+    }
+    Ok((r, vs))
+}
+
+/// `fillOrder` calldata for `order`, the inverse of [`decode_fill_calldata`].
+fn encode_fill_order(order: &OrderJson, r: [u8; 32], vs: [u8; 32], amount: [u8; 32], taker_traits: [u8; 32]) -> Result<Vec<u8>> {
+    let mut data = selector(&FillFunction::FillOrder.signature()).to_vec();
+    data.extend_from_slice(&parse_uint256(&order.salt)?);
+    data.extend_from_slice(&parse_address(&order.maker)?);
+    data.extend_from_slice(&parse_address(&order.receiver)?);
+    data.extend_from_slice(&parse_address(&order.maker_asset)?);
+    data.extend_from_slice(&parse_address(&order.taker_asset)?);
+    data.extend_from_slice(&parse_uint256(&order.making_amount)?);
+    data.extend_from_slice(&parse_uint256(&order.taking_amount)?);
+    data.extend_from_slice(&parse_uint256(&order.maker_traits)?);
+    data.extend_from_slice(&r);
+    data.extend_from_slice(&vs);
+    data.extend_from_slice(&amount);
+    data.extend_from_slice(&taker_traits);
+    Ok(data)
+}
+
+async fn submit_order(file: &str, options: &SubmitOptions<'_>, cli: &crate::Cli) -> Result<()> {
+    let signed: SignedOrder = read_config(file)?;
+    let rpc = RpcClient::from_cli(cli, "order submit")?;
+    let rpc_url = rpc.url().to_string();
+    let chain = check_chain(&rpc, options.allow_mainnet).await?;
+
+    let (r, vs) = compact_signature(&signed.signature)?;
+    // A taker traits of zero means `amount` is in the taker asset
+    let amount = parse_uint256(options.amount.unwrap_or(&signed.order.taking_amount))
+        .map_err(|e| eyre::eyre!("Invalid --amount: {}", e))?;
+    let calldata = encode_fill_order(&signed.order, r, vs, amount, [0; 32])?;
+    let from = match options.from {
+        Some(from) => format_address(&parse_address(from)?),
+        None => rpc.accounts().await?.into_iter().next()
+            .ok_or_else(|| eyre::eyre!("{} has no unlocked accounts; pass --from", rpc_url))?,
+    };
+
+    let network = networks::lookup(&cli.network)?;
+    println!("{} {} (chain {})", "📤 Submitting order to:".cyan(), rpc_url.yellow(), chain);
+    println!("  • Order hash: 0x{}", hex::encode(signed.order.hash(chain)?));
+    println!("  • Fill amount: {}", format_token_amount(&format_uint256(&amount), &signed.order.taker_asset, network));
+    println!("  • From: {}", from);
+    println!("  • To: {} (fillOrder)", LIMIT_ORDER_PROTOCOL);
+    println!();
+
+    if !confirm("Send the fillOrder transaction?", false, cli.assume_yes)? {
+        return Err(eyre::eyre!("Submission cancelled"));
+    }
+
+    let tx = json!({ "from": from, "to": LIMIT_ORDER_PROTOCOL, "data": format!("0x{}", hex::encode(&calldata)) });
+    send_transaction(&rpc, &tx, options.timeout_secs).await?;
+    println!("{}", "✅ Order filled".green());
+    Ok(())
+}
+
+/// The RPC's chain id, refusing any built-in production chain unless
+/// `--allow-mainnet` was given.
+async fn check_chain(rpc: &RpcClient, allow_mainnet: bool) -> Result<u64> {
+    let chain = rpc.chain_id().await?;
+    refuse_production_chain(chain, rpc.url(), allow_mainnet)?;
+    Ok(chain)
+}
+
+fn refuse_production_chain(chain: u64, rpc_url: &str, allow_mainnet: bool) -> Result<()> {
+    match networks::production(chain) {
+        Some(network) if !allow_mainnet => Err(eyre::eyre!(
+            "{} reports chain id {} ({}); pass --allow-mainnet if it is a fork, or start the fork with another chain id",
+            rpc_url, chain, network.name
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Sends `tx` from an unlocked node account and waits up to `timeout_secs`
+/// for its receipt, failing if it reverted.
+async fn send_transaction(rpc: &RpcClient, tx: &serde_json::Value, timeout_secs: u64) -> Result<()> {
+    let hash = rpc.call("eth_sendTransaction", json!([tx])).await?;
+    let hash = hash.as_str().ok_or_else(|| eyre::eyre!("eth_sendTransaction returned {}", hash))?.to_string();
+    println!("  • Transaction: {}", hash);
+
+    let mut shutdown = Shutdown::install();
+    let deadline = std::time::Instant::now() + Duration::from_secs(timeout_secs);
+    let receipt = loop {
+        let receipt = rpc.call("eth_getTransactionReceipt", json!([hash])).await?;
+        if !receipt.is_null() {
+            break receipt;
+        }
+        if std::time::Instant::now() >= deadline {
+            return Err(eyre::eyre!("No receipt for {} after {}s", hash, timeout_secs));
+        }
+        if shutdown.sleep(Duration::from_secs(1)).await {
+            return Err(eyre::eyre!("Stopped waiting for {}; it may still be mined", hash));
+        }
+    };
+
+    let block = receipt.get("blockNumber").and_then(parse_quantity).unwrap_or_default();
+    let gas_used = receipt.get("gasUsed").and_then(parse_quantity).unwrap_or_default();
+    println!("  • Block: {}", block);
+    println!("  • Gas used: {}", gas_used);
+    if receipt.get("status").and_then(parse_quantity) == Some(1) {
+        Ok(())
+    } else {
+        Err(eyre::eyre!("Transaction {} reverted in block {}", hash, block))
+    }
+}
+
+/// The order in a bare or signed order file, with the signature if it has one.
+fn read_order_file(file: &str) -> Result<(OrderJson, Option<String>)> {
+    let value: serde_json::Value = read_config(file)?;
+    let (order, signature) = match value.get("order") {
+        Some(order) => (order.clone(), value.get("signature").and_then(serde_json::Value::as_str).map(str::to_string)),
+        None => (value, None),
+    };
+    let order = serde_json::from_value(order)
+        .map_err(|e| VectorPlusError::Validation(format!("{} is not an order: {}", file, e)))?;
+    Ok((order, signature))
+}
+
+/// Reads a signed order, or a bare order signed by `signature`; a given
+/// `signature` replaces the file's.
+fn load_signed_order(file: &str, signature: Option<&str>) -> Result<SignedOrder> {
+    let (order, file_signature) = read_order_file(file)?;
+    let signature = signature.map(str::to_string).or(file_signature).ok_or_else(|| {
+        VectorPlusError::Validation(format!("{} has no signature; pass --signature", file))
+    })?;
+    Ok(SignedOrder { order, signature })
+}
+
+/// Custom errors `fillOrder` reverts with, and what usually causes each.
+const FILL_ERRORS: [(&str, &str); 16] = [
+    ("BadSignature()", "the signature is not the maker's for this order and chain"),
+    ("OrderExpired()", "the order's expiration has passed"),
+    ("PrivateOrder()", "the order only allows one taker; pass that address as --from"),
+    ("WrongSeriesNonce()", "the maker has advanced the order's series nonce"),
+    ("BitInvalidatedOrder()", "the order was cancelled or another order with its nonce was filled"),
+    ("RemainingInvalidatedOrder()", "the order is already filled or cancelled"),
+    ("PredicateIsFalse()", "the order's predicate returned false"),
+    ("TakingAmountExceeded()", "the fill asks for more than remains of the order"),
+    ("TakingAmountTooHigh()", "the taking amount is above the fill's threshold"),
+    ("MakingAmountTooLow()", "the making amount is below the fill's threshold"),
+    ("PartialFillNotAllowed()", "the order must be filled in full"),
+    ("SwapWithZeroAmount()", "the fill amount rounds to zero"),
+    ("TransferFromMakerToTakerFailed()", "the maker's balance or allowance of the maker asset is too low"),
+    ("TransferFromTakerToMakerFailed()", "the taker's balance or allowance of the taker asset is too low"),
+    ("SafeTransferFromFailed()", "a token transfer failed, usually a balance or allowance shortfall"),
+    ("InvalidMsgValue()", "the call sent ETH the order does not expect"),
+];
+
+/// Reads revert data as `Error(string)`, `Panic(uint256)` or one of [`FILL_ERRORS`].
+fn decode_revert(data: &[u8]) -> String {
+    let Some((prefix, args)) = data.split_first_chunk::<4>() else {
+        return "no revert reason".to_string();
+    };
+    let word = |i: usize| args.get(i * 32..(i + 1) * 32);
+    match prefix {
+        [0x08, 0xc3, 0x79, 0xa0] => {
+            // Error(string): offset, length, then the bytes
+            let text = word(1)
+                .and_then(|length| usize::try_from(u64::from_be_bytes(length[24..].try_into().ok()?)).ok())
+                .and_then(|length| args.get(64..64 + length))
+                .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+            match text {
+                Some(text) => format!("Error({:?})", text),
+                None => "malformed Error(string)".to_string(),
+            }
+        }
+        [0x4e, 0x48, 0x7b, 0x71] => {
+            let code = word(0).map_or(0, |code| code[31]);
+            let cause = match code {
+                0x01 => "assertion failed",
+                0x11 => "arithmetic overflow",
+                0x12 => "division by zero",
+                0x32 => "array index out of bounds",
+                _ => "see the Solidity panic codes",
+            };
+            format!("Panic(0x{:02x}): {}", code, cause)
+        }
+        _ => FILL_ERRORS.iter()
+            .find(|(signature, _)| selector(signature) == *prefix)
+            .map(|(signature, cause)| format!("{}: {}", signature, cause))
+            .unwrap_or_else(|| format!("unknown error 0x{} ({} bytes of data)", hex::encode(prefix), data.len())),
+    }
+}
+
+async fn preflight_order(file: &str, signed: &SignedOrder, amount: Option<&str>, from: Option<&str>, cli: &crate::Cli) -> Result<()> {
+    let rpc = RpcClient::from_cli(cli, "order preflight")?;
+    let rpc_url = rpc.url().to_string();
+    let chain = rpc.chain_id().await?;
+
+    let (r, vs) = compact_signature(&signed.signature)?;
+    // A taker traits of zero means `amount` is in the taker asset
+    let amount = parse_uint256(amount.unwrap_or(&signed.order.taking_amount))
+        .map_err(|e| eyre::eyre!("Invalid --amount: {}", e))?;
+    let calldata = encode_fill_order(&signed.order, r, vs, amount, [0; 32])?;
+    // Public endpoints have no accounts, and eth_accounts may not even exist there
+    let from = match from {
+        Some(from) => format_address(&parse_address(from)?),
+        None => rpc.accounts().await.unwrap_or_default().into_iter().next()
+            .ok_or_else(|| eyre::eyre!("{} has no accounts to call from; pass --from with the taker's address", rpc_url))?,
+    };
+
+    let network = networks::lookup(&cli.network)?;
+    println!("{} {} (chain {})", "🛫 Preflight of order:".cyan(), file.yellow(), chain);
+    println!("  • RPC: {}", rpc_url);
+    println!("  • Order hash: 0x{}", hex::encode(signed.order.hash(chain)?));
+    println!("  • Fill amount: {}", format_token_amount(&format_uint256(&amount), &signed.order.taker_asset, network));
+    println!("  • From: {}", from);
+    println!("  • To: {} (fillOrder)", LIMIT_ORDER_PROTOCOL);
+    println!();
+
+    let tx = json!({ "from": from, "to": LIMIT_ORDER_PROTOCOL, "data": format!("0x{}", hex::encode(&calldata)) });
+    match rpc.simulate(&tx).await? {
+        CallOutcome::Success(result) => {
+            println!("{}", "✅ fillOrder would succeed".green());
+            // fillOrder returns (makingAmount, takingAmount, orderHash)
+            if let (Some(making), Some(taking)) = (result.get(..32), result.get(32..64)) {
+                let making = format_uint256(making.try_into()?);
+                let taking = format_uint256(taking.try_into()?);
+                println!("  • Maker sends: {}", format_token_amount(&making, &signed.order.maker_asset, network));
+                println!("  • Taker sends: {}", format_token_amount(&taking, &signed.order.taker_asset, network));
+            }
+            match rpc.estimate_gas(&tx).await {
+                Ok(gas) => println!("  • Estimated gas: {}", gas),
+                Err(e) => println!("{}", format!("⚠️  Could not estimate gas: {}", e).yellow()),
+            }
+            Ok(())
+        }
+        CallOutcome::Reverted { message, data } => {
+            let reason = match data {
+                Some(data) => decode_revert(&data),
+                None => message,
+            };
+            Err(eyre::eyre!("fillOrder would revert: {}", reason))
+        }
+    }
+}
+
+/// ERC-20 call data: the selector of `signature` followed by address arguments.
+fn erc20_call(signature: &str, addresses: &[&str]) -> Result<Vec<u8>> {
+    let mut data = selector(signature).to_vec();
+    for address in addresses {
+        data.extend_from_slice(&parse_address(address)?);
+    }
+    Ok(data)
+}
+
+/// The first word an ERC-20 view returned.
+fn returned_word(result: &[u8], call: &str) -> Result<[u8; 32]> {
+    result.get(..32)
+        .and_then(|word| word.try_into().ok())
+        .ok_or_else(|| eyre::eyre!("{} returned {} bytes; is the maker asset an ERC-20 token?", call, result.len()))
+}
+
+/// Whether a maker's balance and allowance cover an order's making amount.
+#[derive(Debug, PartialEq)]
+struct MakerCoverage {
+    balance_covers: bool,
+    allowance_covers: bool,
+    zero_allowance: bool,
+}
+
+impl MakerCoverage {
+    /// Big-endian words compare like the numbers they hold.
+    fn of(balance: &[u8; 32], allowance: &[u8; 32], making_amount: &[u8; 32]) -> Self {
+        MakerCoverage {
+            balance_covers: balance >= making_amount,
+            allowance_covers: allowance >= making_amount,
+            zero_allowance: allowance.iter().all(|&b| b == 0),
+        }
+    }
+}
+
+async fn check_maker(file: &str, order: &OrderJson, cli: &crate::Cli) -> Result<()> {
+    let rpc = RpcClient::from_cli(cli, "order check-maker")?;
+    let chain = rpc.chain_id().await?;
+    let network = networks::lookup(&cli.network)?;
+    let asset = &order.maker_asset;
+    let maker = format_address(&parse_address(&order.maker)?);
+
+    let balance = rpc.eth_call(asset, &erc20_call("balanceOf(address)", &[&maker])?).await?;
+    let balance = returned_word(&balance, "balanceOf")?;
+    let allowance = rpc.eth_call(asset, &erc20_call("allowance(address,address)", &[&maker, LIMIT_ORDER_PROTOCOL])?).await?;
+    let allowance = returned_word(&allowance, "allowance")?;
+    let making_amount = parse_uint256(&order.making_amount)?;
+
+    // Unknown tokens report their own decimals; without them amounts stay in base units
+    let (symbol, decimals) = match network.token(asset) {
+        Some(token) => (Some(token.symbol), Some(token.decimals as usize)),
+        None => {
+            let decimals = rpc.eth_call(asset, &erc20_call("decimals()", &[])?).await.ok()
+                .and_then(|result| returned_word(&result, "decimals").ok())
+                .filter(|word| word[..31].iter().all(|&b| b == 0) && word[31] <= 77)
+                .map(|word| word[31] as usize);
+            (None, decimals)
+        }
+    };
+    let show = |word: &[u8; 32]| {
+        if word.iter().all(|&b| b == 0xff) {
+            return "unlimited".to_string();
+        }
+        let units = format_uint256(word);
+        match (symbol, decimals.and_then(|decimals| format_units(&units, decimals).ok())) {
+            (Some(symbol), Some(amount)) => format!("{} {}", amount, symbol),
+            (None, Some(amount)) => format!("{} ({} base units)", amount, units),
+            (_, None) => format!("{} base units", units),
+        }
+    };
+    let mark = |covers: bool| if covers { "✅".to_string() } else { "❌ not enough".red().to_string() };
+
+    let coverage = MakerCoverage::of(&balance, &allowance, &making_amount);
+    println!("{} {} (chain {})", "🔍 Checking maker of:".cyan(), file.yellow(), chain);
+    println!("  • Maker: {}", maker);
+    println!("  • Maker asset: {}", asset);
+    println!("  • Making amount: {}", show(&making_amount));
+    println!("  • Balance: {} {}", show(&balance), mark(coverage.balance_covers));
+    println!("  • Allowance to {}: {} {}", LIMIT_ORDER_PROTOCOL, show(&allowance), mark(coverage.allowance_covers));
+
+    if coverage.zero_allowance {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "⚠️  The maker has not approved the protocol for this token at all. Approve {} to spend the maker asset before the order can be filled",
+                LIMIT_ORDER_PROTOCOL
+            ).yellow()
+        );
+    }
+    match (coverage.balance_covers, coverage.allowance_covers) {
+        (true, true) => {
+            println!("{}", "✅ The maker can cover the order".green());
+            Ok(())
+        }
+        (false, true) => Err(eyre::eyre!("The maker's balance does not cover the making amount")),
+        (true, false) => Err(eyre::eyre!("The maker's allowance to the protocol does not cover the making amount")),
+        (false, false) => Err(eyre::eyre!("Neither the maker's balance nor its allowance covers the making amount")),
+    }
+}
+
+/// `OrderMixin.cancelOrder(MakerTraits makerTraits, bytes32 orderHash)` in LOP v4.
+const CANCEL_ORDER: &str = "cancelOrder(uint256,bytes32)";
+
+/// `cancelOrder` calldata for `order` as hashed on `chain`.
+fn encode_cancel_order(order: &OrderJson, chain: u64) -> Result<Vec<u8>> {
+    let mut data = selector(CANCEL_ORDER).to_vec();
+    data.extend_from_slice(&parse_uint256(&order.maker_traits)?);
+    data.extend_from_slice(&order.hash(chain)?);
+    Ok(data)
+}
+
+/// Rejects orders whose fields do not all parse, or that have no maker: the
+/// contract cancels for `msg.sender`, so only the maker can cancel.
+fn check_cancellable(order: &OrderJson) -> Result<()> {
+    order.struct_hash().map_err(|e| VectorPlusError::Validation(format!("Order is incomplete: {}", e)))?;
+    if parse_address(&order.maker)? == [0; 32] {
+        return Err(VectorPlusError::Validation(
+            "Order has the zero address as maker; only the maker who signed it can cancel".to_string()
+        ).into());
+    }
+    Ok(())
+}
+
+/// What a cancellation invalidates. Orders that cannot be filled more than
+/// once use the maker's bit invalidator, so the cancel also voids every other
+/// order of the maker with the same nonce; the rest are cancelled by hash.
+fn cancel_scope(traits: &[u8; 32]) -> String {
+    let flags = MakerTraits::decode(traits);
+    if flags.uses_bit_invalidator() {
+        format!("nonce {} (bit invalidator; other orders of the maker with this nonce are cancelled too)", flags.nonce)
+    } else {
+        "order hash (remaining invalidator)".to_string()
+    }
+}
+
+async fn cancel_order(file: &str, order: &OrderJson, submit: Option<&SubmitOptions<'_>>, cli: &crate::Cli) -> Result<()> {
+    check_cancellable(order)?;
+    let rpc = match submit {
+        Some(_) => Some(RpcClient::from_cli(cli, "order cancel --submit")?),
+        None => None,
+    };
+    // A submitted cancel hashes the order for the chain it is sent to
+    let chain = match (&rpc, submit) {
+        (Some(rpc), Some(options)) => check_chain(rpc, options.allow_mainnet).await?,
+        _ => networks::lookup(&cli.network)?.chain_id,
+    };
+    let calldata = encode_cancel_order(order, chain)?;
+    let maker = format_address(&parse_address(&order.maker)?);
+
+    println!("{} {} (chain {})", "🚫 Cancelling order:".cyan(), file.yellow(), chain);
+    println!("  • Order hash: 0x{}", hex::encode(order.hash(chain)?));
+    println!("  • Maker: {}", maker);
+    println!("  • Cancels by: {}", cancel_scope(&parse_uint256(&order.maker_traits)?));
+    println!("  • To: {} (cancelOrder)", LIMIT_ORDER_PROTOCOL);
+    println!("  • Calldata: 0x{}", hex::encode(&calldata));
+
+    let (Some(rpc), Some(options)) = (rpc, submit) else {
+        println!();
+        println!("Send the calldata to {} from the maker's account to cancel.", LIMIT_ORDER_PROTOCOL);
+        return Ok(());
+    };
+    println!();
+    if !confirm("Send the cancelOrder transaction from the maker?", false, cli.assume_yes)? {
+        return Err(eyre::eyre!("Cancellation aborted"));
+    }
+    let tx = json!({ "from": maker, "to": LIMIT_ORDER_PROTOCOL, "data": format!("0x{}", hex::encode(&calldata)) });
+    send_transaction(&rpc, &tx, options.timeout_secs).await?;
+    println!("{}", "✅ Order cancelled".green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_order() -> OrderJson {
+        OrderJson {
+            salt: "0x2a".to_string(),
+            maker: "0x00000000000000000000000000000000000000aa".to_string(),
+            receiver: "0x0000000000000000000000000000000000000000".to_string(),
+            maker_asset: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".to_string(),
+            taker_asset: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+            making_amount: "1500000000000000000".to_string(),
+            taking_amount: "3000000000".to_string(),
+            maker_traits: "0x0".to_string(),
+        }
+    }
+
+    fn encode(function: FillFunction, order: &OrderJson, taker_traits: [u8; 32], args: &[u8]) -> String {
+        let mut data = selector(&function.signature()).to_vec();
+        for value in [&order.salt, &order.maker, &order.receiver, &order.maker_asset, &order.taker_asset,
+                      &order.making_amount, &order.taking_amount, &order.maker_traits] {
+            let word = parse_uint256(value).or_else(|_| parse_address(value)).unwrap();
+            data.extend_from_slice(&word);
+        }
+        data.extend_from_slice(&[0x11; 32]); // r
+        data.extend_from_slice(&[0x22; 32]); // vs
+        data.extend_from_slice(&parse_uint256("1000000000000000000").unwrap());
+        data.extend_from_slice(&taker_traits);
+        if function == FillFunction::FillOrderArgs {
+            data.extend_from_slice(&parse_uint256(&(13 * 32).to_string()).unwrap());
+            data.extend_from_slice(&parse_uint256(&args.len().to_string()).unwrap());
+            data.extend_from_slice(args);
+            data.resize(4 + (data.len() - 4).div_ceil(32) * 32, 0);
+        }
+        format!("0x{}", hex::encode(data))
+    }
+
+    #[test]
+    fn decodes_fill_revert_reasons() {
+        let mut error_string = hex::decode("08c379a0").unwrap();
+        error_string.extend_from_slice(&parse_uint256("32").unwrap());
+        error_string.extend_from_slice(&parse_uint256("29").unwrap());
+        let mut text = b"ERC20: insufficient allowance".to_vec();
+        text.resize(32, 0);
+        error_string.extend_from_slice(&text);
+        assert_eq!(decode_revert(&error_string), "Error(\"ERC20: insufficient allowance\")");
+
+        let predicate = selector("PredicateIsFalse()").to_vec();
+        assert_eq!(decode_revert(&predicate), "PredicateIsFalse(): the order's predicate returned false");
+
+        let mut panic = hex::decode("4e487b71").unwrap();
+        panic.extend_from_slice(&parse_uint256("17").unwrap());
+        assert_eq!(decode_revert(&panic), "Panic(0x11): arithmetic overflow");
+
+        assert_eq!(decode_revert(&[]), "no revert reason");
+        assert!(decode_revert(&[0xde, 0xad, 0xbe, 0xef]).starts_with("unknown error 0xdeadbeef"));
+    }
+
+    #[test]
+    fn maker_coverage_compares_full_words() {
+        let word = |units: &str| parse_uint256(units).unwrap();
+        let making = word("1500000000000000000");
+        // 2^128 differs from the making amount only in the high bytes
+        let large = word("340282366920938463463374607431768211456");
+        assert_eq!(
+            MakerCoverage::of(&large, &word("0"), &making),
+            MakerCoverage { balance_covers: true, allowance_covers: false, zero_allowance: true }
+        );
+        assert_eq!(
+            MakerCoverage::of(&word("1499999999999999999"), &making, &making),
+            MakerCoverage { balance_covers: false, allowance_covers: true, zero_allowance: false }
+        );
+        assert_eq!(
+            hex::encode(erc20_call("allowance(address,address)", &[&sample_order().maker, LIMIT_ORDER_PROTOCOL]).unwrap()),
+            format!("dd62ed3e{:0>64}{:0>64}", "aa", LIMIT_ORDER_PROTOCOL[2..].to_lowercase())
+        );
+    }
+
+    #[test]
+    fn refuses_every_production_chain() {
+        for network in &networks::NETWORKS {
+            let err = refuse_production_chain(network.chain_id, "http://fork", false).unwrap_err().to_string();
+            assert!(err.contains(network.name), "{}", err);
+            refuse_production_chain(network.chain_id, "http://fork", true).unwrap();
+        }
+        refuse_production_chain(31337, "http://fork", false).unwrap();
+    }
+
+    #[test]
+    fn selectors_match_the_deployed_protocol() {
+        assert_eq!(hex::encode(selector(&FillFunction::FillOrder.signature())), "9fda64bd");
+        assert_eq!(hex::encode(selector(&FillFunction::FillOrderArgs.signature())), "f497df75");
+    }
+
+    #[test]
+    fn cancel_encodes_traits_and_hash_for_complete_orders() {
+        let order = sample_order();
+        let data = encode_cancel_order(&order, 1).unwrap();
+        assert_eq!(hex::encode(&data[..4]), "b68fb020");
+        assert_eq!(data[4..36], parse_uint256(&order.maker_traits).unwrap());
+        assert_eq!(data[36..], order.hash(1).unwrap());
+        check_cancellable(&order).unwrap();
+
+        let preview = OrderJson { maker: "0x0000000000000000000000000000000000000000".to_string(), ..order.clone() };
+        assert!(check_cancellable(&preview).is_err());
+        let incomplete = OrderJson { taking_amount: String::new(), ..order };
+        assert!(check_cancellable(&incomplete).unwrap_err().to_string().contains("takingAmount"));
+
+        // Single-fill orders cancel through the nonce; multi-fill ones by hash
+        let mut traits = MakerTraits { allow_partial_fill: true, allow_multiple_fills: true, ..MakerTraits::default() }.encode();
+        assert!(cancel_scope(&traits).starts_with("order hash"));
+        set_word_bits(&mut traits, 120, 40, 7);
+        set_word_bits(&mut traits, ALLOW_MULTIPLE_FILLS_FLAG, 1, 0);
+        assert!(cancel_scope(&traits).starts_with("nonce 7"));
+    }
+
+    #[cfg(not(feature = "ledger"))]
+    #[tokio::test]
+    async fn ledger_flag_explains_the_missing_feature() {
+        use clap::Parser;
+        let cli = crate::Cli::try_parse_from(["vector-plus", "order", "sign", "--order", "order.json", "--ledger", "--hd-path", "m/44'/60'/0'/0/1"]).unwrap();
+        let err = crate::run(&cli).await.unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), 2);
+        let message = err.to_string();
+        assert!(message.contains("--features ledger") && message.contains("cast wallet sign --no-hash"), "{}", message);
+    }
+
+    #[test]
+    fn batch_entries_read_numbers_and_collide_on_salt() {
+        let entries: Vec<BatchEntry> = serde_json::from_str(r#"[{"makingAmount": 1.5, "salt": 7}, {"takingAmount": "3000"}]"#).unwrap();
+        assert_eq!(entries[0].making_amount.as_deref(), Some("1.5"));
+        assert_eq!(entries[0].salt.as_deref(), Some("7"));
+        assert_eq!(entries[1].taking_amount.as_deref(), Some("3000"));
+        assert!(serde_json::from_str::<Vec<BatchEntry>>(r#"[{"strike": 1}]"#).is_err());
+
+        // Fillable many times, so only salts and hashes can collide
+        let traits = |traits: MakerTraits| format!("0x{}", hex::encode(traits.encode()));
+        let repeatable = traits(MakerTraits { allow_partial_fill: true, allow_multiple_fills: true, ..MakerTraits::default() });
+        let first = OrderJson { maker_traits: repeatable.clone(), ..sample_order() };
+        let second = OrderJson { making_amount: "1".to_string(), ..first.clone() };
+        let third = OrderJson { salt: "0x2b".to_string(), ..first.clone() };
+        let orders = [("order 1".to_string(), first), ("order 2".to_string(), second), ("order 3".to_string(), third)];
+        let collisions = Collisions::find(&orders, 1).unwrap();
+        assert_eq!(collisions.salts, [("0".repeat(62) + "2a", vec!["order 1".to_string(), "order 2".to_string()])]);
+        assert!(collisions.hashes.is_empty());
+        assert!(collisions.report().is_err());
+        assert!(Collisions::find(&orders[1..], 1).unwrap().is_empty());
+
+        // Single-fill orders of one maker must not share a nonce; multi-fill ones may
+        let single = |salt: &str, nonce: u64| OrderJson {
+            salt: salt.to_string(),
+            maker_traits: traits(MakerTraits { nonce, ..MakerTraits::default() }),
+            ..sample_order()
+        };
+        let orders = [("a".to_string(), single("1", 3)), ("b".to_string(), single("2", 3)), ("c".to_string(), single("3", 4))];
+        let collisions = Collisions::find(&orders, 1).unwrap();
+        assert_eq!(collisions.nonces.len(), 1);
+        assert_eq!(collisions.nonces[0].1, ["a", "b"]);
+        assert!(collisions.nonces[0].0.ends_with(" nonce 3"), "{}", collisions.nonces[0].0);
+        let multi = |salt: &str| OrderJson { salt: salt.to_string(), maker_traits: repeatable.clone(), ..sample_order() };
+        assert!(Collisions::find(&[("a".to_string(), multi("1")), ("b".to_string(), multi("2"))], 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fills_template_placeholders() {
+        let values = BTreeMap::from([("salt", "7".to_string()), ("maker", "0xabc".to_string())]);
+        assert_eq!(
+            fill_template(r#"{"salt":"${salt}","maker":"${maker}","note":"${maker}-${salt}"}"#, &values).unwrap(),
+            r#"{"salt":"7","maker":"0xabc","note":"0xabc-7"}"#
+        );
+        assert_eq!(
+            fill_template(r#"{"makingAmount":"${makingAmount}","desk":"${desk}","x":"${makingAmount}"}"#, &values).unwrap_err(),
+            ["${makingAmount} (--making-amount)", "${desk} (not a known placeholder)"]
+        );
+    }
+
+    #[test]
+    fn decodes_fill_order_back_into_the_order() {
+        let order = sample_order();
+        let fill = decode_fill_calldata(&encode(FillFunction::FillOrder, &order, [0; 32], &[])).unwrap();
+        assert_eq!(fill.function, FillFunction::FillOrder);
+        assert_eq!(fill.order.hash(1).unwrap(), order.hash(1).unwrap());
+        assert_eq!(fill.order.maker_asset, order.maker_asset);
+        assert_eq!(format_uint256(&fill.amount), "1000000000000000000");
+        assert!(fill.extension.is_none());
+
+        let network = networks::lookup("mainnet").unwrap();
+        assert_eq!(format_token_amount(&fill.order.making_amount, &fill.order.maker_asset, network), "1.5 WETH");
+        assert_eq!(format_token_amount(&fill.order.taking_amount, &fill.order.taker_asset, network), "3000 USDC");
+    }
+
+    #[test]
+    fn decodes_the_predicate_from_fill_order_args() {
+        // Extension with only a 4-byte predicate: fields 0-3 end at 0, field 4 and later at 4
+        let mut extension = [0u8; 32];
+        for field in 4..8 {
+            extension[28 - field * 4 + 3] = 4;
+        }
+        let mut extension = extension.to_vec();
+        extension.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let mut taker_traits = [0u8; 32];
+        taker_traits[3] = extension.len() as u8;
+        let calldata = encode(FillFunction::FillOrderArgs, &sample_order(), taker_traits, &extension);
+        let fill = decode_fill_calldata(&calldata).unwrap();
+        let extension = fill.extension.unwrap();
+        assert_eq!(extension_predicate(&extension).unwrap(), Some(&[0xde, 0xad, 0xbe, 0xef][..]));
+    }
+
+    #[test]
+    fn encoded_fills_decode_back() {
+        let mut s = [0u8; 32];
+        s[31] = 7;
+        let signature = format!("0x{}{}1c", "11".repeat(32), hex::encode(s));
+        let (r, vs) = compact_signature(&signature).unwrap();
+        assert_eq!(r, [0x11; 32]);
+        assert_eq!(vs[0], 0x80);
+        assert_eq!(vs[31], 7);
+
+        let order = sample_order();
+        let amount = parse_uint256("1000").unwrap();
+        let calldata = encode_fill_order(&order, r, vs, amount, [0; 32]).unwrap();
+        let fill = decode_fill_calldata(&hex::encode(calldata)).unwrap();
+        assert_eq!(fill.order.hash(1).unwrap(), order.hash(1).unwrap());
+        assert_eq!(fill.amount, amount);
+    }
+
+    #[test]
+    fn rejects_malformed_calldata() {
+        assert!(decode_fill_calldata("0xzz").is_err());
+        assert!(decode_fill_calldata("0x12345678").unwrap_err().to_string().contains("Unsupported function selector"));
+        let truncated = &encode(FillFunction::FillOrder, &sample_order(), [0; 32], &[])[..200];
+        assert!(decode_fill_calldata(truncated).unwrap_err().to_string().contains("Malformed fillOrder calldata"));
+
+        // Offset and length words near u64::MAX must not overflow the bounds checks
+        let args = encode(FillFunction::FillOrderArgs, &sample_order(), [0; 32], &[0xaa; 4]);
+        let offset_word = 2 + 2 * (4 + 12 * 32);
+        let huge = "ff".repeat(8);
+        let bad_offset = format!("{}{}{}", &args[..offset_word + 48], huge, &args[offset_word + 64..]);
+        let err = decode_fill_calldata(&bad_offset).unwrap_err().to_string();
+        assert!(err.contains("offset points past the end of the calldata"), "{}", err);
+        let length_word = 2 + 2 * (4 + 13 * 32);
+        let bad_length = format!("{}{}{}", &args[..length_word + 48], huge, &args[length_word + 64..]);
+        let err = decode_fill_calldata(&bad_length).unwrap_err().to_string();
+        assert!(err.contains("length runs past the end of the calldata"), "{}", err);
+    }
+
+    #[test]
+    fn maker_traits_round_trip_and_reject_contradictions() {
+        let traits = MakerTraits { allow_partial_fill: true, allow_multiple_fills: true, unwrap_weth: true, expiry: Some(1_700_000_000), nonce: 0 };
+        let word = traits.encode();
+        assert_eq!(hex::encode(&word[..2]), "4080");
+        assert_eq!(MakerTraits::decode(&word), traits);
+        // The default order fills whole, once
+        assert_eq!(MakerTraits::decode(&MakerTraits::default().encode()), MakerTraits::default());
+        assert!(word_flag(&MakerTraits::default().encode(), NO_PARTIAL_FILLS_FLAG));
+
+        let mainnet = networks::lookup("mainnet").unwrap();
+        let weth = mainnet.wrapped_native().unwrap().address;
+        assert_eq!(check_maker_traits(&traits, weth, mainnet), (vec![], vec![]));
+        let (_, errors) = check_maker_traits(&traits, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", mainnet);
+        assert!(errors[0].contains("Unwrapping WETH"), "{:?}", errors);
+        let whole_but_repeated = MakerTraits { allow_multiple_fills: true, ..MakerTraits::default() };
+        let (_, errors) = check_maker_traits(&whole_but_repeated, weth, mainnet);
+        assert!(errors[0].contains("Multiple fills are allowed but partial fills are not"), "{:?}", errors);
+        let (warnings, errors) = check_maker_traits(&MakerTraits { allow_partial_fill: true, ..MakerTraits::default() }, weth, mainnet);
+        assert!(errors.is_empty() && warnings[0].contains("first partial fill closes the order"), "{:?}", warnings);
+
+        // Single-fill orders need their own nonce, which round-trips through bits 120..160
+        let (warnings, _) = check_maker_traits(&MakerTraits::default(), weth, mainnet);
+        assert!(warnings[0].contains("Single-fill order with nonce 0"), "{:?}", warnings);
+        let numbered = MakerTraits { nonce: (1 << 40) - 1, ..MakerTraits::default() };
+        assert_eq!(check_maker_traits(&numbered, weth, mainnet), (vec![], vec![]));
+        assert_eq!(MakerTraits::decode(&numbered.encode()), numbered);
+        assert_eq!(word_bits(&numbered.encode(), 120, 40), (1 << 40) - 1);
+        assert_eq!(word_bits(&numbered.encode(), 160, 40), 0);
+    }
+}
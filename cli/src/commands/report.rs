@@ -0,0 +1,332 @@
+use colored::*;
+use eyre::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::combined::CombinedStrategy;
+use super::options::OptionConfig;
+use super::strategy::StrategyKind;
+use super::twap::TwapConfig;
+use super::volatility::{Regime, VolatilityConfig};
+use crate::utils::{format_amount, format_time_at, TimeFormat};
+use crate::utils::table::{Align, Cell, OutputFormat, Table};
+
+/// How hard a TWAP pushes the market, judged by its interval length and slippage allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Aggressiveness {
+    Passive,
+    Moderate,
+    Aggressive,
+}
+
+impl Aggressiveness {
+    /// Fills less than a minute apart or slippage above 3% are aggressive;
+    /// fills at least ten minutes apart are passive.
+    fn classify(config: &TwapConfig) -> Self {
+        let interval_secs = config.duration / config.intervals.max(1) as u64;
+        if interval_secs < 60 || config.max_slippage_bps > 300 {
+            Aggressiveness::Aggressive
+        } else if interval_secs < 600 {
+            Aggressiveness::Moderate
+        } else {
+            Aggressiveness::Passive
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Aggressiveness::Passive => "passive",
+            Aggressiveness::Moderate => "moderate",
+            Aggressiveness::Aggressive => "aggressive",
+        }
+    }
+}
+
+/// Where an option stands relative to the `--expiring-within-hours` window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expiry {
+    Expired,
+    Soon,
+    Later,
+}
+
+/// One config file's classification.
+struct Entry {
+    file: String,
+    kind: Option<StrategyKind>,
+    status: String,
+    color: Option<Color>,
+    /// Max execution size in ETH, for configs that size orders
+    notional_eth: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Summary {
+    configs: usize,
+    by_type: BTreeMap<&'static str, usize>,
+    emergency_volatility: usize,
+    elevated_volatility: usize,
+    aggressive_twap: usize,
+    expired_options: usize,
+    expiring_options: usize,
+    notional_eth: f64,
+    invalid: usize,
+    skipped: usize,
+}
+
+pub async fn generate_report(
+    dir: &str,
+    expiring_within_hours: u64,
+    format: OutputFormat,
+    precision: u8,
+    time_format: TimeFormat,
+) -> Result<()> {
+    let now = chrono::Utc::now().timestamp() as u64;
+    let (entries, summary) = scan(dir, now, expiring_within_hours * 3600, time_format)?;
+    let table = entries_table(&entries, precision);
+
+    // The JSON report wraps the rows with the summary; CSV has only the rows
+    if format == OutputFormat::Csv {
+        println!("{}", table.format(format));
+        return Ok(());
+    }
+    if format == OutputFormat::Json {
+        let report = serde_json::json!({
+            "dir": dir,
+            "generated_at": now,
+            "expiring_within_hours": expiring_within_hours,
+            "summary": summary,
+            "configs": table.to_json(),
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{} {}", "📋 Risk report for:".cyan(), dir.yellow());
+    println!();
+    if entries.is_empty() {
+        println!("No strategy configs found");
+    } else {
+        println!("{}", table.render());
+    }
+    println!();
+    println!("{}", "📊 Summary:".bold());
+    let by_type: Vec<String> = summary.by_type.iter().map(|(kind, count)| format!("{} {}", count, kind)).collect();
+    println!("  • Configs: {}{}", summary.configs, if by_type.is_empty() { String::new() } else { format!(" ({})", by_type.join(", ")) });
+    println!("  • Configured notional: {} ETH max execution", format_amount(summary.notional_eth, precision));
+    let emergency = format!("  • Emergency-regime volatility configs: {}", summary.emergency_volatility);
+    println!("{}", if summary.emergency_volatility > 0 { emergency.red().to_string() } else { emergency });
+    println!("  • Elevated-regime volatility configs: {}", summary.elevated_volatility);
+    println!("  • Aggressive TWAPs: {}", summary.aggressive_twap);
+    let expiring = format!(
+        "  • Options expiring within {}h: {} ({} already expired)",
+        expiring_within_hours, summary.expiring_options, summary.expired_options
+    );
+    println!("{}", if summary.expiring_options > 0 { expiring.yellow().to_string() } else { expiring });
+    if summary.invalid > 0 {
+        println!("{}", format!("  • Invalid configs: {}", summary.invalid).red());
+    }
+    if summary.skipped > 0 {
+        println!("  • Skipped {} JSON files that are not strategy configs", summary.skipped);
+    }
+    Ok(())
+}
+
+/// Classifies every strategy config among the `.json` files in `dir`, in file
+/// name order, as of `now`.
+fn scan(dir: &str, now: u64, window_secs: u64, time_format: TimeFormat) -> Result<(Vec<Entry>, Summary)> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .map_err(|_| eyre::eyre!("Could not read directory: {}", dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    let mut summary = Summary::default();
+    let mut entries = Vec::with_capacity(files.len());
+
+    for path in &files {
+        let value = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+        let Some(kind) = value.as_ref().and_then(StrategyKind::detect) else {
+            summary.skipped += 1;
+            continue;
+        };
+        let value = value.expect("detected configs were parsed");
+        let entry = classify(path, kind, value, now, window_secs, time_format, &mut summary);
+        if entry.status.starts_with("invalid") {
+            summary.invalid += 1;
+        }
+        summary.configs += 1;
+        *summary.by_type.entry(kind.name()).or_default() += 1;
+        summary.notional_eth += entry.notional_eth.unwrap_or(0.0);
+        entries.push(entry);
+    }
+    Ok((entries, summary))
+}
+
+fn entries_table(entries: &[Entry], precision: u8) -> Table {
+    let mut table = Table::new()
+        .column("file", "File", Align::Left)
+        .column("type", "Type", Align::Left)
+        .column("status", "Status", Align::Left)
+        .column("notional_eth", "Notional (ETH)", Align::Right);
+    for entry in entries {
+        let cells = vec![
+            Cell::text(entry.file.as_str()),
+            Cell::text(entry.kind.map_or("unknown", |kind| kind.name())),
+            Cell::text(entry.status.as_str()),
+            match entry.notional_eth {
+                Some(eth) => Cell::new(format_amount(eth, precision), eth),
+                None => Cell::new("-", serde_json::Value::Null),
+            },
+        ];
+        match entry.color {
+            Some(color) => table.colored_row(cells, color),
+            None => table.row(cells),
+        }
+    }
+    table
+}
+
+fn classify(
+    path: &Path,
+    kind: StrategyKind,
+    value: serde_json::Value,
+    now: u64,
+    window_secs: u64,
+    time_format: TimeFormat,
+    summary: &mut Summary,
+) -> Entry {
+    let mut entry = Entry {
+        file: path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned()),
+        kind: Some(kind),
+        status: String::new(),
+        color: None,
+        notional_eth: None,
+    };
+    let result = match kind {
+        StrategyKind::Volatility => serde_json::from_value::<VolatilityConfig>(value).map(|config| {
+            let regime = volatility_status(&config, summary);
+            entry.notional_eth = Some(config.execution_limits().1.as_eth_f64());
+            (format!("{} regime", regime.name()), regime_color(regime))
+        }),
+        StrategyKind::Twap => serde_json::from_value::<TwapConfig>(value).map(|config| {
+            let level = twap_status(&config, summary);
+            (format!("{} TWAP", level.name()), (level == Aggressiveness::Aggressive).then_some(Color::Yellow))
+        }),
+        StrategyKind::Combined => serde_json::from_value::<CombinedStrategy>(value).map(|strategy| {
+            let regime = volatility_status(&strategy.volatility, summary);
+            let level = twap_status(&strategy.twap, summary);
+            entry.notional_eth = Some(strategy.volatility.execution_limits().1.as_eth_f64());
+            (format!("{} regime, {} TWAP", regime.name(), level.name()), regime_color(regime))
+        }),
+        StrategyKind::Option => serde_json::from_value::<OptionConfig>(value).map(|config| {
+            let expiry = match config.expiration {
+                at if at <= now => Expiry::Expired,
+                at if at - now <= window_secs => Expiry::Soon,
+                _ => Expiry::Later,
+            };
+            let side = if config.is_call { "call" } else { "put" };
+            let when = format_time_at(config.expiration, time_format, now);
+            match expiry {
+                Expiry::Expired => {
+                    summary.expired_options += 1;
+                    (format!("{} expired {}", side, when), Some(Color::Red))
+                }
+                Expiry::Soon => {
+                    summary.expiring_options += 1;
+                    (format!("{} expires {}", side, when), Some(Color::Yellow))
+                }
+                Expiry::Later => (format!("{} expires {}", side, when), None),
+            }
+        }),
+    };
+    match result {
+        Ok((status, color)) => {
+            entry.status = status;
+            entry.color = color;
+        }
+        Err(e) => {
+            entry.status = format!("invalid: {}", e);
+            entry.color = Some(Color::Red);
+        }
+    }
+    entry
+}
+
+fn volatility_status(config: &VolatilityConfig, summary: &mut Summary) -> Regime {
+    let regime = Regime::classify(config);
+    match regime {
+        Regime::Emergency => summary.emergency_volatility += 1,
+        Regime::Elevated => summary.elevated_volatility += 1,
+        Regime::Low | Regime::Normal => {}
+    }
+    regime
+}
+
+fn twap_status(config: &TwapConfig, summary: &mut Summary) -> Aggressiveness {
+    let level = Aggressiveness::classify(config);
+    if level == Aggressiveness::Aggressive {
+        summary.aggressive_twap += 1;
+    }
+    level
+}
+
+fn regime_color(regime: Regime) -> Option<Color> {
+    match regime {
+        Regime::Emergency => Some(Color::Red),
+        Regime::Elevated => Some(Color::Yellow),
+        Regime::Low | Regime::Normal => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_a_fixed_directory() {
+        let dir = std::env::temp_dir().join(format!("vector-plus-report-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let now = 1_700_000_000;
+        let files = [
+            ("vol.json", r#"{"baseline_volatility":300,"current_volatility":1500,"max_execution_size":"5000000000000000000","min_execution_size":"100000000000000000","volatility_threshold":600,"conservative_mode":false,"emergency_threshold":1200,"last_update_time":0}"#.to_string()),
+            ("twap.json", r#"{"start_time":0,"duration":600,"intervals":20,"executed_intervals":0,"randomize_execution":false,"min_execution_gap":0,"max_slippage_bps":50}"#.to_string()),
+            ("option.json", format!(r#"{{"is_call":true,"strike_price":3000,"expiration":{},"premium":12.5,"implied_volatility":8000,"creation_time":0}}"#, now + 7200)),
+            ("broken.json", r#"{"baseline_volatility":"high"}"#.to_string()),
+            ("notes.json", r#"{"owner":"desk"}"#.to_string()),
+            ("readme.txt", "not json".to_string()),
+        ];
+        for (name, content) in &files {
+            fs::write(dir.join(name), content).unwrap();
+        }
+
+        let (entries, summary) = scan(dir.to_str().unwrap(), now, 24 * 3600, TimeFormat::Relative).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let csv = entries_table(&entries, 4).format(OutputFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "file,type,status,notional_eth");
+        assert!(lines[1].starts_with("broken.json,volatility,invalid: "), "{}", lines[1]);
+        assert_eq!(lines[2..], ["option.json,option,call expires in 2h,", "twap.json,TWAP,aggressive TWAP,", "vol.json,volatility,EMERGENCY regime,5"]);
+
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::json!({
+                "configs": 4,
+                "by_type": { "TWAP": 1, "option": 1, "volatility": 2 },
+                "emergency_volatility": 1,
+                "elevated_volatility": 0,
+                "aggressive_twap": 1,
+                "expired_options": 0,
+                "expiring_options": 1,
+                "notional_eth": 5.0,
+                "invalid": 1,
+                "skipped": 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use colored::*;
+use eyre::Result;
+
+use super::order::MakerTraits;
+use super::volatility::{size_execution, taking_amount, volatility_adjustment_factor, Regime, VolatilityConfig};
+use crate::amount::{Amount, DECIMALS};
+use crate::eip712::{domain_separator, format_address, parse_address, OrderJson, LIMIT_ORDER_PROTOCOL};
+use crate::networks::{self, Network};
+use crate::utils::{backup_existing, confirm_overwrite, format_amount, read_config, verbose, write_json_file};
+
+/// Inputs of `vector-plus simulate`, one order from strategy to hash.
+pub struct PipelineInputs<'a> {
+    pub volatility_config: &'a str,
+    pub amount: Amount,
+    /// Taker asset per maker asset
+    pub price: f64,
+    pub maker_asset: &'a str,
+    pub taker_asset: &'a str,
+    pub maker: &'a str,
+    pub maker_decimals: Option<u32>,
+    pub taker_decimals: Option<u32>,
+    pub salt: Option<&'a str>,
+    pub expires_in_secs: Option<u64>,
+    pub out: Option<&'a str>,
+}
+
+/// An order asset given as a known token symbol or an address.
+pub(crate) struct Asset {
+    pub address: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+impl Asset {
+    pub(crate) fn resolve(value: &str, decimals: Option<u32>, flag: &str, network: &Network) -> Result<Self> {
+        let known = network.tokens.iter()
+            .find(|token| token.symbol.eq_ignore_ascii_case(value) || token.address.eq_ignore_ascii_case(value));
+        if let Some(token) = known {
+            return Ok(Asset {
+                address: token.address.to_string(),
+                symbol: token.symbol.to_string(),
+                decimals: decimals.unwrap_or(token.decimals),
+            });
+        }
+        let address = parse_address(value).map_err(|_| {
+            let symbols: Vec<&str> = network.tokens.iter().map(|token| token.symbol).collect();
+            eyre::eyre!(
+                "--{} must be an address or a {} token symbol ({}), got {}",
+                flag, network.name, symbols.join(", "), value
+            )
+        })?;
+        let decimals = decimals.ok_or_else(|| {
+            eyre::eyre!("{} is not a known {} token; pass --{}-decimals", value, network.name, flag.trim_end_matches("-asset"))
+        })?;
+        Ok(Asset { address: format_address(&address), symbol: "tokens".to_string(), decimals })
+    }
+}
+
+/// `makerTraits` of an order `order build` would make with the same expiry:
+/// filled whole, once.
+fn maker_traits(expiry: Option<u64>) -> String {
+    format!("0x{}", hex::encode(MakerTraits { expiry, ..MakerTraits::default() }.encode()))
+}
+
+/// Runs volatility → sizing → amounts → order → EIP-712 hash, printing each stage.
+pub async fn run_pipeline(inputs: &PipelineInputs<'_>, cli: &crate::Cli) -> Result<()> {
+    let precision = cli.precision;
+    let network = networks::lookup(&cli.network)?;
+    if !inputs.price.is_finite() || inputs.price <= 0.0 {
+        return Err(eyre::eyre!("--price must be a positive number, got {}", inputs.price));
+    }
+    let maker_asset = Asset::resolve(inputs.maker_asset, inputs.maker_decimals, "maker-asset", network)?;
+    let taker_asset = Asset::resolve(inputs.taker_asset, inputs.taker_decimals, "taker-asset", network)?;
+    let maker = format_address(&parse_address(inputs.maker).map_err(|e| eyre::eyre!("Invalid --maker: {}", e))?);
+    let config: VolatilityConfig = read_config(inputs.volatility_config)?;
+    verbose!(cli.verbose, "loaded {}", inputs.volatility_config);
+
+    println!("{} {} → order on {}", "🧪 Simulating".cyan(), inputs.volatility_config.yellow(), network.name);
+    println!();
+
+    let factor = volatility_adjustment_factor(&config);
+    println!("{}", "1️⃣  Volatility".bold());
+    println!("  • Baseline: {}bps, current: {}bps", config.baseline_volatility, config.current_volatility);
+    println!("  • Regime: {}", Regime::classify(&config).label());
+    println!("  • Adjustment factor: {}%", factor);
+    println!();
+
+    let (min_amount, max_amount) = config.execution_limits();
+    let (adjusted, final_amount) = size_execution(&config, inputs.amount);
+    println!("{}", "2️⃣  Sizing".bold());
+    println!("  • Requested: {} {}", inputs.amount.format_eth(precision), maker_asset.symbol);
+    println!("  • Adjusted: {} {}", adjusted.format_eth(precision), maker_asset.symbol);
+    let clamp = if final_amount < adjusted {
+        " (capped at maximum)"
+    } else if final_amount > adjusted {
+        " (raised to minimum)"
+    } else {
+        ""
+    };
+    println!(
+        "  • Final: {} {}{} within [{}, {}]",
+        final_amount.format_eth(precision).yellow(), maker_asset.symbol, clamp,
+        min_amount.format_eth(precision), max_amount.format_eth(precision)
+    );
+    println!();
+
+    // Sizing is exact in 18 decimals; other maker decimals truncate. The
+    // taking side is the same integer product as in `volatility calculate`
+    let making_amount = final_amount.to_base_units(maker_asset.decimals as usize);
+    let taking = taking_amount(final_amount, inputs.price)
+        .ok_or_else(|| eyre::eyre!("--price {} is too large for {} {}", inputs.price, final_amount, maker_asset.symbol))?;
+    let taking_units = taking.to_base_units(taker_asset.decimals as usize);
+    println!("{}", "3️⃣  Amounts".bold());
+    println!("  • Making: {} {} ({} base units, {} decimals)", final_amount.format_eth(precision), maker_asset.symbol, making_amount, maker_asset.decimals);
+    println!(
+        "  • Taking: {} {} ({} base units, {} decimals) @ {}",
+        taking.format_eth(precision), taker_asset.symbol, taking_units, taker_asset.decimals, format_amount(inputs.price, precision)
+    );
+    if maker_asset.decimals as usize > DECIMALS {
+        println!("  {} Maker asset has more than 18 decimals; the amount is padded", "ℹ️ ".blue());
+    }
+    println!();
+
+    let now = chrono::Utc::now();
+    let order = OrderJson {
+        salt: inputs.salt.map(str::to_string).unwrap_or_else(|| now.timestamp_millis().to_string()),
+        maker: maker.clone(),
+        receiver: format_address(&[0u8; 32]),
+        maker_asset: maker_asset.address.clone(),
+        taker_asset: taker_asset.address.clone(),
+        making_amount,
+        taking_amount: taking_units,
+        maker_traits: maker_traits(inputs.expires_in_secs.map(|secs| now.timestamp() as u64 + secs)),
+    };
+    println!("{}", "4️⃣  Order".bold());
+    println!("{}", serde_json::to_string_pretty(&order)?);
+    if parse_address(&maker)?.iter().all(|&b| b == 0) {
+        println!("  {} Maker is the zero address; pass --maker to build a fillable order", "ℹ️ ".blue());
+    }
+    if let Some(out) = inputs.out {
+        confirm_overwrite(out, cli.assume_yes)?;
+        if let Some(backup) = backup_existing(out)? {
+            println!("  • Kept the previous {} as {}", out, backup);
+        }
+        write_json_file(out, &order)?;
+        crate::history::record(out, &order);
+        println!("  • Saved to {}", out);
+    }
+    println!();
+
+    println!("{}", "5️⃣  EIP-712".bold());
+    println!("  • Domain: 1inch Aggregation Router v6, chain {}, {}", network.chain_id, LIMIT_ORDER_PROTOCOL);
+    println!("  • Domain separator: 0x{}", hex::encode(domain_separator(network.chain_id, LIMIT_ORDER_PROTOCOL)?));
+    println!("  • Struct hash: 0x{}", hex::encode(order.struct_hash()?));
+    println!("  • Order hash: {}", format!("0x{}", hex::encode(order.hash(network.chain_id)?)).bold());
+    println!();
+    println!("{}", "✅ Pipeline complete; sign the order hash to publish the order".green());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pipeline_takes_the_same_amount_as_calculate() {
+        use clap::Parser;
+        let dir = std::env::temp_dir().join(format!("vector-plus-simulate-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (config, out) = (dir.join("vol.json"), dir.join("order.json"));
+        std::fs::write(&config, r#"{"baseline_volatility":300,"current_volatility":300,"max_execution_size":"5000000000000000000","min_execution_size":"100000000000000000","volatility_threshold":600,"conservative_mode":false,"emergency_threshold":1200,"last_update_time":0}"#).unwrap();
+
+        let cli = crate::Cli::try_parse_from([
+            "vector-plus", "--assume-yes", "--network", "mainnet", "simulate",
+            "--volatility-config", config.to_str().unwrap(),
+            "--amount", "4.999999999999999999", "--price", "3000.123456",
+            "--maker-asset", "WETH", "--taker-asset", "USDC", "--salt", "1",
+            "--out", out.to_str().unwrap(),
+        ]).unwrap();
+        crate::run(&cli).await.unwrap();
+        let order: OrderJson = read_config(out.to_str().unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // 15000.617279999999996999876544 USDC rounded down, as `volatility calculate` reports
+        assert_eq!(order.making_amount, "4999999999999999999");
+        assert_eq!(order.taking_amount, "15000617279");
+        assert_eq!(order.maker_traits, maker_traits(None));
+        assert_eq!(order.taking_amount, taking_amount("4.999999999999999999".parse().unwrap(), 3000.123456).unwrap().to_base_units(6));
+    }
+
+    #[test]
+    fn encodes_expiry_where_decode_reads_it() {
+        // Only the no-partial-fills bit, as `order build` sets by default
+        assert_eq!(maker_traits(None), format!("0x80{}", "0".repeat(62)));
+        let traits = crate::eip712::parse_uint256(&maker_traits(Some(1_700_000_000))).unwrap();
+        // Bits 80..120 are bytes 17..22 counted from the most significant end
+        assert_eq!(u64::from_be_bytes([0, 0, 0, traits[17], traits[18], traits[19], traits[20], traits[21]]), 1_700_000_000);
+        assert!(traits[1..17].iter().chain(&traits[22..]).all(|&b| b == 0));
+        assert_eq!(traits[0], 0x80);
+    }
+}
@@ -0,0 +1,376 @@
+use clap::{Subcommand, ValueEnum};
+use colored::*;
+use eyre::Result;
+
+use super::combined::{check_combined_strategy, CombinedStrategy};
+use super::twap::TwapConfig;
+use super::volatility::{check_volatility_config, VolatilityConfig};
+use crate::error::VectorPlusError;
+use crate::utils::{glob_files, output_name, read_config, status, write_created_config, TimeFormat};
+
+#[derive(Subcommand)]
+pub enum StrategyCommands {
+    /// Detect the type of each config file and validate it
+    Inspect {
+        /// Configuration files to inspect
+        #[arg(required_unless_present = "glob")]
+        files: Vec<String>,
+
+        /// Also inspect every file matching this pattern, e.g. 'configs/*.json'
+        #[arg(long)]
+        glob: Option<String>,
+
+        /// Fail on unknown fields and duplicate keys instead of warning
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Promote a volatility config into a combined strategy, or extract one back out
+    Convert {
+        /// Volatility config or combined strategy to convert
+        file: String,
+
+        /// Type of config to write
+        #[arg(long, value_enum)]
+        to: ConvertTarget,
+
+        /// TWAP config for the combined strategy (default: a TWAP from the --config defaults)
+        #[arg(long)]
+        twap_config: Option<String>,
+
+        /// Output file, or - for stdout (default: combined-strategy.json or volatility-config.json)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Share strategy configs as a single zip bundle
+    Bundle {
+        #[command(subcommand)]
+        command: super::bundle::BundleCommands,
+    },
+}
+
+/// What `strategy convert` turns a config into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConvertTarget {
+    /// A combined TWAP + volatility strategy
+    Combined,
+    /// A standalone volatility config
+    Volatility,
+}
+
+/// The kinds of config file the tool produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StrategyKind {
+    Volatility,
+    Twap,
+    Combined,
+    Option,
+}
+
+impl StrategyKind {
+    /// Identifies a config by the fields that only its type carries.
+    pub(crate) fn detect(value: &serde_json::Value) -> Option<Self> {
+        let object = value.as_object()?;
+        if object.contains_key("twap") && object.contains_key("volatility") {
+            Some(Self::Combined)
+        } else if object.contains_key("baseline_volatility") {
+            Some(Self::Volatility)
+        } else if object.contains_key("intervals") && object.contains_key("duration") {
+            Some(Self::Twap)
+        } else if object.contains_key("strike_price") && object.contains_key("is_call") {
+            Some(Self::Option)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Volatility => "volatility",
+            Self::Twap => "TWAP",
+            Self::Combined => "combined",
+            Self::Option => "option",
+        }
+    }
+}
+
+/// How many warnings and errors a validator reported for one file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Findings {
+    pub(crate) warnings: usize,
+    pub(crate) errors: usize,
+}
+
+impl Findings {
+    pub(crate) fn of(warnings: &[String], errors: &[String]) -> Self {
+        Findings { warnings: warnings.len(), errors: errors.len() }
+    }
+}
+
+/// Counts of files by outcome over one validation run. Each file is checked
+/// in full and counted; the run fails only once every file has been reported.
+#[derive(Debug, Default)]
+pub(crate) struct ValidationSummary {
+    files: usize,
+    ok: usize,
+    warnings: usize,
+    errors: usize,
+    missing: Vec<String>,
+}
+
+impl ValidationSummary {
+    /// Counts one file; a file that could not be validated at all, such as
+    /// one that does not parse, has its error printed and counts as an error.
+    /// A file that does not exist is also remembered for [`Self::finish`].
+    pub(crate) fn record(&mut self, file: &str, result: Result<Findings>) {
+        self.files += 1;
+        match result {
+            Ok(Findings { errors: 0, warnings: 0 }) => self.ok += 1,
+            Ok(Findings { errors: 0, .. }) => self.warnings += 1,
+            Ok(_) => self.errors += 1,
+            Err(e) => {
+                println!("{}", format!("❌ {}: {}", file, e).red());
+                if crate::error::exit_code(&e) == crate::error::EXIT_CONFIG_NOT_FOUND {
+                    self.missing.push(file.to_string());
+                }
+                self.errors += 1;
+            }
+        }
+    }
+
+    /// Prints the counts, failing with a validation error if any file had
+    /// errors, or with a file-not-found error if any file was missing.
+    pub(crate) fn finish(self) -> Result<()> {
+        println!();
+        println!(
+            "{} {} files: {} ok, {} with warnings, {} with errors",
+            "📋 Validated".bold(),
+            self.files,
+            self.ok.to_string().green(),
+            self.warnings.to_string().yellow(),
+            self.errors.to_string().red()
+        );
+        if !self.missing.is_empty() {
+            return Err(VectorPlusError::FileNotFound(self.missing.join(", ")).into());
+        }
+        if self.errors > 0 {
+            return Err(VectorPlusError::Validation(format!(
+                "{} of {} files failed validation",
+                self.errors, self.files
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Validation of a single file, as the per-type `validate` commands run it.
+    pub(crate) fn single(file: &str, result: Result<Findings>) -> Result<()> {
+        let mut summary = ValidationSummary::default();
+        summary.record(file, result);
+        summary.finish()
+    }
+}
+
+pub async fn handle_command(command: &StrategyCommands, cli: &crate::Cli) -> Result<()> {
+    match command {
+        StrategyCommands::Inspect { files, glob, strict } => {
+            inspect_strategies(files, glob.as_deref(), *strict, cli.precision, cli.time_format).await
+        }
+        StrategyCommands::Convert { file, to, twap_config, output } => {
+            convert_strategy(file, *to, twap_config.as_deref(), output.as_deref(), &cli.config)
+        }
+        StrategyCommands::Bundle { command } => super::bundle::handle_command(command, cli).await,
+    }
+}
+
+/// Inspects `files` and those matching `glob`, reporting every file before
+/// the summary rather than stopping at the first that fails.
+async fn inspect_strategies(
+    files: &[String],
+    glob: Option<&str>,
+    strict: bool,
+    precision: u8,
+    time_format: TimeFormat,
+) -> Result<()> {
+    let mut files = files.to_vec();
+    if let Some(pattern) = glob {
+        let matched = glob_files(pattern)?;
+        if matched.is_empty() {
+            return Err(eyre::eyre!("No files match {}", pattern));
+        }
+        for file in matched {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+
+    let mut summary = ValidationSummary::default();
+    for (i, file) in files.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        summary.record(file, inspect_strategy(file, strict, precision, time_format).await);
+    }
+    summary.finish()
+}
+
+async fn inspect_strategy(file: &str, strict: bool, precision: u8, time_format: TimeFormat) -> Result<Findings> {
+    let value: serde_json::Value = read_config(file)?;
+
+    let kind = StrategyKind::detect(&value).ok_or_else(|| {
+        eyre::eyre!(
+            "Unrecognized config file: {}\n  Expected a volatility, TWAP, combined or option config \
+             created by `vector-plus <volatility|twap|combined|options> create...`",
+            file
+        )
+    })?;
+
+    println!("{} {} config", "🔎 Detected:".cyan(), kind.name().yellow().bold());
+    println!();
+
+    match kind {
+        StrategyKind::Volatility => super::volatility::validate_volatility_config(file, strict, time_format).await,
+        StrategyKind::Twap => super::twap::validate_twap_config(file, strict).await,
+        StrategyKind::Combined => super::combined::validate_combined_strategy(file, strict).await,
+        StrategyKind::Option => super::options::validate_option_config(file, strict, precision, time_format).await,
+    }
+}
+
+/// Converts between a volatility config and a combined strategy, refusing to
+/// write a result that fails validation.
+fn convert_strategy(
+    file: &str,
+    to: ConvertTarget,
+    twap_config: Option<&str>,
+    output: Option<&str>,
+    config_file: &str,
+) -> Result<()> {
+    let value: serde_json::Value = read_config(file)?;
+    let kind = StrategyKind::detect(&value)
+        .ok_or_else(|| eyre::eyre!("Unrecognized config file: {}", file))?;
+    if twap_config.is_some() && to != ConvertTarget::Combined {
+        return Err(eyre::eyre!("--twap-config only applies with --to combined"));
+    }
+
+    let (json, (warnings, errors), default_output) = match (kind, to) {
+        (StrategyKind::Volatility, ConvertTarget::Combined) => {
+            let volatility: VolatilityConfig = serde_json::from_value(value)?;
+            let twap = match twap_config {
+                Some(twap_file) => read_config(twap_file)?,
+                None => {
+                    let defaults = crate::config::load_or_default(config_file)?.defaults.twap;
+                    TwapConfig::new(defaults.duration / 60, defaults.intervals, defaults.randomize_execution)
+                }
+            };
+            status!("{} {} into a combined strategy", "🔄 Promoting".cyan(), file.yellow());
+            status!(
+                "  • TWAP: {} intervals over {} minutes{}",
+                twap.intervals,
+                twap.duration / 60,
+                twap_config.map_or(" (config defaults)".to_string(), |twap_file| format!(" from {}", twap_file))
+            );
+            let strategy = CombinedStrategy { twap, volatility };
+            (serde_json::to_value(&strategy)?, check_combined_strategy(&strategy), "combined-strategy.json")
+        }
+        (StrategyKind::Combined, ConvertTarget::Volatility) => {
+            let strategy: CombinedStrategy = serde_json::from_value(value)?;
+            status!("{} the volatility block of {}", "🔄 Extracting".cyan(), file.yellow());
+            status!(
+                "  • Dropped the TWAP block ({} intervals over {} minutes)",
+                strategy.twap.intervals,
+                strategy.twap.duration / 60
+            );
+            let volatility = strategy.volatility;
+            (serde_json::to_value(&volatility)?, check_volatility_config(&volatility), "volatility-config.json")
+        }
+        (StrategyKind::Combined, ConvertTarget::Combined) | (StrategyKind::Volatility, ConvertTarget::Volatility) => {
+            return Err(eyre::eyre!("{} is already a {} config", file, kind.name()));
+        }
+        (kind, _) => {
+            return Err(eyre::eyre!(
+                "Only volatility configs and combined strategies convert; {} is a {} config",
+                file,
+                kind.name()
+            ));
+        }
+    };
+
+    let output = output.unwrap_or(default_output);
+    for warning in &warnings {
+        status!("{}", warning.yellow());
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            status!("{}", error.red());
+        }
+        return Err(VectorPlusError::Validation(format!(
+            "Converted config is invalid; {} was not written",
+            output_name(output)
+        ))
+        .into());
+    }
+
+    write_created_config(output, &json)?;
+    status!("{} {}", "✅ Converted config written:".green(), output_name(output));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_fails_only_when_a_file_has_errors() {
+        let mut summary = ValidationSummary::default();
+        summary.record("ok.json", Ok(Findings::default()));
+        summary.record("warned.json", Ok(Findings { warnings: 2, errors: 0 }));
+        assert!(summary.finish().is_ok());
+
+        let mut summary = ValidationSummary::default();
+        summary.record("ok.json", Ok(Findings::default()));
+        summary.record("broken.json", Err(eyre::eyre!("Invalid JSON")));
+        summary.record("invalid.json", Ok(Findings { warnings: 1, errors: 1 }));
+        assert_eq!((summary.files, summary.ok, summary.warnings, summary.errors), (3, 1, 0, 2));
+        let err = summary.finish().unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), crate::error::EXIT_VALIDATION);
+        assert_eq!(err.to_string(), "2 of 3 files failed validation");
+
+        let mut summary = ValidationSummary::default();
+        summary.record("invalid.json", Ok(Findings { warnings: 0, errors: 1 }));
+        summary.record("missing.json", Err(VectorPlusError::FileNotFound("missing.json".to_string()).into()));
+        let err = summary.finish().unwrap_err();
+        assert_eq!(crate::error::exit_code(&err), crate::error::EXIT_CONFIG_NOT_FOUND);
+        assert_eq!(err.to_string(), "File not found: missing.json");
+    }
+
+    #[test]
+    fn converts_to_combined_and_back() {
+        let dir = std::env::temp_dir().join(format!("vector-plus-convert-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+        let volatility = VolatilityConfig::new(
+            crate::bps::Bps::from_bps(300),
+            crate::bps::Bps::from_bps(350),
+            "5".parse().unwrap(),
+            "0.1".parse().unwrap(),
+            false,
+        );
+        std::fs::write(path("volatility.json"), serde_json::to_string(&volatility).unwrap()).unwrap();
+        std::fs::write(path("twap.json"), serde_json::to_string(&TwapConfig::new(90, 9, false)).unwrap()).unwrap();
+
+        let no_defaults = path("missing-vector-plus.json");
+        convert_strategy(&path("volatility.json"), ConvertTarget::Combined, Some(&path("twap.json")), Some(&path("combined.json")), &no_defaults).unwrap();
+        let combined: CombinedStrategy = read_config(path("combined.json")).unwrap();
+        assert_eq!(combined.twap.intervals, 9);
+
+        convert_strategy(&path("combined.json"), ConvertTarget::Volatility, None, Some(&path("extracted.json")), &no_defaults).unwrap();
+        let extracted: serde_json::Value = read_config(path("extracted.json")).unwrap();
+        assert_eq!(extracted, serde_json::to_value(&volatility).unwrap());
+
+        let err = convert_strategy(&path("twap.json"), ConvertTarget::Combined, None, None, &no_defaults).unwrap_err();
+        assert!(err.to_string().contains("is a TWAP config"), "{}", err);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
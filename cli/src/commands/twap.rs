@@ -1,6 +1,17 @@
-use clap::Subcommand;
+use clap::{Args, Subcommand, ValueEnum};
 use colored::*;
 use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::strategy::{Findings, ValidationSummary};
+use crate::amount::Amount;
+use crate::error::VectorPlusError;
+use crate::rng;
+use crate::rounding::{Rounding, RoundingArgs};
+use crate::utils::{add_key_findings, format_amount, output_name, read_config, status, verbose, write_created_config, STDOUT_PATH};
+use crate::utils::table::{Align, Cell, Table};
+use crate::utils::timeline::{Mark, Timeline};
 
 #[derive(Subcommand)]
 pub enum TwapCommands {
@@ -9,48 +20,1665 @@ pub enum TwapCommands {
         /// Execution duration in minutes
         #[arg(long)]
         duration: u64,
-        
+
         /// Number of intervals
         #[arg(long)]
         intervals: u32,
-        
+
         /// Enable randomization
         #[arg(long)]
         randomize: bool,
-        
+
+        /// How the order is distributed across intervals
+        #[arg(long, value_enum, default_value_t = Weighting::Flat)]
+        weighting: Weighting,
+
+        /// Per-interval weights for `--weighting custom`, e.g. 3,2,1
+        #[arg(long, value_delimiter = ',', value_parser = crate::utils::parse_finite_f64)]
+        weights: Option<Vec<f64>>,
+
+        /// Show only a slice of each interval's chunk at a time, revealing the next as it fills
+        #[arg(long, requires = "visible_pct")]
+        iceberg: bool,
+
+        /// Percent of each chunk visible at once with --iceberg, above 0 and at most 100
+        #[arg(long, requires = "iceberg", value_parser = crate::utils::parse_positive_f64)]
+        visible_pct: Option<f64>,
+
         /// Output file
         #[arg(short, long, default_value = "twap-config.json")]
         output: String,
+
+        /// Order size in ETH; checks that per-fill gas stays small next to each chunk
+        #[arg(long, requires = "gas_price_gwei", value_parser = crate::utils::parse_positive_f64)]
+        order_size: Option<f64>,
+
+        /// Gas price in gwei for the gas check
+        #[arg(long, requires = "order_size", value_parser = crate::utils::parse_non_negative_f64)]
+        gas_price_gwei: Option<f64>,
+
+        /// Gas used by one fill
+        #[arg(long, default_value_t = DEFAULT_GAS_PER_FILL, requires = "order_size")]
+        gas_per_fill: u64,
+
+        /// Price of the gas token in ETH, needed where gas isn't paid in ETH
+        #[arg(long, requires = "order_size", value_parser = crate::utils::parse_positive_f64)]
+        gas_token_price_eth: Option<f64>,
+
+        /// Most gas a fill may cost, as a percentage of its chunk's value
+        #[arg(long, default_value_t = DEFAULT_MAX_GAS_PCT, requires = "order_size", value_parser = crate::utils::parse_non_negative_f64)]
+        max_gas_pct: f64,
+
+        /// Fail instead of warning when fills are too small for their gas
+        #[arg(long, requires = "order_size")]
+        strict: bool,
+
+        #[command(flatten)]
+        blackouts: BlackoutArgs,
+
+        #[command(flatten)]
+        limits: IntervalLimitArgs,
     },
-    
+
     /// Simulate TWAP execution
     Simulate {
         /// Configuration file
         #[arg(long, default_value = "twap-config.json")]
         config: String,
-        
+
         /// Order size in ETH
+        #[arg(long, value_parser = crate::utils::parse_positive_amount)]
+        order_size: Amount,
+
+        /// Chart each interval's fill on a time axis, or list them when stdout is not a terminal
         #[arg(long)]
-        order_size: f64,
+        show_timeline: bool,
+
+        /// Compare the sliced schedule's price impact against a single-shot execution
+        #[arg(long)]
+        compare_market_impact: bool,
+
+        /// Price impact coefficient in basis points per ETH executed in one fill
+        #[arg(long, default_value = "1.0", requires = "compare_market_impact", value_parser = crate::utils::parse_non_negative_f64)]
+        impact_coeff: f64,
+
+        /// File with one price per interval (one per line) to compute the realized VWAP against
+        #[arg(long)]
+        price_series: Option<String>,
+
+        /// Keep the weighted split in ETH per interval, or in quote value at the --price-series prices
+        #[arg(long, value_enum, default_value = "quantity", requires_if("notional", "price_series"))]
+        slice_by: SliceBy,
+
+        /// Override the config's weighting
+        #[arg(long, value_enum)]
+        weighting: Option<Weighting>,
+
+        /// Per-interval weights for `--weighting custom`, e.g. 3,2,1
+        #[arg(long, value_delimiter = ',', value_parser = crate::utils::parse_finite_f64)]
+        weights: Option<Vec<f64>>,
+
+        /// Gas price in gwei; estimates the gas cost of the fills in the network's gas token
+        #[arg(long, value_parser = crate::utils::parse_non_negative_f64)]
+        gas_price_gwei: Option<f64>,
+
+        /// Gas used by one fill
+        #[arg(long, default_value_t = DEFAULT_GAS_PER_FILL, requires = "gas_price_gwei")]
+        gas_per_fill: u64,
+
+        /// Price of the gas token in the quote asset, to convert the gas cost
+        #[arg(long, requires = "gas_price_gwei", value_parser = crate::utils::parse_positive_f64)]
+        gas_token_price: Option<f64>,
+
+        /// Snap fill offsets to the nearest block boundary
+        #[arg(long)]
+        align_to_blocks: bool,
+
+        /// Block time in seconds; defaults to the network's typical block time
+        #[arg(long, requires = "align_to_blocks", value_parser = crate::utils::parse_positive_f64)]
+        block_time_secs: Option<f64>,
+
+        /// Block the TWAP starts at, to report target block numbers
+        #[arg(long, requires = "align_to_blocks")]
+        start_block: Option<u64>,
+
+        #[command(flatten)]
+        rounding: RoundingArgs,
+
+        /// Chance in percent that a fill attempt fails (revert, no liquidity)
+        #[arg(long, value_parser = crate::utils::parse_finite_f64)]
+        failure_rate: Option<f64>,
+
+        /// Seed for which attempts fail; the same seed gives the same run (default: 0)
+        #[arg(long, requires = "failure_rate")]
+        seed: Option<u64>,
+
+        /// What happens to a failed interval's chunk
+        #[arg(long, value_enum, default_value = "skip", requires = "failure_rate")]
+        on_failure: FailurePolicy,
+
+        #[command(flatten)]
+        blackouts: BlackoutArgs,
+
+        #[command(flatten)]
+        limits: IntervalLimitArgs,
+    },
+
+    /// Report the next interval's chunk and what remains of the order
+    NextFill {
+        /// Configuration file
+        #[arg(long, default_value = "twap-config.json")]
+        config: String,
+
+        /// Order size in ETH
+        #[arg(long, value_parser = crate::utils::parse_positive_amount)]
+        order_size: Amount,
     },
+
+    /// Validate TWAP configuration
+    Validate {
+        /// Configuration file to validate
+        file: String,
+
+        /// Fail on unknown fields and duplicate keys instead of warning
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+/// Fill attempts per interval under `--on-failure retry`, the first included
+const MAX_FILL_ATTEMPTS: u32 = 3;
+
+/// Default cap on the interval count, well past any schedule that fills economically
+const DEFAULT_MAX_INTERVALS: u32 = 1000;
+
+/// Guard against fat-fingered interval counts, shared by `create-config` and `simulate`.
+#[derive(Args)]
+pub struct IntervalLimitArgs {
+    /// Most intervals accepted without --allow-large-intervals
+    #[arg(long, default_value_t = DEFAULT_MAX_INTERVALS)]
+    max_intervals: u32,
+
+    /// Accept more intervals than --max-intervals
+    #[arg(long)]
+    allow_large_intervals: bool,
+}
+
+impl IntervalLimitArgs {
+    fn check(&self, intervals: u32) -> Result<()> {
+        if intervals > self.max_intervals && !self.allow_large_intervals {
+            return Err(VectorPlusError::Validation(format!(
+                "{} intervals exceeds the limit of {}; pass --allow-large-intervals (or raise --max-intervals) if this is intended",
+                intervals, self.max_intervals
+            )).into());
+        }
+        Ok(())
+    }
+}
+
+/// Daily windows to keep fills out of, shared by `create-config` and `simulate`.
+#[derive(Args)]
+pub struct BlackoutArgs {
+    /// UTC windows in which no fill executes, e.g. 22:00-02:00,12:00-13:00
+    #[arg(long, value_delimiter = ',')]
+    blackout: Option<Vec<Blackout>>,
+
+    /// Move a fill inside a blackout to when it ends, or drop it (default: shift)
+    #[arg(long, value_enum)]
+    on_blackout: Option<BlackoutPolicy>,
+}
+
+impl BlackoutArgs {
+    /// Windows in force: the flags', else those saved in `config`.
+    fn resolve(&self, config: Option<&TwapConfig>) -> Result<Option<Blackouts>> {
+        let windows = match (&self.blackout, config) {
+            (Some(windows), _) => windows.clone(),
+            (None, Some(config)) => config.blackouts.clone(),
+            (None, None) => Vec::new(),
+        };
+        if windows.is_empty() {
+            if self.on_blackout.is_some() {
+                return Err(VectorPlusError::Validation("--on-blackout needs --blackout windows".to_string()).into());
+            }
+            return Ok(None);
+        }
+        check_blackouts(&windows).map_err(VectorPlusError::Validation)?;
+        let policy = self.on_blackout.or(config.and_then(|config| config.on_blackout)).unwrap_or_default();
+        Ok(Some(Blackouts { windows, policy }))
+    }
+}
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// A daily UTC window such as `22:00-02:00`, in minutes of the day. A window
+/// ending before it starts runs past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Blackout {
+    start: u32,
+    end: u32,
+}
+
+impl Blackout {
+    /// Seconds from `timestamp` until the window closes, or `None` outside it.
+    fn remaining(&self, timestamp: u64) -> Option<u64> {
+        let start = self.start as u64 * 60;
+        let mut end = self.end as u64 * 60;
+        if end <= start {
+            end += SECS_PER_DAY;
+        }
+        let now = timestamp % SECS_PER_DAY;
+        [now, now + SECS_PER_DAY].into_iter().find(|t| (start..end).contains(t)).map(|t| end - t)
+    }
+}
+
+impl std::str::FromStr for Blackout {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let minutes = |time: &str| -> Option<u32> {
+            let (hours, mins) = time.trim().split_once(':')?;
+            let (hours, mins): (u32, u32) = (hours.parse().ok()?, mins.parse().ok()?);
+            (mins < 60 && (hours < 24 || hours == 24 && mins == 0)).then_some(hours * 60 + mins)
+        };
+        let invalid = || format!("Blackout window must look like HH:MM-HH:MM in UTC, got '{}'", s);
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let (start, end) = (minutes(start).ok_or_else(invalid)?, minutes(end).ok_or_else(invalid)?);
+        if start % 1440 == end % 1440 && !(start == 0 && end == 1440) {
+            return Err(format!("Blackout window '{}' is empty", s));
+        }
+        Ok(Self { start: start % 1440, end })
+    }
+}
+
+impl std::fmt::Display for Blackout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}-{:02}:{:02}", self.start / 60, self.start % 60, self.end / 60, self.end % 60)
+    }
+}
+
+impl TryFrom<String> for Blackout {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Blackout> for String {
+    fn from(blackout: Blackout) -> Self {
+        blackout.to_string()
+    }
+}
+
+/// What happens to a fill scheduled inside a blackout window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlackoutPolicy {
+    /// Fill the whole chunk once the window closes, pushing later fills back as needed
+    #[default]
+    Shift,
+    /// Drop the chunk, leaving it unfilled
+    Skip,
+}
+
+impl BlackoutPolicy {
+    fn name(&self) -> &'static str {
+        match self {
+            BlackoutPolicy::Shift => "shift",
+            BlackoutPolicy::Skip => "skip",
+        }
+    }
+}
+
+/// Windows that leave no time to fill can never be shifted out of.
+fn check_blackouts(windows: &[Blackout]) -> std::result::Result<(), String> {
+    let open = (0..SECS_PER_DAY).step_by(60).any(|t| windows.iter().all(|window| window.remaining(t).is_none()));
+    if open {
+        Ok(())
+    } else {
+        Err("Blackout windows cover the whole day, leaving no time to fill".to_string())
+    }
+}
+
+/// Blackout windows in force and the policy for fills inside them.
+struct Blackouts {
+    windows: Vec<Blackout>,
+    policy: BlackoutPolicy,
+}
+
+impl Blackouts {
+    fn describe(&self) -> String {
+        let windows: Vec<String> = self.windows.iter().map(Blackout::to_string).collect();
+        format!("{} UTC ({})", windows.join(", "), self.policy.name())
+    }
+
+    /// Earliest time at or after `timestamp` outside every window; overlapping
+    /// windows may take several steps.
+    fn next_open(&self, mut timestamp: u64) -> u64 {
+        while let Some(wait) = self.windows.iter().filter_map(|window| window.remaining(timestamp)).max() {
+            timestamp += wait;
+        }
+        timestamp
+    }
+
+    /// Where each fill at `offsets` from `start_time` lands, or `None` where it
+    /// is skipped. Shifted fills keep `min_gap` from the fill before them, so
+    /// fills from one window queue up behind each other instead of stacking.
+    fn place(&self, start_time: u64, offsets: &[u64], min_gap: u64) -> Vec<Option<u64>> {
+        let mut previous: Option<u64> = None;
+        offsets.iter().map(|&offset| {
+            let at = start_time + offset;
+            match self.policy {
+                BlackoutPolicy::Skip => (self.next_open(at) == at).then_some(offset),
+                BlackoutPolicy::Shift => {
+                    let earliest = previous.map_or(at, |previous| at.max(previous + min_gap));
+                    let placed = self.next_open(earliest);
+                    previous = Some(placed);
+                    Some(placed - start_time)
+                }
+            }
+        }).collect()
+    }
+}
+
+/// How `twap simulate --failure-rate` handles a failed fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailurePolicy {
+    /// Retry within the interval, up to 3 attempts in all
+    Retry,
+    /// Drop the chunk
+    Skip,
+    /// Add the chunk to the next interval's
+    Rollover,
 }
 
-pub async fn handle_command(command: &TwapCommands, _cli: &crate::Cli) -> Result<()> {
+impl FailurePolicy {
+    fn name(&self) -> &'static str {
+        match self {
+            FailurePolicy::Retry => "retry",
+            FailurePolicy::Skip => "skip",
+            FailurePolicy::Rollover => "rollover",
+        }
+    }
+}
+
+/// Random fill failures for `twap simulate`.
+struct FailureModel {
+    /// Probability in `[0, 1]` that one attempt fails
+    rate: f64,
+    seed: Option<u64>,
+    policy: FailurePolicy,
+}
+
+/// What got filled once failures are applied.
+#[derive(Debug, PartialEq)]
+struct FailureOutcome {
+    filled: Vec<Amount>,
+    failed_attempts: u32,
+    /// 1-based intervals whose fill ultimately failed
+    failed_intervals: Vec<usize>,
+}
+
+/// Plays out `chunks` with each attempt failing independently at `model.rate`.
+/// A rolled-over chunk that fails in the last interval stays unfilled.
+fn simulate_failures(chunks: &[Amount], model: &FailureModel) -> FailureOutcome {
+    let mut rng = rng::seeded(model.seed);
+    let attempts = match model.policy {
+        FailurePolicy::Retry => MAX_FILL_ATTEMPTS,
+        FailurePolicy::Skip | FailurePolicy::Rollover => 1,
+    };
+    let mut outcome = FailureOutcome { filled: Vec::with_capacity(chunks.len()), failed_attempts: 0, failed_intervals: Vec::new() };
+    let mut carried = Amount::ZERO;
+    for (i, chunk) in chunks.iter().enumerate() {
+        let due = *chunk + carried;
+        carried = Amount::ZERO;
+        let mut filled = false;
+        for _ in 0..attempts {
+            if rng.next_f64() >= model.rate {
+                filled = true;
+                break;
+            }
+            outcome.failed_attempts += 1;
+        }
+        if filled {
+            outcome.filled.push(due);
+        } else {
+            outcome.filled.push(Amount::ZERO);
+            outcome.failed_intervals.push(i + 1);
+            if model.policy == FailurePolicy::Rollover {
+                carried = due;
+            }
+        }
+    }
+    outcome
+}
+
+/// Rough gas for one limit order fill through the protocol
+pub(crate) const DEFAULT_GAS_PER_FILL: u64 = 150_000;
+pub(crate) const DEFAULT_MAX_GAS_PCT: f64 = 1.0;
+
+/// Gas cost of one fill in the network's gas token.
+pub(crate) fn fill_gas_cost(gas_per_fill: u64, gas_price_gwei: f64) -> f64 {
+    gas_per_fill as f64 * gas_price_gwei / 1e9
+}
+
+/// ETH per unit of `network`'s gas token: 1 where gas is paid in ETH,
+/// otherwise the `--gas-token-price-eth` given.
+pub(crate) fn gas_token_eth(network: &str, gas_token_price_eth: Option<f64>) -> Result<f64> {
+    let network = crate::networks::lookup(network)?;
+    match (network.gas_token, gas_token_price_eth) {
+        (_, Some(price)) => Ok(price),
+        ("ETH", None) => Ok(1.0),
+        (token, None) => Err(eyre::eyre!(
+            "Gas on {} is paid in {}; pass --gas-token-price-eth to compare it with ETH chunks",
+            network.name, token
+        )),
+    }
+}
+
+/// `twap create-config` check that fills are not dwarfed by their gas.
+struct GasCheck {
+    order_size: f64,
+    /// Gas cost of one fill, converted to ETH
+    fill_cost_eth: f64,
+    max_gas_pct: f64,
+    strict: bool,
+}
+
+/// Optional analyses `twap simulate` prints, and how it lays out the schedule.
+struct SimulateAnalyses<'a> {
+    limits: &'a IntervalLimitArgs,
+    blackouts: &'a BlackoutArgs,
+    impact_coeff: Option<f64>,
+    price_series: Option<&'a str>,
+    slice_by: SliceBy,
+    gas: Option<GasSettings>,
+    blocks: Option<BlockAlignment>,
+    rounding: Option<Rounding>,
+    failures: Option<FailureModel>,
+    timeline: bool,
+}
+
+/// Snaps schedule offsets to block boundaries.
+struct BlockAlignment {
+    block_time_ms: u64,
+    /// Block at offset zero, to turn block offsets into block numbers
+    start_block: Option<u64>,
+}
+
+struct GasSettings {
+    network: &'static crate::networks::Network,
+    gas_price_gwei: f64,
+    gas_per_fill: u64,
+    gas_token_price: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct TwapConfig {
+    pub(crate) start_time: u64,
+    pub(crate) duration: u64,
+    pub(crate) intervals: u32,
+    pub(crate) executed_intervals: u32,
+    pub(crate) randomize_execution: bool,
+    pub(crate) min_execution_gap: u64,
+    pub(crate) max_slippage_bps: u64,
+    #[serde(default)]
+    pub(crate) weighting: Weighting,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) weights: Option<Vec<f64>>,
+    /// Percent of each chunk shown at once for iceberg execution; unset shows whole chunks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) visible_pct: Option<f64>,
+    /// Daily UTC windows in which no fill executes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) blackouts: Vec<Blackout>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) on_blackout: Option<BlackoutPolicy>,
+}
+
+/// Distribution of the order across intervals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Weighting {
+    /// Equal chunks
+    #[default]
+    Flat,
+    /// Linearly decreasing chunks, to reduce exposure early
+    Front,
+    /// Linearly increasing chunks, to ride a trend
+    Back,
+    /// Chunks proportional to `--weights`
+    Custom,
+}
+
+/// What `twap simulate` splits by the weights.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SliceBy {
+    /// ETH per interval
+    #[default]
+    Quantity,
+    /// Quote value per interval, buying more when the price is low
+    Notional,
+}
+
+impl SliceBy {
+    fn name(&self) -> &'static str {
+        match self {
+            SliceBy::Quantity => "quantity",
+            SliceBy::Notional => "notional",
+        }
+    }
+}
+
+/// Quantity weights that give interval `i` `weights[i]` of the quote value
+/// when filled at `prices[i]`, since quantity is notional over price.
+fn notional_weights(weights: &[f64], prices: &[f64]) -> Vec<f64> {
+    weights.iter().zip(prices).map(|(weight, price)| weight / price).collect()
+}
+
+impl Weighting {
+    /// Per-interval weights; `custom` must match the interval count.
+    pub(crate) fn weights(self, custom: Option<&[f64]>, intervals: u32) -> Result<Vec<f64>> {
+        let n = intervals as usize;
+        if self != Weighting::Custom && custom.is_some() {
+            return Err(eyre::eyre!("Weights are only used with custom weighting"));
+        }
+        match self {
+            Weighting::Flat => Ok(vec![1.0; n]),
+            Weighting::Front => Ok((0..n).map(|i| (n - i) as f64).collect()),
+            Weighting::Back => Ok((0..n).map(|i| (i + 1) as f64).collect()),
+            Weighting::Custom => {
+                let weights = custom.ok_or_else(|| eyre::eyre!("Custom weighting needs --weights"))?;
+                if weights.len() != n {
+                    return Err(eyre::eyre!(
+                        "Got {} weights but the TWAP has {} intervals",
+                        weights.len(),
+                        intervals
+                    ));
+                }
+                if weights.iter().any(|w| !w.is_finite() || *w < 0.0) || weights.iter().sum::<f64>() <= 0.0 {
+                    return Err(eyre::eyre!("Weights must be non-negative with a positive sum"));
+                }
+                Ok(weights.to_vec())
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Weighting::Flat => "flat",
+            Weighting::Front => "front-loaded",
+            Weighting::Back => "back-loaded",
+            Weighting::Custom => "custom",
+        }
+    }
+}
+
+pub async fn handle_command(command: &TwapCommands, cli: &crate::Cli) -> Result<()> {
     match command {
-        TwapCommands::CreateConfig { duration, intervals, randomize, output } => {
-            println!("{}", "🕒 Creating TWAP configuration...".cyan());
-            println!("  • Duration: {} minutes", duration);
-            println!("  • Intervals: {}", intervals);
-            println!("  • Randomization: {}", if *randomize { "enabled" } else { "disabled" });
-            println!("{} {}", "✅ TWAP config created:".green(), output);
-            Ok(())
-        }
-        TwapCommands::Simulate { config, order_size } => {
-            println!("{}", "🎯 Simulating TWAP execution...".cyan());
-            println!("  • Config: {}", config);
-            println!("  • Order size: {} ETH", order_size);
-            println!("{}", "✅ Simulation complete".green());
-            Ok(())
-        }
-    }
-}
\ No newline at end of file
+        TwapCommands::CreateConfig {
+            duration,
+            intervals,
+            randomize,
+            weighting,
+            weights,
+            iceberg: _,
+            visible_pct,
+            output,
+            order_size,
+            gas_price_gwei,
+            gas_per_fill,
+            gas_token_price_eth,
+            max_gas_pct,
+            strict,
+            blackouts,
+            limits,
+        } => {
+            limits.check(*intervals)?;
+            let blackouts = blackouts.resolve(None)?;
+            let gas_check = match (order_size, gas_price_gwei) {
+                (Some(order_size), Some(gas_price_gwei)) => {
+                    let gas_token_eth = gas_token_eth(&cli.network, *gas_token_price_eth)?;
+                    Some(GasCheck {
+                        order_size: *order_size,
+                        fill_cost_eth: fill_gas_cost(*gas_per_fill, *gas_price_gwei) * gas_token_eth,
+                        max_gas_pct: *max_gas_pct,
+                        strict: *strict,
+                    })
+                }
+                _ => None,
+            };
+            let schedule = Schedule {
+                randomize: *randomize,
+                weighting: *weighting,
+                weights: weights.as_deref(),
+                visible_pct: *visible_pct,
+                blackouts: blackouts.as_ref(),
+            };
+            create_twap_config(*duration, *intervals, &schedule, output, gas_check.as_ref()).await
+        }
+        TwapCommands::Simulate {
+            config,
+            order_size,
+            show_timeline,
+            compare_market_impact,
+            impact_coeff,
+            price_series,
+            slice_by,
+            weighting,
+            weights,
+            gas_price_gwei,
+            gas_per_fill,
+            gas_token_price,
+            align_to_blocks,
+            block_time_secs,
+            start_block,
+            rounding,
+            failure_rate,
+            seed,
+            on_failure,
+            blackouts,
+            limits,
+        } => {
+            let failures = match failure_rate {
+                Some(pct) if pct.is_finite() && (0.0..=100.0).contains(pct) => Some(FailureModel {
+                    rate: pct / 100.0,
+                    seed: *seed,
+                    policy: *on_failure,
+                }),
+                Some(pct) => return Err(eyre::eyre!("--failure-rate must be between 0 and 100, got {}", pct)),
+                None => None,
+            };
+            let blocks = match align_to_blocks {
+                true => {
+                    let block_time_ms = match block_time_secs {
+                        Some(secs) if secs.is_finite() && *secs >= 0.001 => (secs * 1000.0).round() as u64,
+                        Some(secs) => return Err(eyre::eyre!("Block time must be at least 0.001 seconds, got {}", secs)),
+                        None => crate::networks::lookup(&cli.network)?.block_time_ms,
+                    };
+                    Some(BlockAlignment { block_time_ms, start_block: *start_block })
+                }
+                false => None,
+            };
+            let gas = match gas_price_gwei {
+                Some(gas_price_gwei) => Some(GasSettings {
+                    network: crate::networks::lookup(&cli.network)?,
+                    gas_price_gwei: *gas_price_gwei,
+                    gas_per_fill: *gas_per_fill,
+                    gas_token_price: *gas_token_price,
+                }),
+                None => None,
+            };
+            let analyses = SimulateAnalyses {
+                limits,
+                blackouts,
+                impact_coeff: if *compare_market_impact { Some(*impact_coeff) } else { None },
+                price_series: price_series.as_deref(),
+                slice_by: *slice_by,
+                gas,
+                blocks,
+                rounding: rounding.resolve()?,
+                failures,
+                timeline: *show_timeline,
+            };
+            simulate_twap_execution(
+                config,
+                *order_size,
+                *weighting,
+                weights.as_deref(),
+                &analyses,
+                cli.precision,
+                cli.verbose,
+            ).await
+        }
+        TwapCommands::NextFill { config, order_size } => next_fill(config, *order_size, cli.precision),
+        TwapCommands::Validate { file, strict } => {
+            ValidationSummary::single(file, validate_twap_config(file, *strict).await)
+        }
+    }
+}
+
+/// How `create-config` spreads the order over its intervals.
+struct Schedule<'a> {
+    randomize: bool,
+    weighting: Weighting,
+    weights: Option<&'a [f64]>,
+    visible_pct: Option<f64>,
+    blackouts: Option<&'a Blackouts>,
+}
+
+impl TwapConfig {
+    /// Builds a fresh config starting now; `duration` is given in minutes.
+    pub(crate) fn new(duration: u64, intervals: u32, randomize: bool) -> Self {
+        let duration_secs = duration * 60;
+        Self {
+            start_time: chrono::Utc::now().timestamp() as u64,
+            duration: duration_secs,
+            intervals,
+            executed_intervals: 0,
+            randomize_execution: randomize,
+            min_execution_gap: duration_secs / intervals.max(1) as u64 / 2,
+            max_slippage_bps: 100,
+            weighting: Weighting::Flat,
+            weights: None,
+            visible_pct: None,
+            blackouts: Vec::new(),
+            on_blackout: None,
+        }
+    }
+
+    /// Part of `chunk` shown at once; all of it unless the config is an iceberg.
+    pub(crate) fn visible_slice(&self, chunk: Amount) -> Amount {
+        match self.visible_pct {
+            Some(pct) => chunk.mul_div((pct * 100.0).round() as u64, 10_000),
+            None => chunk,
+        }
+    }
+}
+
+/// Slices needed to fill `chunk` when `visible` of it is shown at a time.
+fn reveals(chunk: Amount, visible: Amount) -> u128 {
+    chunk.wei().div_ceil(visible.wei().max(1))
+}
+
+fn check_visible_pct(pct: f64) -> std::result::Result<(), String> {
+    if pct > 0.0 && pct <= 100.0 {
+        Ok(())
+    } else {
+        Err(format!("Visible slice must be above 0% and at most 100%, got {}%", pct))
+    }
+}
+
+async fn create_twap_config(
+    duration: u64,
+    intervals: u32,
+    schedule: &Schedule<'_>,
+    output: &str,
+    gas_check: Option<&GasCheck>,
+) -> Result<()> {
+    let Schedule { randomize, weighting, weights, visible_pct, blackouts } = *schedule;
+    status!("{}", "🕒 Creating TWAP configuration...".cyan());
+
+    if intervals == 0 {
+        return Err(eyre::eyre!("Number of intervals must be greater than zero"));
+    }
+    if duration == 0 {
+        return Err(eyre::eyre!("Duration must be greater than zero"));
+    }
+    match duration.checked_mul(60) {
+        None => return Err(eyre::eyre!("Duration of {} minutes is too long", duration)),
+        Some(secs) if secs < intervals as u64 => {
+            return Err(eyre::eyre!("{} intervals over {} minutes would be shorter than a second each", intervals, duration));
+        }
+        Some(_) => {}
+    }
+
+    if let Some(pct) = visible_pct {
+        check_visible_pct(pct).map_err(VectorPlusError::Validation)?;
+    }
+
+    // Validates custom weights against the interval count before writing
+    let resolved = weighting.weights(weights, intervals)?;
+
+    if let Some(check) = gas_check {
+        check_fill_gas(check, weighting, &resolved)?;
+    }
+
+    let mut config = TwapConfig::new(duration, intervals, randomize);
+    config.weighting = weighting;
+    config.weights = weights.map(<[f64]>::to_vec);
+    config.visible_pct = visible_pct;
+    if let Some(blackouts) = blackouts {
+        config.blackouts = blackouts.windows.clone();
+        config.on_blackout = Some(blackouts.policy);
+    }
+
+    write_created_config(output, &config)?;
+
+    status!("  • Duration: {} minutes", duration);
+    status!("  • Intervals: {}", intervals);
+    status!("  • Randomization: {}", if randomize { "enabled" } else { "disabled" });
+    status!("  • Weighting: {}", weighting.name());
+    if let Some(pct) = visible_pct {
+        status!("  • Iceberg: {}% of each chunk visible at a time", pct);
+    }
+    if let Some(blackouts) = blackouts {
+        status!("  • Blackouts: {}", blackouts.describe());
+        report_blackout_moves(&config, blackouts);
+    }
+    status!("{} {}", "✅ TWAP config created:".green(), output_name(output));
+    if output != STDOUT_PATH {
+        status!();
+        status!("{}", "🚀 Next steps:".bold());
+        status!("  {} vector-plus twap simulate --order-size 10.0 --config {}", "•".blue(), output);
+    }
+
+    Ok(())
+}
+
+/// Fills listed by `create-config` before the rest are summed up
+const LISTED_BLACKOUT_MOVES: usize = 5;
+
+/// Lists the fills of a new config that blackouts move or drop.
+fn report_blackout_moves(config: &TwapConfig, blackouts: &Blackouts) {
+    let offsets = schedule_offsets(config);
+    let placed = blackouts.place(config.start_time, &offsets, config.min_execution_gap);
+    let moves: Vec<String> = offsets.iter().zip(&placed).enumerate()
+        .filter(|(_, (offset, placed))| **placed != Some(**offset))
+        .map(|(i, (offset, placed))| match placed {
+            Some(placed) => format!("interval {}: +{}s → +{}s", i + 1, offset, placed),
+            None => format!("interval {}: +{}s skipped", i + 1, offset),
+        })
+        .collect();
+    if moves.is_empty() {
+        status!("    no fills fall in a blackout from the current start time");
+        return;
+    }
+    for line in moves.iter().take(LISTED_BLACKOUT_MOVES) {
+        status!("    {}", line);
+    }
+    if moves.len() > LISTED_BLACKOUT_MOVES {
+        status!("    ...and {} more", moves.len() - LISTED_BLACKOUT_MOVES);
+    }
+}
+
+/// Offset in seconds of each interval's fill from the config's start.
+fn schedule_offsets(config: &TwapConfig) -> Vec<u64> {
+    let interval_secs = config.duration / config.intervals.max(1) as u64;
+    (0..config.intervals as u64).map(|i| interval_secs * i).collect()
+}
+
+/// Smallest non-empty chunk of `order_size` under `weights`.
+fn smallest_chunk(order_size: f64, weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    weights.iter()
+        .filter(|w| **w > 0.0)
+        .map(|w| order_size * w / total)
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Largest interval count below `intervals` whose smallest chunk still covers
+/// its gas, for the built-in weightings. Chunks only shrink as intervals grow,
+/// so this is a binary search.
+fn suggest_intervals(check: &GasCheck, weighting: Weighting, intervals: u32) -> Option<u32> {
+    let fits = |n: u32| {
+        let weights = weighting.weights(None, n).unwrap_or_default();
+        check.fill_cost_eth <= smallest_chunk(check.order_size, &weights) * check.max_gas_pct / 100.0
+    };
+    if weighting == Weighting::Custom || !fits(1) {
+        return None;
+    }
+    let (mut low, mut high) = (1, intervals);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if fits(mid) { low = mid } else { high = mid }
+    }
+    Some(low)
+}
+
+fn check_fill_gas(check: &GasCheck, weighting: Weighting, weights: &[f64]) -> Result<()> {
+    if !(check.order_size > 0.0 && check.order_size.is_finite()) {
+        return Err(eyre::eyre!("Order size must be positive"));
+    }
+    if !(check.fill_cost_eth >= 0.0 && check.fill_cost_eth.is_finite()) {
+        return Err(eyre::eyre!("Gas price must be a non-negative number"));
+    }
+
+    let chunk = smallest_chunk(check.order_size, weights);
+    let gas_pct = check.fill_cost_eth / chunk * 100.0;
+    if gas_pct <= check.max_gas_pct {
+        status!(
+            "  • Gas check: ~{:.6} ETH per fill, {:.3}% of the smallest chunk (limit {}%)",
+            check.fill_cost_eth, gas_pct, check.max_gas_pct
+        );
+        return Ok(());
+    }
+
+    let warning = format!(
+        "⚠️  Each fill costs ~{:.6} ETH in gas, {:.2}% of the smallest chunk ({:.6} ETH); limit is {}%",
+        check.fill_cost_eth, gas_pct, chunk, check.max_gas_pct
+    );
+    let suggestion = match suggest_intervals(check, weighting, weights.len() as u32) {
+        Some(n) => format!("Use at most {} intervals to keep gas under {}% per fill", n, check.max_gas_pct),
+        None if weighting == Weighting::Custom => "Use fewer intervals or flatter weights".to_string(),
+        None => "Even a single fill exceeds the limit; consider a larger order".to_string(),
+    };
+
+    if check.strict {
+        return Err(eyre::eyre!("{}\n  {}", warning.trim_start_matches("⚠️  "), suggestion));
+    }
+    status!("{}", warning.yellow());
+    status!("  💡 {}", suggestion);
+    Ok(())
+}
+
+async fn simulate_twap_execution(
+    config_file: &str,
+    order_size: Amount,
+    weighting: Option<Weighting>,
+    weights: Option<&[f64]>,
+    analyses: &SimulateAnalyses<'_>,
+    precision: u8,
+    verbose: bool,
+) -> Result<()> {
+    let config: TwapConfig = read_config(config_file)?;
+
+    if config.intervals == 0 {
+        return Err(eyre::eyre!("TWAP config must have at least one interval"));
+    }
+    if let Some(pct) = config.visible_pct {
+        check_visible_pct(pct).map_err(VectorPlusError::Validation)?;
+    }
+    analyses.limits.check(config.intervals)?;
+    let blackouts = analyses.blackouts.resolve(Some(&config))?;
+    if config.duration < config.intervals as u64 {
+        return Err(eyre::eyre!(
+            "TWAP config spreads {} intervals over {} seconds; each must last at least a second",
+            config.intervals, config.duration
+        ));
+    }
+
+    // Flags override the config's weighting; custom weights only come along with it
+    let weighting = weighting.unwrap_or(config.weighting);
+    let weights = weights.or(config.weights.as_deref().filter(|_| weighting == Weighting::Custom));
+    let weights = weighting.weights(weights, config.intervals)?;
+    let total_weight: f64 = weights.iter().sum();
+    verbose!(verbose, "loaded {}", config_file);
+    verbose!(verbose, "{} weights {:?} (sum {})", weighting.name(), weights, total_weight);
+
+    // Load before printing anything so a mismatched series fails up front
+    let prices = analyses.price_series
+        .map(|file| load_price_series(file, config.intervals))
+        .transpose()?;
+
+    let chunks = match (analyses.slice_by, &prices) {
+        (SliceBy::Notional, Some(prices)) => order_size.split(&notional_weights(&weights, prices)),
+        (SliceBy::Notional, None) => return Err(eyre::eyre!("--slice-by notional needs --price-series")),
+        (SliceBy::Quantity, _) => order_size.split(&weights),
+    };
+    let chunks = match &analyses.rounding {
+        Some(rounding) => rounding.split(order_size, &chunks),
+        None => chunks,
+    };
+
+    println!("{}", "🎯 Simulating TWAP execution...".cyan());
+    println!("  • Config: {}", config_file);
+    println!("  • Order size: {} ETH", order_size.format_eth(precision));
+    println!("  • Duration: {} minutes", config.duration / 60);
+    // Dust orders and zero weights leave intervals without a fill
+    let effective = chunks.iter().filter(|chunk| !chunk.is_zero()).count();
+    if effective == chunks.len() {
+        println!("  • Intervals: {}", config.intervals);
+    } else {
+        println!("  • Intervals: {} ({} with a fill)", config.intervals, effective);
+    }
+    println!("  • Weighting: {} by {}", weighting.name(), analyses.slice_by.name());
+    if let Some(rounding) = &analyses.rounding {
+        println!("  • Rounding: {}, remainder in the last chunk", rounding.describe());
+    }
+    if let Some(pct) = config.visible_pct {
+        println!("  • Iceberg: {}% of each chunk visible at a time", pct);
+    }
+    if let Some(blackouts) = &blackouts {
+        println!("  • Blackouts: {}", blackouts.describe());
+    }
+    println!();
+
+    let interval_secs = config.duration / config.intervals as u64;
+
+    let offsets: Vec<u64> = (0..chunks.len() as u64).map(|i| interval_secs * i).collect();
+    // Where each fill lands once blackouts apply; `None` marks a skipped fill
+    let placed: Vec<Option<u64>> = match &blackouts {
+        Some(blackouts) => blackouts.place(config.start_time, &offsets, config.min_execution_gap),
+        None => offsets.iter().copied().map(Some).collect(),
+    };
+    let fill_offsets: Vec<u64> = placed.iter().zip(&offsets).map(|(placed, offset)| placed.unwrap_or(*offset)).collect();
+
+    println!("📅 Execution Schedule:");
+    let mut table = Table::new()
+        .column("interval", "Interval", Align::Right)
+        .column("offset_secs", "Offset", Align::Right);
+    if blackouts.is_some() {
+        table = table.column("adjusted_offset_secs", "Adjusted", Align::Right);
+    }
+    if analyses.blocks.is_some() {
+        table = table.column("block", "Block", Align::Right);
+    }
+    table = table
+        .column("size", "Size (ETH)", Align::Right)
+        .column("share_pct", "Share", Align::Right);
+    if config.visible_pct.is_some() {
+        table = table
+            .column("visible", "Visible (ETH)", Align::Right)
+            .column("reveals", "Reveals", Align::Right);
+    }
+    if prices.is_some() {
+        table = table
+            .column("price", "Price", Align::Right)
+            .column("notional", "Notional", Align::Right);
+    }
+
+    let block_offsets = analyses.blocks.as_ref()
+        .map(|blocks| snap_to_blocks(&fill_offsets, blocks.block_time_ms));
+    for (i, (chunk, offset)) in chunks.iter().zip(&fill_offsets).enumerate() {
+        let share = if order_size.is_zero() { 0.0 } else { chunk.as_eth_f64() / order_size.as_eth_f64() * 100.0 };
+        let mut cells = vec![Cell::new((i + 1).to_string(), i + 1)];
+        // With blackouts, Offset keeps the planned time and Adjusted shows where the fill lands
+        if blackouts.is_some() {
+            cells.push(Cell::new(format!("+{}s", offsets[i]), offsets[i]));
+        }
+        match (&analyses.blocks, &block_offsets) {
+            _ if placed[i].is_none() => {
+                cells.push(Cell::text("skipped"));
+                if analyses.blocks.is_some() {
+                    cells.push(Cell::text("-"));
+                }
+            }
+            (Some(blocks), Some(block_offsets)) => {
+                let block = block_offsets[i];
+                let offset_ms = block * blocks.block_time_ms;
+                cells.push(Cell::new(format!("+{}s", format_millis(offset_ms)), offset_ms as f64 / 1000.0));
+                cells.push(match blocks.start_block {
+                    Some(start) => Cell::new(format!("#{}", start + block), start + block),
+                    None => Cell::new(format!("+{}", block), block),
+                });
+            }
+            _ => cells.push(Cell::new(format!("+{}s", offset), *offset)),
+        }
+        cells.push(Cell::new(chunk.format_eth(precision), chunk.as_eth_f64()));
+        cells.push(Cell::new(format!("{:.1}%", share), share));
+        if config.visible_pct.is_some() {
+            let visible = config.visible_slice(*chunk);
+            let count = reveals(*chunk, visible);
+            cells.push(Cell::new(visible.format_eth(precision), visible.as_eth_f64()));
+            cells.push(Cell::new(count.to_string(), count as u64));
+        }
+        if let Some(prices) = &prices {
+            let notional = chunk.as_eth_f64() * prices[i];
+            cells.push(Cell::new(format_amount(prices[i], precision), prices[i]));
+            cells.push(Cell::new(format_amount(notional, precision), notional));
+        }
+        table.row(cells);
+    }
+    println!("{}", table.render());
+    if let (Some(blocks), Some(block_offsets)) = (&analyses.blocks, &block_offsets) {
+        verbose!(verbose, "block time {}s", format_millis(blocks.block_time_ms));
+        println!("  {} Offsets snapped to {}s blocks", "ℹ️ ".blue(), format_millis(blocks.block_time_ms));
+        let shared = block_offsets.windows(2).filter(|pair| pair[0] == pair[1]).count();
+        if shared > 0 {
+            println!(
+                "{}",
+                format!("⚠️  {} fills land in the same block as the previous one - intervals are shorter than a block", shared).yellow()
+            );
+        }
+    }
+    let empty = chunks.len() - effective;
+    if analyses.rounding.is_some() && empty > 0 {
+        println!("{}", format!("⚠️  {} chunks round to zero - use a smaller lot size or fewer intervals", empty).yellow());
+    } else if empty > 0 {
+        println!("{}", format!("⚠️  {} chunks are empty - the order is too small for this many intervals or some weights are zero", empty).yellow());
+    }
+    if config.randomize_execution {
+        println!("  {} Chunk sizes are randomized on-chain around the mean shown", "ℹ️ ".blue());
+    }
+    if config.visible_pct.is_some() {
+        println!("  {} Each chunk is revealed one visible slice at a time, the next appearing as the last fills", "ℹ️ ".blue());
+    }
+    if let Some(blackouts) = &blackouts {
+        report_blackout_effect(blackouts, &chunks, &offsets, &placed, order_size, precision);
+    }
+    // Skipped fills execute nothing, so the analyses below only see what is filled
+    let chunks: Vec<Amount> = chunks.iter().zip(&placed)
+        .map(|(chunk, placed)| if placed.is_some() { *chunk } else { Amount::ZERO })
+        .collect();
+
+    if analyses.timeline {
+        println!();
+        println!("{}", "🗓️  Timeline:".bold());
+        let mut timeline = Timeline::new();
+        for (i, (chunk, offset)) in chunks.iter().zip(&fill_offsets).enumerate() {
+            let mark = if chunk.is_zero() { Mark::Skipped } else { Mark::Fill };
+            let size = format!("{} ETH", chunk.format_eth(precision));
+            timeline.span((i + 1).to_string(), *offset, offset + interval_secs, chunk.as_eth_f64(), size, mark);
+        }
+        println!("{}", timeline.render_for_stdout());
+    }
+
+    if let Some(coeff) = analyses.impact_coeff {
+        println!();
+        print_market_impact_comparison(order_size, &chunks, coeff, precision);
+    }
+
+    if let Some(prices) = &prices {
+        println!();
+        print_vwap_tracking(&chunks, prices, precision);
+    }
+
+    if let Some(gas) = &analyses.gas {
+        println!();
+        print_gas_estimate(placed.iter().flatten().count(), gas, precision)?;
+    }
+
+    if let Some(model) = &analyses.failures {
+        println!();
+        print_failure_simulation(order_size, &chunks, model, precision);
+    }
+
+    println!();
+    println!("{}", "✅ Simulation complete".green());
+    Ok(())
+}
+
+pub(crate) async fn validate_twap_config(file: &str, strict: bool) -> Result<Findings> {
+    println!("{} {}", "🔍 Validating TWAP config:".cyan(), file.yellow());
+
+    let config: TwapConfig = read_config(file)?;
+
+    let (mut warnings, mut errors) = check_twap_config(&config);
+    add_key_findings::<TwapConfig>(file, strict, &mut warnings, &mut errors);
+
+    if errors.is_empty() && warnings.is_empty() {
+        println!("{}", "✅ TWAP configuration is valid!".green());
+        println!("📊 Configuration summary:");
+        println!("  • Duration: {} minutes", config.duration / 60);
+        println!("  • Intervals: {} ({} executed)", config.intervals, config.executed_intervals);
+        println!("  • Min execution gap: {}s", config.min_execution_gap);
+        println!("  • Max slippage: {}bps", config.max_slippage_bps);
+        println!("  • Randomization: {}", if config.randomize_execution { "enabled" } else { "disabled" });
+        println!("  • Weighting: {}", config.weighting.name());
+        if let Some(pct) = config.visible_pct {
+            println!("  • Iceberg: {}% visible", pct);
+        }
+        if !config.blackouts.is_empty() {
+            let blackouts = Blackouts { windows: config.blackouts.clone(), policy: config.on_blackout.unwrap_or_default() };
+            println!("  • Blackouts: {}", blackouts.describe());
+        }
+    } else {
+        for warning in &warnings {
+            println!("{}", warning.yellow());
+        }
+        for error in &errors {
+            println!("{}", error.red());
+        }
+    }
+
+    Ok(Findings::of(&warnings, &errors))
+}
+
+/// Runs all validation checks without printing, returning `(warnings, errors)`.
+pub(crate) fn check_twap_config(config: &TwapConfig) -> (Vec<String>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    if config.intervals == 0 {
+        errors.push("❌ Number of intervals must be greater than zero".to_string());
+    } else if config.min_execution_gap > config.duration / config.intervals as u64 {
+        errors.push("❌ Min execution gap is longer than a single interval".to_string());
+    }
+
+    if config.duration == 0 {
+        errors.push("❌ Duration must be greater than zero".to_string());
+    }
+
+    if config.executed_intervals > config.intervals {
+        errors.push("❌ Executed intervals exceed total intervals".to_string());
+    }
+
+    if config.intervals > DEFAULT_MAX_INTERVALS {
+        warnings.push(format!("⚠️  {} intervals is above the usual limit of {}", config.intervals, DEFAULT_MAX_INTERVALS));
+    }
+
+    if config.max_slippage_bps > 1000 {
+        warnings.push("⚠️  Max slippage is above 10%".to_string());
+    }
+
+    if let Err(e) = config.weighting.weights(config.weights.as_deref(), config.intervals) {
+        errors.push(format!("❌ {}", e));
+    }
+
+    if let Some(Err(e)) = config.visible_pct.map(check_visible_pct) {
+        errors.push(format!("❌ {}", e));
+    }
+
+    if !config.blackouts.is_empty() {
+        if let Err(e) = check_blackouts(&config.blackouts) {
+            errors.push(format!("❌ {}", e));
+        }
+    } else if config.on_blackout.is_some() {
+        warnings.push("⚠️  on_blackout is set but there are no blackout windows".to_string());
+    }
+
+    (warnings, errors)
+}
+
+fn next_fill(config_file: &str, order_size: Amount, precision: u8) -> Result<()> {
+    let config: TwapConfig = read_config(config_file)?;
+    let (_, errors) = check_twap_config(&config);
+    if let Some(error) = errors.first() {
+        return Err(VectorPlusError::Validation(error.trim_start_matches("❌ ").to_string()).into());
+    }
+
+    let weights = config.weighting.weights(config.weights.as_deref(), config.intervals)?;
+    let chunks = order_size.split(&weights);
+    let executed = config.executed_intervals as usize;
+    let filled: Amount = chunks[..executed].iter().copied().sum();
+    let remaining = order_size.saturating_sub(filled);
+
+    println!("{}", "⏭️  Next TWAP fill".cyan());
+    println!("  • Config: {}", config_file);
+    println!("  • Order size: {} ETH", order_size.format_eth(precision));
+    println!("  • Executed: {} of {} intervals", executed, config.intervals);
+    println!("  • Remaining: {} ETH", remaining.format_eth(precision));
+
+    let Some(chunk) = chunks.get(executed).copied() else {
+        println!("{}", "✅ All intervals executed - nothing left to fill".green());
+        return Ok(());
+    };
+    println!("  • Next interval: {} of {}", executed + 1, config.intervals);
+    println!("  • Interval chunk: {} ETH", chunk.format_eth(precision));
+    if let Some(pct) = config.visible_pct {
+        let visible = config.visible_slice(chunk);
+        println!(
+            "  • Visible now: {} ETH ({}% of the chunk, {} reveals)",
+            visible.format_eth(precision), pct, reveals(chunk, visible)
+        );
+    }
+    Ok(())
+}
+
+/// Block index, counted from the start, nearest to each offset in seconds.
+/// Summarizes how blackouts changed the schedule: how far shifted fills moved
+/// the end, or how much of the order skipped fills leave unfilled.
+fn report_blackout_effect(
+    blackouts: &Blackouts,
+    chunks: &[Amount],
+    offsets: &[u64],
+    placed: &[Option<u64>],
+    order_size: Amount,
+    precision: u8,
+) {
+    let moved = offsets.iter().zip(placed).filter(|(offset, placed)| **placed != Some(**offset)).count();
+    if moved == 0 {
+        println!("  {} No fills fall in a blackout window", "ℹ️ ".blue());
+        return;
+    }
+    match blackouts.policy {
+        BlackoutPolicy::Shift => {
+            let planned_end = offsets.last().copied().unwrap_or(0);
+            let end = placed.iter().flatten().max().copied().unwrap_or(0);
+            println!(
+                "  {} {} fills shifted out of blackout windows; the full order still fills, the last fill moving from +{}s to +{}s",
+                "ℹ️ ".blue(), moved, planned_end, end
+            );
+        }
+        BlackoutPolicy::Skip => {
+            let unfilled: Amount = chunks.iter().zip(placed)
+                .filter(|(_, placed)| placed.is_none())
+                .map(|(chunk, _)| *chunk)
+                .sum();
+            let pct = if order_size.is_zero() { 0.0 } else { unfilled.as_eth_f64() / order_size.as_eth_f64() * 100.0 };
+            println!(
+                "{}",
+                format!(
+                    "⚠️  {} fills fall in blackout windows and are skipped, leaving {} ETH ({:.1}%) unfilled",
+                    moved, unfilled.format_eth(precision), pct
+                ).yellow()
+            );
+        }
+    }
+}
+
+fn snap_to_blocks(offsets_secs: &[u64], block_time_ms: u64) -> Vec<u64> {
+    offsets_secs.iter()
+        .map(|offset| (offset * 1000 + block_time_ms / 2) / block_time_ms)
+        .collect()
+}
+
+/// Milliseconds as seconds without trailing zeros, e.g. 12000 as `12`, 250 as `0.25`.
+fn format_millis(ms: u64) -> String {
+    let text = format!("{}.{:03}", ms / 1000, ms % 1000);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Linear impact model: a fill of `q` ETH moves the price by `coeff * q` bps.
+pub(crate) fn impact_bps(size_eth: f64, coeff: f64) -> f64 {
+    coeff * size_eth
+}
+
+/// Inverse of [`impact_bps`]: the largest fill in ETH that moves the price at
+/// most `bps`, or `None` when a zero coefficient never moves it.
+pub(crate) fn max_size_for_impact(bps: f64, coeff: f64) -> Option<f64> {
+    (coeff > 0.0).then(|| bps / coeff)
+}
+
+/// Under [`impact_bps`], a fill of `q` ETH costs `q * coeff * q / 10_000` ETH.
+/// Slicing into `n` equal chunks divides the per-fill impact by `n`, assuming
+/// the book recovers between fills; uneven chunks save less.
+fn print_market_impact_comparison(order_size: Amount, chunks: &[Amount], coeff: f64, precision: u8) {
+    // The impact model is a float estimate; the amounts it reads are exact
+    let order_size = order_size.as_eth_f64();
+    let chunks: Vec<f64> = chunks.iter().map(|chunk| chunk.as_eth_f64()).collect();
+    let single_shot_bps = impact_bps(order_size, coeff);
+    let single_shot_cost = order_size * single_shot_bps / 10_000.0;
+
+    let sliced_cost: f64 = chunks.iter().map(|&q| q * impact_bps(q, coeff) / 10_000.0).sum();
+    let sliced_bps = if order_size > 0.0 { sliced_cost * 10_000.0 / order_size } else { 0.0 };
+
+    println!("📉 Market Impact Comparison (impact coeff: {} bps/ETH):", coeff);
+    println!("  • Single-shot impact: {:.2} bps ({} ETH)", single_shot_bps, format_amount(single_shot_cost, precision));
+    println!("  • TWAP impact: {:.2} bps ({} ETH)", sliced_bps, format_amount(sliced_cost, precision));
+    println!(
+        "  • Estimated savings: {} ({} ETH)",
+        format!("{:.2} bps", single_shot_bps - sliced_bps).green(),
+        format_amount(single_shot_cost - sliced_cost, precision).green()
+    );
+}
+
+fn print_gas_estimate(fills: usize, gas: &GasSettings, precision: u8) -> Result<()> {
+    if !gas.gas_price_gwei.is_finite() || gas.gas_price_gwei < 0.0 {
+        return Err(eyre::eyre!("Gas price must be a non-negative number of gwei"));
+    }
+
+    let token = gas.network.gas_token;
+    let per_fill = fill_gas_cost(gas.gas_per_fill, gas.gas_price_gwei);
+    let total = per_fill * fills as f64;
+    let in_quote = |amount: f64| {
+        gas.gas_token_price
+            .map(|price| format!(" (≈ ${})", format_amount(amount * price, precision)))
+            .unwrap_or_default()
+    };
+
+    println!("⛽ Gas Estimate ({}, paid in {}):", gas.network.name, token);
+    println!("  • Gas per fill: {} @ {} gwei", gas.gas_per_fill, gas.gas_price_gwei);
+    println!("  • Single-shot: {} {}{}", format_amount(per_fill, precision), token, in_quote(per_fill));
+    println!("  • TWAP ({} fills): {} {}{}", fills, format_amount(total, precision), token, in_quote(total));
+    Ok(())
+}
+
+fn print_failure_simulation(order_size: Amount, chunks: &[Amount], model: &FailureModel, precision: u8) {
+    let outcome = simulate_failures(chunks, model);
+    let filled: Amount = outcome.filled.iter().sum();
+    let shortfall = order_size.saturating_sub(filled);
+    let fill_pct = if order_size.is_zero() { 100.0 } else { filled.as_eth_f64() / order_size.as_eth_f64() * 100.0 };
+
+    println!(
+        "💥 Failure Simulation ({}% per attempt, seed {}, on failure: {}):",
+        model.rate * 100.0,
+        model.seed.unwrap_or(rng::DEFAULT_SEED),
+        model.policy.name()
+    );
+    println!("  • Failed attempts: {}", outcome.failed_attempts);
+    if outcome.failed_intervals.is_empty() {
+        println!("  • Failed intervals: none");
+    } else {
+        let intervals: Vec<String> = outcome.failed_intervals.iter().map(|i| i.to_string()).collect();
+        println!("  • Failed intervals ({}): {}", intervals.len(), intervals.join(", "));
+    }
+    println!("  • Filled: {} of {} ETH ({:.1}%)", filled.format_eth(precision), order_size.format_eth(precision), fill_pct);
+    let text = format!("{} ETH", shortfall.format_eth(precision));
+    if !shortfall.is_zero() {
+        println!("  • Shortfall: {}", text.yellow());
+    } else {
+        println!("  • Shortfall: {}", text.green());
+    }
+}
+
+/// Reads one positive price per line (blank lines and `#` comments are skipped),
+/// requiring exactly one price per TWAP interval.
+fn load_price_series(file: &str, intervals: u32) -> Result<Vec<f64>> {
+    let content = fs::read_to_string(file)
+        .map_err(|_| eyre::eyre!("Could not read file: {}", file))?;
+
+    let mut prices = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let price: f64 = line.parse()
+            .map_err(|_| eyre::eyre!("Invalid price on line {} of {}: {}", i + 1, file, line))?;
+        if !price.is_finite() || price <= 0.0 {
+            return Err(eyre::eyre!("Price on line {} of {} must be positive: {}", i + 1, file, line));
+        }
+        prices.push(price);
+    }
+
+    if prices.len() != intervals as usize {
+        return Err(eyre::eyre!(
+            "Price series has {} prices but the TWAP config has {} intervals",
+            prices.len(),
+            intervals
+        ));
+    }
+
+    Ok(prices)
+}
+
+/// The VWAP achieved by filling `chunks[i]` at `prices[i]`, against the
+/// series' simple time-weighted average price.
+#[derive(Debug, PartialEq)]
+struct VwapTracking {
+    vwap: f64,
+    twap: f64,
+    /// Slippage of the achieved VWAP from the series TWAP
+    deviation_bps: f64,
+    low: f64,
+    high: f64,
+}
+
+impl VwapTracking {
+    fn new(chunks: &[Amount], prices: &[f64]) -> Self {
+        let chunks: Vec<f64> = chunks.iter().map(|chunk| chunk.as_eth_f64()).collect();
+        let volume: f64 = chunks.iter().sum();
+        let notional: f64 = chunks.iter().zip(prices).map(|(q, p)| q * p).sum();
+        let vwap = if volume > 0.0 { notional / volume } else { 0.0 };
+        let twap = prices.iter().sum::<f64>() / prices.len() as f64;
+        VwapTracking {
+            vwap,
+            twap,
+            deviation_bps: (vwap - twap) / twap * 10_000.0,
+            low: prices.iter().cloned().fold(f64::INFINITY, f64::min),
+            high: prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+fn print_vwap_tracking(chunks: &[Amount], prices: &[f64], precision: u8) {
+    let tracking = VwapTracking::new(chunks, prices);
+
+    println!(
+        "📈 Price Tracking ({} prices, range {} - {}):",
+        prices.len(),
+        format_amount(tracking.low, precision),
+        format_amount(tracking.high, precision)
+    );
+    println!("  • Achieved VWAP: {}", format_amount(tracking.vwap, precision));
+    println!("  • Series TWAP: {}", format_amount(tracking.twap, precision));
+    let deviation = format!("{:+.2} bps", tracking.deviation_bps);
+    if tracking.deviation_bps.abs() < 1.0 {
+        println!("  • Deviation: {}", deviation.green());
+    } else {
+        println!("  • Deviation: {}", deviation.yellow());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_weighting_splits_the_whole_order() {
+        let order: Amount = "10.000000000000000007".parse().unwrap();
+        let custom = [3.0, 0.0, 1.5, 2.25, 0.1];
+        for (weighting, custom) in [
+            (Weighting::Flat, None),
+            (Weighting::Front, None),
+            (Weighting::Back, None),
+            (Weighting::Custom, Some(&custom[..])),
+        ] {
+            let weights = weighting.weights(custom, 5).unwrap();
+            assert_eq!(weights.len(), 5);
+            let chunks = order.split(&weights);
+            assert_eq!(chunks.into_iter().sum::<Amount>(), order, "{:?}", weighting);
+        }
+        assert_eq!(Weighting::Front.weights(None, 3).unwrap(), vec![3.0, 2.0, 1.0]);
+        assert_eq!(Weighting::Back.weights(None, 3).unwrap(), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn order_size_must_be_positive() {
+        use clap::Parser;
+        let parse = |size: &str| crate::Cli::try_parse_from(["vector-plus", "twap", "simulate", "--order-size", size]);
+        assert!(parse("1.5").is_ok());
+        for size in ["0", "0.0", "-1"] {
+            assert!(parse(size).is_err(), "{}", size);
+        }
+    }
+
+    #[test]
+    fn tracks_vwap_against_the_price_series() {
+        let amount = |text: &str| text.parse::<Amount>().unwrap();
+        let prices = [100.0, 110.0, 90.0];
+
+        // (1 × 100 + 2 × 110 + 1 × 90) / 4 = 102.5 against a TWAP of 100
+        let tracking = VwapTracking::new(&[amount("1"), amount("2"), amount("1")], &prices);
+        assert_eq!(tracking, VwapTracking { vwap: 102.5, twap: 100.0, deviation_bps: 250.0, low: 90.0, high: 110.0 });
+
+        // Flat chunks fill at the TWAP itself
+        let flat = VwapTracking::new(&[amount("0.5"); 3], &prices);
+        assert_eq!((flat.vwap, flat.deviation_bps), (100.0, 0.0));
+
+        // Notional slicing buys more where the price is low: 1/100 + 1/110 + 1/90
+        // ETH for 300 of notional is the harmonic mean, below the TWAP
+        let weights = notional_weights(&[1.0; 3], &prices);
+        let chunks = amount("3").split(&weights);
+        let notional = VwapTracking::new(&chunks, &prices);
+        let harmonic = 3.0 / prices.iter().map(|p| 1.0 / p).sum::<f64>();
+        assert!((notional.vwap - harmonic).abs() < 1e-9, "{:?}", notional);
+        assert!((notional.deviation_bps - (harmonic - 100.0) * 100.0).abs() < 1e-6, "{:?}", notional);
+    }
+
+    #[test]
+    fn snaps_offsets_to_the_nearest_block() {
+        // 12s blocks: 0, 25s and 30s round to blocks 0, 2 and 3 (ties round up)
+        assert_eq!(snap_to_blocks(&[0, 25, 30], 12_000), vec![0, 2, 3]);
+        assert_eq!(snap_to_blocks(&[1, 2], 250), vec![4, 8]);
+        assert_eq!(format_millis(12_000), "12");
+        assert_eq!(format_millis(250), "0.25");
+    }
+
+    #[test]
+    fn caps_interval_counts_unless_allowed() {
+        let limits = IntervalLimitArgs { max_intervals: DEFAULT_MAX_INTERVALS, allow_large_intervals: false };
+        assert!(limits.check(1000).is_ok());
+        let err = limits.check(1001).unwrap_err();
+        assert!(err.to_string().contains("1001 intervals exceeds the limit of 1000"), "{}", err);
+        assert!(IntervalLimitArgs { allow_large_intervals: true, ..limits }.check(u32::MAX).is_ok());
+        assert!(IntervalLimitArgs { max_intervals: 10, allow_large_intervals: false }.check(11).is_err());
+    }
+
+    #[test]
+    fn iceberg_reveals_a_slice_of_each_chunk() {
+        let mut config = TwapConfig::new(3600, 4, false);
+        let chunk: Amount = "2.5".parse().unwrap();
+        assert_eq!(config.visible_slice(chunk), chunk);
+
+        config.visible_pct = Some(20.0);
+        let visible = config.visible_slice(chunk);
+        assert_eq!(visible, "0.5".parse().unwrap());
+        assert_eq!(reveals(chunk, visible), 5);
+        // A partial last slice still takes a reveal
+        assert_eq!(reveals(chunk, "0.75".parse().unwrap()), 4);
+
+        assert!(check_twap_config(&config).1.is_empty());
+        for pct in [0.0, -5.0, 100.5] {
+            config.visible_pct = Some(pct);
+            assert_eq!(check_twap_config(&config).1.len(), 1, "{}", pct);
+        }
+    }
+
+    #[test]
+    fn notional_slicing_spends_equally() {
+        let order: Amount = "7".parse().unwrap();
+        let prices = [1000.0, 2000.0, 4000.0];
+        let chunks = order.split(&notional_weights(&[1.0; 3], &prices));
+        let expected: Vec<Amount> = ["4", "2", "1"].iter().map(|eth| eth.parse().unwrap()).collect();
+        assert_eq!(chunks, expected);
+        for (chunk, price) in chunks.iter().zip(prices) {
+            assert_eq!(chunk.as_eth_f64() * price, 4000.0);
+        }
+
+        // Weights still apply, now to the quote value
+        let chunks = order.split(&notional_weights(&[2.0, 1.0, 1.0], &prices));
+        assert_eq!(chunks.iter().sum::<Amount>(), order);
+        assert_eq!(chunks[0].as_eth_f64() * prices[0], 2.0 * chunks[1].as_eth_f64() * prices[1]);
+    }
+
+    #[test]
+    fn failure_policies_account_for_every_chunk() {
+        let one: Amount = "1".parse().unwrap();
+        let total = Amount::from_wei(20 * one.wei());
+        let chunks = [one; 20];
+        let model = |policy| FailureModel { rate: 0.3, seed: Some(7), policy };
+        let skip = simulate_failures(&chunks, &model(FailurePolicy::Skip));
+        assert_eq!(skip, simulate_failures(&chunks, &model(FailurePolicy::Skip)));
+        assert!(!skip.failed_intervals.is_empty());
+        let skipped = Amount::from_wei(skip.failed_intervals.len() as u128 * one.wei());
+        assert_eq!(skip.filled.iter().sum::<Amount>(), total - skipped);
+
+        // Rollover loses only a failure in the last interval
+        let rollover = simulate_failures(&chunks, &model(FailurePolicy::Rollover));
+        assert!(!rollover.failed_intervals.is_empty());
+        let complete = rollover.filled.iter().sum::<Amount>() == total;
+        assert_eq!(complete, !rollover.failed_intervals.contains(&20));
+
+        // Retrying makes a failed interval far less likely than a failed attempt
+        let retry = simulate_failures(&chunks, &model(FailurePolicy::Retry));
+        assert!(retry.failed_intervals.len() < skip.failed_intervals.len());
+
+        let never = simulate_failures(&chunks, &FailureModel { rate: 0.0, seed: Some(1), policy: FailurePolicy::Skip });
+        assert_eq!(never.failed_attempts, 0);
+        let always = simulate_failures(&chunks, &FailureModel { rate: 1.0, seed: Some(1), policy: FailurePolicy::Rollover });
+        assert_eq!(always.filled.iter().sum::<Amount>(), Amount::ZERO);
+    }
+
+    #[test]
+    fn suggests_the_largest_interval_count_that_covers_gas() {
+        // 0.003 ETH per fill against a 1% limit needs chunks of at least 0.3 ETH
+        let check = GasCheck { order_size: 10.0, fill_cost_eth: 0.003, max_gas_pct: 1.0, strict: false };
+        assert_eq!(suggest_intervals(&check, Weighting::Flat, 1000), Some(33));
+
+        // Front-loading shrinks the last chunk, so fewer intervals fit
+        let front = suggest_intervals(&check, Weighting::Front, 1000).unwrap();
+        let weights = Weighting::Front.weights(None, front).unwrap();
+        assert!(smallest_chunk(10.0, &weights) * 0.01 >= 0.003);
+        let weights = Weighting::Front.weights(None, front + 1).unwrap();
+        assert!(smallest_chunk(10.0, &weights) * 0.01 < 0.003);
+
+        let tiny = GasCheck { order_size: 0.1, ..check };
+        assert_eq!(suggest_intervals(&tiny, Weighting::Flat, 10), None);
+    }
+
+    #[test]
+    fn blackouts_shift_or_skip_fills() {
+        let overnight: Blackout = "22:00-02:00".parse().unwrap();
+        assert_eq!(overnight.to_string(), "22:00-02:00");
+        assert_eq!(overnight.remaining(23 * 3600), Some(3 * 3600));
+        assert_eq!(overnight.remaining(3600), Some(3600));
+        assert_eq!(overnight.remaining(12 * 3600), None);
+        assert!("25:00-02:00".parse::<Blackout>().is_err());
+        assert!("09:00-09:00".parse::<Blackout>().is_err());
+        assert!(check_blackouts(&["00:00-24:00".parse().unwrap()]).is_err());
+
+        // Hourly fills from midnight against a 01:00-03:30 blackout
+        let offsets: Vec<u64> = (0..6).map(|i| i * 3600).collect();
+        let windows = vec!["01:00-03:30".parse().unwrap()];
+        let shift = Blackouts { windows: windows.clone(), policy: BlackoutPolicy::Shift };
+        let placed: Vec<u64> = shift.place(0, &offsets, 1800).into_iter().flatten().collect();
+        assert_eq!(placed, [0, 12600, 14400, 16200, 18000, 19800]);
+
+        let skip = Blackouts { windows, policy: BlackoutPolicy::Skip };
+        assert_eq!(skip.place(0, &offsets, 1800), [Some(0), None, None, None, Some(14400), Some(18000)]);
+    }
+}
@@ -1,232 +1,4496 @@
-use clap::Subcommand;
+use clap::{Args, Subcommand, ValueEnum};
 use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input};
 use eyre::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+mod portfolio;
+
+use super::strategy::{Findings, ValidationSummary};
+use crate::bps::Bps;
+use crate::error::VectorPlusError;
+use crate::rounding::RoundingArgs;
+use crate::price_stream::{price_from_json, PriceStream, StreamEvent};
+use crate::shutdown::Shutdown;
+use crate::utils::table::{Align, Cell, OutputFormat, Table};
+use crate::amount::{serialize_eth, Amount};
+use crate::eip712::selector;
+use portfolio::{calculate_portfolio, PortfolioOptions};
+use crate::utils::{add_key_findings, backup_existing, confirm_overwrite, status, verbose, format_amount, format_time, format_units, read_config, output_name, write_created_config, write_json_file_atomic, TimeFormat, STDOUT_PATH};
+
+// Shared by the create-config flags and the interactive prompts
+const DEFAULT_BASELINE_VOLATILITY: Bps = Bps::from_bps(300);
+const DEFAULT_CURRENT_VOLATILITY: Bps = Bps::from_bps(350);
+const DEFAULT_MAX_EXECUTION_SIZE: Amount = Amount::from_wei(5_000_000_000_000_000_000);
+const DEFAULT_MIN_EXECUTION_SIZE: Amount = Amount::from_wei(100_000_000_000_000_000);
+const DEFAULT_CONSERVATIVE_REDUCTION_PCT: u64 = 10;
+// Caps on the low- and high-volatility adjustments, as on-chain
+const DEFAULT_MAX_BOOST_PCT: u64 = 50;
+const DEFAULT_MAX_REDUCTION_PCT: u64 = 50;
+const DEFAULT_STATE_FILE: &str = "volatility-state.json";
+const DEFAULT_OUTPUT: &str = "volatility-config.json";
+const DEFAULT_SIZING_STATE: &str = "sizing-state.json";
+const DEFAULT_FACTOR_LOG: &str = "factor-log.json";
+/// Longest `volatility watch` waits for one polled price
+const PRICE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const DEFAULT_ORACLE_STATE: &str = "oracle-prices.json";
+/// Oracle rounds kept for the volatility estimate, matching `watch`'s default window
+const ORACLE_HISTORY: usize = 30;
+/// RiskMetrics decay, the `estimate` default
+const ORACLE_EWMA_LAMBDA: f64 = 0.94;
 
 #[derive(Subcommand)]
 pub enum VolatilityCommands {
     /// Generate volatility configuration file
     CreateConfig {
-        /// Baseline volatility in basis points
-        #[arg(long, default_value = "300")]
-        baseline_volatility: u64,
+        /// Baseline volatility in basis points, e.g. 300 or 3.25bps
+        #[arg(long, default_value_t = DEFAULT_BASELINE_VOLATILITY)]
+        baseline_volatility: Bps,
         
-        /// Current market volatility in basis points
-        #[arg(long, default_value = "350")]
-        current_volatility: u64,
+        /// Current market volatility in basis points, e.g. 350 or 3.5bps
+        #[arg(long, default_value_t = DEFAULT_CURRENT_VOLATILITY)]
+        current_volatility: Bps,
         
         /// Maximum execution size in ETH
-        #[arg(long, default_value = "5.0")]
-        max_execution_size: f64,
+        #[arg(long, default_value_t = DEFAULT_MAX_EXECUTION_SIZE)]
+        max_execution_size: Amount,
         
         /// Minimum execution size in ETH
-        #[arg(long, default_value = "0.1")]
-        min_execution_size: f64,
+        #[arg(long, default_value_t = DEFAULT_MIN_EXECUTION_SIZE)]
+        min_execution_size: Amount,
+        
+        /// Maximum execution size as an exact integer amount of wei
+        #[arg(long, conflicts_with = "max_execution_size")]
+        max_execution_size_wei: Option<String>,
+        
+        /// Minimum execution size as an exact integer amount of wei
+        #[arg(long, conflicts_with = "min_execution_size")]
+        min_execution_size_wei: Option<String>,
         
         /// Enable conservative mode
         #[arg(long)]
         conservative_mode: bool,
         
+        /// Percentage conservative mode cuts from sizes in normal volatility (0-100)
+        #[arg(long, default_value_t = DEFAULT_CONSERVATIVE_REDUCTION_PCT)]
+        conservative_reduction_pct: u64,
+        
+        /// Largest percentage low volatility may raise sizes by (0-100)
+        #[arg(long, default_value_t = DEFAULT_MAX_BOOST_PCT)]
+        max_boost_pct: u64,
+        
+        /// Largest percentage high volatility may cut sizes by (0-100)
+        #[arg(long, default_value_t = DEFAULT_MAX_REDUCTION_PCT)]
+        max_reduction_pct: u64,
+        
+        /// How far volatility must fall below the threshold before ELEVATED is left, e.g. 50 or 0.5bps
+        #[arg(long, default_value_t = Bps::ZERO)]
+        hysteresis_margin: Bps,
+        
         /// Output file path
-        #[arg(short, long, default_value = "volatility-config.json")]
+        #[arg(short, long, default_value = DEFAULT_OUTPUT)]
         output: String,
+        
+        /// Prompt for each field, offering the flag values as defaults
+        #[arg(long)]
+        interactive: bool,
+        
+        /// Validate the written file, failing on errors as `volatility validate` does
+        #[arg(long)]
+        validate_after: bool,
     },
     
     /// Validate volatility configuration
     Validate {
         /// Configuration file to validate
         file: String,
+        
+        /// Fail on unknown fields and duplicate keys instead of warning
+        #[arg(long)]
+        strict: bool,
+    },
+    
+    /// Layer an overlay's non-default fields over a base config
+    Merge {
+        /// Shared base config
+        base: String,
+        
+        /// Config (possibly partial) whose fields that differ from the defaults win
+        overlay: String,
+        
+        /// Merged config file
+        #[arg(long)]
+        out: String,
     },
     
     /// Calculate volatility adjustment for given amount
     Calculate {
         /// Base amount in ETH
+        #[arg(long, required_unless_present_any = ["portfolio", "output_schema", "pct_of"], conflicts_with = "portfolio")]
+        amount: Option<Amount>,
+        
+        /// Portfolio size in ETH; the base amount is --pct of it instead of a fixed --amount
+        #[arg(long, requires = "pct", conflicts_with_all = ["amount", "portfolio", "range"])]
+        pct_of: Option<Amount>,
+        
+        /// Percentage of --pct-of to size, above 0 and at most 100
+        #[arg(long, requires = "pct_of", value_parser = crate::utils::parse_positive_f64)]
+        pct: Option<f64>,
+        
+        /// Print the JSON Schema of the --json output and exit
+        #[arg(long, conflicts_with_all = ["amount", "portfolio", "pct_of"])]
+        output_schema: bool,
+        
+        /// JSON file listing several positions to size at once
+        #[arg(long, conflicts_with_all = ["price", "quote_decimals"])]
+        portfolio: Option<String>,
+        
+        /// Print the portfolio result as CSV, the same as --format csv
+        #[arg(long, requires = "portfolio", conflicts_with = "json")]
+        csv: bool,
+
+        /// Output format of the portfolio result
+        #[arg(long, value_enum, requires = "portfolio", conflicts_with_all = ["json", "csv"])]
+        format: Option<OutputFormat>,
+        
+        /// Volatility config file
+        #[arg(long, default_value = "volatility-config.json")]
+        config: String,
+        
+        /// Quote asset price per ETH, used to derive the taking amount
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+        price: Option<f64>,
+        
+        /// Decimals of the quote asset
+        #[arg(long, default_value = "6")]
+        quote_decimals: u32,
+        
+        /// Symbol of the quote asset
+        #[arg(long, default_value = "USDC")]
+        quote_symbol: String,
+        
+        /// Print the result as JSON
+        #[arg(long)]
+        json: bool,
+        
+        /// Show each step from volatility to final size; with --json, adds it as `derivation`
+        #[arg(long, conflicts_with_all = ["portfolio", "range"])]
+        explain: bool,
+        
+        /// Print the result as JSON with its derivation, the same as --json --explain
+        #[arg(long, conflicts_with_all = ["portfolio", "range", "json"])]
+        explain_json: bool,
+        
+        /// Don't let the size increase again within this many seconds of the last change
+        #[arg(long, conflicts_with = "portfolio")]
+        cooldown_secs: Option<u64>,
+        
+        /// File remembering the last size for --cooldown-secs
+        #[arg(long, requires = "cooldown_secs", default_value = DEFAULT_SIZING_STATE)]
+        state_file: String,
+
+        /// Chainlink-style price feed read over --rpc-url; its price history sets current volatility
+        #[arg(long, conflicts_with = "portfolio")]
+        oracle_address: Option<String>,
+
+        /// File keeping the oracle's recent rounds between runs
+        #[arg(long, requires = "oracle_address", default_value = DEFAULT_ORACLE_STATE)]
+        oracle_state: String,
+
+        /// Fail when the feed's latest round is older than this
+        #[arg(long, requires = "oracle_address", default_value_t = 3600)]
+        max_oracle_age_secs: u64,
+
+        /// Rounding of each position's final amount, for --portfolio
+        #[command(flatten)]
+        rounding: Box<RoundingArgs>,
+        
+        /// Report a conservative/base/aggressive size range, one regime band either side of current volatility
+        #[arg(long, conflicts_with_all = ["portfolio", "cooldown_secs", "price"])]
+        range: bool,
+
+        /// Print only `unchanged` unless the factor moved more than this many percentage points since the last printed result
+        #[arg(long, conflicts_with_all = ["portfolio", "range"])]
+        min_factor_change: Option<u64>,
+
+        /// File remembering the last printed factor for --min-factor-change
+        #[arg(long, requires = "min_factor_change", default_value = DEFAULT_FACTOR_LOG)]
+        factor_log: String,
+
+        /// Skip the warnings about volatility values that look like percentages rather than bps
+        #[arg(long)]
+        no_unit_warnings: bool,
+
+        /// Slippage budget capping the size
+        #[command(flatten)]
+        slippage: Box<SlippageArgs>,
+        
+        /// Refuse to run unless `config approve` recorded the config's current contents
+        #[arg(long)]
+        require_approval: bool,
+    },
+    
+    /// Monitor market volatility and the resulting sizing regime
+    Watch {
+        /// Volatility config file
+        #[arg(long, default_value = "volatility-config.json")]
+        config: String,
+        
+        /// HTTP endpoint returning the current ETH price, polled while no stream is connected;
+        /// without it, the config's price_command is polled instead
+        #[arg(long)]
+        price_url: Option<String>,
+        
+        /// Asset symbol passed to the config's price_command
+        #[arg(long, default_value = "ETH")]
+        price_symbol: String,
+        
+        /// WebSocket feed pushing prices as JSON messages; volatility is recomputed on every tick
+        #[arg(long)]
+        ws_url: Option<String>,
+        
+        /// JSON pointer to the price in the response or stream message (e.g. /ethereum/usd)
+        #[arg(long)]
+        price_pointer: Option<String>,
+        
+        /// Seconds between price polls (a stream silent for three intervals is reconnected)
+        #[arg(long, default_value = "60", value_parser = clap::value_parser!(u64).range(1..))]
+        interval_secs: u64,
+        
+        /// Number of recent prices used for the volatility estimate
+        #[arg(long, default_value = "30")]
+        window: usize,
+        
+        /// File persisting the price history across runs
+        #[arg(long)]
+        state_file: Option<String>,
+    },
+    
+    /// Classify the config's current volatility, remembering the regime between runs
+    Status {
+        /// Volatility config file
+        #[arg(long, default_value = "volatility-config.json")]
+        config: String,
+        
+        /// File persisting the last regime; the same format `watch --state-file` uses
+        #[arg(long, default_value = DEFAULT_STATE_FILE)]
+        state_file: String,
+    },
+    
+    /// Estimate volatility from historical prices
+    Estimate {
+        /// CSV with a `close` (or `price`) column and optional `high`/`low` columns
+        #[arg(long)]
+        prices: String,
+        
+        /// Estimator to use
+        #[arg(long, value_enum, default_value_t = ModelKind::Ewma)]
+        model: ModelKind,
+        
+        /// EWMA decay factor
+        #[arg(long, default_value = "0.94", value_parser = crate::utils::parse_finite_f64)]
+        lambda: f64,
+        
+        /// Rows per day, used to scale the estimate to daily volatility
+        #[arg(long, default_value = "1", value_parser = crate::utils::parse_positive_f64)]
+        periods_per_day: f64,
+        
+        /// Estimate over each trailing window of this many rows, writing a rolling series
+        #[arg(long)]
+        window: Option<usize>,
+        
+        /// CSV the rolling series is written to (default: volatility-series.csv)
+        #[arg(long, requires = "window")]
+        output: Option<String>,
+    },
+    
+    /// Replay the sizing logic over historical prices
+    Backtest {
+        /// CSV with a `close` (or `price`) column and optional `timestamp` column
+        #[arg(long, required_unless_present = "volatility", conflicts_with = "volatility")]
+        prices: Option<String>,
+        
+        /// Rolling series from `estimate --window`, used instead of estimating from --prices
+        #[arg(long)]
+        volatility: Option<String>,
+        
+        /// Volatility config file
+        #[arg(long, default_value = "volatility-config.json")]
+        config: String,
+        
+        /// Order amount in ETH sized at every row
+        #[arg(long)]
+        amount: Amount,
+        
+        /// Trailing rows used for each volatility estimate
+        #[arg(long, default_value = "30")]
+        window: usize,
+        
+        /// Rows per day, used to scale estimates to daily volatility
+        #[arg(long, default_value = "1", value_parser = crate::utils::parse_positive_f64)]
+        periods_per_day: f64,
+        
+        /// First timestamp to evaluate (inclusive)
+        #[arg(long, conflicts_with_all = ["start", "end"])]
+        since: Option<u64>,
+        
+        /// Last timestamp to evaluate (inclusive)
+        #[arg(long, conflicts_with_all = ["start", "end"])]
+        until: Option<u64>,
+        
+        /// First row index to evaluate (0-based, inclusive)
+        #[arg(long)]
+        start: Option<usize>,
+        
+        /// Row index to stop before (exclusive)
+        #[arg(long)]
+        end: Option<usize>,
+        
+        /// Refuse to run unless `config approve` recorded the config's current contents
+        #[arg(long)]
+        require_approval: bool,
+    },
+    
+    /// Size an order under a battery of named volatility scenarios
+    Stress {
+        /// Volatility config file
+        #[arg(long, default_value = "volatility-config.json")]
+        config: String,
+        
+        /// JSON file listing named scenarios, each with a current volatility
         #[arg(long)]
-        amount: f64,
+        scenarios: String,
         
+        /// Order amount in ETH sized under every scenario
+        #[arg(long)]
+        amount: Amount,
+        
+        /// Print the comparison as JSON, the same as --format json
+        #[arg(long)]
+        json: bool,
+
+        /// Output format of the comparison
+        #[arg(long, value_enum, conflicts_with = "json")]
+        format: Option<OutputFormat>,
+    },
+    
+    /// Grid of sized amounts across order amounts and volatilities
+    Heatmap {
         /// Volatility config file
         #[arg(long, default_value = "volatility-config.json")]
         config: String,
+        
+        /// Order amounts in ETH, one row each
+        #[arg(long, value_delimiter = ',', required = true)]
+        amounts: Vec<Amount>,
+        
+        /// Current volatilities in bps, one column each
+        #[arg(long, value_delimiter = ',', required = true)]
+        volatilities: Vec<Bps>,
+        
+        /// Show the effective factor (final size as a percent of the amount) instead of the final size
+        #[arg(long)]
+        factor: bool,
+        
+        /// Print the grid as JSON, with a nested array of values
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Estimate Value-at-Risk for a notional from historical prices
+    Var {
+        /// CSV with a `close` (or `price`) column and optional `high`/`low` columns
+        #[arg(long)]
+        prices: String,
+        
+        /// Confidence (percent) that the loss stays within the VaR
+        #[arg(long, default_value = "95", value_parser = crate::utils::parse_finite_f64)]
+        confidence: f64,
+        
+        /// Holding period in days
+        #[arg(long, default_value = "1")]
+        horizon_days: u32,
+        
+        /// Position size; the VaR is in the same unit
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+        notional: f64,
+        
+        /// Estimator for the volatility behind the parametric VaR
+        #[arg(long, value_enum, default_value_t = ModelKind::Ewma)]
+        model: ModelKind,
+        
+        /// EWMA decay factor
+        #[arg(long, default_value = "0.94", value_parser = crate::utils::parse_finite_f64)]
+        lambda: f64,
+        
+        /// Rows per day, used to scale volatility and returns to days
+        #[arg(long, default_value = "1", value_parser = crate::utils::parse_positive_f64)]
+        periods_per_day: f64,
+        
+        /// Use only the trailing window of this many rows
+        #[arg(long)]
+        window: Option<usize>,
+        
+        /// Print the estimate as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct VolatilityConfig {
-    baseline_volatility: u64,
-    current_volatility: u64,
-    max_execution_size: String,
-    min_execution_size: String,
-    volatility_threshold: u64,
-    conservative_mode: bool,
-    emergency_threshold: u64,
-    last_update_time: u64,
-}
-
-pub async fn handle_command(command: &VolatilityCommands, _cli: &crate::Cli) -> Result<()> {
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ModelKind {
+    /// Exponentially weighted moving average of squared returns
+    Ewma,
+    /// Close-to-close sample standard deviation
+    Stddev,
+    /// Parkinson high-low range estimator
+    Parkinson,
+    /// GARCH(1,1) fitted by maximum likelihood, forecasting the next row's volatility
+    Garch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub(crate) struct VolatilityConfig {
+    #[schemars(with = "f64")]
+    pub(crate) baseline_volatility: Bps,
+    #[schemars(with = "f64")]
+    pub(crate) current_volatility: Bps,
+    #[serde(deserialize_with = "deserialize_max_execution_size", serialize_with = "serialize_wei_field")]
+    pub(crate) max_execution_size: String,
+    #[serde(deserialize_with = "deserialize_min_execution_size", serialize_with = "serialize_wei_field")]
+    pub(crate) min_execution_size: String,
+    #[schemars(with = "f64")]
+    pub(crate) volatility_threshold: Bps,
+    pub(crate) conservative_mode: bool,
+    #[serde(default = "default_conservative_reduction_pct")]
+    pub(crate) conservative_reduction_pct: u64,
+    #[serde(default = "default_max_boost_pct")]
+    pub(crate) max_boost_pct: u64,
+    #[serde(default = "default_max_reduction_pct")]
+    pub(crate) max_reduction_pct: u64,
+    #[schemars(with = "f64")]
+    pub(crate) emergency_threshold: Bps,
+    /// ELEVATED is only left once current volatility drops this far below
+    /// `volatility_threshold`; zero disables hysteresis
+    #[serde(default, skip_serializing_if = "is_zero_bps")]
+    #[schemars(with = "f64")]
+    pub(crate) hysteresis_margin: Bps,
+    pub(crate) last_update_time: u64,
+}
+
+fn is_zero_bps(bps: &Bps) -> bool {
+    *bps == Bps::ZERO
+}
+
+fn default_conservative_reduction_pct() -> u64 {
+    DEFAULT_CONSERVATIVE_REDUCTION_PCT
+}
+
+fn default_max_boost_pct() -> u64 {
+    DEFAULT_MAX_BOOST_PCT
+}
+
+fn default_max_reduction_pct() -> u64 {
+    DEFAULT_MAX_REDUCTION_PCT
+}
+
+/// A wei amount field in a volatility config that is not a positive integer.
+#[derive(Debug)]
+pub(crate) struct InvalidWeiField {
+    pub(crate) field: &'static str,
+    pub(crate) value: String,
+    pub(crate) reason: &'static str,
+}
+
+impl std::fmt::Display for InvalidWeiField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {} {:?}: {}", self.field, self.value, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidWeiField {}
+
+/// Parses a wei amount strictly: digits only, optionally followed by an
+/// all-zero fraction (as written by older `create-config` versions), and
+/// greater than zero.
+pub(crate) fn parse_wei_field(field: &'static str, value: &str) -> std::result::Result<u128, InvalidWeiField> {
+    let invalid = |reason| InvalidWeiField { field, value: value.to_string(), reason };
+    
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid("expected an integer amount in wei"));
+    }
+    if !fraction.bytes().all(|b| b == b'0') {
+        return Err(invalid("wei amounts cannot have a fractional part"));
+    }
+    let wei: u128 = whole.parse().map_err(|_| invalid("amount is too large"))?;
+    if wei == 0 {
+        return Err(invalid("amount must be greater than zero"));
+    }
+    Ok(wei)
+}
+
+fn deserialize_wei_field<'de, D>(field: &'static str, deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    parse_wei_field(field, &value).map_err(serde::de::Error::custom)?;
+    Ok(value)
+}
+
+/// Whole part of a wei amount with an all-zero fraction; other values as given.
+fn integer_wei(value: &str) -> &str {
+    match value.split_once('.') {
+        Some((whole, fraction)) if fraction.bytes().all(|b| b == b'0') => whole,
+        _ => value,
+    }
+}
+
+/// Writes wei amounts as plain integers, so a config loaded with an older
+/// version's `.000000000000000000` suffix is saved without it.
+fn serialize_wei_field<S>(value: &str, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(integer_wei(value))
+}
+
+fn deserialize_max_execution_size<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_wei_field("max_execution_size", deserializer)
+}
+
+fn deserialize_min_execution_size<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_wei_field("min_execution_size", deserializer)
+}
+
+pub async fn handle_command(command: &VolatilityCommands, cli: &crate::Cli) -> Result<()> {
+    // A portfolio's positions may each name a config, so calculate_portfolio checks those
+    if let VolatilityCommands::Calculate { config, require_approval: true, portfolio: None, .. }
+    | VolatilityCommands::Backtest { config, require_approval: true, .. } = command
+    {
+        let approval = crate::commands::config::require_approval(config)?;
+        verbose!(
+            cli.verbose,
+            "{} approved by {} {}",
+            config, approval.approver, format_time(approval.approved_at.timestamp() as u64, cli.time_format)
+        );
+    }
+    
     match command {
         VolatilityCommands::CreateConfig { 
             baseline_volatility, 
             current_volatility, 
             max_execution_size,
             min_execution_size,
+            max_execution_size_wei,
+            min_execution_size_wei,
             conservative_mode,
-            output 
+            conservative_reduction_pct,
+            max_boost_pct,
+            max_reduction_pct,
+            hysteresis_margin,
+            output,
+            interactive,
+            validate_after,
+        } => {
+            let mut inputs = VolatilityConfigInputs {
+                baseline_volatility: *baseline_volatility,
+                current_volatility: *current_volatility,
+                max_execution_size: *max_execution_size,
+                min_execution_size: *min_execution_size,
+                max_execution_size_wei: max_execution_size_wei.clone(),
+                min_execution_size_wei: min_execution_size_wei.clone(),
+                conservative_mode: *conservative_mode,
+                conservative_reduction_pct: *conservative_reduction_pct,
+                max_boost_pct: *max_boost_pct,
+                max_reduction_pct: *max_reduction_pct,
+                hysteresis_margin: *hysteresis_margin,
+                output: output.clone(),
+            };
+            if *interactive {
+                inputs = inputs.prompt(cli.assume_yes)?;
+            }
+            if *validate_after && inputs.output == STDOUT_PATH {
+                return Err(eyre::eyre!("--validate-after needs --output to name a file, not -"));
+            }
+            create_volatility_config(&inputs, cli.precision, cli.verbose).await?;
+            if *validate_after {
+                println!();
+                ValidationSummary::single(&inputs.output, validate_volatility_config(&inputs.output, false, cli.time_format).await)?;
+            }
+            Ok(())
+        }
+        VolatilityCommands::Validate { file, strict } => {
+            ValidationSummary::single(file, validate_volatility_config(file, *strict, cli.time_format).await)
+        }
+        VolatilityCommands::Merge { base, overlay, out } => merge_configs(base, overlay, out),
+        VolatilityCommands::Calculate { output_schema: true, .. } => {
+            let schema = schemars::schema_for!(CalculationResult);
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+            Ok(())
+        }
+        VolatilityCommands::Calculate {
+            portfolio: Some(portfolio), config, json, csv, format, rounding, no_unit_warnings, require_approval, ..
+        } => {
+            let format = OutputFormat::resolve(*format, *json, *csv);
+            let options = PortfolioOptions {
+                rounding: rounding.resolve()?,
+                unit_warnings: !no_unit_warnings,
+                require_approval: *require_approval,
+                precision: cli.precision,
+            };
+            calculate_portfolio(portfolio, config, format, &options).await
+        }
+        VolatilityCommands::Calculate {
+            amount,
+            config,
+            price,
+            quote_decimals,
+            quote_symbol,
+            json,
+            explain,
+            explain_json,
+            cooldown_secs,
+            state_file,
+            oracle_address,
+            oracle_state,
+            max_oracle_age_secs,
+            rounding,
+            range,
+            min_factor_change,
+            factor_log,
+            pct_of,
+            pct,
+            no_unit_warnings,
+            slippage,
+            ..
         } => {
-            create_volatility_config(
-                *baseline_volatility,
-                *current_volatility,
-                *max_execution_size,
-                *min_execution_size,
-                *conservative_mode,
-                output
+            if rounding.resolve()?.is_some() {
+                return Err(eyre::eyre!("--rounding, --round-decimals and --lot-size apply to --portfolio sizing"));
+            }
+            let oracle = oracle_address.as_deref().map(|address| OracleFeed {
+                address,
+                state_file: oracle_state,
+                max_age_secs: *max_oracle_age_secs,
+            });
+            if *range {
+                return calculate_sizing_range(amount.unwrap_or_default(), config, oracle.as_ref(), *json, !no_unit_warnings, cli).await;
+            }
+            let quote = OrderQuote { price: *price, decimals: *quote_decimals, symbol: quote_symbol };
+            let constraints = SizingConstraints {
+                cooldown: cooldown_secs.map(|secs| Cooldown { secs, state_file }),
+                slippage: slippage.budget(),
+            };
+            let display = CalculationDisplay {
+                json: *json || *explain_json,
+                explain: *explain || *explain_json,
+                changes_only: min_factor_change.map(|min_change| ChangeFilter { min_change, log_file: factor_log }),
+                unit_warnings: !no_unit_warnings,
+            };
+            let base = match (pct_of, pct) {
+                (Some(total), Some(pct)) => BaseAmount::share_of(*total, *pct)?,
+                _ => BaseAmount::Fixed(amount.unwrap_or_default()),
+            };
+            calculate_volatility_adjustment(
+                base,
+                config,
+                &quote,
+                &constraints,
+                oracle.as_ref(),
+                display,
+                cli,
+            ).await
+        }
+        VolatilityCommands::Watch { config, price_url, price_symbol, ws_url, price_pointer, interval_secs, window, state_file } => {
+            // --price-url beats the config's price_command, which is only read when needed
+            let price_command = match price_url {
+                Some(_) => None,
+                None => match crate::config::load(&cli.config) {
+                    Ok(settings) => settings.price_command,
+                    Err(e) if e.is::<crate::config::ConfigNotFound>() => None,
+                    Err(e) => return Err(e),
+                },
+            };
+            let poll = match (price_url, &price_command) {
+                (Some(url), _) => Some(PollSource::Http(url)),
+                (None, Some(command)) => Some(PollSource::Command { command, symbol: price_symbol }),
+                (None, None) if ws_url.is_some() => None,
+                (None, None) => {
+                    return Err(VectorPlusError::Validation(format!(
+                        "No price source: pass --price-url or --ws-url, or set price_command in {}",
+                        cli.config
+                    )).into());
+                }
+            };
+            watch_volatility(
+                config,
+                PriceSources { poll, ws: ws_url.as_deref(), pointer: price_pointer.as_deref() },
+                *interval_secs,
+                *window,
+                state_file.as_deref(),
+                cli.precision,
             ).await
         }
-        VolatilityCommands::Validate { file } => {
-            validate_volatility_config(file).await
+        VolatilityCommands::Status { config, state_file } => volatility_status(config, state_file),
+        VolatilityCommands::Estimate { prices, model, lambda, periods_per_day, window, output } => {
+            let rolling = window.map(|window| {
+                (window, output.clone().unwrap_or_else(|| "volatility-series.csv".to_string()))
+            });
+            estimate_volatility(prices, *model, *lambda, *periods_per_day, rolling, cli.assume_yes).await
+        }
+        VolatilityCommands::Backtest {
+            prices,
+            volatility,
+            config,
+            amount,
+            window,
+            periods_per_day,
+            since,
+            until,
+            start,
+            end,
+            ..
+        } => {
+            let range = match (since, until) {
+                (None, None) => BacktestRange::Rows(*start, *end),
+                _ => BacktestRange::Time(*since, *until),
+            };
+            let source = match (prices, volatility) {
+                (_, Some(file)) => BacktestSource::Series(file),
+                (Some(file), None) => BacktestSource::Prices { file, window: *window, periods_per_day: *periods_per_day },
+                (None, None) => unreachable!("clap requires --prices or --volatility"),
+            };
+            backtest_volatility(source, config, *amount, range, cli.precision).await
+        }
+        VolatilityCommands::Stress { config, scenarios, amount, json, format } => {
+            stress_test(config, scenarios, *amount, OutputFormat::resolve(*format, *json, false), cli.precision)
         }
-        VolatilityCommands::Calculate { amount, config } => {
-            calculate_volatility_adjustment(*amount, config).await
+        VolatilityCommands::Heatmap { config, amounts, volatilities, factor, json } => {
+            heatmap(config, amounts, volatilities, *factor, *json, cli.precision)
+        }
+        VolatilityCommands::Var {
+            prices,
+            confidence,
+            horizon_days,
+            notional,
+            model,
+            lambda,
+            periods_per_day,
+            window,
+            json,
+        } => {
+            let inputs = VarInputs {
+                file: prices,
+                model: *model,
+                lambda: *lambda,
+                periods_per_day: *periods_per_day,
+                window: *window,
+                confidence: *confidence,
+                horizon_days: *horizon_days,
+                notional: *notional,
+            };
+            value_at_risk(&inputs, *json, cli.precision)
         }
     }
 }
 
-async fn create_volatility_config(
-    baseline_volatility: u64,
-    current_volatility: u64,
-    max_execution_size: f64,
-    min_execution_size: f64,
-    conservative_mode: bool,
-    output: &str,
-) -> Result<()> {
-    let config = VolatilityConfig {
-        baseline_volatility,
-        current_volatility,
-        max_execution_size: format!("{:.18}", max_execution_size * 1e18),
-        min_execution_size: format!("{:.18}", min_execution_size * 1e18),
-        volatility_threshold: baseline_volatility * 2,
-        conservative_mode,
-        emergency_threshold: baseline_volatility * 4,
-        last_update_time: chrono::Utc::now().timestamp() as u64,
-    };
-
-    let json = serde_json::to_string_pretty(&config)?;
-    fs::write(output, json)?;
-
-    println!("{} {}", "✅ Created volatility config:".green(), output.cyan());
-    println!("📊 Baseline volatility: {}bps", baseline_volatility.to_string().yellow());
-    println!("📈 Current volatility: {}bps", current_volatility.to_string().yellow());
-    println!("💰 Max execution: {} ETH", max_execution_size.to_string().yellow());
-    println!("🔒 Conservative mode: {}", if conservative_mode { "ON".green() } else { "OFF".red() });
-    println!();
-    println!("{}", "🚀 Next steps:".bold());
-    println!("  {} vector-plus volatility validate {}", "•".blue(), output);
-    println!("  {} vector-plus volatility calculate --amount 1.0 --config {}", "•".blue(), output);
-
-    Ok(())
+/// Market regime implied by the current volatility relative to the config's thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub(crate) enum Regime {
+    Low,
+    Normal,
+    Elevated,
+    Emergency,
 }
 
-async fn validate_volatility_config(file: &str) -> Result<()> {
-    println!("{} {}", "🔍 Validating volatility config:".cyan(), file.yellow());
-    
-    let content = fs::read_to_string(file)
-        .map_err(|_| eyre::eyre!("Could not read file: {}", file))?;
-    
-    let config: VolatilityConfig = serde_json::from_str(&content)
-        .map_err(|e| eyre::eyre!("Invalid JSON format: {}", e))?;
-    
-    let mut warnings = Vec::new();
-    let mut errors = Vec::new();
+impl Regime {
+    pub(crate) fn classify(config: &VolatilityConfig) -> Self {
+        if config.current_volatility > config.emergency_threshold {
+            Regime::Emergency
+        } else if config.current_volatility > config.volatility_threshold {
+            Regime::Elevated
+        } else if config.current_volatility <= config.baseline_volatility {
+            Regime::Low
+        } else {
+            Regime::Normal
+        }
+    }
     
-    // Validation checks
-    if config.current_volatility > config.baseline_volatility * 3 {
-        warnings.push("⚠️  Current volatility is >3x baseline - consider conservative mode".yellow());
+    /// Classifies like [`Regime::classify`], except that after ELEVATED or
+    /// EMERGENCY the regime stays ELEVATED until volatility drops below the
+    /// config's exit threshold. Returns the regime and whether hysteresis is
+    /// what holds it there.
+    pub(crate) fn classify_from(config: &VolatilityConfig, previous: Option<Regime>) -> (Self, bool) {
+        let regime = Regime::classify(config);
+        let was_elevated = matches!(previous, Some(Regime::Elevated | Regime::Emergency));
+        if was_elevated
+            && matches!(regime, Regime::Low | Regime::Normal)
+            && config.current_volatility > config.exit_threshold()
+        {
+            (Regime::Elevated, true)
+        } else {
+            (regime, false)
+        }
     }
     
-    if config.current_volatility > config.emergency_threshold {
-        errors.push("🚨 Current volatility exceeds emergency threshold!".red());
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Regime::Low => "LOW",
+            Regime::Normal => "NORMAL",
+            Regime::Elevated => "ELEVATED",
+            Regime::Emergency => "EMERGENCY",
+        }
     }
     
-    let max_size: f64 = config.max_execution_size.parse().unwrap_or(0.0);
-    let min_size: f64 = config.min_execution_size.parse().unwrap_or(0.0);
+    pub(crate) fn label(&self) -> ColoredString {
+        match self {
+            Regime::Low => self.name().green(),
+            Regime::Normal => self.name().normal(),
+            Regime::Elevated => self.name().yellow(),
+            Regime::Emergency => self.name().red().bold(),
+        }
+    }
+}
+
+/// Price history persisted by `volatility watch` so restarts keep their window.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchState {
+    prices: Vec<f64>,
+    last_volatility: Option<Bps>,
+    last_regime: Option<Regime>,
+    updated_at: u64,
+}
+
+impl VolatilityConfig {
+    /// Min and max execution sizes, rejecting malformed fields.
+    pub(crate) fn checked_execution_limits(&self) -> std::result::Result<(Amount, Amount), InvalidWeiField> {
+        Ok((
+            Amount::from_wei(parse_wei_field("min_execution_size", &self.min_execution_size)?),
+            Amount::from_wei(parse_wei_field("max_execution_size", &self.max_execution_size)?),
+        ))
+    }
     
-    if max_size <= min_size {
-        errors.push("❌ Max execution size must be > min execution size".red());
+    /// Min and max execution sizes. Fields are validated on load, so this
+    /// only sees zeros for configs built in code with bad values.
+    pub(crate) fn execution_limits(&self) -> (Amount, Amount) {
+        self.checked_execution_limits().unwrap_or_default()
     }
     
-    let age = chrono::Utc::now().timestamp() as u64 - config.last_update_time;
-    if age > 3600 {
-        warnings.push("⚠️  Configuration is more than 1 hour old".yellow());
+    /// Volatility ELEVATED is left below: the threshold less the hysteresis margin.
+    pub(crate) fn exit_threshold(&self) -> Bps {
+        self.volatility_threshold - self.hysteresis_margin.min(self.volatility_threshold)
     }
     
-    // Print results
-    if errors.is_empty() && warnings.is_empty() {
-        println!("{}", "✅ Volatility configuration is valid!".green());
-        println!("📊 Configuration summary:");
-        println!("  • Baseline: {}bps", config.baseline_volatility);
-        println!("  • Current: {}bps", config.current_volatility);
-        println!("  • Threshold: {}bps", config.volatility_threshold);
-        println!("  • Emergency: {}bps", config.emergency_threshold);
-    } else {
-        for warning in &warnings {
-            println!("{}", warning);
-        }
-        for error in &errors {
-            println!("{}", error);
+    /// Builds a config with thresholds derived from the baseline.
+    pub(crate) fn new(
+        baseline_volatility: Bps,
+        current_volatility: Bps,
+        max_execution_size: Amount,
+        min_execution_size: Amount,
+        conservative_mode: bool,
+    ) -> Self {
+        Self {
+            baseline_volatility,
+            current_volatility,
+            max_execution_size: max_execution_size.wei().to_string(),
+            min_execution_size: min_execution_size.wei().to_string(),
+            volatility_threshold: baseline_volatility * 2,
+            conservative_mode,
+            conservative_reduction_pct: DEFAULT_CONSERVATIVE_REDUCTION_PCT,
+            max_boost_pct: DEFAULT_MAX_BOOST_PCT,
+            max_reduction_pct: DEFAULT_MAX_REDUCTION_PCT,
+            emergency_threshold: baseline_volatility * 4,
+            hysteresis_margin: Bps::ZERO,
+            last_update_time: chrono::Utc::now().timestamp() as u64,
         }
-        if !errors.is_empty() {
-            return Err(eyre::eyre!("Configuration validation failed"));
+    }
+}
+
+/// Inputs to `volatility create-config`, collected from flags or prompts.
+pub(crate) struct VolatilityConfigInputs {
+    pub(crate) baseline_volatility: Bps,
+    pub(crate) current_volatility: Bps,
+    pub(crate) max_execution_size: Amount,
+    pub(crate) min_execution_size: Amount,
+    /// Exact wei amounts that take the place of the ETH sizes when set
+    pub(crate) max_execution_size_wei: Option<String>,
+    pub(crate) min_execution_size_wei: Option<String>,
+    pub(crate) conservative_mode: bool,
+    pub(crate) conservative_reduction_pct: u64,
+    pub(crate) max_boost_pct: u64,
+    pub(crate) max_reduction_pct: u64,
+    pub(crate) hysteresis_margin: Bps,
+    pub(crate) output: String,
+}
+
+impl Default for VolatilityConfigInputs {
+    fn default() -> Self {
+        Self {
+            baseline_volatility: DEFAULT_BASELINE_VOLATILITY,
+            current_volatility: DEFAULT_CURRENT_VOLATILITY,
+            max_execution_size: DEFAULT_MAX_EXECUTION_SIZE,
+            min_execution_size: DEFAULT_MIN_EXECUTION_SIZE,
+            max_execution_size_wei: None,
+            min_execution_size_wei: None,
+            conservative_mode: false,
+            conservative_reduction_pct: DEFAULT_CONSERVATIVE_REDUCTION_PCT,
+            max_boost_pct: DEFAULT_MAX_BOOST_PCT,
+            max_reduction_pct: DEFAULT_MAX_REDUCTION_PCT,
+            hysteresis_margin: Bps::ZERO,
+            output: DEFAULT_OUTPUT.to_string(),
         }
     }
-    
-    Ok(())
 }
 
-async fn calculate_volatility_adjustment(amount: f64, config_file: &str) -> Result<()> {
-    let content = fs::read_to_string(config_file)?;
-    let config: VolatilityConfig = serde_json::from_str(&content)?;
+impl VolatilityConfigInputs {
+    /// Prompts for every field, using the current values as defaults, and
+    /// confirms overwriting an existing output file.
+    pub(crate) fn prompt(self, assume_yes: bool) -> Result<Self> {
+        let theme = ColorfulTheme::default();
+        
+        let baseline_volatility: Bps = Input::with_theme(&theme)
+            .with_prompt("Baseline volatility (basis points)")
+            .default(self.baseline_volatility)
+            .interact()?;
+        
+        let current_volatility: Bps = Input::with_theme(&theme)
+            .with_prompt("Current volatility (basis points)")
+            .default(self.current_volatility)
+            .interact()?;
+        
+        // Sizes already given exactly in wei are kept rather than prompted in ETH
+        let max_execution_size: Amount = match self.max_execution_size_wei {
+            Some(_) => self.max_execution_size,
+            None => Input::with_theme(&theme)
+                .with_prompt("Maximum execution size (ETH)")
+                .default(self.max_execution_size)
+                .interact()?,
+        };
+        
+        let min_execution_size: Amount = match self.min_execution_size_wei {
+            Some(_) => self.min_execution_size,
+            None => Input::with_theme(&theme)
+                .with_prompt("Minimum execution size (ETH)")
+                .default(self.min_execution_size)
+                .interact()?,
+        };
+        
+        let conservative_mode = Confirm::with_theme(&theme)
+            .with_prompt("Enable conservative mode?")
+            .default(self.conservative_mode)
+            .interact()?;
+        
+        let conservative_reduction_pct: u64 = if conservative_mode {
+            Input::with_theme(&theme)
+                .with_prompt("Conservative reduction (%)")
+                .default(self.conservative_reduction_pct)
+                .interact()?
+        } else {
+            self.conservative_reduction_pct
+        };
+        
+        let output: String = Input::with_theme(&theme)
+            .with_prompt("Output file")
+            .default(self.output)
+            .interact_text()?;
+        confirm_overwrite(&output, assume_yes)?;
+        
+        println!();
+        Ok(Self {
+            baseline_volatility,
+            current_volatility,
+            max_execution_size,
+            min_execution_size,
+            max_execution_size_wei: self.max_execution_size_wei,
+            min_execution_size_wei: self.min_execution_size_wei,
+            conservative_mode,
+            conservative_reduction_pct,
+            max_boost_pct: self.max_boost_pct,
+            max_reduction_pct: self.max_reduction_pct,
+            hysteresis_margin: self.hysteresis_margin,
+            output,
+        })
+    }
     
-    println!("{} {} ETH", "🧮 Calculating volatility adjustment for:".cyan(), amount.to_string().yellow());
+    /// Min and max execution sizes as wei strings: the wei inputs verbatim,
+    /// otherwise the ETH inputs converted.
+    fn execution_sizes_wei(&self) -> (String, String) {
+        let min = self.min_execution_size_wei.clone()
+            .unwrap_or_else(|| self.min_execution_size.wei().to_string());
+        let max = self.max_execution_size_wei.clone()
+            .unwrap_or_else(|| self.max_execution_size.wei().to_string());
+        (min, max)
+    }
     
-    let adjustment_factor = if config.current_volatility <= config.baseline_volatility {
-        // Low volatility: increase amount
-        let boost = (config.baseline_volatility - config.current_volatility) * 50 / config.baseline_volatility;
-        100 + std::cmp::min(boost, 50)
-    } else if config.current_volatility > config.volatility_threshold {
-        // High volatility: decrease amount
-        let reduction = (config.current_volatility - config.baseline_volatility) * 50 / config.baseline_volatility;
-        let reduction = std::cmp::min(reduction, 50);
-        100 - reduction
-    } else {
-        // Normal volatility
-        if config.conservative_mode { 90 } else { 100 }
+    fn validate(&self) -> Result<()> {
+        if self.baseline_volatility == Bps::ZERO {
+            return Err(VectorPlusError::Validation("Baseline volatility must be greater than zero".to_string()).into());
+        }
+        for (flag, value) in [
+            ("--max-execution-size-wei", &self.max_execution_size_wei),
+            ("--min-execution-size-wei", &self.min_execution_size_wei),
+        ] {
+            if let Some(value) = value {
+                if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(VectorPlusError::Validation(format!("{} must be a non-negative integer, got: {}", flag, value)).into());
+                }
+            }
+        }
+        
+        let (min, max) = self.execution_sizes_wei();
+        let is_zero = |wei: &str| wei.bytes().all(|b| b == b'0');
+        if is_zero(&min) {
+            return Err(VectorPlusError::Validation("Min execution size must be at least 1 wei".to_string()).into());
+        }
+        let min_wei = parse_wei_field("min_execution_size", &min).map_err(|e| VectorPlusError::Validation(e.to_string()))?;
+        let max_wei = match is_zero(&max) {
+            true => 0,
+            false => parse_wei_field("max_execution_size", &max).map_err(|e| VectorPlusError::Validation(e.to_string()))?,
+        };
+        if max_wei <= min_wei {
+            return Err(VectorPlusError::Validation("Max execution size must be > min execution size".to_string()).into());
+        }
+        if self.conservative_reduction_pct > 100 {
+            return Err(VectorPlusError::Validation("Conservative reduction must be between 0 and 100%".to_string()).into());
+        }
+        if self.max_boost_pct > 100 {
+            return Err(VectorPlusError::Validation("Max boost must be between 0 and 100%".to_string()).into());
+        }
+        if self.max_reduction_pct > 100 {
+            return Err(VectorPlusError::Validation("Max reduction must be between 0 and 100%".to_string()).into());
+        }
+        // The threshold is 2x baseline, so the exit threshold stays above baseline
+        if self.hysteresis_margin >= self.baseline_volatility {
+            return Err(VectorPlusError::Validation(format!(
+                "Hysteresis margin must be below the baseline volatility ({}bps)",
+                self.baseline_volatility
+            )).into());
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn create_volatility_config(inputs: &VolatilityConfigInputs, precision: u8, verbose: bool) -> Result<()> {
+    inputs.validate()?;
+    
+    let VolatilityConfigInputs {
+        baseline_volatility,
+        current_volatility,
+        max_execution_size,
+        min_execution_size,
+        conservative_mode,
+        conservative_reduction_pct,
+        max_boost_pct,
+        max_reduction_pct,
+        hysteresis_margin,
+        ref output,
+        ..
+    } = *inputs;
+    
+    let mut config = VolatilityConfig::new(
+        baseline_volatility,
+        current_volatility,
+        max_execution_size,
+        min_execution_size,
+        conservative_mode,
+    );
+    (config.min_execution_size, config.max_execution_size) = inputs.execution_sizes_wei();
+    config.conservative_reduction_pct = conservative_reduction_pct;
+    config.max_boost_pct = max_boost_pct;
+    config.max_reduction_pct = max_reduction_pct;
+    config.hysteresis_margin = hysteresis_margin;
+
+    verbose!(
+        verbose,
+        "execution sizes: min {} wei, max {} wei; thresholds {}bps / {}bps",
+        config.min_execution_size, config.max_execution_size, config.volatility_threshold, config.emergency_threshold
+    );
+
+    write_created_config(output, &config)?;
+    if output != STDOUT_PATH {
+        verbose!(verbose, "wrote {}", fs::canonicalize(output)?.display());
+    }
+
+    status!("{} {}", "✅ Created volatility config:".green(), output_name(output).cyan());
+    status!("📊 Baseline volatility: {}bps", baseline_volatility.to_string().yellow());
+    status!("📈 Current volatility: {}bps", current_volatility.to_string().yellow());
+    status!("💰 Max execution: {} ETH", config.execution_limits().1.format_eth(precision).yellow());
+    if conservative_mode {
+        status!("🔒 Conservative mode: {} (-{}%)", "ON".green(), conservative_reduction_pct);
+    } else {
+        status!("🔒 Conservative mode: {}", "OFF".red());
+    }
+    if output != STDOUT_PATH {
+        status!();
+        status!("{}", "🚀 Next steps:".bold());
+        status!("  {} vector-plus volatility validate {}", "•".blue(), output);
+        status!("  {} vector-plus volatility calculate --amount 1.0 --config {}", "•".blue(), output);
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn validate_volatility_config(file: &str, strict: bool, time_format: TimeFormat) -> Result<Findings> {
+    println!("{} {}", "🔍 Validating volatility config:".cyan(), file.yellow());
+    
+    let config: VolatilityConfig = read_config(file)?;
+    
+    let (mut warnings, mut errors) = check_volatility_config(&config);
+    errors.extend(wei_format_errors(&config));
+    add_key_findings::<VolatilityConfig>(file, strict, &mut warnings, &mut errors);
+    
+    // Print results
+    if errors.is_empty() && warnings.is_empty() {
+        println!("{}", "✅ Volatility configuration is valid!".green());
+        println!("📊 Configuration summary:");
+        println!("  • Baseline: {}bps", config.baseline_volatility);
+        println!("  • Current: {}bps", config.current_volatility);
+        println!("  • Threshold: {}bps", config.volatility_threshold);
+        println!("  • Emergency: {}bps", config.emergency_threshold);
+        println!("  • Adjustment caps: +{}% / -{}%", config.max_boost_pct, config.max_reduction_pct);
+        if config.conservative_mode {
+            println!("  • Conservative mode: ON (-{}% in normal volatility)", config.conservative_reduction_pct);
+        } else {
+            println!("  • Conservative mode: OFF");
+        }
+        println!("  • Last updated: {}", format_time(config.last_update_time, time_format));
+    } else {
+        for warning in &warnings {
+            println!("{}", warning.yellow());
+        }
+        for error in &errors {
+            println!("{}", error.red());
+        }
+    }
+    
+    Ok(Findings::of(&warnings, &errors))
+}
+
+/// Volatility below this is implausible for a volatile asset; 5bps was likely meant as 5%.
+const MIN_PLAUSIBLE_VOLATILITY: Bps = Bps::from_bps(10);
+/// Volatility above this (500%) likely carries a unit mistake.
+const MAX_PLAUSIBLE_VOLATILITY: Bps = Bps::from_bps(50_000);
+
+/// Volatility fields whose size suggests percent and bps were mixed up.
+/// Bps inputs are bare numbers, so `35` meaning 35% would otherwise size
+/// silently wrong. Zero is left alone as a deliberate value.
+fn unit_warnings(config: &VolatilityConfig) -> Vec<String> {
+    let fields = [
+        ("baseline_volatility", config.baseline_volatility),
+        ("current_volatility", config.current_volatility),
+        ("volatility_threshold", config.volatility_threshold),
+        ("emergency_threshold", config.emergency_threshold),
+    ];
+    fields
+        .into_iter()
+        .filter_map(|(field, value)| {
+            let pct = value.as_f64() / 100.0;
+            if value > Bps::ZERO && value < MIN_PLAUSIBLE_VOLATILITY {
+                let meant = Bps::from_f64(value.as_f64() * 100.0).unwrap_or(value);
+                Some(format!("{} is {}bps ({}%); if you meant {}%, enter {}", field, value, pct, value, meant))
+            } else if value > MAX_PLAUSIBLE_VOLATILITY {
+                Some(format!("{} is {}bps ({}%); volatility is in bps, where 1% is 100", field, value, pct))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Prints [`unit_warnings`] to stderr, keeping `--json` output clean.
+fn warn_on_units(config_file: &str, config: &VolatilityConfig) {
+    let warnings = unit_warnings(config);
+    for warning in &warnings {
+        eprintln!("{} {}: {}", "⚠️ ".yellow(), config_file, warning);
+    }
+    if !warnings.is_empty() {
+        eprintln!("{}", "   Check the units, or pass --no-unit-warnings if the values are intended".dimmed());
+    }
+}
+
+/// Wei amounts written with a decimal point by older versions. They still
+/// load, and are saved without it, but integer parsers downstream reject
+/// them, so `validate` fails until the file is fixed.
+pub(crate) fn wei_format_errors(config: &VolatilityConfig) -> Vec<String> {
+    [("max_execution_size", &config.max_execution_size), ("min_execution_size", &config.min_execution_size)]
+        .into_iter()
+        .filter(|(_, value)| value.contains('.'))
+        .map(|(field, value)| format!(
+            "❌ {} {:?} has a decimal point; wei amounts must be integers, e.g. {:?}",
+            field, value, integer_wei(value)
+        ))
+        .collect()
+}
+
+/// Runs all validation checks without printing, returning `(warnings, errors)`.
+pub(crate) fn check_volatility_config(config: &VolatilityConfig) -> (Vec<String>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+    
+    if config.current_volatility > config.baseline_volatility * 3 {
+        warnings.push("⚠️  Current volatility is >3x baseline - consider conservative mode".to_string());
+    }
+    
+    if config.current_volatility > config.emergency_threshold {
+        errors.push("🚨 Current volatility exceeds emergency threshold!".to_string());
+    }
+
+    // Regimes and the adjustment factor assume NORMAL, ELEVATED and EMERGENCY in this order
+    if !(config.baseline_volatility < config.volatility_threshold && config.volatility_threshold < config.emergency_threshold) {
+        errors.push(format!(
+            "❌ Thresholds must satisfy baseline < volatility threshold < emergency threshold, got {}bps, {}bps and {}bps",
+            config.baseline_volatility, config.volatility_threshold, config.emergency_threshold
+        ));
+    }
+    
+    if config.conservative_reduction_pct > 100 {
+        errors.push("❌ Conservative reduction must be between 0 and 100%".to_string());
+    }
+    
+    if config.max_boost_pct > 100 {
+        errors.push("❌ Max boost must be between 0 and 100%".to_string());
+    } else if config.max_boost_pct > 50 {
+        warnings.push("⚠️  Max boost above 50% has no effect - the low-volatility boost peaks at 50% at zero volatility".to_string());
+    }
+    
+    if config.max_reduction_pct > 100 {
+        errors.push("❌ Max reduction must be between 0 and 100%".to_string());
+    }
+    
+    if config.hysteresis_margin >= config.volatility_threshold && config.hysteresis_margin > Bps::ZERO {
+        errors.push("❌ Hysteresis margin must be below the volatility threshold".to_string());
+    } else if config.hysteresis_margin > Bps::ZERO && config.exit_threshold() <= config.baseline_volatility {
+        warnings.push(format!(
+            "⚠️  Exit threshold ({}bps) is at or below baseline - ELEVATED would hold through low volatility",
+            config.exit_threshold()
+        ));
+    }
+    
+    match config.checked_execution_limits() {
+        Ok((min_size, max_size)) if max_size <= min_size => {
+            errors.push("❌ Max execution size must be > min execution size".to_string());
+        }
+        Ok(_) => {}
+        Err(e) => errors.push(format!("❌ Config field {}", e)),
+    }
+    
+    let age = (chrono::Utc::now().timestamp() as u64).saturating_sub(config.last_update_time);
+    if age > 3600 {
+        warnings.push("⚠️  Configuration is more than 1 hour old".to_string());
+    }
+    
+    (warnings, errors)
+}
+
+/// Where a merged field's value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MergeSource {
+    Base,
+    Overlay,
+    /// Set in the overlay, but to the default, so the base value stands
+    OverlayDefault,
+}
+
+/// A merged field's path, value and origin.
+type MergedField = (String, Value, MergeSource);
+
+/// Merges `overlay` over `base` field by field, as `config diff` compares
+/// them. Overlay fields win when they differ from the `create-config`
+/// defaults; values are normalized first, so `"6.5bps"` and `6.5` compare
+/// equal. `last_update_time` takes the newer of the two.
+fn merge_volatility_values(base: &Value, overlay: &Value) -> Result<(Value, Vec<MergedField>)> {
+    let Value::Object(overlay) = overlay else {
+        return Err(eyre::eyre!("Overlay must be a JSON object of config fields"));
+    };
+    let defaults = serde_json::to_value(VolatilityConfig::new(
+        DEFAULT_BASELINE_VOLATILITY,
+        DEFAULT_CURRENT_VOLATILITY,
+        DEFAULT_MAX_EXECUTION_SIZE,
+        DEFAULT_MIN_EXECUTION_SIZE,
+        false,
+    ))?;
+    // Round-trips one overlay field through a full config to get its canonical form
+    let normalize = |field: &str, value: &Value| -> Result<Value> {
+        let mut candidate = defaults.clone();
+        candidate[field] = value.clone();
+        let config: VolatilityConfig = serde_json::from_value(candidate)
+            .map_err(|e| eyre::eyre!("Overlay field {}: {}", field, e))?;
+        Ok(serde_json::to_value(config)?[field].clone())
     };
     
-    let adjusted_amount = (amount * adjustment_factor as f64) / 100.0;
-    let max_eth = config.max_execution_size.parse::<f64>().unwrap_or(0.0) / 1e18;
-    let min_eth = config.min_execution_size.parse::<f64>().unwrap_or(0.0) / 1e18;
+    let base: VolatilityConfig = serde_json::from_value(base.clone())?;
+    let mut fields = BTreeMap::new();
+    let mut default_fields = BTreeMap::new();
+    crate::commands::config::flatten("", &serde_json::to_value(&base)?, &mut fields);
+    crate::commands::config::flatten("", &defaults, &mut default_fields);
     
-    let final_amount = adjusted_amount.max(min_eth).min(max_eth);
+    let known = volatility_config_fields()?;
+    if let Some(unknown) = overlay.keys().find(|key| !known.contains(*key)) {
+        return Err(eyre::eyre!("Overlay field {} is not a volatility config field", unknown));
+    }
+    let mut merged = BTreeMap::new();
+    let mut sources = Vec::with_capacity(known.len());
+    for field in &known {
+        let (value, source) = match (overlay.get(field), fields.get(field)) {
+            (None, None) => continue,
+            (None, Some(value)) => (value.clone(), MergeSource::Base),
+            (Some(raw), Some(value)) if field == "last_update_time" => {
+                let overlay_time = normalize(field, raw)?;
+                if overlay_time.as_u64() > value.as_u64() {
+                    (overlay_time, MergeSource::Overlay)
+                } else {
+                    (value.clone(), MergeSource::Base)
+                }
+            }
+            (Some(raw), base_value) => {
+                // Fields left out when they hold their default normalize to null
+                let normalized = normalize(field, raw)?;
+                if normalized.is_null() || default_fields.get(field) == Some(&normalized) {
+                    match base_value {
+                        Some(value) => (value.clone(), MergeSource::OverlayDefault),
+                        None => continue,
+                    }
+                } else {
+                    (normalized, MergeSource::Overlay)
+                }
+            }
+        };
+        merged.insert(field.clone(), value.clone());
+        sources.push((field.clone(), value, source));
+    }
+    // Volatility configs are flat, so the dotted paths are plain keys
+    Ok((Value::Object(merged.into_iter().collect()), sources))
+}
+
+/// Every field a volatility config may set, from its schema rather than a
+/// serialized config, which leaves out fields such as a zero `hysteresis_margin`.
+fn volatility_config_fields() -> Result<std::collections::BTreeSet<String>> {
+    let schema = serde_json::to_value(schemars::schema_for!(VolatilityConfig))?;
+    let properties = schema["properties"].as_object()
+        .ok_or_else(|| eyre::eyre!("Volatility config schema has no properties"))?;
+    Ok(properties.keys().cloned().collect())
+}
+
+fn merge_configs(base_file: &str, overlay_file: &str, out: &str) -> Result<()> {
+    let base: Value = read_config(base_file)?;
+    read_config::<VolatilityConfig>(base_file)?;
+    let overlay: Value = read_config(overlay_file)?;
+    let (merged, sources) = merge_volatility_values(&base, &overlay)
+        .map_err(|e| eyre::eyre!("Could not merge {} over {}: {}", overlay_file, base_file, e))?;
+    let config: VolatilityConfig = serde_json::from_value(merged)?;
     
-    println!("📊 Volatility Analysis:");
-    println!("  • Baseline volatility: {}bps", config.baseline_volatility);
-    println!("  • Current volatility: {}bps", config.current_volatility);
-    println!("  • Adjustment factor: {}%", adjustment_factor);
+    println!("{} {} + {}", "🧩 Merging volatility configs:".cyan(), base_file.yellow(), overlay_file.yellow());
     println!();
-    println!("💰 Execution Amounts:");
-    println!("  • Original amount: {} ETH", amount);
-    println!("  • Adjusted amount: {} ETH", adjusted_amount);
-    println!("  • Final amount: {} ETH", final_amount);
-    println!("  • Min allowed: {} ETH", min_eth);
-    println!("  • Max allowed: {} ETH", max_eth);
-    
-    if final_amount != adjusted_amount {
-        if final_amount == max_eth {
-            println!("{}", "⚠️  Amount capped at maximum limit".yellow());
+    let mut table = Table::new()
+        .column("field", "Field", Align::Left)
+        .column("value", "Value", Align::Right)
+        .column("source", "Source", Align::Left);
+    for (field, value, source) in &sources {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let cells = vec![
+            Cell::text(field.as_str()),
+            Cell::text(value),
+            Cell::text(match source {
+                MergeSource::Base => base_file.to_string(),
+                MergeSource::Overlay => overlay_file.to_string(),
+                MergeSource::OverlayDefault => format!("{} (overlay sets the default)", base_file),
+            }),
+        ];
+        match source {
+            MergeSource::Overlay => table.colored_row(cells, Color::Green),
+            _ => table.row(cells),
+        }
+    }
+    println!("{}", table.render());
+    println!();
+    
+    let (warnings, errors) = check_volatility_config(&config);
+    for warning in &warnings {
+        println!("{}", warning.yellow());
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{}", error.red());
+        }
+        return Err(VectorPlusError::Validation(format!("Merged config is invalid; {} was not written", out)).into());
+    }
+    
+    if let Some(backup) = backup_existing(out)? {
+        println!("  • Kept the previous {} as {}", out, backup);
+    }
+    fs::write(out, serde_json::to_string_pretty(&config)?)?;
+    crate::history::record(out, &config);
+    let overridden = sources.iter().filter(|(_, _, source)| *source == MergeSource::Overlay).count();
+    println!("{} {} ({} of {} fields from the overlay)", "✅ Merged config written:".green(), out, overridden, sources.len());
+    Ok(())
+}
+
+/// Scales `amount` by the adjustment factor, rounding down to the wei as
+/// on-chain, returning `(adjusted, final)` where `final` is clamped to the
+/// config's execution limits.
+pub(crate) fn size_execution(config: &VolatilityConfig, amount: Amount) -> (Amount, Amount) {
+    let adjusted = amount.scale_pct(volatility_adjustment_factor(config));
+    let (min, max) = config.execution_limits();
+    (adjusted, adjusted.max(min).min(max))
+}
+
+/// Quote asset amount for `making` at `price` quote per ETH, multiplied in wei
+/// from the price's shortest decimal and rounded down. `None` for a price that
+/// is not a positive finite number or a product too large to hold.
+pub(crate) fn taking_amount(making: Amount, price: f64) -> Option<Amount> {
+    Amount::from_eth_f64(price).and_then(|price| making.checked_mul(price))
+}
+
+/// Percentage (100 = unchanged) to scale an execution amount by, mirroring
+/// `VolatilityCalculator._applyVolatilityAdjustment` on-chain.
+pub(crate) fn volatility_adjustment_factor(config: &VolatilityConfig) -> u64 {
+    // Same integer math as on-chain, in hundredths of a bp; the ratios, and so
+    // the floors, match whole-bp configs exactly
+    let baseline = config.baseline_volatility.hundredths();
+    let current = config.current_volatility.hundredths();
+    if current <= baseline {
+        // Low volatility: increase amount
+        let boost = (baseline - current) * 50 / baseline;
+        100 + std::cmp::min(boost, config.max_boost_pct)
+    } else if config.current_volatility > config.volatility_threshold {
+        high_volatility_factor(config)
+    } else {
+        // Normal volatility; on-chain conservative mode always cuts 10%, the default here
+        if config.conservative_mode {
+            100u64.saturating_sub(config.conservative_reduction_pct)
         } else {
-            println!("{}", "⚠️  Amount raised to minimum limit".yellow());
+            100
+        }
+    }
+}
+
+/// High volatility: decrease amount, by half the rise over baseline up to the cap.
+fn high_volatility_factor(config: &VolatilityConfig) -> u64 {
+    let baseline = config.baseline_volatility.hundredths();
+    let current = config.current_volatility.hundredths();
+    let reduction = current.saturating_sub(baseline) * 50 / baseline;
+    let reduction = std::cmp::min(reduction, config.max_reduction_pct);
+    100u64.saturating_sub(reduction)
+}
+
+/// [`volatility_adjustment_factor`] in `regime`, which differs only when
+/// hysteresis holds ELEVATED below the threshold: the high-volatility cut
+/// still applies there.
+pub(crate) fn regime_adjustment_factor(config: &VolatilityConfig, regime: Regime) -> u64 {
+    if regime == Regime::Elevated && config.current_volatility <= config.volatility_threshold {
+        high_volatility_factor(config)
+    } else {
+        volatility_adjustment_factor(config)
+    }
+}
+
+/// Which branch of [`volatility_adjustment_factor`] applies and why, as the
+/// `factor` step of `--explain` and the `--verbose` trace.
+fn factor_step(config: &VolatilityConfig) -> DerivationStep {
+    let factor = volatility_adjustment_factor(config);
+    let (baseline, current) = (config.baseline_volatility, config.current_volatility);
+    let (input, note) = if current <= baseline {
+        (
+            format!("min(({} - {}) × 50 / {}, {})", baseline, current, baseline, config.max_boost_pct),
+            format!("current {}bps <= baseline {}bps: low volatility, size boosted", current, baseline),
+        )
+    } else if current > config.volatility_threshold {
+        (
+            format!("min(({} - {}) × 50 / {}, {})", current, baseline, baseline, config.max_reduction_pct),
+            format!("current {}bps > threshold {}bps: high volatility, size cut", current, config.volatility_threshold),
+        )
+    } else if config.conservative_mode {
+        (
+            format!("100 - {}", config.conservative_reduction_pct),
+            format!("baseline < current {}bps <= threshold {}bps: normal volatility, conservative mode", current, config.volatility_threshold),
+        )
+    } else {
+        (
+            "100".to_string(),
+            format!("baseline < current {}bps <= threshold {}bps: normal volatility", current, config.volatility_threshold),
+        )
+    };
+    DerivationStep { stage: "factor", input, output: format!("{}%", factor), note }
+}
+
+/// Quote side of the order derived by `volatility calculate`.
+struct OrderQuote<'a> {
+    /// Quote asset price per ETH, when the taking amount should be derived
+    price: Option<f64>,
+    decimals: u32,
+    symbol: &'a str,
+}
+
+/// `--cooldown-secs` settings for `volatility calculate`.
+struct Cooldown<'a> {
+    secs: u64,
+    state_file: &'a str,
+}
+
+/// Limits `volatility calculate` applies after the execution limits.
+struct SizingConstraints<'a> {
+    cooldown: Option<Cooldown<'a>>,
+    slippage: Option<SlippageBudget>,
+}
+
+/// Flags for sizing `volatility calculate` to a slippage budget.
+#[derive(Args)]
+pub struct SlippageArgs {
+    /// Largest acceptable price impact in bps; caps the size using the --impact-coeff model
+    #[arg(long, value_name = "BPS", conflicts_with_all = ["portfolio", "range"])]
+    target_slippage: Option<Bps>,
+
+    /// Price impact coefficient in basis points per ETH executed in one fill, for --target-slippage
+    #[arg(long, default_value = "1.0", requires = "target_slippage", value_parser = crate::utils::parse_non_negative_f64)]
+    impact_coeff: f64,
+}
+
+impl SlippageArgs {
+    fn budget(&self) -> Option<SlippageBudget> {
+        self.target_slippage.map(|target| SlippageBudget { target, impact_coeff: self.impact_coeff })
+    }
+}
+
+/// `--target-slippage` settings: the size may move the price at most
+/// `target` under the linear impact model shared with `twap simulate`.
+#[derive(Debug, Clone, Copy)]
+struct SlippageBudget {
+    target: Bps,
+    impact_coeff: f64,
+}
+
+impl SlippageBudget {
+    /// The largest size within the budget, or `None` when a zero coefficient
+    /// never moves the price. Rounds down to the wei so the cap is never exceeded.
+    fn max_amount(&self) -> Option<Amount> {
+        super::twap::max_size_for_impact(self.target.as_f64(), self.impact_coeff)
+            .map(|eth| Amount::from_eth_f64(eth).unwrap_or(Amount::from_wei(u128::MAX)))
+    }
+
+    fn impact_bps(&self, amount: Amount) -> f64 {
+        super::twap::impact_bps(amount.as_eth_f64(), self.impact_coeff)
+    }
+}
+
+/// Last size emitted by `volatility calculate --cooldown-secs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SizingState {
+    #[serde(deserialize_with = "deserialize_last_amount")]
+    last_amount: Amount,
+    /// When `last_amount` last changed; increases wait for the cooldown from here
+    changed_at: u64,
+}
+
+/// Reads `last_amount` as wei, or as a JSON number of ETH in state files
+/// written before amounts were exact.
+fn deserialize_last_amount<'de, D>(deserializer: D) -> std::result::Result<Amount, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Wei(Amount),
+        Eth(f64),
+    }
+
+    match Raw::deserialize(deserializer)? {
+        Raw::Wei(amount) => Ok(amount),
+        Raw::Eth(eth) => Amount::from_eth_f64(eth)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid last_amount: {}", eth))),
+    }
+}
+
+/// `--oracle-address` settings for `volatility calculate`.
+struct OracleFeed<'a> {
+    address: &'a str,
+    state_file: &'a str,
+    max_age_secs: u64,
+}
+
+/// One answer from a Chainlink-style feed's `latestRoundData()`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct OracleRound {
+    round_id: u128,
+    price: f64,
+    updated_at: u64,
+}
+
+/// Recent oracle rounds kept by `volatility calculate --oracle-address`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OracleState {
+    address: String,
+    rounds: Vec<OracleRound>,
+}
+
+/// What the oracle contributed to a calculation.
+struct OracleReport {
+    round: OracleRound,
+    age_secs: u64,
+    history: usize,
+    /// EWMA estimate over the history, once it holds 3 rounds
+    volatility: Option<Bps>,
+}
+
+/// Reads the latest round of the feed at `address`, rejecting stale or
+/// incomplete rounds and non-positive answers.
+async fn read_oracle(rpc: &crate::rpc::RpcClient, address: &str, now: u64, max_age_secs: u64) -> Result<OracleRound> {
+    let decimals = rpc.eth_call(address, &selector("decimals()")).await?;
+    let decimals = decimals.get(..32).map(|word| word[31] as usize)
+        .ok_or_else(|| eyre::eyre!("{} did not answer decimals(); is it a price feed?", address))?;
+    let data = rpc.eth_call(address, &selector("latestRoundData()")).await?;
+    decode_round(&data, decimals, now, max_age_secs).map_err(|e| eyre::eyre!("Oracle {}: {}", address, e))
+}
+
+/// Decodes `(uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)`.
+fn decode_round(data: &[u8], decimals: usize, now: u64, max_age_secs: u64) -> Result<OracleRound> {
+    if data.len() < 5 * 32 {
+        return Err(eyre::eyre!("latestRoundData() returned {} bytes, expected 160", data.len()));
+    }
+    let word = |i: usize| -> [u8; 32] { data[i * 32..(i + 1) * 32].try_into().expect("32-byte word") };
+    let low_u128 = |w: [u8; 32]| u128::from_be_bytes(w[16..].try_into().expect("16 bytes"));
+    let (round_id, answer, updated_at, answered_in_round) = (low_u128(word(0)), word(1), low_u128(word(3)), low_u128(word(4)));
+
+    if answer[0] & 0x80 != 0 || answer.iter().all(|b| *b == 0) {
+        return Err(eyre::eyre!("round {} has a non-positive answer", round_id));
+    }
+    if updated_at == 0 {
+        return Err(eyre::eyre!("round {} is incomplete", round_id));
+    }
+    if answered_in_round < round_id {
+        return Err(eyre::eyre!("round {} carries a stale answer from round {}", round_id, answered_in_round));
+    }
+    let updated_at = updated_at as u64;
+    let age = now.saturating_sub(updated_at);
+    if age > max_age_secs {
+        return Err(eyre::eyre!(
+            "latest round is {}s old, beyond --max-oracle-age-secs {}; the feed may be stale",
+            age, max_age_secs
+        ));
+    }
+    let price: f64 = format_units(&crate::eip712::format_uint256(&answer), decimals)?.parse()?;
+    Ok(OracleRound { round_id, price, updated_at })
+}
+
+/// Adds `round` to the history unless it is already the latest, keeping the
+/// last `ORACLE_HISTORY` rounds, and estimates volatility once there are 3.
+fn record_round(state: &mut OracleState, round: OracleRound) -> Option<Bps> {
+    if state.rounds.last().map(|last| last.round_id) != Some(round.round_id) {
+        state.rounds.push(round);
+    }
+    if state.rounds.len() > ORACLE_HISTORY {
+        let excess = state.rounds.len() - ORACLE_HISTORY;
+        state.rounds.drain(..excess);
+    }
+    if state.rounds.len() < 3 {
+        return None;
+    }
+    // Scale by the feed's own update rate
+    let span = state.rounds[state.rounds.len() - 1].updated_at.saturating_sub(state.rounds[0].updated_at);
+    let mean_gap = (span as f64 / (state.rounds.len() - 1) as f64).max(1.0);
+    let series = PriceSeries {
+        close: state.rounds.iter().map(|round| round.price).collect(),
+        high: None,
+        low: None,
+        timestamps: None,
+        periods_per_day: 86_400.0 / mean_gap,
+    };
+    EwmaModel { lambda: ORACLE_EWMA_LAMBDA }.estimate(&series).ok()
+}
+
+async fn apply_oracle(feed: &OracleFeed<'_>, config: &mut VolatilityConfig, cli: &crate::Cli) -> Result<OracleReport> {
+    let rpc = crate::rpc::RpcClient::from_cli(cli, "volatility calculate --oracle-address")?;
+    let now = chrono::Utc::now().timestamp() as u64;
+    let round = read_oracle(&rpc, feed.address, now, feed.max_age_secs).await?;
+    verbose!(cli.verbose, "oracle {} round {}: {} (updated {})", feed.address, round.round_id, round.price, round.updated_at);
+
+    let mut state = match Path::new(feed.state_file).exists() {
+        true => crate::utils::read_json_file::<OracleState>(feed.state_file)
+            .map_err(|e| eyre::eyre!("Could not read state file {}: {}", feed.state_file, e))?,
+        false => OracleState::default(),
+    };
+    // A different feed's prices would make a meaningless series
+    if !state.address.eq_ignore_ascii_case(feed.address) {
+        state = OracleState { address: feed.address.to_string(), rounds: Vec::new() };
+    }
+    let volatility = record_round(&mut state, round);
+    write_json_file_atomic(feed.state_file, &state)?;
+    verbose!(cli.verbose, "wrote {} oracle rounds to {}", state.rounds.len(), feed.state_file);
+
+    if let Some(volatility) = volatility {
+        config.current_volatility = volatility;
+    }
+    Ok(OracleReport { round, age_secs: now.saturating_sub(round.updated_at), history: state.rounds.len(), volatility })
+}
+
+/// Outcome of applying a cooldown to a freshly computed size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CooledSize {
+    amount: Amount,
+    /// True when the size was held back from increasing
+    constrained: bool,
+}
+
+/// Holds `amount` at the previous size if it would increase within
+/// `cooldown_secs` of the last change. Decreases always pass through; the
+/// held size is still kept at or above `min_amount`.
+fn apply_cooldown(amount: Amount, min_amount: Amount, previous: Option<&SizingState>, now: u64, cooldown_secs: u64) -> CooledSize {
+    match previous {
+        Some(state) if amount > state.last_amount && now.saturating_sub(state.changed_at) < cooldown_secs => {
+            let held = state.last_amount.max(min_amount).min(amount);
+            CooledSize { amount: held, constrained: held < amount }
+        }
+        _ => CooledSize { amount, constrained: false },
+    }
+}
+
+async fn calculate_volatility_adjustment(
+    base: BaseAmount,
+    config_file: &str,
+    quote: &OrderQuote<'_>,
+    constraints: &SizingConstraints<'_>,
+    oracle: Option<&OracleFeed<'_>>,
+    display: CalculationDisplay<'_>,
+    cli: &crate::Cli,
+) -> Result<()> {
+    let precision = cli.precision;
+    let mut config: VolatilityConfig = read_config(config_file)?;
+    verbose!(cli.verbose, "loaded {}", config_file);
+    if display.unit_warnings {
+        warn_on_units(config_file, &config);
+    }
+    let oracle_report = match oracle {
+        Some(feed) => Some(apply_oracle(feed, &mut config, cli).await?),
+        None => None,
+    };
+    // The oracle price quotes the order unless --price is given
+    let price = quote.price.or(oracle_report.as_ref().map(|report| report.round.price));
+    verbose!(
+        cli.verbose,
+        "baseline {}bps, current {}bps, threshold {}bps, emergency {}bps, conservative {}",
+        config.baseline_volatility, config.current_volatility, config.volatility_threshold,
+        config.emergency_threshold, config.conservative_mode
+    );
+    
+    verbose!(cli.verbose, "{}", factor_step(&config));
+    
+    if let Some(cap) = constraints.slippage.as_ref().and_then(SlippageBudget::max_amount) {
+        let (min_amount, _) = config.execution_limits();
+        if cap < min_amount {
+            return Err(VectorPlusError::Validation(format!(
+                "--target-slippage allows at most {} ETH, below the config's minimum of {} ETH",
+                cap, min_amount
+            )).into());
         }
     }
+    let cooldown = constraints.cooldown.as_ref();
+    let mut sizing = None;
+    if let Some(cooldown) = cooldown {
+        let previous = match Path::new(cooldown.state_file).exists() {
+            true => Some(crate::utils::read_json_file::<SizingState>(cooldown.state_file)
+                .map_err(|e| eyre::eyre!("Could not read state file {}: {}", cooldown.state_file, e))?),
+            false => None,
+        };
+        sizing = Some(CooldownInput { secs: cooldown.secs, previous, now: chrono::Utc::now().timestamp() as u64 });
+    }
+    let oracle_result = oracle.zip(oracle_report.as_ref()).map(|(feed, report)| OracleResult {
+        address: feed.address.to_string(),
+        round_id: report.round.round_id.to_string(),
+        price: report.round.price,
+        updated_at: report.round.updated_at,
+        age_secs: report.age_secs,
+        history: report.history,
+        estimated_volatility: report.volatility,
+    });
+    let amount = base.amount();
+    let mut result = CalculationResult::compute(
+        &config,
+        amount,
+        &OrderQuote { price, ..*quote },
+        sizing.as_ref(),
+        constraints.slippage.as_ref(),
+        oracle_result,
+    );
+    if let BaseAmount::PortfolioShare { total, pct } = base {
+        result.portfolio_share = Some(PortfolioShare::of(total, pct, result.final_amount));
+    }
+    verbose!(
+        cli.verbose,
+        "{} × {}% = {} ETH, clamped to [{}, {}] = {} ETH",
+        amount, result.factor_pct, result.adjusted_amount, result.min_amount, result.max_amount, result.clamped_amount()
+    );
+    
+    if let (Some(cooldown), Some(sizing)) = (cooldown, &sizing) {
+        let state = match &sizing.previous {
+            Some(state) if state.last_amount == result.final_amount => state.clone(),
+            _ => SizingState { last_amount: result.final_amount, changed_at: sizing.now },
+        };
+        crate::utils::write_json_file_atomic(cooldown.state_file, &state)?;
+        verbose!(cli.verbose, "wrote sizing state to {}", cooldown.state_file);
+    }
     
+    if let Some(filter) = display.changes_only {
+        let previous = match Path::new(filter.log_file).exists() {
+            true => Some(crate::utils::read_json_file::<FactorLog>(filter.log_file)
+                .map_err(|e| eyre::eyre!("Could not read factor log {}: {}", filter.log_file, e))?),
+            false => None,
+        };
+        let changed = factor_changed(previous.as_ref(), result.factor_pct, filter.min_change);
+        result.significant_change = Some(changed);
+        if changed {
+            let log = FactorLog { factor_pct: result.factor_pct, logged_at: chrono::Utc::now().timestamp() as u64 };
+            crate::utils::write_json_file_atomic(filter.log_file, &log)?;
+            verbose!(cli.verbose, "wrote factor log to {}", filter.log_file);
+        } else if !display.json {
+            let previous = previous.expect("an unchanged factor has a previous log");
+            println!(
+                "unchanged: factor {}% (last printed: {}%, {})",
+                result.factor_pct, previous.factor_pct, format_time(previous.logged_at, cli.time_format)
+            );
+            return Ok(());
+        }
+    }
+
+    let derivation = display.explain.then(|| result.derivation(&config));
+    if display.json {
+        result.derivation = derivation;
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        print_calculation(&result, oracle.map(|feed| feed.state_file), precision, cli.time_format);
+        if let Some(steps) = derivation {
+            println!();
+            println!("{}", "🧾 Derivation:".bold());
+            for (i, step) in steps.iter().enumerate() {
+                println!("  {}. {}", i + 1, step);
+            }
+        }
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// What `volatility calculate` sizes: a fixed amount, or a percentage of a
+/// stated portfolio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BaseAmount {
+    Fixed(Amount),
+    PortfolioShare { total: Amount, pct: f64 },
+}
+
+impl BaseAmount {
+    /// `pct` percent of `total`, rejecting percentages outside (0, 100].
+    fn share_of(total: Amount, pct: f64) -> Result<Self> {
+        if !(pct > 0.0 && pct <= 100.0) {
+            return Err(VectorPlusError::Validation(format!("--pct must be above 0 and at most 100, got {}", pct)).into());
+        }
+        Ok(BaseAmount::PortfolioShare { total, pct })
+    }
+
+    fn amount(self) -> Amount {
+        match self {
+            BaseAmount::Fixed(amount) => amount,
+            // Millionths of a percent, so fractional percentages stay exact to the wei
+            BaseAmount::PortfolioShare { total, pct } => total.mul_div((pct * 1_000_000.0).round() as u64, 100_000_000),
+        }
+    }
+}
+
+/// How `volatility calculate` prints its result.
+#[derive(Debug, Clone, Copy)]
+struct CalculationDisplay<'a> {
+    json: bool,
+    explain: bool,
+    changes_only: Option<ChangeFilter<'a>>,
+    /// Warn on stderr about volatility values that look like the wrong unit
+    unit_warnings: bool,
+}
+
+/// `--min-factor-change` settings: text output is cut to `unchanged` until
+/// the factor moves more than `min_change` points from the last printed one.
+#[derive(Debug, Clone, Copy)]
+struct ChangeFilter<'a> {
+    min_change: u64,
+    log_file: &'a str,
+}
+
+/// Last factor printed by `volatility calculate --min-factor-change`.
+#[derive(Debug, Serialize, Deserialize)]
+struct FactorLog {
+    factor_pct: u64,
+    logged_at: u64,
+}
+
+/// Whether `factor_pct` moved more than `min_change` points from the last
+/// printed factor. Comparing against the printed factor rather than the last
+/// run keeps slow drifts from going unreported.
+fn factor_changed(previous: Option<&FactorLog>, factor_pct: u64, min_change: u64) -> bool {
+    previous.is_none_or(|log| log.factor_pct.abs_diff(factor_pct) > min_change)
+}
+
+/// One stage of `volatility calculate --explain`, from volatility to final size.
+#[derive(Debug, Serialize, JsonSchema)]
+struct DerivationStep {
+    /// `regime`, `factor`, `adjust`, `clamp`, `cooldown` or `quote`
+    stage: &'static str,
+    input: String,
+    output: String,
+    note: String,
+}
+
+impl std::fmt::Display for DerivationStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} → {} ({})", self.stage, self.input, self.output, self.note)
+    }
+}
+
+/// Why the final amount differs from the volatility-adjusted one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ClampEvent {
+    /// The adjusted amount was within the execution limits
+    None,
+    /// The adjusted amount was above max_amount
+    CappedAtMax,
+    /// The adjusted amount was below min_amount
+    RaisedToMin,
+}
+
+impl ClampEvent {
+    /// How the execution limits moved `adjusted` to `limited`.
+    fn between(adjusted: Amount, limited: Amount) -> Self {
+        match limited.cmp(&adjusted) {
+            std::cmp::Ordering::Less => ClampEvent::CappedAtMax,
+            std::cmp::Ordering::Greater => ClampEvent::RaisedToMin,
+            std::cmp::Ordering::Equal => ClampEvent::None,
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        match self {
+            ClampEvent::None => "none",
+            ClampEvent::CappedAtMax => "capped at max",
+            ClampEvent::RaisedToMin => "raised to min",
+        }
+    }
+}
+
+/// Cooldown inputs for [`CalculationResult::compute`], read before sizing.
+struct CooldownInput {
+    secs: u64,
+    previous: Option<SizingState>,
+    now: u64,
+}
+
+// Both the --json and the text output render this, so they cannot drift;
+// --output-schema prints its schema, so field changes are contract changes
+/// Output of `vector-plus volatility calculate --json`. Amounts are ETH.
+#[derive(Debug, Serialize, JsonSchema)]
+struct CalculationResult {
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    original_amount: Amount,
+    /// Volatility adjustment applied to the original amount, in percent
+    factor_pct: u64,
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    adjusted_amount: Amount,
+    /// Amount after the execution limits and any cooldown
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    final_amount: Amount,
+    clamp_event: ClampEvent,
+    regime: Regime,
+    /// Basis points
+    #[schemars(with = "f64")]
+    baseline_volatility: Bps,
+    /// Basis points, from the oracle when one is read
+    #[schemars(with = "f64")]
+    current_volatility: Bps,
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    min_amount: Amount,
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    max_amount: Amount,
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    making_amount: Amount,
+    making_amount_wei: String,
+    /// Quote asset price per ETH
+    price: Option<f64>,
+    quote_symbol: String,
+    quote_decimals: u32,
+    /// Taking amount in the quote asset
+    taking_amount: Option<f64>,
+    /// Taking amount in the quote asset's smallest unit
+    taking_amount_units: Option<String>,
+    cooldown: Option<CooldownResult>,
+    slippage: Option<SlippageResult>,
+    oracle: Option<OracleResult>,
+    /// Steps from volatility to final size, with --explain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    derivation: Option<Vec<DerivationStep>>,
+    /// With --min-factor-change, whether the factor moved more than it since the last printed result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    significant_change: Option<bool>,
+    /// With --pct-of, the original amount as a share of the stated portfolio
+    portfolio_share: Option<PortfolioShare>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct PortfolioShare {
+    /// Portfolio size in ETH
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    total_amount: Amount,
+    /// Percentage of the portfolio sized, giving `original_amount`
+    pct: f64,
+    /// `final_amount` as a percentage of the portfolio
+    final_pct: f64,
+}
+
+impl PortfolioShare {
+    fn of(total: Amount, pct: f64, final_amount: Amount) -> Self {
+        let final_pct = if total.is_zero() { 0.0 } else { final_amount.as_eth_f64() / total.as_eth_f64() * 100.0 };
+        // Same millionth-of-a-percent resolution the share is sized with
+        let final_pct = (final_pct * 1e6).round() / 1e6;
+        PortfolioShare { total_amount: total, pct, final_pct }
+    }
+}
+
+/// The stage that set the final amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum BindingConstraint {
+    /// The volatility-adjusted amount, within every limit
+    Volatility,
+    /// The config's max_execution_size
+    MaxLimit,
+    /// The config's min_execution_size
+    MinLimit,
+    /// The cooldown held an increase back
+    Cooldown,
+    /// The --target-slippage cap
+    Slippage,
+}
+
+impl BindingConstraint {
+    fn name(&self) -> &'static str {
+        match self {
+            BindingConstraint::Volatility => "volatility adjustment",
+            BindingConstraint::MaxLimit => "max execution size",
+            BindingConstraint::MinLimit => "min execution size",
+            BindingConstraint::Cooldown => "cooldown",
+            BindingConstraint::Slippage => "slippage budget",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct SlippageResult {
+    /// Basis points
+    #[schemars(with = "f64")]
+    target_slippage_bps: Bps,
+    /// Basis points per ETH
+    impact_coeff: f64,
+    /// Largest size within the target, null when the coefficient is zero
+    max_amount: Option<f64>,
+    /// Size before the slippage cap, in ETH
+    #[serde(serialize_with = "serialize_eth")]
+    #[schemars(with = "f64")]
+    uncapped_amount: Amount,
+    /// Estimated impact of `final_amount`, in basis points
+    estimated_slippage_bps: f64,
+    binding: BindingConstraint,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct CooldownResult {
+    cooldown_secs: u64,
+    /// True when the size was held back from increasing
+    constrained: bool,
+    /// ETH the size would have been without the cooldown
+    uncooled_amount: f64,
+    /// ETH emitted by the previous run, if any
+    previous_amount: Option<f64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct OracleResult {
+    address: String,
+    round_id: String,
+    price: f64,
+    updated_at: u64,
+    age_secs: u64,
+    /// Rounds recorded for the volatility estimate
+    history: usize,
+    /// EWMA volatility in basis points, once 3 rounds are recorded
+    #[schemars(with = "Option<f64>")]
+    estimated_volatility: Option<Bps>,
+}
+
+impl CalculationResult {
+    fn compute(
+        config: &VolatilityConfig,
+        amount: Amount,
+        quote: &OrderQuote<'_>,
+        cooldown: Option<&CooldownInput>,
+        slippage: Option<&SlippageBudget>,
+        oracle: Option<OracleResult>,
+    ) -> Self {
+        let (min_amount, max_amount) = config.execution_limits();
+        let (adjusted_amount, limited_amount) = size_execution(config, amount);
+        let clamp_event = ClampEvent::between(adjusted_amount, limited_amount);
+        
+        let mut final_amount = limited_amount;
+        let cooldown = cooldown.map(|input| {
+            let cooled = apply_cooldown(limited_amount, min_amount, input.previous.as_ref(), input.now, input.secs);
+            final_amount = cooled.amount;
+            CooldownResult {
+                cooldown_secs: input.secs,
+                constrained: cooled.constrained,
+                uncooled_amount: limited_amount.as_eth_f64(),
+                previous_amount: input.previous.as_ref().map(|state| state.last_amount.as_eth_f64()),
+            }
+        });
+        
+        let slippage = slippage.map(|budget| {
+            let cap = budget.max_amount();
+            let uncapped = final_amount;
+            let binding = match (cap, &cooldown, clamp_event) {
+                (Some(cap), _, _) if cap < uncapped => BindingConstraint::Slippage,
+                (_, Some(cooldown), _) if cooldown.constrained => BindingConstraint::Cooldown,
+                (_, _, ClampEvent::CappedAtMax) => BindingConstraint::MaxLimit,
+                (_, _, ClampEvent::RaisedToMin) => BindingConstraint::MinLimit,
+                _ => BindingConstraint::Volatility,
+            };
+            final_amount = cap.map_or(uncapped, |cap| uncapped.min(cap));
+            SlippageResult {
+                target_slippage_bps: budget.target,
+                impact_coeff: budget.impact_coeff,
+                max_amount: cap.map(Amount::as_eth_f64),
+                uncapped_amount: uncapped,
+                estimated_slippage_bps: budget.impact_bps(final_amount),
+                binding,
+            }
+        });
+        
+        // Taking amount in the quote asset's smallest unit, e.g. 6 decimals for USDC
+        let taking = quote.price.and_then(|price| taking_amount(final_amount, price));
+        let taking_amount = taking.map(Amount::as_eth_f64);
+        let taking_amount_units = taking.map(|taking| taking.to_base_units(quote.decimals as usize));
+        
+        CalculationResult {
+            original_amount: amount,
+            factor_pct: volatility_adjustment_factor(config),
+            adjusted_amount,
+            final_amount,
+            clamp_event,
+            regime: Regime::classify(config),
+            baseline_volatility: config.baseline_volatility,
+            current_volatility: config.current_volatility,
+            min_amount,
+            max_amount,
+            making_amount: final_amount,
+            making_amount_wei: final_amount.wei().to_string(),
+            price: quote.price,
+            quote_symbol: quote.symbol.to_string(),
+            quote_decimals: quote.decimals,
+            taking_amount,
+            taking_amount_units,
+            cooldown,
+            slippage,
+            oracle,
+            derivation: None,
+            significant_change: None,
+            portfolio_share: None,
+        }
+    }
+    
+    /// The `--explain` trace of this result, one step per stage that shaped it.
+    fn derivation(&self, config: &VolatilityConfig) -> Vec<DerivationStep> {
+        let source = match &self.oracle {
+            Some(oracle) => format!(" (from oracle {})", oracle.address),
+            None => String::new(),
+        };
+        let mut steps = vec![
+            DerivationStep {
+                stage: "regime",
+                input: format!("current {}bps{}", self.current_volatility, source),
+                output: self.regime.name().to_string(),
+                note: format!(
+                    "baseline {}bps, threshold {}bps, emergency {}bps",
+                    self.baseline_volatility, config.volatility_threshold, config.emergency_threshold
+                ),
+            },
+            factor_step(config),
+            DerivationStep {
+                stage: "adjust",
+                input: format!("{} ETH × {}%", self.original_amount, self.factor_pct),
+                output: format!("{} ETH", self.adjusted_amount),
+                note: "original amount scaled by the factor".to_string(),
+            },
+            DerivationStep {
+                stage: "clamp",
+                input: format!("{} ETH", self.adjusted_amount),
+                output: format!("{} ETH", self.clamped_amount()),
+                note: match self.clamp_event {
+                    ClampEvent::None => format!("within limits [{}, {}] ETH", self.min_amount, self.max_amount),
+                    event => format!("{} of [{}, {}] ETH", event.name(), self.min_amount, self.max_amount),
+                },
+            },
+        ];
+        if let Some(cooldown) = &self.cooldown {
+            steps.push(DerivationStep {
+                stage: "cooldown",
+                input: format!("{} ETH", self.clamped_amount()),
+                output: format!("{} ETH", self.uncapped_amount()),
+                note: match cooldown.constrained {
+                    true => format!("increase held back within {}s of the last change", cooldown.cooldown_secs),
+                    false => format!("not constraining ({}s)", cooldown.cooldown_secs),
+                },
+            });
+        }
+        if let Some(slippage) = &self.slippage {
+            steps.push(DerivationStep {
+                stage: "slippage",
+                input: format!("{} ETH", self.uncapped_amount()),
+                output: format!("{} ETH", self.final_amount),
+                note: match (slippage.binding, slippage.max_amount) {
+                    (BindingConstraint::Slippage, Some(max)) => {
+                        format!("capped at {} ETH for {}bps at {} bps/ETH", max, slippage.target_slippage_bps, slippage.impact_coeff)
+                    }
+                    _ => format!("within {}bps: estimated {:.2}bps", slippage.target_slippage_bps, slippage.estimated_slippage_bps),
+                },
+            });
+        }
+        if let (Some(price), Some(taking)) = (self.price, self.taking_amount) {
+            steps.push(DerivationStep {
+                stage: "quote",
+                input: format!("{} ETH × {} {}/ETH", self.final_amount, price, self.quote_symbol),
+                output: format!("{} {}", taking, self.quote_symbol),
+                note: format!("taking amount, {} units at {} decimals", self.taking_amount_units.as_deref().unwrap_or("0"), self.quote_decimals),
+            });
+        }
+        steps
+    }
+    
+    /// The amount after the limits and any cooldown, before any slippage cap.
+    fn uncapped_amount(&self) -> Amount {
+        self.slippage.as_ref().map_or(self.final_amount, |slippage| slippage.uncapped_amount)
+    }
+    
+    /// The adjusted amount after the execution limits, before any cooldown.
+    fn clamped_amount(&self) -> Amount {
+        match self.clamp_event {
+            ClampEvent::None => self.adjusted_amount,
+            ClampEvent::CappedAtMax => self.max_amount,
+            ClampEvent::RaisedToMin => self.min_amount,
+        }
+    }
+}
+
+fn print_calculation(result: &CalculationResult, oracle_state: Option<&str>, precision: u8, time_format: TimeFormat) {
+    println!("{} {} ETH", "🧮 Calculating volatility adjustment for:".cyan(), result.original_amount.format_eth(precision).yellow());
+    if let Some(share) = &result.portfolio_share {
+        println!("  • {}% of a {} ETH portfolio", share.pct, share.total_amount.format_eth(precision));
+    }
+    
+    if let Some(oracle) = &result.oracle {
+        println!("🔮 Oracle {}:", oracle.address);
+        println!(
+            "  • Round {}: {} (updated {})",
+            oracle.round_id, format_amount(oracle.price, precision), format_time(oracle.updated_at, time_format)
+        );
+        match oracle.estimated_volatility {
+            Some(volatility) => println!("  • EWMA volatility over {} rounds: {}bps", oracle.history, volatility),
+            None => println!(
+                "  • {} of 3 rounds recorded in {}; using the config's current volatility until then",
+                oracle.history, oracle_state.unwrap_or(DEFAULT_ORACLE_STATE)
+            ),
+        }
+    }
+    
+    println!("📊 Volatility Analysis:");
+    println!("  • Baseline volatility: {}bps", result.baseline_volatility);
+    println!("  • Current volatility: {}bps", result.current_volatility);
+    println!("  • Adjustment factor: {}%", result.factor_pct);
+    println!();
+    println!("💰 Execution Amounts:");
+    println!("  • Original amount: {} ETH", result.original_amount.format_eth(precision));
+    println!("  • Adjusted amount: {} ETH", result.adjusted_amount.format_eth(precision));
+    match &result.portfolio_share {
+        Some(share) => println!(
+            "  • Final amount: {} ETH ({:.2}% of portfolio)",
+            result.final_amount.format_eth(precision), share.final_pct
+        ),
+        None => println!("  • Final amount: {} ETH", result.final_amount.format_eth(precision)),
+    }
+    println!("  • Min allowed: {} ETH", result.min_amount.format_eth(precision));
+    println!("  • Max allowed: {} ETH", result.max_amount.format_eth(precision));
+    
+    match result.clamp_event {
+        ClampEvent::CappedAtMax => println!("{}", "⚠️  Amount capped at maximum limit".yellow()),
+        ClampEvent::RaisedToMin => println!("{}", "⚠️  Amount raised to minimum limit".yellow()),
+        ClampEvent::None => {}
+    }
+    if let Some(cooldown) = &result.cooldown {
+        if cooldown.constrained {
+            println!(
+                "{} held at {} ETH instead of {} ETH (cooldown {}s)",
+                "⏳ Increase blocked:".yellow(),
+                result.uncapped_amount().format_eth(precision),
+                result.clamped_amount().format_eth(precision),
+                cooldown.cooldown_secs
+            );
+        } else {
+            println!("  • Cooldown: not constraining ({}s)", cooldown.cooldown_secs);
+        }
+    }
+    if let Some(slippage) = &result.slippage {
+        println!();
+        println!("🎯 Slippage Budget ({}bps at {} bps/ETH):", slippage.target_slippage_bps, slippage.impact_coeff);
+        match slippage.max_amount {
+            Some(max) => println!("  • Largest size within budget: {} ETH", format_amount(max, precision)),
+            None => println!("  • Largest size within budget: unlimited (zero impact coefficient)"),
+        }
+        println!("  • Estimated slippage: {:.2}bps", slippage.estimated_slippage_bps);
+        let binding = format!("  • Binding constraint: {}", slippage.binding.name());
+        match slippage.binding {
+            BindingConstraint::Slippage => println!("{}", binding.yellow()),
+            _ => println!("{}", binding),
+        }
+    }
+    
+    if let (Some(price), Some(taking), Some(units)) = (result.price, result.taking_amount, &result.taking_amount_units) {
+        println!();
+        println!("📝 Order Amounts (@ {} {}/ETH):", format_amount(price, precision), result.quote_symbol);
+        println!("  • Making amount: {} ETH ({} wei)", result.making_amount.format_eth(precision), result.making_amount_wei);
+        println!(
+            "  • Taking amount: {} {} ({} units, {} decimals)",
+            format_amount(taking, precision), result.quote_symbol, units, result.quote_decimals
+        );
+    }
+}
+
+/// Output of `vector-plus volatility calculate --range --json`. Amounts are ETH.
+#[derive(Debug, Serialize)]
+struct SizingRange {
+    #[serde(serialize_with = "serialize_eth")]
+    original_amount: Amount,
+    /// Basis points the conservative and aggressive ends shift volatility by
+    band_width: Bps,
+    #[serde(serialize_with = "serialize_eth")]
+    min_amount: Amount,
+    #[serde(serialize_with = "serialize_eth")]
+    max_amount: Amount,
+    /// Sized one band above current volatility
+    conservative: RangePoint,
+    /// Sized at current volatility
+    base: RangePoint,
+    /// Sized one band below current volatility
+    aggressive: RangePoint,
+}
+
+#[derive(Debug, Serialize)]
+struct RangePoint {
+    /// Basis points
+    current_volatility: Bps,
+    regime: Regime,
+    factor_pct: u64,
+    #[serde(serialize_with = "serialize_eth")]
+    adjusted_amount: Amount,
+    #[serde(serialize_with = "serialize_eth")]
+    final_amount: Amount,
+    clamp_event: ClampEvent,
+}
+
+impl RangePoint {
+    fn at(config: &VolatilityConfig, amount: Amount, volatility: Bps) -> Self {
+        let mut shifted = config.clone();
+        shifted.current_volatility = volatility;
+        let (adjusted_amount, final_amount) = size_execution(&shifted, amount);
+        RangePoint {
+            current_volatility: volatility,
+            regime: Regime::classify(&shifted),
+            factor_pct: volatility_adjustment_factor(&shifted),
+            adjusted_amount,
+            final_amount,
+            clamp_event: ClampEvent::between(adjusted_amount, final_amount),
+        }
+    }
+}
+
+impl SizingRange {
+    /// Sizes `amount` at the config's current volatility and one regime band
+    /// either side of it. A band is the width of NORMAL, from baseline to the
+    /// volatility threshold, or the baseline itself when the threshold does not
+    /// sit above it.
+    fn compute(config: &VolatilityConfig, amount: Amount) -> Self {
+        let band_width = match config.volatility_threshold - config.baseline_volatility.min(config.volatility_threshold) {
+            Bps::ZERO => config.baseline_volatility,
+            width => width,
+        };
+        let current = config.current_volatility;
+        let (min_amount, max_amount) = config.execution_limits();
+        SizingRange {
+            original_amount: amount,
+            band_width,
+            min_amount,
+            max_amount,
+            conservative: RangePoint::at(config, amount, current + band_width),
+            base: RangePoint::at(config, amount, current),
+            aggressive: RangePoint::at(config, amount, current - band_width.min(current)),
+        }
+    }
+}
+
+async fn calculate_sizing_range(
+    amount: Amount,
+    config_file: &str,
+    oracle: Option<&OracleFeed<'_>>,
+    json: bool,
+    unit_warnings: bool,
+    cli: &crate::Cli,
+) -> Result<()> {
+    let precision = cli.precision;
+    let mut config: VolatilityConfig = read_config(config_file)?;
+    if unit_warnings {
+        warn_on_units(config_file, &config);
+    }
+    if let Some(feed) = oracle {
+        apply_oracle(feed, &mut config, cli).await?;
+    }
+    let range = SizingRange::compute(&config, amount);
+    verbose!(cli.verbose, "band width {}bps around {}bps", range.band_width, config.current_volatility);
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&range)?);
+        return Ok(());
+    }
+    
+    let mut table = Table::new()
+        .column("end", "End", Align::Left)
+        .column("current_volatility", "Volatility", Align::Right)
+        .column("regime", "Regime", Align::Left)
+        .column("factor_pct", "Factor", Align::Right)
+        .column("adjusted_amount", "Adjusted (ETH)", Align::Right)
+        .column("final_amount", "Final (ETH)", Align::Right)
+        .column("clamp_event", "Clamp", Align::Left);
+    for (name, point) in [("conservative", &range.conservative), ("base", &range.base), ("aggressive", &range.aggressive)] {
+        let cells = vec![
+            Cell::text(name),
+            Cell::new(format!("{}bps", point.current_volatility), point.current_volatility.as_f64()),
+            Cell::text(point.regime.name()),
+            Cell::new(format!("{}%", point.factor_pct), point.factor_pct),
+            Cell::new(point.adjusted_amount.format_eth(precision), point.adjusted_amount.as_eth_f64()),
+            Cell::new(point.final_amount.format_eth(precision), point.final_amount.as_eth_f64()),
+            Cell::text(point.clamp_event.name()),
+        ];
+        match point.regime {
+            Regime::Emergency => table.colored_row(cells, Color::Red),
+            Regime::Elevated => table.colored_row(cells, Color::Yellow),
+            _ => table.row(cells),
+        }
+    }
+    
+    println!("{} {} ETH", "🧮 Sizing range for:".cyan(), amount.format_eth(precision).yellow());
+    println!("  • Current volatility: {}bps, ±{}bps per regime band", config.current_volatility, range.band_width);
+    println!(
+        "  • Execution limits: {} - {} ETH",
+        range.min_amount.format_eth(precision), range.max_amount.format_eth(precision)
+    );
+    println!();
+    println!("{}", table.render());
+    println!();
+    println!(
+        "💰 Work within {} - {} ETH",
+        range.conservative.final_amount.min(range.aggressive.final_amount).format_eth(precision),
+        range.conservative.final_amount.max(range.aggressive.final_amount).format_eth(precision)
+    );
+    if range.conservative.regime == Regime::Emergency {
+        println!(
+            "{}",
+            "🚨 One band up crosses the emergency threshold - the on-chain calculator would halt there".red()
+        );
+    }
+    Ok(())
+}
+
+/// Where `volatility watch` reads prices: a WebSocket stream when given,
+/// with polling as the fallback while it is down.
+struct PriceSources<'a> {
+    poll: Option<PollSource<'a>>,
+    ws: Option<&'a str>,
+    pointer: Option<&'a str>,
+}
+
+/// A price source `volatility watch` asks once per interval.
+enum PollSource<'a> {
+    Http(&'a str),
+    /// The config's `price_command`, run with the asset symbol
+    Command { command: &'a str, symbol: &'a str },
+}
+
+impl PollSource<'_> {
+    fn describe(&self) -> String {
+        match self {
+            PollSource::Http(url) => url.to_string(),
+            PollSource::Command { command, symbol } => format!("`{} {}`", command, symbol),
+        }
+    }
+
+    async fn fetch(&self, client: &reqwest::Client, pointer: Option<&str>) -> Result<f64> {
+        match self {
+            PollSource::Http(url) => fetch_price(client, url, pointer).await,
+            PollSource::Command { command, symbol } => {
+                crate::price_stream::price_from_command(command, symbol, pointer, PRICE_TIMEOUT).await
+            }
+        }
+    }
+}
+
+async fn watch_volatility(
+    config_file: &str,
+    sources: PriceSources<'_>,
+    interval_secs: u64,
+    window: usize,
+    state_file: Option<&str>,
+    precision: u8,
+) -> Result<()> {
+    let mut config: VolatilityConfig = read_config(config_file)?;
+    
+    if window < 3 {
+        return Err(eyre::eyre!("Window must hold at least 3 prices"));
+    }
+    
+    let mut reloader = match ConfigReloader::new(config_file) {
+        Ok(reloader) => Some(reloader),
+        Err(e) => {
+            println!("{} {}", "⚠️  Config hot-reload disabled:".yellow(), e);
+            None
+        }
+    };
+    
+    let mut state = match state_file {
+        Some(path) if std::path::Path::new(path).exists() => crate::utils::read_json_file::<WatchState>(path)
+            .map_err(|e| eyre::eyre!("Could not read state file {}: {}", path, e))?,
+        _ => WatchState::default(),
+    };
+    
+    let client = reqwest::Client::builder()
+        .timeout(PRICE_TIMEOUT)
+        .build()?;
+    let mut stream = sources.ws.map(PriceStream::new);
+    let interval = std::time::Duration::from_secs(interval_secs);
+    let idle_timeout = interval * 3;
+    let mut shutdown = Shutdown::install();
+    
+    println!("{} {}", "👀 Watching volatility for:".cyan(), config_file.yellow());
+    match (sources.ws, &sources.poll) {
+        (Some(ws), Some(poll)) => println!("  • Price stream: {} (polling {} while it is down)", ws, poll.describe()),
+        (Some(ws), None) => println!("  • Price stream: {}", ws),
+        (None, Some(poll)) => println!("  • Price source: {}", poll.describe()),
+        (None, None) => unreachable!("a watch has a stream or a poll source"),
+    }
+    println!("  • Poll interval: {}s, window: {} prices", interval_secs, window);
+    if !state.prices.is_empty() {
+        println!("  • Resumed {} prices from state", state.prices.len());
+    }
+    println!("  {} Press Ctrl+C to stop", "ℹ️ ".blue());
+    println!();
+    
+    let mut ticks = 0u64;
+    let mut failures = 0u64;
+    let mut regime_changes = 0u64;
+    // Arrival times of this session's prices, to scale irregular stream ticks
+    let mut tick_times: std::collections::VecDeque<std::time::Instant> = std::collections::VecDeque::new();
+    let mut next_poll = std::time::Instant::now();
+    
+    loop {
+        if let Some(reloaded) = reloader.as_mut().and_then(ConfigReloader::poll) {
+            config = reloaded;
+        }
+        
+        if let Some(stream) = stream.as_mut().filter(|stream| !stream.is_connected() && stream.retry_due()) {
+            match stream.connect().await {
+                Ok(()) => println!("{} {}", "🔌 Streaming prices from".cyan(), stream.url()),
+                Err(e) => println!("{} {}", "⚠️  Price stream unavailable:".yellow(), e),
+            }
+        }
+        
+        let fetched = match stream.as_mut().filter(|stream| stream.is_connected()) {
+            Some(stream) => {
+                let event = tokio::select! {
+                    event = stream.next(sources.pointer, idle_timeout) => event,
+                    _ = shutdown.triggered() => break,
+                };
+                match event {
+                    StreamEvent::Price(price) => Some(Ok(price)),
+                    StreamEvent::Invalid(e) => Some(Err(e)),
+                    StreamEvent::Dropped(reason) => {
+                        println!(
+                            "{} {} - {} (reconnecting in {}s)",
+                            "⚠️  Price stream dropped:".yellow(),
+                            reason,
+                            if sources.poll.is_some() { "polling" } else { "waiting" },
+                            stream.retry_secs()
+                        );
+                        next_poll = std::time::Instant::now();
+                        None
+                    }
+                }
+            }
+            None => match &sources.poll {
+                Some(poll) if std::time::Instant::now() >= next_poll => {
+                    next_poll = std::time::Instant::now() + interval;
+                    Some(poll.fetch(&client, sources.pointer).await)
+                }
+                _ => None,
+            },
+        };
+        
+        let fetched_any = fetched.is_some();
+        match fetched {
+            Some(Ok(price)) => {
+                ticks += 1;
+                state.prices.push(price);
+                tick_times.push_back(std::time::Instant::now());
+                if state.prices.len() > window {
+                    let excess = state.prices.len() - window;
+                    state.prices.drain(..excess);
+                }
+                while tick_times.len() > state.prices.len() {
+                    tick_times.pop_front();
+                }
+                
+                // Stream ticks arrive irregularly, so once the window holds only this
+                // session's prices their average spacing stands in for the interval
+                let period_secs = match (&stream, tick_times.front(), tick_times.back()) {
+                    (Some(_), Some(first), Some(last)) if tick_times.len() == state.prices.len() && tick_times.len() > 1 => {
+                        last.duration_since(*first).as_secs_f64() / (tick_times.len() - 1) as f64
+                    }
+                    _ => interval_secs as f64,
+                };
+                
+                let time = chrono::Utc::now().format("%H:%M:%S");
+                match realized_volatility_bps(&state.prices, period_secs) {
+                    Some(volatility) => {
+                        let mut current = config.clone();
+                        current.current_volatility = volatility;
+                        let (regime, held) = Regime::classify_from(&current, state.last_regime);
+                        if state.last_regime.is_some_and(|last| last != regime) {
+                            regime_changes += 1;
+                        }
+                        println!(
+                            "[{}] price {} | volatility {}bps | {}{} | factor {}%",
+                            time,
+                            format_amount(price, precision),
+                            volatility,
+                            regime.label(),
+                            if held { " (held)" } else { "" },
+                            regime_adjustment_factor(&current, regime)
+                        );
+                        state.last_volatility = Some(volatility);
+                        state.last_regime = Some(regime);
+                    }
+                    None => {
+                        println!(
+                            "[{}] price {} | collecting data ({}/3)",
+                            time, format_amount(price, precision), state.prices.len()
+                        );
+                    }
+                }
+                state.updated_at = chrono::Utc::now().timestamp() as u64;
+            }
+            Some(Err(e)) => {
+                failures += 1;
+                println!("{} {}", "⚠️  Price fetch failed:".yellow(), e);
+            }
+            None => {}
+        }
+        
+        // Persist at the iteration boundary so an interrupt never leaves a half-written state
+        if fetched_any {
+            if let Some(path) = state_file {
+                write_json_file_atomic(path, &state)?;
+            }
+        }
+        
+        if stream.as_ref().is_some_and(PriceStream::is_connected) {
+            if shutdown.is_triggered() {
+                break;
+            }
+            continue;
+        }
+        // Wake for whichever comes first: the next HTTP poll or the next reconnect
+        let now = std::time::Instant::now();
+        let until_poll = sources.poll.as_ref().map(|_| next_poll.saturating_duration_since(now));
+        let until_retry = stream.as_ref().map(PriceStream::until_retry);
+        let wait = until_poll.into_iter().chain(until_retry).min().unwrap_or(interval);
+        if shutdown.sleep(wait).await {
+            break;
+        }
+    }
+    
+    println!();
+    println!("{}", "🛑 Shutting down volatility watch".cyan());
+    println!("📊 Session summary:");
+    println!("  • Ticks: {} ({} failed fetches)", ticks, failures);
+    println!("  • Regime changes: {}", regime_changes);
+    if let Some(stream) = &stream {
+        println!("  • Stream drops: {}", stream.reconnects);
+    }
+    if let (Some(volatility), Some(regime)) = (state.last_volatility, state.last_regime) {
+        println!("  • Last volatility: {}bps ({})", volatility, regime.label());
+    }
+    if let Some(path) = state_file {
+        println!("  • State saved to: {}", path);
+    }
+    
+    Ok(())
+}
+
+/// Classifies the config's current volatility against the regime recorded in
+/// `state_file`, so ELEVATED is only left below the exit threshold, and
+/// records the result for the next run.
+fn volatility_status(config_file: &str, state_file: &str) -> Result<()> {
+    let config: VolatilityConfig = read_config(config_file)?;
+    let mut state = if std::path::Path::new(state_file).exists() {
+        crate::utils::read_json_file::<WatchState>(state_file)
+            .map_err(|e| eyre::eyre!("Could not read state file {}: {}", state_file, e))?
+    } else {
+        WatchState::default()
+    };
+    
+    let previous = state.last_regime;
+    let (regime, held) = Regime::classify_from(&config, previous);
+    
+    println!("{} {}", "📊 Volatility status for:".cyan(), config_file.yellow());
+    println!("  • Current volatility: {}bps", config.current_volatility);
+    println!("  • Enter ELEVATED above: {}bps", config.volatility_threshold);
+    println!("  • Exit ELEVATED below: {}bps", config.exit_threshold());
+    println!("  • Previous regime: {}", previous.map_or("none".normal(), |regime| regime.label()));
+    println!("  • Regime: {}", regime.label());
+    if held {
+        println!(
+            "  • {} holding ELEVATED until volatility falls below {}bps",
+            "Hysteresis:".yellow(),
+            config.exit_threshold()
+        );
+    } else {
+        println!("  • Hysteresis: not holding");
+    }
+    println!("  • Adjustment factor: {}%", regime_adjustment_factor(&config, regime));
+    
+    state.last_volatility = Some(config.current_volatility);
+    state.last_regime = Some(regime);
+    state.updated_at = chrono::Utc::now().timestamp() as u64;
+    write_json_file_atomic(state_file, &state)?;
+    println!("  • State saved to: {}", state_file);
+    Ok(())
+}
+
+/// Watches a volatility config on disk so `watch` can pick up retuned
+/// thresholds between ticks. The parent directory is watched rather than the
+/// file itself, because editors often save by replacing the file.
+struct ConfigReloader {
+    path: PathBuf,
+    changed: Arc<AtomicBool>,
+    last_error: Option<String>,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl ConfigReloader {
+    fn new(config_file: &str) -> Result<Self> {
+        use notify::Watcher;
+        
+        let path = PathBuf::from(config_file);
+        let file_name = path.file_name()
+            .ok_or_else(|| eyre::eyre!("Not a file path: {}", config_file))?
+            .to_owned();
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        
+        let changed = Arc::new(AtomicBool::new(false));
+        let flag = changed.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let touches_config = event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str()));
+                if touches_config && !event.kind.is_access() {
+                    flag.store(true, Ordering::SeqCst);
+                }
+            }
+        })?;
+        watcher.watch(&dir, notify::RecursiveMode::NonRecursive)?;
+        
+        Ok(Self { path, changed, last_error: None, _watcher: watcher })
+    }
+    
+    /// Returns the new config if the file changed and now loads and validates.
+    /// An invalid file (e.g. a save in progress) keeps the change pending, so
+    /// it is retried on the next tick; each distinct failure is logged once.
+    fn poll(&mut self) -> Option<VolatilityConfig> {
+        if !self.changed.swap(false, Ordering::SeqCst) {
+            return None;
+        }
+        
+        match load_valid_config(&self.path) {
+            Ok(config) => {
+                self.last_error = None;
+                println!(
+                    "{} threshold {}bps, emergency {}bps",
+                    "🔄 Reloaded config:".cyan(), config.volatility_threshold, config.emergency_threshold
+                );
+                Some(config)
+            }
+            Err(e) => {
+                let message = e.to_string();
+                if self.last_error.as_deref() != Some(message.as_str()) {
+                    println!("{} {} (keeping previous config)", "⚠️  Config reload failed:".yellow(), message);
+                    self.last_error = Some(message);
+                }
+                self.changed.store(true, Ordering::SeqCst);
+                None
+            }
+        }
+    }
+}
+
+fn load_valid_config(path: &Path) -> Result<VolatilityConfig> {
+    let config: VolatilityConfig = read_config(path)?;
+    let (_, errors) = check_volatility_config(&config);
+    if !errors.is_empty() {
+        return Err(eyre::eyre!("{}", errors.join("; ")));
+    }
+    Ok(config)
+}
+
+async fn fetch_price(client: &reqwest::Client, url: &str, pointer: Option<&str>) -> Result<f64> {
+    let body: serde_json::Value = client.get(url).send().await?.error_for_status()?.json().await?;
+    price_from_json(&body, pointer)
+}
+
+/// Daily realized volatility in bps: the close-to-close estimate over the
+/// polled prices, scaled by sqrt(polls per day) for polls `period_secs` apart.
+fn realized_volatility_bps(prices: &[f64], period_secs: f64) -> Option<Bps> {
+    if prices.len() < 3 {
+        return None;
+    }
+    let series = PriceSeries {
+        close: prices.to_vec(),
+        high: None,
+        low: None,
+        timestamps: None,
+        periods_per_day: 86_400.0 / period_secs.max(0.001),
+    };
+    StdDevModel.estimate(&series).ok()
+}
+
+/// Price rows loaded for volatility estimation; `high`/`low` are only
+/// present when the source has those columns.
+pub(crate) struct PriceSeries {
+    pub(crate) close: Vec<f64>,
+    pub(crate) high: Option<Vec<f64>>,
+    pub(crate) low: Option<Vec<f64>>,
+    /// Unix timestamps, when the source has a `timestamp` column
+    pub(crate) timestamps: Option<Vec<u64>>,
+    /// Rows per day, used to scale per-row volatility to daily
+    pub(crate) periods_per_day: f64,
+}
+
+impl PriceSeries {
+    /// Loads a CSV with a header row naming `close` (or `price`), `high`,
+    /// `low` and `timestamp` columns; other columns are ignored. A file of bare numbers is
+    /// read as a single close column.
+    pub(crate) fn load(file: &str, periods_per_day: f64) -> Result<Self> {
+        let content = fs::read_to_string(file)
+            .map_err(|_| eyre::eyre!("Could not read file: {}", file))?;
+        let mut lines = content.lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+        
+        let header: Vec<String> = match lines.peek() {
+            Some((_, line)) if line.parse::<f64>().is_err() => {
+                let header = line.split(',').map(|c| c.trim().to_lowercase()).collect();
+                lines.next();
+                header
+            }
+            Some(_) => vec!["close".to_string()],
+            None => return Err(eyre::eyre!("No prices in {}", file)),
+        };
+        
+        let column = |names: &[&str]| header.iter().position(|c| names.contains(&c.as_str()));
+        let close_col = column(&["close", "price"])
+            .ok_or_else(|| eyre::eyre!("{} has no close or price column", file))?;
+        let range_cols = column(&["high"]).zip(column(&["low"]));
+        let timestamp_col = column(&["timestamp", "time"]);
+        
+        let mut series = PriceSeries {
+            close: Vec::new(),
+            high: range_cols.map(|_| Vec::new()),
+            low: range_cols.map(|_| Vec::new()),
+            timestamps: timestamp_col.map(|_| Vec::new()),
+            periods_per_day,
+        };
+        for (line_no, line) in lines {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |col: usize| -> Result<f64> {
+                let raw = fields.get(col)
+                    .ok_or_else(|| eyre::eyre!("Line {} of {} is missing column {}", line_no, file, header[col]))?;
+                let value: f64 = raw.parse()
+                    .map_err(|_| eyre::eyre!("Invalid {} on line {} of {}: {}", header[col], line_no, file, raw))?;
+                if !value.is_finite() || value <= 0.0 {
+                    return Err(eyre::eyre!("{} on line {} of {} must be positive", header[col], line_no, file));
+                }
+                Ok(value)
+            };
+            series.close.push(field(close_col)?);
+            if let (Some((high_col, low_col)), Some(high), Some(low)) = (range_cols, &mut series.high, &mut series.low) {
+                let (row_high, row_low) = (field(high_col)?, field(low_col)?);
+                if row_high < row_low {
+                    return Err(eyre::eyre!("High {} on line {} of {} is below low {}", row_high, line_no, file, row_low));
+                }
+                high.push(row_high);
+                low.push(row_low);
+            }
+            if let (Some(col), Some(timestamps)) = (timestamp_col, &mut series.timestamps) {
+                let raw = fields.get(col).copied().unwrap_or_default();
+                let timestamp = raw.parse()
+                    .map_err(|_| eyre::eyre!("Invalid timestamp on line {} of {}: {}", line_no, file, raw))?;
+                timestamps.push(timestamp);
+            }
+        }
+        
+        if series.close.len() < 3 {
+            return Err(eyre::eyre!("Need at least 3 prices to estimate volatility, found {}", series.close.len()));
+        }
+        Ok(series)
+    }
+    
+    /// Rows `start..end` without timestamps, used for rolling estimates.
+    fn rows(&self, start: usize, end: usize) -> PriceSeries {
+        PriceSeries {
+            close: self.close[start..end].to_vec(),
+            high: self.high.as_ref().map(|high| high[start..end].to_vec()),
+            low: self.low.as_ref().map(|low| low[start..end].to_vec()),
+            timestamps: None,
+            periods_per_day: self.periods_per_day,
+        }
+    }
+    
+    /// The estimate at every row over the `window` rows ending there, or over
+    /// as many as exist; rows with fewer than `min_rows` get `None`.
+    fn rolling(&self, model: &dyn VolatilityModel, window: usize, min_rows: usize) -> Result<Vec<Option<Bps>>> {
+        (0..self.close.len())
+            .map(|row| {
+                let lookback = (row + 1).saturating_sub(window);
+                (row + 1 - lookback >= min_rows.max(3))
+                    .then(|| model.estimate(&self.rows(lookback, row + 1)))
+                    .transpose()
+                    .map_err(|e| eyre::eyre!("Window ending at row {}: {}", row, e))
+            })
+            .collect()
+    }
+    
+    fn log_returns(&self) -> Vec<f64> {
+        self.close.windows(2).map(|w| (w[1] / w[0]).ln()).collect()
+    }
+    
+    /// Scales a per-row variance to daily volatility, to the nearest hundredth of a bp.
+    fn daily_bps(&self, variance: f64) -> Bps {
+        Bps::from_f64(variance.sqrt() * self.periods_per_day.sqrt() * 10_000.0).unwrap_or(Bps::ZERO)
+    }
+}
+
+impl ModelKind {
+    fn build(self, lambda: f64) -> Box<dyn VolatilityModel> {
+        match self {
+            ModelKind::Ewma => Box::new(EwmaModel { lambda }),
+            ModelKind::Stddev => Box::new(StdDevModel),
+            ModelKind::Parkinson => Box::new(ParkinsonModel),
+            ModelKind::Garch => Box::new(GarchModel),
+        }
+    }
+}
+
+/// A volatility estimator over a price series.
+pub(crate) trait VolatilityModel {
+    fn name(&self) -> &'static str;
+    
+    /// Whether the model needs `high`/`low` columns in addition to closes.
+    fn requires_range(&self) -> bool {
+        false
+    }
+    
+    /// Daily volatility in bps; callers ensure at least 3 rows and, when
+    /// `requires_range`, the high/low columns. Fails only for models that
+    /// need more data or cannot be fitted to it.
+    fn estimate(&self, data: &PriceSeries) -> Result<Bps>;
+}
+
+pub(crate) struct StdDevModel;
+
+impl VolatilityModel for StdDevModel {
+    fn name(&self) -> &'static str {
+        "close-to-close stddev"
+    }
+    
+    fn estimate(&self, data: &PriceSeries) -> Result<Bps> {
+        let returns = data.log_returns();
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+        Ok(data.daily_bps(variance))
+    }
+}
+
+/// RiskMetrics-style EWMA: `var = lambda * var + (1 - lambda) * r^2`, seeded
+/// with the first squared return.
+pub(crate) struct EwmaModel {
+    pub(crate) lambda: f64,
+}
+
+impl VolatilityModel for EwmaModel {
+    fn name(&self) -> &'static str {
+        "EWMA"
+    }
+    
+    fn estimate(&self, data: &PriceSeries) -> Result<Bps> {
+        let returns = data.log_returns();
+        let variance = returns[1..].iter().fold(returns[0].powi(2), |var, r| {
+            self.lambda * var + (1.0 - self.lambda) * r.powi(2)
+        });
+        Ok(data.daily_bps(variance))
+    }
+}
+
+/// Parkinson (1980): `var = mean(ln(high / low)^2) / (4 ln 2)`; every row
+/// needs `high >= low > 0`.
+pub(crate) struct ParkinsonModel;
+
+impl VolatilityModel for ParkinsonModel {
+    fn name(&self) -> &'static str {
+        "Parkinson high-low"
+    }
+    
+    fn requires_range(&self) -> bool {
+        true
+    }
+    
+    fn estimate(&self, data: &PriceSeries) -> Result<Bps> {
+        let (Some(high), Some(low)) = (&data.high, &data.low) else {
+            return Ok(Bps::ZERO);
+        };
+        if let Some(row) = high.iter().zip(low).position(|(h, l)| !(*l > 0.0 && h >= l)) {
+            // Reported as the file line, after the header row
+            return Err(eyre::eyre!("Line {} has high {} below low {} or a non-positive low", row + 2, high[row], low[row]));
+        }
+        let sum: f64 = high.iter().zip(low).map(|(h, l)| (h / l).ln().powi(2)).sum();
+        let variance = sum / (high.len() as f64 * 4.0 * std::f64::consts::LN_2);
+        Ok(data.daily_bps(variance))
+    }
+}
+
+/// Fewest returns a GARCH(1,1) fit accepts; shorter series leave its three
+/// parameters to noise.
+const MIN_GARCH_RETURNS: usize = 30;
+/// Fits whose `alpha + beta` reach this are integrated rather than stationary:
+/// shocks never die out and there is no long-run variance to revert to.
+const MAX_GARCH_PERSISTENCE: f64 = 0.999;
+/// Step at which the likelihood search stops refining
+const GARCH_TOLERANCE: f64 = 1e-6;
+const GARCH_MAX_ITERATIONS: usize = 2000;
+
+/// GARCH(1,1): `var[t+1] = omega + alpha * r[t]^2 + beta * var[t]`, fitted by
+/// maximum likelihood with the variance targeted at the sample variance, so
+/// `omega = sample * (1 - alpha - beta)`. The estimate is the forecast for the
+/// row after the series, which reflects volatility clustering where EWMA
+/// reverts to nothing and stddev weighs all rows alike.
+pub(crate) struct GarchModel;
+
+/// Fitted GARCH(1,1) parameters, in per-row variance units.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GarchFit {
+    pub(crate) omega: f64,
+    pub(crate) alpha: f64,
+    pub(crate) beta: f64,
+    /// Variance the forecasts revert to
+    pub(crate) long_run_variance: f64,
+    /// Conditional variance forecast for the row after the series
+    pub(crate) next_variance: f64,
+    pub(crate) log_likelihood: f64,
+}
+
+impl GarchFit {
+    fn persistence(&self) -> f64 {
+        self.alpha + self.beta
+    }
+
+    /// Rows for a shock's effect on the variance to halve; `None` when it
+    /// is gone by the next row.
+    fn half_life(&self) -> Option<f64> {
+        (self.persistence() > 0.0).then(|| 0.5f64.ln() / self.persistence().ln())
+    }
+}
+
+/// Runs the GARCH recursion over demeaned returns, returning the negative
+/// log-likelihood (constant dropped) and the next row's variance. Parameters
+/// outside the stationary region score infinitely badly.
+fn garch_filter(residuals: &[f64], sample_variance: f64, alpha: f64, beta: f64) -> (f64, f64) {
+    if alpha < 0.0 || beta < 0.0 || alpha + beta >= MAX_GARCH_PERSISTENCE {
+        return (f64::INFINITY, sample_variance);
+    }
+    let omega = sample_variance * (1.0 - alpha - beta);
+    let mut variance = sample_variance;
+    let mut cost = 0.0;
+    for residual in residuals {
+        cost += variance.ln() + residual * residual / variance;
+        variance = omega + alpha * residual * residual + beta * variance;
+    }
+    (cost / 2.0, variance)
+}
+
+impl GarchModel {
+    pub(crate) fn fit(returns: &[f64]) -> Result<GarchFit> {
+        if returns.len() < MIN_GARCH_RETURNS {
+            return Err(eyre::eyre!(
+                "GARCH(1,1) needs at least {} returns ({} prices), found {}",
+                MIN_GARCH_RETURNS, MIN_GARCH_RETURNS + 1, returns.len()
+            ));
+        }
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let residuals: Vec<f64> = returns.iter().map(|r| r - mean).collect();
+        let sample_variance = residuals.iter().map(|e| e * e).sum::<f64>() / residuals.len() as f64;
+        if !(sample_variance > 0.0 && sample_variance.is_finite()) {
+            return Err(eyre::eyre!("Prices never move, leaving no variance for GARCH(1,1) to model"));
+        }
+        let cost = |(alpha, beta): (f64, f64)| garch_filter(&residuals, sample_variance, alpha, beta).0;
+
+        // A coarse grid picks the starting point, so the search below does not
+        // settle in a local dip far from the best fit
+        let mut best = (0.0, 0.0);
+        let mut best_cost = cost(best);
+        for a in 0..=10 {
+            for b in 0..20 {
+                let point = (a as f64 * 0.05, b as f64 * 0.05);
+                let point_cost = cost(point);
+                if point_cost < best_cost {
+                    (best, best_cost) = (point, point_cost);
+                }
+            }
+        }
+
+        // Pattern search: move a step along whichever parameter improves the
+        // fit most, halving the step once neither does
+        let mut step = 0.025;
+        let mut iterations = 0;
+        while step > GARCH_TOLERANCE {
+            iterations += 1;
+            if iterations > GARCH_MAX_ITERATIONS {
+                return Err(eyre::eyre!(
+                    "GARCH(1,1) fit did not converge in {} iterations; use --model ewma instead",
+                    GARCH_MAX_ITERATIONS
+                ));
+            }
+            let (alpha, beta) = best;
+            let moves = [(alpha + step, beta), (alpha - step, beta), (alpha, beta + step), (alpha, beta - step)];
+            match moves.into_iter().map(|point| (cost(point), point)).min_by(|x, y| x.0.total_cmp(&y.0)) {
+                Some((point_cost, point)) if point_cost < best_cost => (best, best_cost) = (point, point_cost),
+                _ => step /= 2.0,
+            }
+        }
+
+        let (alpha, beta) = best;
+        if !best_cost.is_finite() || alpha + beta > MAX_GARCH_PERSISTENCE - 1e-3 {
+            return Err(eyre::eyre!(
+                "GARCH(1,1) fit did not converge to a stationary model (alpha + beta = {:.4}); shocks in this series never die out, use --model ewma instead",
+                alpha + beta
+            ));
+        }
+        let (_, next_variance) = garch_filter(&residuals, sample_variance, alpha, beta);
+        let n = residuals.len() as f64;
+        Ok(GarchFit {
+            omega: sample_variance * (1.0 - alpha - beta),
+            alpha,
+            beta,
+            long_run_variance: sample_variance,
+            next_variance,
+            log_likelihood: -best_cost - n / 2.0 * (2.0 * std::f64::consts::PI).ln(),
+        })
+    }
+}
+
+impl VolatilityModel for GarchModel {
+    fn name(&self) -> &'static str {
+        "GARCH(1,1)"
+    }
+
+    fn estimate(&self, data: &PriceSeries) -> Result<Bps> {
+        let fit = GarchModel::fit(&data.log_returns())?;
+        Ok(data.daily_bps(fit.next_variance))
+    }
+}
+
+/// Rolling daily volatility, one value per window step, as written by
+/// `estimate --window` and read by `backtest --volatility`.
+pub(crate) struct VolatilitySeries {
+    /// Index of the last price row in each estimate's window
+    pub(crate) rows: Vec<usize>,
+    pub(crate) timestamps: Option<Vec<u64>>,
+    pub(crate) volatility: Vec<Bps>,
+}
+
+impl VolatilitySeries {
+    /// Keeps the rows of `estimates` that have a value.
+    fn from_estimates(estimates: &[Option<Bps>], timestamps: Option<&[u64]>) -> Self {
+        let rows: Vec<usize> = estimates.iter().enumerate().filter(|(_, e)| e.is_some()).map(|(row, _)| row).collect();
+        VolatilitySeries {
+            timestamps: timestamps.map(|timestamps| rows.iter().map(|&row| timestamps[row]).collect()),
+            volatility: estimates.iter().flatten().copied().collect(),
+            rows,
+        }
+    }
+    
+    fn to_csv(&self) -> String {
+        let mut csv = match self.timestamps {
+            Some(_) => "row,timestamp,volatility_bps\n".to_string(),
+            None => "row,volatility_bps\n".to_string(),
+        };
+        for (i, (row, volatility)) in self.rows.iter().zip(&self.volatility).enumerate() {
+            match &self.timestamps {
+                Some(timestamps) => csv.push_str(&format!("{},{},{}\n", row, timestamps[i], volatility)),
+                None => csv.push_str(&format!("{},{}\n", row, volatility)),
+            }
+        }
+        csv
+    }
+    
+    /// Loads a CSV with a `volatility_bps` column and optional `row` and
+    /// `timestamp` columns.
+    pub(crate) fn load(file: &str) -> Result<Self> {
+        let content = fs::read_to_string(file)
+            .map_err(|_| eyre::eyre!("Could not read file: {}", file))?;
+        let mut lines = content.lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        
+        let header: Vec<String> = lines.next()
+            .map(|(_, line)| line.split(',').map(|c| c.trim().to_lowercase()).collect())
+            .ok_or_else(|| eyre::eyre!("No volatility rows in {}", file))?;
+        let column = |name: &str| header.iter().position(|c| c == name);
+        let volatility_col = column("volatility_bps")
+            .ok_or_else(|| eyre::eyre!("{} has no volatility_bps column; write one with `volatility estimate --window`", file))?;
+        let row_col = column("row");
+        let timestamp_col = column("timestamp");
+        
+        let mut series = VolatilitySeries {
+            rows: Vec::new(),
+            timestamps: timestamp_col.map(|_| Vec::new()),
+            volatility: Vec::new(),
+        };
+        for (line_no, line) in lines {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |col: usize| fields.get(col).copied().unwrap_or_default();
+            let volatility = field(volatility_col).parse::<Bps>()
+                .map_err(|e| eyre::eyre!("Line {} of {}: {}", line_no, file, e))?;
+            let row = match row_col {
+                Some(col) => field(col).parse()
+                    .map_err(|_| eyre::eyre!("Invalid row on line {} of {}: {}", line_no, file, field(col)))?,
+                None => series.rows.len(),
+            };
+            if let (Some(col), Some(timestamps)) = (timestamp_col, &mut series.timestamps) {
+                let timestamp = field(col).parse()
+                    .map_err(|_| eyre::eyre!("Invalid timestamp on line {} of {}: {}", line_no, file, field(col)))?;
+                timestamps.push(timestamp);
+            }
+            series.rows.push(row);
+            series.volatility.push(volatility);
+        }
+        
+        if series.volatility.is_empty() {
+            return Err(eyre::eyre!("No volatility rows in {}", file));
+        }
+        Ok(series)
+    }
+}
+
+async fn estimate_volatility(
+    file: &str,
+    kind: ModelKind,
+    lambda: f64,
+    periods_per_day: f64,
+    rolling: Option<(usize, String)>,
+    assume_yes: bool,
+) -> Result<()> {
+    if !(0.0..1.0).contains(&lambda) {
+        return Err(eyre::eyre!("Lambda must be in [0, 1)"));
+    }
+    if !(periods_per_day.is_finite() && periods_per_day > 0.0) {
+        return Err(eyre::eyre!("Periods per day must be positive"));
+    }
+    
+    let model = kind.build(lambda);
+    let series = PriceSeries::load(file, periods_per_day)?;
+    if model.requires_range() && series.high.is_none() {
+        return Err(eyre::eyre!("The {} model needs high and low columns in {}", model.name(), file));
+    }
+    if let Some((window, output)) = rolling {
+        return write_rolling_estimates(&series, model.as_ref(), file, window, &output, assume_yes);
+    }
+    
+    let daily = model.estimate(&series)?;
+    
+    println!("{} {}", "📐 Estimating volatility from:".cyan(), file.yellow());
+    println!("  • Model: {}", model.name());
+    println!("  • Rows: {} ({} per day)", series.close.len(), periods_per_day);
+    println!("  • Daily volatility: {}bps", daily.to_string().yellow().bold());
+    if let ModelKind::Garch = kind {
+        let fit = GarchModel::fit(&series.log_returns())?;
+        println!("  • Long-run daily volatility: {}bps", series.daily_bps(fit.long_run_variance));
+        println!("  • Parameters: omega {:.3e}, alpha {:.4}, beta {:.4}", fit.omega, fit.alpha, fit.beta);
+        match fit.half_life() {
+            Some(rows) => println!("  • Persistence: {:.4} (shocks halve in {:.1} rows)", fit.persistence(), rows),
+            None => println!("  • Persistence: 0 (no volatility clustering)"),
+        }
+        println!("  • Log-likelihood: {:.2}", fit.log_likelihood);
+    }
+    println!();
+    println!("{}", "🚀 Next steps:".bold());
+    println!("  {} vector-plus volatility create-config --current-volatility {}", "•".blue(), daily);
+    
+    Ok(())
+}
+
+/// Inputs of `volatility var`.
+struct VarInputs<'a> {
+    file: &'a str,
+    model: ModelKind,
+    lambda: f64,
+    periods_per_day: f64,
+    window: Option<usize>,
+    confidence: f64,
+    horizon_days: u32,
+    notional: f64,
+}
+
+/// Potential loss on a notional over a horizon, at a confidence level.
+#[derive(Debug, Serialize)]
+struct VarReport {
+    model: &'static str,
+    rows: usize,
+    daily_volatility: Bps,
+    confidence_pct: f64,
+    horizon_days: u32,
+    notional: f64,
+    /// Normal-distribution VaR from the estimated volatility
+    parametric_var: f64,
+    /// Loss at the confidence quantile of the observed returns over the horizon,
+    /// when there are enough of them
+    historical_var: Option<f64>,
+    historical_samples: usize,
+}
+
+impl VarReport {
+    fn compute(series: &PriceSeries, model: &dyn VolatilityModel, inputs: &VarInputs<'_>) -> Result<Self> {
+        let daily_volatility = model.estimate(series)?;
+        let z = super::options::normal_quantile(inputs.confidence / 100.0);
+        let horizon_sigma = daily_volatility.as_f64() / 10_000.0 * (inputs.horizon_days as f64).sqrt();
+        
+        // Overlapping returns over as many rows as the horizon spans
+        let step = ((inputs.horizon_days as f64 * series.periods_per_day).round() as usize).max(1);
+        let mut returns: Vec<f64> = series.close.iter()
+            .zip(series.close.iter().skip(step))
+            .map(|(start, end)| end / start - 1.0)
+            .collect();
+        returns.sort_by(f64::total_cmp);
+        let tail = 1.0 - inputs.confidence / 100.0;
+        // The quantile needs at least one return beyond it
+        let historical_var = (returns.len() as f64 * tail >= 1.0).then(|| {
+            let quantile = returns[(returns.len() as f64 * tail).floor() as usize - 1];
+            (-quantile).max(0.0) * inputs.notional
+        });
+        
+        Ok(VarReport {
+            model: model.name(),
+            rows: series.close.len(),
+            daily_volatility,
+            confidence_pct: inputs.confidence,
+            horizon_days: inputs.horizon_days,
+            notional: inputs.notional,
+            parametric_var: inputs.notional * z * horizon_sigma,
+            historical_var,
+            historical_samples: returns.len(),
+        })
+    }
+}
+
+fn value_at_risk(inputs: &VarInputs<'_>, json: bool, precision: u8) -> Result<()> {
+    if !(50.0..100.0).contains(&inputs.confidence) {
+        return Err(eyre::eyre!("Confidence must be at least 50% and below 100%"));
+    }
+    if inputs.horizon_days == 0 {
+        return Err(eyre::eyre!("Horizon must be at least 1 day"));
+    }
+    if !(0.0..1.0).contains(&inputs.lambda) {
+        return Err(eyre::eyre!("Lambda must be in [0, 1)"));
+    }
+    
+    let model = inputs.model.build(inputs.lambda);
+    let mut series = PriceSeries::load(inputs.file, inputs.periods_per_day)?;
+    if model.requires_range() && series.high.is_none() {
+        return Err(eyre::eyre!("The {} model needs high and low columns in {}", model.name(), inputs.file));
+    }
+    let total_rows = series.close.len();
+    if let Some(window) = inputs.window {
+        if window < 3 {
+            return Err(eyre::eyre!("Window must hold at least 3 prices"));
+        }
+        series = series.rows(total_rows.saturating_sub(window), total_rows);
+    }
+    let report = VarReport::compute(&series, model.as_ref(), inputs)?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    
+    let share = |var: f64| var / inputs.notional * 100.0;
+    println!("{} {}", "📉 Value-at-Risk from:".cyan(), inputs.file.yellow());
+    println!("  • Model: {}", report.model);
+    match inputs.window {
+        Some(_) => println!("  • Rows: last {} of {} ({} per day)", report.rows, total_rows, inputs.periods_per_day),
+        None => println!("  • Rows: {} ({} per day)", report.rows, inputs.periods_per_day),
+    }
+    println!("  • Daily volatility: {}bps", report.daily_volatility);
+    println!(
+        "  • Confidence: {}%, horizon: {} day{}",
+        report.confidence_pct, report.horizon_days, if report.horizon_days == 1 { "" } else { "s" }
+    );
+    println!("  • Notional: {}", format_amount(report.notional, precision));
+    println!();
+    println!(
+        "{} {} ({:.2}% of notional)",
+        "💥 Parametric VaR:".bold(),
+        format_amount(report.parametric_var, precision).red().bold(),
+        share(report.parametric_var)
+    );
+    match report.historical_var {
+        Some(var) => println!(
+            "{} {} ({:.2}% of notional, from {} returns)",
+            "📜 Historical VaR:".bold(),
+            format_amount(var, precision).red(),
+            share(var),
+            report.historical_samples
+        ),
+        None => println!(
+            "{} needs at least {:.0} returns over the horizon, found {}",
+            "📜 Historical VaR:".bold(),
+            (1.0 / (1.0 - report.confidence_pct / 100.0)).ceil(),
+            report.historical_samples
+        ),
+    }
+    println!();
+    println!(
+        "  {} With {}% confidence the position loses no more than this over {} day{}",
+        "ℹ️ ".blue(),
+        report.confidence_pct,
+        report.horizon_days,
+        if report.horizon_days == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+fn write_rolling_estimates(
+    series: &PriceSeries,
+    model: &dyn VolatilityModel,
+    file: &str,
+    window: usize,
+    output: &str,
+    assume_yes: bool,
+) -> Result<()> {
+    let rows = series.close.len();
+    if window < 3 {
+        return Err(eyre::eyre!("Window must hold at least 3 prices"));
+    }
+    if window >= rows {
+        return Err(eyre::eyre!("Window of {} rows must be smaller than the {} rows in {}", window, rows, file));
+    }
+    
+    let estimates = series.rolling(model, window, window)?;
+    let rolling = VolatilitySeries::from_estimates(&estimates, series.timestamps.as_deref());
+    confirm_overwrite(output, assume_yes)?;
+    fs::write(output, rolling.to_csv())?;
+    
+    let values = &rolling.volatility;
+    let mean = values.iter().map(|v| v.as_f64()).sum::<f64>() / values.len() as f64;
+    let (min_at, min) = values.iter().enumerate().min_by_key(|(_, v)| **v).expect("window is smaller than the data");
+    let (max_at, max) = values.iter().enumerate().max_by_key(|(_, v)| **v).expect("window is smaller than the data");
+    let latest = values[values.len() - 1];
+    let at = |i: usize| match &rolling.timestamps {
+        Some(timestamps) => format!("row {}, timestamp {}", rolling.rows[i], timestamps[i]),
+        None => format!("row {}", rolling.rows[i]),
+    };
+    
+    println!("{} {}", "📐 Rolling volatility from:".cyan(), file.yellow());
+    println!("  • Model: {}", model.name());
+    println!("  • Window: {} rows ({} per day), {} estimates", window, series.periods_per_day, values.len());
+    println!("  • Mean: {:.2}bps", mean);
+    println!("  • Min: {}bps ({})", min, at(min_at));
+    println!("  • Max: {}bps ({})", max, at(max_at));
+    println!("  • Latest: {}bps", latest.to_string().yellow().bold());
+    println!("{} {}", "✅ Series written to:".green(), output);
+    println!();
+    println!("{}", "🚀 Next steps:".bold());
+    println!("  {} vector-plus volatility backtest --volatility {} --amount <ETH>", "•".blue(), output);
+    
+    Ok(())
+}
+
+/// Where a backtest gets the volatility at each row.
+enum BacktestSource<'a> {
+    /// Trailing close-to-close estimates over a price CSV
+    Prices { file: &'a str, window: usize, periods_per_day: f64 },
+    /// A series written by `estimate --window`
+    Series(&'a str),
+}
+
+/// Rows to evaluate in a backtest, by timestamp or by index.
+enum BacktestRange {
+    /// Inclusive timestamp bounds; requires a `timestamp` column
+    Time(Option<u64>, Option<u64>),
+    /// `start..end` row indices
+    Rows(Option<usize>, Option<usize>),
+}
+
+impl BacktestRange {
+    /// Resolves to a non-empty `start..end` range within `rows` rows; time
+    /// bounds need ascending timestamps.
+    fn resolve(&self, rows: usize, timestamps: Option<&[u64]>, file: &str) -> Result<(usize, usize)> {
+        let (start, end) = match *self {
+            BacktestRange::Rows(start, end) => {
+                let (start, end) = (start.unwrap_or(0), end.unwrap_or(rows));
+                if end > rows {
+                    return Err(eyre::eyre!("--end {} is past the last row ({} rows in {})", end, rows, file));
+                }
+                (start, end)
+            }
+            BacktestRange::Time(since, until) => {
+                let timestamps = timestamps.ok_or_else(|| {
+                    eyre::eyre!("--since/--until need a timestamp column in {}", file)
+                })?;
+                let since = since.unwrap_or(0);
+                let until = until.unwrap_or(u64::MAX);
+                if since > until {
+                    return Err(eyre::eyre!("--since {} is after --until {}", since, until));
+                }
+                // Rows stay aligned with their estimates, so out-of-order
+                // timestamps are refused rather than sorted.
+                if let Some(row) = timestamps.windows(2).position(|pair| pair[1] < pair[0]) {
+                    return Err(eyre::eyre!(
+                        "Timestamps in {} must be ascending: line {} ({}) comes before line {} ({})",
+                        file, row + 2, timestamps[row], row + 3, timestamps[row + 1]
+                    ));
+                }
+                let start = timestamps.iter().position(|&t| t >= since).unwrap_or(rows);
+                let end = timestamps.iter().rposition(|&t| t <= until).map_or(0, |i| i + 1);
+                (start, end)
+            }
+        };
+        
+        if start >= end {
+            return Err(eyre::eyre!("Backtest window is empty ({} rows in {})", rows, file));
+        }
+        Ok((start, end))
+    }
+}
+
+async fn backtest_volatility(
+    source: BacktestSource<'_>,
+    config_file: &str,
+    amount: Amount,
+    range: BacktestRange,
+    precision: u8,
+) -> Result<()> {
+    let config: VolatilityConfig = read_config(config_file)?;
+    
+    // Estimates may look back before `start`, so a window isolates the rows
+    // evaluated without starving the first estimates of history.
+    let (file, estimates, timestamps) = match source {
+        BacktestSource::Prices { file, window, periods_per_day } => {
+            if window < 3 {
+                return Err(eyre::eyre!("Window must hold at least 3 prices"));
+            }
+            if !(periods_per_day.is_finite() && periods_per_day > 0.0) {
+                return Err(eyre::eyre!("Periods per day must be positive"));
+            }
+            let series = PriceSeries::load(file, periods_per_day)?;
+            (file, series.rolling(&StdDevModel, window, 3)?, series.timestamps)
+        }
+        BacktestSource::Series(file) => {
+            let series = VolatilitySeries::load(file)?;
+            (file, series.volatility.into_iter().map(Some).collect(), series.timestamps)
+        }
+    };
+    let (start, end) = range.resolve(estimates.len(), timestamps.as_deref(), file)?;
+    
+    println!("{} {}", "⏪ Backtesting volatility sizing over:".cyan(), file.yellow());
+    match &timestamps {
+        Some(timestamps) => println!(
+            "  • Window: rows {}..{} (timestamps {} to {})",
+            start, end, timestamps[start], timestamps[end - 1]
+        ),
+        None => println!("  • Window: rows {}..{} of {}", start, end, estimates.len()),
+    }
+    match source {
+        BacktestSource::Prices { window, .. } => println!(
+            "  • Order amount: {} ETH, volatility window: {} rows", amount.format_eth(precision), window
+        ),
+        BacktestSource::Series(_) => println!(
+            "  • Order amount: {} ETH, precomputed volatility series", amount.format_eth(precision)
+        ),
+    }
+    println!();
+    
+    let mut regimes: Vec<(Regime, usize)> = Vec::new();
+    let mut sizes = Vec::new();
+    let mut capped = 0;
+    let mut warming_up = 0;
+    for estimate in &estimates[start..end] {
+        let Some(volatility) = *estimate else {
+            warming_up += 1;
+            continue;
+        };
+        let mut current = config.clone();
+        current.current_volatility = volatility;
+        let regime = Regime::classify(&current);
+        match regimes.iter_mut().find(|(r, _)| *r == regime) {
+            Some((_, count)) => *count += 1,
+            None => regimes.push((regime, 1)),
+        }
+        let (adjusted, sized) = size_execution(&current, amount);
+        if sized != adjusted {
+            capped += 1;
+        }
+        sizes.push(sized);
+    }
+    
+    if sizes.is_empty() {
+        return Err(eyre::eyre!("No row in the window has 3 prices of history to estimate from"));
+    }
+    
+    let average = Amount::from_wei(sizes.iter().map(|size: &Amount| size.wei()).sum::<u128>() / sizes.len() as u128);
+    let smallest = sizes.iter().copied().min().unwrap_or_default();
+    let largest = sizes.iter().copied().max().unwrap_or_default();
+    
+    println!("📊 Backtest Results ({} rows evaluated):", sizes.len());
+    if warming_up > 0 {
+        println!("  • Skipped {} rows without 3 prices of history", warming_up);
+    }
+    regimes.sort_by_key(|(regime, _)| *regime as u8);
+    for (regime, count) in &regimes {
+        println!("  • {}: {} rows ({:.1}%)", regime.label(), count, *count as f64 * 100.0 / sizes.len() as f64);
+    }
+    println!();
+    println!("💰 Sized Amounts:");
+    println!("  • Average: {} ETH", average.format_eth(precision));
+    println!("  • Min: {} ETH", smallest.format_eth(precision));
+    println!("  • Max: {} ETH", largest.format_eth(precision));
+    println!("  • Clamped to limits: {} rows", capped);
+    
+    Ok(())
+}
+
+/// Scenarios file read by `volatility stress`.
+#[derive(Debug, Deserialize)]
+struct StressScenarios {
+    scenarios: Vec<StressScenario>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StressScenario {
+    name: String,
+    current_volatility: Bps,
+}
+
+/// What the on-chain calculator does in a regime: above the emergency
+/// threshold it reverts with `EmergencyModeTriggered`, so no fill goes through.
+fn emergency_action(regime: Regime) -> &'static str {
+    match regime {
+        Regime::Emergency => "halt",
+        _ => "none",
+    }
+}
+
+/// Sizes `amount` under the config's own current volatility and then under
+/// each scenario's, one table row apiece.
+fn stress_table(config: &VolatilityConfig, scenarios: &[StressScenario], amount: Amount, precision: u8) -> Table {
+    let mut table = Table::new()
+        .column("scenario", "Scenario", Align::Left)
+        .column("current_volatility", "Volatility", Align::Right)
+        .column("regime", "Regime", Align::Left)
+        .column("factor_pct", "Factor", Align::Right)
+        .column("final_amount", "Final (ETH)", Align::Right)
+        .column("emergency_action", "Emergency", Align::Left);
+    let current = ("(config)", config.current_volatility);
+    let rows = std::iter::once(current)
+        .chain(scenarios.iter().map(|scenario| (scenario.name.as_str(), scenario.current_volatility)));
+    for (name, volatility) in rows {
+        let mut shocked = config.clone();
+        shocked.current_volatility = volatility;
+        let regime = Regime::classify(&shocked);
+        let factor = volatility_adjustment_factor(&shocked);
+        let (_, final_amount) = size_execution(&shocked, amount);
+        let cells = vec![
+            Cell::text(name),
+            Cell::new(format!("{}bps", volatility), volatility.as_f64()),
+            Cell::text(regime.name()),
+            Cell::new(format!("{}%", factor), factor),
+            Cell::new(final_amount.format_eth(precision), final_amount.as_eth_f64()),
+            Cell::text(emergency_action(regime)),
+        ];
+        match regime {
+            Regime::Emergency => table.colored_row(cells, Color::Red),
+            Regime::Elevated => table.colored_row(cells, Color::Yellow),
+            _ => table.row(cells),
+        }
+    }
+    table
+}
+
+fn stress_test(config_file: &str, scenarios_file: &str, amount: Amount, format: OutputFormat, precision: u8) -> Result<()> {
+    let config: VolatilityConfig = read_config(config_file)?;
+    let StressScenarios { scenarios } = read_config(scenarios_file)?;
+    if scenarios.is_empty() {
+        return Err(eyre::eyre!("Scenarios file {} has no scenarios", scenarios_file));
+    }
+    let table = stress_table(&config, &scenarios, amount, precision);
+    
+    if format.is_machine() {
+        println!("{}", table.format(format));
+        return Ok(());
+    }
+    
+    println!("{} {}", "🌪️  Stress testing volatility sizing for:".cyan(), config_file.yellow());
+    println!("  • Order amount: {} ETH", amount.format_eth(precision));
+    println!(
+        "  • Baseline {}bps, threshold {}bps, emergency {}bps",
+        config.baseline_volatility, config.volatility_threshold, config.emergency_threshold
+    );
+    println!();
+    println!("{}", table.render());
+    
+    let halted: Vec<&str> = scenarios.iter()
+        .filter(|scenario| scenario.current_volatility > config.emergency_threshold)
+        .map(|scenario| scenario.name.as_str())
+        .collect();
+    println!();
+    if halted.is_empty() {
+        println!("{}", "✅ No scenario crosses the emergency threshold".green());
+    } else {
+        println!(
+            "{} {} of {} scenarios halt execution: {}",
+            "🚨".red(), halted.len(), scenarios.len(), halted.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Final size of each amount (rows) under each volatility (columns); `None`
+/// where the volatility crosses the emergency threshold and fills halt.
+fn heatmap_grid(config: &VolatilityConfig, amounts: &[Amount], volatilities: &[Bps]) -> Vec<Vec<Option<Amount>>> {
+    let shocked: Vec<VolatilityConfig> = volatilities.iter()
+        .map(|&volatility| {
+            let mut shocked = config.clone();
+            shocked.current_volatility = volatility;
+            shocked
+        })
+        .collect();
+    amounts.iter()
+        .map(|&amount| {
+            shocked.iter()
+                .map(|shocked| match Regime::classify(shocked) {
+                    Regime::Emergency => None,
+                    _ => Some(size_execution(shocked, amount).1),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Final size as a percent of the requested amount, after the execution limits.
+fn effective_factor(amount: Amount, final_amount: Amount) -> f64 {
+    final_amount.as_eth_f64() / amount.as_eth_f64() * 100.0
+}
+
+fn heatmap(config_file: &str, amounts: &[Amount], volatilities: &[Bps], factor: bool, json: bool, precision: u8) -> Result<()> {
+    let config: VolatilityConfig = read_config(config_file)?;
+    if amounts.iter().any(|amount| amount.is_zero()) {
+        return Err(VectorPlusError::Validation("--amounts must all be greater than zero".to_string()).into());
+    }
+    let grid = heatmap_grid(&config, amounts, volatilities);
+    let value = |amount: Amount, cell: Option<Amount>| {
+        cell.map(|final_amount| if factor { effective_factor(amount, final_amount) } else { final_amount.as_eth_f64() })
+    };
+    
+    if json {
+        let values: Vec<Vec<Option<f64>>> = amounts.iter().zip(&grid)
+            .map(|(&amount, row)| row.iter().map(|&cell| value(amount, cell)).collect())
+            .collect();
+        let output = serde_json::json!({
+            "metric": if factor { "factor_pct" } else { "final_amount" },
+            "amounts": amounts.iter().map(|amount| amount.as_eth_f64()).collect::<Vec<_>>(),
+            "volatilities_bps": volatilities.iter().map(|volatility| volatility.as_f64()).collect::<Vec<_>>(),
+            "values": values,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    
+    let metric = if factor { "effective factor" } else { "final size in ETH" };
+    println!("{} {} ({})", "🌡️  Volatility heatmap for:".cyan(), config_file.yellow(), metric);
+    println!(
+        "  • Baseline {}bps, threshold {}bps, emergency {}bps",
+        config.baseline_volatility, config.volatility_threshold, config.emergency_threshold
+    );
+    println!();
+    
+    let labels: Vec<String> = amounts.iter().map(|amount| format!("{} ETH", amount.format_eth(precision))).collect();
+    let cells: Vec<Vec<String>> = amounts.iter().zip(&grid)
+        .map(|(&amount, row)| {
+            row.iter()
+                .map(|&cell| match (cell, factor) {
+                    (None, _) => "halt".to_string(),
+                    (Some(final_amount), true) => format!("{:.1}%", effective_factor(amount, final_amount)),
+                    (Some(final_amount), false) => final_amount.format_eth(precision),
+                })
+                .collect()
+        })
+        .collect();
+    let headers: Vec<String> = volatilities.iter().map(|volatility| format!("{}bps", volatility)).collect();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let width = headers.iter().chain(cells.iter().flatten()).map(String::len).max().unwrap_or(0);
+    
+    let header: Vec<String> = headers.iter().map(|h| format!("{:>width$}", h)).collect();
+    println!("{:label_width$}  {}", "", header.join("  ").bold());
+    for ((label, row), (&amount, values)) in labels.iter().zip(&cells).zip(amounts.iter().zip(&grid)) {
+        let row: Vec<String> = row.iter().zip(values)
+            .map(|(text, &cell)| {
+                let text = format!("{:>width$}", text);
+                match cell {
+                    None => text.red().to_string(),
+                    Some(final_amount) if final_amount > amount => text.green().to_string(),
+                    Some(final_amount) if final_amount < amount => text.yellow().to_string(),
+                    Some(_) => text,
+                }
+            })
+            .collect();
+        println!("{:>label_width$}  {}", label, row.join("  "));
+    }
+    println!();
+    println!(
+        "  {} above the amount  {} below it  ■ unchanged  {} emergency halt",
+        "■".green(), "■".yellow(), "■".red()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config with the given min and max execution sizes, as written in
+    /// the file; shared with the portfolio tests.
+    pub(super) fn config_json(max: &str, min: &str) -> String {
+        format!(
+            r#"{{"baseline_volatility":300,"current_volatility":350,"max_execution_size":"{}","min_execution_size":"{}","volatility_threshold":600,"conservative_mode":false,"emergency_threshold":1200,"last_update_time":0}}"#,
+            max, min
+        )
+    }
+
+    #[test]
+    fn parses_wei_fields() {
+        assert_eq!(parse_wei_field("max_execution_size", "5000000000000000000").unwrap(), 5_000_000_000_000_000_000);
+        assert_eq!(parse_wei_field("max_execution_size", "100.000000000000000000").unwrap(), 100);
+    }
+
+    #[test]
+    fn rejects_malformed_wei_fields() {
+        for value in ["", "abc", "5e18", "-1", "0", "0.0", "1.5", " 1", "340282366920938463463374607431768211456"] {
+            let err = parse_wei_field("min_execution_size", value).unwrap_err();
+            assert_eq!(err.field, "min_execution_size");
+            assert_eq!(err.value, value);
+        }
+    }
+
+    #[test]
+    fn loading_reports_the_bad_field() {
+        let err = serde_json::from_str::<VolatilityConfig>(&config_json("5 ETH", "100")).unwrap_err();
+        assert!(err.to_string().contains("invalid max_execution_size \"5 ETH\""), "{}", err);
+
+        let err = serde_json::from_str::<VolatilityConfig>(&config_json("100", "0")).unwrap_err();
+        assert!(err.to_string().contains("min_execution_size"), "{}", err);
+
+        assert!(serde_json::from_str::<VolatilityConfig>(&config_json("5000", "100")).is_ok());
+    }
+
+    #[test]
+    fn legacy_decimal_wei_is_saved_as_an_integer_but_fails_validation() {
+        let legacy = config_json("5000000000000000000.000000000000000000", "100000000000000000");
+        let config: VolatilityConfig = serde_json::from_str(&legacy).unwrap();
+        let errors = wei_format_errors(&config);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("max_execution_size"), "{}", errors[0]);
+
+        let saved = serde_json::to_value(&config).unwrap();
+        assert_eq!(saved["max_execution_size"], "5000000000000000000");
+        assert_eq!(saved["min_execution_size"], "100000000000000000");
+        let reloaded: VolatilityConfig = serde_json::from_value(saved).unwrap();
+        assert!(wei_format_errors(&reloaded).is_empty());
+    }
+
+    #[test]
+    fn decodes_oracle_rounds_and_rejects_stale_ones() {
+        assert_eq!(hex::encode(selector("latestRoundData()")), "feaf968c");
+        assert_eq!(hex::encode(selector("decimals()")), "313ce567");
+
+        let round_data = |round: u64, answer: u64, updated_at: u64, answered_in: u64| {
+            let mut data = vec![0u8; 160];
+            for (i, value) in [round, answer, 0, updated_at, answered_in].into_iter().enumerate() {
+                data[i * 32 + 24..(i + 1) * 32].copy_from_slice(&value.to_be_bytes());
+            }
+            data
+        };
+        let round = decode_round(&round_data(7, 250_012_345_678, 1_000, 7), 8, 1_060, 3600).unwrap();
+        assert_eq!(round, OracleRound { round_id: 7, price: 2500.12345678, updated_at: 1_000 });
+
+        let stale = decode_round(&round_data(7, 250_000_000_000, 1_000, 7), 8, 10_000, 3600).unwrap_err();
+        assert!(stale.to_string().contains("9000s old"), "{}", stale);
+        assert!(decode_round(&round_data(7, 250_000_000_000, 1_000, 6), 8, 1_060, 3600).is_err());
+        assert!(decode_round(&round_data(7, 0, 1_000, 7), 8, 1_060, 3600).is_err());
+        assert!(decode_round(&round_data(7, 1, 0, 7), 8, 1_060, 3600).is_err());
+
+        let mut state = OracleState::default();
+        let at = |round_id: u128, price: f64| OracleRound { round_id, price, updated_at: round_id as u64 * 3600 };
+        assert_eq!(record_round(&mut state, at(1, 2000.0)), None);
+        assert_eq!(record_round(&mut state, at(1, 2000.0)), None);
+        assert_eq!(record_round(&mut state, at(2, 2010.0)), None);
+        assert!(record_round(&mut state, at(3, 1990.0)).is_some_and(|bps| bps > Bps::ZERO));
+        assert_eq!(state.rounds.len(), 3);
+        for round_id in 4..40 {
+            record_round(&mut state, at(round_id, 2000.0));
+        }
+        assert_eq!(state.rounds.len(), ORACLE_HISTORY);
+    }
+
+    fn source_of(sources: &[MergedField], field: &str) -> MergeSource {
+        sources.iter().find(|(f, _, _)| f == field).unwrap().2
+    }
+
+    #[test]
+    fn merges_non_default_overlay_fields() {
+        let base = serde_json::from_str::<Value>(&config_json("5000000000000000000", "100000000000000000")).unwrap();
+        let overlay = serde_json::json!({
+            "volatility_threshold": "6.5bps",
+            "conservative_mode": false,
+            "emergency_threshold": 1500,
+        });
+        let (merged, sources) = merge_volatility_values(&base, &overlay).unwrap();
+        let config: VolatilityConfig = serde_json::from_value(merged).unwrap();
+        assert_eq!(config.volatility_threshold, "6.5".parse().unwrap());
+        assert_eq!(config.emergency_threshold, Bps::from_bps(1500));
+        assert_eq!(config.baseline_volatility, Bps::from_bps(300));
+        assert_eq!(source_of(&sources, "volatility_threshold"), MergeSource::Overlay);
+        assert_eq!(source_of(&sources, "conservative_mode"), MergeSource::OverlayDefault);
+        assert_eq!(source_of(&sources, "current_volatility"), MergeSource::Base);
+
+        assert!(merge_volatility_values(&base, &serde_json::json!({ "typo": 1 })).is_err());
+        assert!(merge_volatility_values(&base, &serde_json::json!({ "max_execution_size": "5 ETH" })).is_err());
+
+        // A zero hysteresis margin is left out of the base, but the overlay may still set it
+        let (merged, sources) = merge_volatility_values(&base, &serde_json::json!({ "hysteresis_margin": 50 })).unwrap();
+        let config: VolatilityConfig = serde_json::from_value(merged).unwrap();
+        assert_eq!(config.hysteresis_margin, Bps::from_bps(50));
+        assert_eq!(source_of(&sources, "hysteresis_margin"), MergeSource::Overlay);
+        let (merged, _) = merge_volatility_values(&base, &serde_json::json!({ "hysteresis_margin": 0 })).unwrap();
+        assert!(merged.get("hysteresis_margin").is_none());
+    }
+
+    #[test]
+    fn var_scales_with_confidence_and_horizon() {
+        // Twenty daily returns of +1% and -2%, alternating
+        let mut close = vec![100.0];
+        for i in 0..20 {
+            let last = close[close.len() - 1];
+            close.push(last * if i % 2 == 0 { 1.01 } else { 0.98 });
+        }
+        let prices = PriceSeries { close, high: None, low: None, timestamps: None, periods_per_day: 1.0 };
+        let inputs = VarInputs {
+            file: "prices.csv",
+            model: ModelKind::Stddev,
+            lambda: 0.94,
+            periods_per_day: 1.0,
+            window: None,
+            confidence: 95.0,
+            horizon_days: 1,
+            notional: 1000.0,
+        };
+        let daily = VarReport::compute(&prices, &StdDevModel, &inputs).unwrap();
+        let sigma = daily.daily_volatility.as_f64() / 10_000.0;
+        assert!((daily.parametric_var - 1000.0 * 1.644854 * sigma).abs() < 1e-3);
+        assert_eq!(daily.historical_samples, 20);
+        assert!((daily.historical_var.unwrap() - 20.0).abs() < 1e-9);
+        
+        let weekly = VarReport::compute(&prices, &StdDevModel, &VarInputs { horizon_days: 4, ..inputs }).unwrap();
+        assert!((weekly.parametric_var - 2.0 * daily.parametric_var).abs() < 1e-9);
+        // 17 four-day returns are too few for a 5% tail
+        assert_eq!((weekly.historical_samples, weekly.historical_var), (17, None));
+    }
+
+    /// Closes whose log returns are exactly 0.01, 0.02 and -0.03.
+    fn known_returns(periods_per_day: f64) -> PriceSeries {
+        let close = [0.0, 0.01, 0.03, 0.0].iter().map(|r: &f64| 100.0 * r.exp()).collect();
+        PriceSeries { close, high: None, low: None, timestamps: None, periods_per_day }
+    }
+
+    #[test]
+    fn stddev_matches_the_sample_deviation() {
+        // Mean 0, sample variance (1 + 4 + 9)e-4 / 2 = 7e-4
+        assert_eq!(StdDevModel.estimate(&known_returns(1.0)).unwrap(), "264.58".parse().unwrap());
+        // Four rows a day double the daily figure
+        assert_eq!(StdDevModel.estimate(&known_returns(4.0)).unwrap(), "529.15".parse().unwrap());
+    }
+
+    #[test]
+    fn ewma_decays_from_the_first_squared_return() {
+        // 1e-4, then 0.5 × 1e-4 + 0.5 × 4e-4 = 2.5e-4, then 0.5 × 2.5e-4 + 0.5 × 9e-4 = 5.75e-4
+        let model = EwmaModel { lambda: 0.5 };
+        assert_eq!(model.estimate(&known_returns(1.0)).unwrap(), "239.79".parse().unwrap());
+        // Lambda 0 keeps only the last return
+        assert_eq!(EwmaModel { lambda: 0.0 }.estimate(&known_returns(1.0)).unwrap(), Bps::from_bps(300));
+    }
+
+    #[test]
+    fn parkinson_reads_the_high_low_range() {
+        // ln(high / low) = 0.02 on every row: var = 4e-4 / (4 ln 2)
+        let low = vec![100.0, 105.0, 98.0];
+        let high = low.iter().map(|l: &f64| l * 0.02f64.exp()).collect();
+        let mut series = PriceSeries { close: low.clone(), high: Some(high), low: Some(low), timestamps: None, periods_per_day: 1.0 };
+        assert_eq!(ParkinsonModel.estimate(&series).unwrap(), "120.11".parse().unwrap());
+
+        series.high.as_mut().unwrap()[1] = 104.0;
+        let err = ParkinsonModel.estimate(&series).unwrap_err();
+        assert!(err.to_string().contains("Line 3 has high 104 below low 105"), "{}", err);
+        series.high.as_mut().unwrap()[1] = f64::NAN;
+        assert!(ParkinsonModel.estimate(&series).is_err());
+
+        // Loading names the line as it appears in the file, comments included
+        let file = std::env::temp_dir().join(format!("vector-plus-parkinson-{}.csv", std::process::id()));
+        std::fs::write(&file, "# ETH hourly\nclose,high,low\n100,101,99\n105,104,105\n98,99,97\n").unwrap();
+        let Err(err) = PriceSeries::load(file.to_str().unwrap(), 24.0) else { panic!("loaded an inverted row") };
+        assert!(err.to_string().starts_with("High 104 on line 4 of"), "{}", err);
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn estimate_rejects_non_finite_periods_per_day() {
+        for periods_per_day in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let err = estimate_volatility("unused.csv", ModelKind::Stddev, 0.94, periods_per_day, None, true).await.unwrap_err();
+            assert!(err.to_string().contains("Periods per day must be positive"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn garch_recovers_simulated_parameters() {
+        // Simulate GARCH(1,1) with alpha 0.1, beta 0.85 and 1% long-run volatility
+        let (alpha, beta, long_run) = (0.1, 0.85, 1e-4);
+        let mut rng = crate::rng::seeded(Some(7));
+        let mut variance: f64 = long_run;
+        let mut close = vec![100.0];
+        for _ in 0..3000 {
+            // Box-Muller
+            let (u, v) = (1.0 - rng.next_f64(), rng.next_f64());
+            let shock = (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos();
+            let r = variance.sqrt() * shock;
+            close.push(close[close.len() - 1] * r.exp());
+            variance = long_run * (1.0 - alpha - beta) + alpha * r * r + beta * variance;
+        }
+        let prices = PriceSeries { close, high: None, low: None, timestamps: None, periods_per_day: 1.0 };
+        let fit = GarchModel::fit(&prices.log_returns()).unwrap();
+        assert!((fit.alpha - alpha).abs() < 0.05, "{:?}", fit);
+        assert!((fit.beta - beta).abs() < 0.07, "{:?}", fit);
+        assert!(GarchModel.estimate(&prices).unwrap() > Bps::ZERO);
+
+        let short = prices.rows(0, MIN_GARCH_RETURNS);
+        let err = GarchModel.estimate(&short).unwrap_err();
+        assert!(err.to_string().contains("at least 30 returns"), "{}", err);
+        let flat = PriceSeries { close: vec![100.0; 50], high: None, low: None, timestamps: None, periods_per_day: 1.0 };
+        assert!(GarchModel.estimate(&flat).is_err());
+    }
+
+    #[test]
+    fn backtest_range_resolves_rows_and_times() {
+        let timestamps = [10, 20, 20, 30, 40];
+        let resolve = |range: BacktestRange| range.resolve(5, Some(&timestamps), "prices.csv");
+        assert_eq!(resolve(BacktestRange::Rows(None, None)).unwrap(), (0, 5));
+        assert_eq!(resolve(BacktestRange::Rows(Some(1), Some(3))).unwrap(), (1, 3));
+        let err = resolve(BacktestRange::Rows(None, Some(6))).unwrap_err();
+        assert!(err.to_string().contains("--end 6 is past the last row"), "{}", err);
+
+        // Both bounds are inclusive and repeated timestamps stay together
+        assert_eq!(resolve(BacktestRange::Time(Some(20), Some(30))).unwrap(), (1, 4));
+        assert_eq!(resolve(BacktestRange::Time(Some(15), None)).unwrap(), (1, 5));
+        assert_eq!(resolve(BacktestRange::Time(None, Some(20))).unwrap(), (0, 3));
+        assert!(resolve(BacktestRange::Time(Some(30), Some(20))).is_err());
+
+        for empty in [BacktestRange::Time(Some(41), None), BacktestRange::Time(Some(21), Some(29)), BacktestRange::Rows(Some(3), Some(3))] {
+            let err = resolve(empty).unwrap_err();
+            assert!(err.to_string().contains("Backtest window is empty"), "{}", err);
+        }
+
+        let err = BacktestRange::Time(None, None).resolve(3, None, "prices.csv").unwrap_err();
+        assert!(err.to_string().contains("need a timestamp column"), "{}", err);
+        let err = BacktestRange::Time(Some(20), None).resolve(3, Some(&[10, 30, 20]), "prices.csv").unwrap_err();
+        assert!(err.to_string().contains("must be ascending: line 3 (30) comes before line 4 (20)"), "{}", err);
+    }
+
+    #[test]
+    fn rolling_series_round_trips_through_csv() {
+        let prices = PriceSeries {
+            close: vec![100.0, 101.0, 99.5, 102.0, 100.5, 103.0],
+            high: None,
+            low: None,
+            timestamps: Some(vec![10, 20, 30, 40, 50, 60]),
+            periods_per_day: 1.0,
+        };
+        let estimates = prices.rolling(&StdDevModel, 4, 4).unwrap();
+        assert_eq!(estimates.iter().filter(|e| e.is_none()).count(), 3);
+        assert_eq!(estimates[5], Some(StdDevModel.estimate(&prices.rows(2, 6)).unwrap()));
+
+        let series = VolatilitySeries::from_estimates(&estimates, prices.timestamps.as_deref());
+        let path = std::env::temp_dir().join(format!("vector-plus-series-{}.csv", std::process::id()));
+        fs::write(&path, series.to_csv()).unwrap();
+        let loaded = VolatilitySeries::load(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.rows, vec![3, 4, 5]);
+        assert_eq!(loaded.timestamps, Some(vec![40, 50, 60]));
+        assert_eq!(loaded.volatility, series.volatility);
+    }
+
+    #[test]
+    fn new_writes_loadable_integer_wei() {
+        let config = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(350), DEFAULT_MAX_EXECUTION_SIZE, DEFAULT_MIN_EXECUTION_SIZE, false);
+        assert_eq!(config.max_execution_size, "5000000000000000000");
+        assert_eq!(config.min_execution_size, "100000000000000000");
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(serde_json::from_str::<VolatilityConfig>(&json).is_ok());
+    }
+
+    #[test]
+    fn adjustment_factor_uses_fractional_bps() {
+        let bps = |s: &str| s.parse::<Bps>().unwrap();
+        let mut config = VolatilityConfig::new(bps("3.25"), bps("2.5"), DEFAULT_MAX_EXECUTION_SIZE, DEFAULT_MIN_EXECUTION_SIZE, false);
+        // (325 - 250) × 50 / 325 = 11.5, floored as on-chain
+        assert_eq!(volatility_adjustment_factor(&config), 111);
+
+        config.current_volatility = bps("9.75");
+        assert_eq!(config.volatility_threshold, bps("6.5"));
+        assert_eq!(volatility_adjustment_factor(&config), 50);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""baseline_volatility":3.25"#), "{}", json);
+        let whole: VolatilityConfig = serde_json::from_str(&config_json("5000", "100")).unwrap();
+        assert_eq!(volatility_adjustment_factor(&whole), 100);
+    }
+
+    #[test]
+    fn calculation_output_matches_its_schema() {
+        let config = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(1500), DEFAULT_MAX_EXECUTION_SIZE, DEFAULT_MIN_EXECUTION_SIZE, false);
+        let quote = OrderQuote { price: Some(3000.0), decimals: 6, symbol: "USDC" };
+        let cooldown = CooldownInput { secs: 60, previous: None, now: 0 };
+        let mut result = CalculationResult::compute(&config, Amount::from_wei(100_000_000_000_000), &quote, Some(&cooldown), None, None);
+        assert_eq!(result.clamp_event, ClampEvent::RaisedToMin);
+        assert_eq!(result.clamped_amount(), DEFAULT_MIN_EXECUTION_SIZE);
+        assert_eq!(result.regime, Regime::Emergency);
+
+        let derivation = result.derivation(&config);
+        let stages: Vec<&str> = derivation.iter().map(|step| step.stage).collect();
+        assert_eq!(stages, ["regime", "factor", "adjust", "clamp", "cooldown", "quote"]);
+        assert_eq!(derivation[1].output, "50%");
+        assert_eq!(derivation[3].output, "0.1 ETH");
+        result.derivation = Some(derivation);
+        result.significant_change = Some(true);
+
+        let output = serde_json::to_value(&result).unwrap();
+        let schema = serde_json::to_value(schemars::schema_for!(CalculationResult)).unwrap();
+        let keys = |value: &Value| value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&output), keys(&schema["properties"]));
+        assert_eq!(output["clamp_event"], "raised_to_min");
+        assert_eq!(output["final_amount"], 0.1);
+    }
+
+    #[test]
+    fn taking_amount_is_exact_in_quote_base_units() {
+        let config = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(300), DEFAULT_MAX_EXECUTION_SIZE, DEFAULT_MIN_EXECUTION_SIZE, false);
+        let quote = OrderQuote { price: Some(3000.123456), decimals: 6, symbol: "USDC" };
+        let result = CalculationResult::compute(&config, "4.999999999999999999".parse().unwrap(), &quote, None, None, None);
+        // 15000.617279999999996999876544 USDC, rounded down; as floats this came out as 15000617280
+        assert_eq!(result.taking_amount_units.as_deref(), Some("15000617279"));
+        assert_eq!(result.making_amount_wei, "4999999999999999999");
+
+        let unpriced = OrderQuote { price: None, ..quote };
+        let result = CalculationResult::compute(&config, "1".parse().unwrap(), &unpriced, None, None, None);
+        assert_eq!((result.taking_amount, result.taking_amount_units), (None, None));
+    }
+
+    #[test]
+    fn slippage_budget_caps_the_size_and_names_the_binding_constraint() {
+        let config = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(300), "5".parse().unwrap(), "0.1".parse().unwrap(), false);
+        let quote = OrderQuote { price: None, decimals: 6, symbol: "USDC" };
+        let eth = |s: &str| s.parse::<Amount>().unwrap();
+        let sized = |amount: &str, target: u64, impact_coeff: f64| {
+            let budget = SlippageBudget { target: Bps::from_bps(target), impact_coeff };
+            CalculationResult::compute(&config, eth(amount), &quote, None, Some(&budget), None)
+        };
+
+        // 20bps at 10 bps/ETH allows 2 ETH
+        let capped = sized("4", 20, 10.0);
+        assert_eq!(capped.final_amount, eth("2"));
+        let slippage = capped.slippage.as_ref().unwrap();
+        assert_eq!((slippage.binding, slippage.max_amount, slippage.estimated_slippage_bps), (BindingConstraint::Slippage, Some(2.0), 20.0));
+        assert_eq!(capped.uncapped_amount(), eth("4"));
+        let derivation = capped.derivation(&config);
+        assert_eq!(derivation.last().unwrap().stage, "slippage");
+        assert_eq!(derivation.last().unwrap().output, "2 ETH");
+
+        let within = sized("4", 100, 10.0);
+        assert_eq!(within.final_amount, eth("4"));
+        assert_eq!(within.slippage.unwrap().binding, BindingConstraint::Volatility);
+        assert_eq!(sized("8", 100, 10.0).slippage.unwrap().binding, BindingConstraint::MaxLimit);
+        let unlimited = sized("4", 1, 0.0).slippage.unwrap();
+        assert_eq!((unlimited.max_amount, unlimited.binding), (None, BindingConstraint::Volatility));
+    }
+
+    #[test]
+    fn adjustment_caps_are_configurable() {
+        let mut config = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(1500), DEFAULT_MAX_EXECUTION_SIZE, DEFAULT_MIN_EXECUTION_SIZE, false);
+        assert_eq!(volatility_adjustment_factor(&config), 50);
+        config.max_reduction_pct = 80;
+        // (1500 - 300) × 50 / 300 = 200, capped at 80
+        assert_eq!(volatility_adjustment_factor(&config), 20);
+
+        config.current_volatility = Bps::from_bps(0);
+        config.max_boost_pct = 25;
+        assert_eq!(volatility_adjustment_factor(&config), 125);
+
+        config.max_reduction_pct = 101;
+        let (_, errors) = check_volatility_config(&config);
+        assert!(errors.iter().any(|e| e.contains("Max reduction")), "{:?}", errors);
+    }
+
+    #[test]
+    fn thresholds_must_rise_from_baseline_to_emergency() {
+        let mut config = VolatilityConfig::new(Bps::from_bps(300), Bps::from_bps(300), "5".parse().unwrap(), "0.1".parse().unwrap(), false);
+        let ordering = |config: &VolatilityConfig| {
+            check_volatility_config(config).1.into_iter().filter(|e| e.contains("Thresholds must satisfy")).collect::<Vec<_>>()
+        };
+        assert!(ordering(&config).is_empty());
+
+        config.volatility_threshold = Bps::from_bps(1500);
+        assert_eq!(ordering(&config), ["❌ Thresholds must satisfy baseline < volatility threshold < emergency threshold, got 300bps, 1500bps and 1200bps"]);
+        config.volatility_threshold = Bps::from_bps(300);
+        assert_eq!(ordering(&config).len(), 1);
+        config.volatility_threshold = Bps::from_bps(1200);
+        assert_eq!(ordering(&config).len(), 1);
+    }
+
+    #[test]
+    fn cooldown_blocks_only_increases_inside_the_window() {
+        let eth = |text: &str| text.parse::<Amount>().unwrap();
+        let previous = SizingState { last_amount: eth("2"), changed_at: 1_000 };
+        let held = apply_cooldown(eth("3"), eth("0.1"), Some(&previous), 1_030, 60);
+        assert_eq!(held, CooledSize { amount: eth("2"), constrained: true });
+        
+        // Decreases pass straight through, as do increases once the window has passed
+        assert_eq!(apply_cooldown(eth("1"), eth("0.1"), Some(&previous), 1_030, 60).amount, eth("1"));
+        assert_eq!(apply_cooldown(eth("3"), eth("0.1"), Some(&previous), 1_060, 60), CooledSize { amount: eth("3"), constrained: false });
+        assert!(!apply_cooldown(eth("3"), eth("0.1"), None, 1_030, 60).constrained);
+        
+        // A raised minimum still applies to the held size
+        assert_eq!(apply_cooldown(eth("3"), eth("2.5"), Some(&previous), 1_030, 60).amount, eth("2.5"));
+        
+        // State files from before exact amounts stored ETH as a number
+        let legacy: SizingState = serde_json::from_str(r#"{"last_amount": 2.5, "changed_at": 1}"#).unwrap();
+        assert_eq!(legacy.last_amount, eth("2.5"));
+        let json = serde_json::to_string(&previous).unwrap();
+        assert_eq!(serde_json::from_str::<SizingState>(&json).unwrap().last_amount, eth("2"));
+    }
+
+    #[test]
+    fn flags_volatility_that_looks_like_the_wrong_unit() {
+        let mut config = VolatilityConfig::new(Bps::from_bps(35), Bps::from_bps(500), "5".parse().unwrap(), "0.1".parse().unwrap(), false);
+        // 35bps is low but plausible
+        assert!(unit_warnings(&config).is_empty());
+
+        config.emergency_threshold = Bps::from_bps(60_000);
+        config.baseline_volatility = Bps::from_bps(5);
+        config.volatility_threshold = Bps::ZERO;
+        let warnings = unit_warnings(&config);
+        assert_eq!(warnings, [
+            "baseline_volatility is 5bps (0.05%); if you meant 5%, enter 500",
+            "emergency_threshold is 60000bps (600%); volatility is in bps, where 1% is 100",
+        ]);
+    }
+
+    #[test]
+    fn portfolio_percentages_size_the_base_amount() {
+        let total = "200".parse::<Amount>().unwrap();
+        assert_eq!(BaseAmount::share_of(total, 2.5).unwrap().amount(), "5".parse::<Amount>().unwrap());
+        assert_eq!(BaseAmount::share_of(total, 100.0).unwrap().amount(), total);
+        assert_eq!(BaseAmount::share_of(total, 0.125).unwrap().amount(), "0.25".parse::<Amount>().unwrap());
+        for pct in [0.0, -1.0, 100.01, f64::NAN] {
+            assert!(BaseAmount::share_of(total, pct).is_err(), "accepted {}", pct);
+        }
+        let share = PortfolioShare::of(total, 2.5, "4".parse::<Amount>().unwrap());
+        assert_eq!(share.final_pct, 2.0);
+    }
+
+    #[test]
+    fn factor_changes_are_measured_from_the_last_printed_factor() {
+        let log = FactorLog { factor_pct: 100, logged_at: 0 };
+        assert!(factor_changed(None, 100, 5));
+        assert!(!factor_changed(Some(&log), 105, 5));
+        assert!(!factor_changed(Some(&log), 95, 5));
+        assert!(factor_changed(Some(&log), 106, 5));
+        assert!(factor_changed(Some(&log), 94, 5));
+        assert!(factor_changed(Some(&log), 101, 0));
+    }
+
+    #[test]
+    fn wei_sizes_override_eth_sizes_verbatim() {
+        let inputs = VolatilityConfigInputs {
+            max_execution_size_wei: Some("5000000000000000001".to_string()),
+            ..VolatilityConfigInputs::default()
+        };
+        assert!(inputs.validate().is_ok());
+        assert_eq!(
+            inputs.execution_sizes_wei(),
+            ("100000000000000000".to_string(), "5000000000000000001".to_string())
+        );
+
+        let below_min = VolatilityConfigInputs {
+            max_execution_size_wei: Some("100".to_string()),
+            min_execution_size_wei: Some("100".to_string()),
+            ..VolatilityConfigInputs::default()
+        };
+        assert!(below_min.validate().is_err());
+
+        for bad in ["", "-1", "1.5", "1e18"] {
+            let inputs = VolatilityConfigInputs {
+                min_execution_size_wei: Some(bad.to_string()),
+                ..VolatilityConfigInputs::default()
+            };
+            assert!(inputs.validate().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn sizing_range_spans_one_band_either_side() {
+        let config: VolatilityConfig = serde_json::from_str(&config_json("5000000000000000000", "100000000000000000")).unwrap();
+        let range = SizingRange::compute(&config, "2".parse().unwrap());
+        assert_eq!(range.band_width, Bps::from_bps(300));
+        
+        assert_eq!(range.conservative.current_volatility, Bps::from_bps(650));
+        assert_eq!(range.conservative.regime, Regime::Elevated);
+        assert_eq!(range.conservative.final_amount, "1".parse().unwrap());
+        assert_eq!(range.base.factor_pct, 100);
+        assert_eq!(range.base.final_amount, "2".parse().unwrap());
+        assert_eq!(range.aggressive.current_volatility, Bps::from_bps(50));
+        assert_eq!(range.aggressive.factor_pct, 141);
+        assert_eq!(range.aggressive.final_amount, "2.82".parse().unwrap());
+        
+        let range = SizingRange::compute(&config, "4".parse().unwrap());
+        assert_eq!(range.aggressive.clamp_event, ClampEvent::CappedAtMax);
+        assert_eq!(range.aggressive.final_amount, "5".parse().unwrap());
+    }
+    
+    #[test]
+    fn hysteresis_holds_elevated_until_the_exit_threshold() {
+        let mut config: VolatilityConfig = serde_json::from_str(&config_json("5000000000000000000", "100000000000000000")).unwrap();
+        config.hysteresis_margin = Bps::from_bps(100);
+        assert_eq!(config.exit_threshold(), Bps::from_bps(500));
+        
+        config.current_volatility = Bps::from_bps(550);
+        assert_eq!(Regime::classify_from(&config, None), (Regime::Normal, false));
+        assert_eq!(Regime::classify_from(&config, Some(Regime::Elevated)), (Regime::Elevated, true));
+        assert_eq!(regime_adjustment_factor(&config, Regime::Elevated), 59);
+        
+        config.current_volatility = Bps::from_bps(500);
+        assert_eq!(Regime::classify_from(&config, Some(Regime::Elevated)), (Regime::Normal, false));
+        
+        config.hysteresis_margin = Bps::ZERO;
+        config.current_volatility = Bps::from_bps(550);
+        assert_eq!(Regime::classify_from(&config, Some(Regime::Elevated)), (Regime::Normal, false));
+    }
+    
+    #[test]
+    fn stress_rows_follow_each_scenario() {
+        let config: VolatilityConfig = serde_json::from_str(&config_json("5000000000000000000", "100000000000000000")).unwrap();
+        let StressScenarios { scenarios } = serde_json::from_str(r#"{"scenarios": [
+            {"name": "calm", "current_volatility": 150},
+            {"name": "2x baseline", "current_volatility": 700},
+            {"name": "emergency spike", "current_volatility": 1500}
+        ]}"#).unwrap();
+        let rows = stress_table(&config, &scenarios, Amount::from_wei(2_000_000_000_000_000_000), 4).to_json();
+        let column = |key: &str| rows.as_array().unwrap().iter().map(|row| row[key].clone()).collect::<Vec<_>>();
+        
+        assert_eq!(column("scenario"), ["(config)", "calm", "2x baseline", "emergency spike"]);
+        assert_eq!(column("regime"), ["NORMAL", "LOW", "ELEVATED", "EMERGENCY"]);
+        assert_eq!(column("factor_pct"), [100, 125, 50, 50]);
+        assert_eq!(column("final_amount"), [2.0, 2.5, 1.0, 1.0]);
+        assert_eq!(column("emergency_action"), ["none", "none", "none", "halt"]);
+    }
+
+    #[test]
+    fn heatmap_clamps_and_halts_cells() {
+        let config: VolatilityConfig = serde_json::from_str(&config_json("5000000000000000000", "100000000000000000")).unwrap();
+        let amounts: Vec<Amount> = ["2", "8"].iter().map(|a| a.parse().unwrap()).collect();
+        let volatilities: Vec<Bps> = ["150", "350", "1500"].iter().map(|v| v.parse().unwrap()).collect();
+        let grid = heatmap_grid(&config, &amounts, &volatilities);
+        let eth = |row: &Vec<Option<Amount>>| row.iter().map(|cell| cell.map(Amount::as_eth_f64)).collect::<Vec<_>>();
+        
+        assert_eq!(eth(&grid[0]), [Some(2.5), Some(2.0), None]);
+        // 8 ETH is clamped to the 5 ETH maximum even when the factor would grow it
+        assert_eq!(eth(&grid[1]), [Some(5.0), Some(5.0), None]);
+        assert_eq!(effective_factor(amounts[1], grid[1][1].unwrap()), 62.5);
+    }
+
+    #[test]
+    fn watch_interval_must_be_positive() {
+        use clap::Parser;
+        let parse = |secs: &str| crate::Cli::try_parse_from(["vector-plus", "volatility", "watch", "--interval-secs", secs]);
+        assert!(parse("1").is_ok());
+        assert!(parse("0").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! `volatility calculate --portfolio`: sizing several positions, each with
+//! its own config, and totalling their notional in one quote asset.
+
+use colored::*;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{volatility_adjustment_factor, warn_on_units, VolatilityConfig};
+use crate::amount::{serialize_eth, Amount, DECIMALS};
+use crate::rounding::Rounding;
+use crate::utils::{format_amount, read_config};
+use crate::utils::table::{Align, Cell, OutputFormat, Table};
+
+/// Positions sized together by `volatility calculate --portfolio`.
+#[derive(Debug, Deserialize)]
+struct Portfolio {
+    /// Quote asset all position prices are expressed in
+    #[serde(default = "default_quote_symbol")]
+    quote_symbol: String,
+    positions: Vec<Position>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    asset: String,
+    #[serde(deserialize_with = "crate::amount::deserialize_eth")]
+    amount: Amount,
+    /// Price of one unit of the asset in the portfolio's quote asset
+    price: f64,
+    #[serde(default = "default_asset_decimals")]
+    decimals: u32,
+    /// Volatility config for this asset; defaults to `--config`
+    config: Option<String>,
+}
+
+fn default_quote_symbol() -> String {
+    "USDC".to_string()
+}
+
+fn default_asset_decimals() -> u32 {
+    18
+}
+
+#[derive(Debug, Serialize)]
+struct SizedPosition {
+    asset: String,
+    config: String,
+    #[serde(serialize_with = "serialize_eth")]
+    original_amount: Amount,
+    adjustment_factor: u64,
+    #[serde(serialize_with = "serialize_eth")]
+    adjusted_amount: Amount,
+    #[serde(serialize_with = "serialize_eth")]
+    final_amount: Amount,
+    final_amount_units: String,
+    decimals: u32,
+    price: f64,
+    original_notional: f64,
+    final_notional: f64,
+}
+
+/// Options of `volatility calculate --portfolio` beyond the files and format.
+pub(super) struct PortfolioOptions {
+    pub(super) rounding: Option<Rounding>,
+    pub(super) unit_warnings: bool,
+    /// Refuse to run unless every config a position uses is approved
+    pub(super) require_approval: bool,
+    pub(super) precision: u8,
+}
+
+/// A config's execution limits for a position: the config's wei amounts are
+/// base units of the position's asset, so a 6-decimal asset's limits are
+/// scaled up by 10^12 to compare with its whole-token amount.
+fn position_limits(config: &VolatilityConfig, decimals: u32) -> (Amount, Amount) {
+    let scale = 10u128.pow(DECIMALS as u32 - decimals);
+    let (min, max) = config.execution_limits();
+    (Amount::from_wei(min.wei().saturating_mul(scale)), Amount::from_wei(max.wei().saturating_mul(scale)))
+}
+
+/// Sizes `amount` like [`size_execution`](super::size_execution) does, against the
+/// limits scaled to the position's decimals, returning `(adjusted, final)`.
+fn size_position(config: &VolatilityConfig, amount: Amount, decimals: u32, rounding: Option<&Rounding>) -> (Amount, Amount) {
+    let (min, max) = position_limits(config, decimals);
+    let adjusted = amount.scale_pct(volatility_adjustment_factor(config));
+    let clamped = adjusted.max(min).min(max);
+    let final_amount = match rounding {
+        Some(rounding) => rounding.round_within(clamped, min, max),
+        None => clamped,
+    };
+    (adjusted, final_amount)
+}
+
+pub(super) async fn calculate_portfolio(file: &str, default_config: &str, format: OutputFormat, options: &PortfolioOptions) -> Result<()> {
+    let &PortfolioOptions { ref rounding, unit_warnings, require_approval, precision } = options;
+    let portfolio: Portfolio = read_config(file)?;
+    if portfolio.positions.is_empty() {
+        return Err(eyre::eyre!("Portfolio {} has no positions", file));
+    }
+    if require_approval {
+        let configs: std::collections::BTreeSet<&str> = portfolio.positions.iter()
+            .map(|position| position.config.as_deref().unwrap_or(default_config))
+            .collect();
+        for config in configs {
+            crate::commands::config::require_approval(config)?;
+        }
+    }
+    
+    let mut sized = Vec::with_capacity(portfolio.positions.len());
+    let mut checked_units = std::collections::BTreeSet::new();
+    for position in &portfolio.positions {
+        if position.amount.is_zero() || position.price <= 0.0 {
+            return Err(eyre::eyre!("Position {} needs a positive amount and price", position.asset));
+        }
+        if position.decimals as usize > DECIMALS {
+            return Err(eyre::eyre!("Position {} has {} decimals; at most {} are supported", position.asset, position.decimals, DECIMALS));
+        }
+        let config_file = position.config.as_deref().unwrap_or(default_config);
+        let config: VolatilityConfig = read_config(config_file)?;
+        if unit_warnings && checked_units.insert(config_file) {
+            warn_on_units(config_file, &config);
+        }
+        
+        let (adjusted_amount, final_amount) = size_position(&config, position.amount, position.decimals, rounding.as_ref());
+        sized.push(SizedPosition {
+            asset: position.asset.clone(),
+            config: config_file.to_string(),
+            original_amount: position.amount,
+            adjustment_factor: volatility_adjustment_factor(&config),
+            adjusted_amount,
+            final_amount,
+            final_amount_units: final_amount.to_base_units(position.decimals as usize),
+            decimals: position.decimals,
+            price: position.price,
+            original_notional: position.amount.as_eth_f64() * position.price,
+            final_notional: final_amount.as_eth_f64() * position.price,
+        });
+    }
+    
+    let total_original: f64 = sized.iter().map(|p| p.original_notional).sum();
+    let total_final: f64 = sized.iter().map(|p| p.final_notional).sum();
+    
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "quote_symbol": portfolio.quote_symbol,
+                "positions": sized,
+                "total_original_notional": total_original,
+                "total_final_notional": total_final,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Csv => {
+            let mut table = Table::new();
+            for key in [
+                "asset", "config", "original_amount", "adjustment_factor", "final_amount",
+                "final_amount_units", "decimals", "price", "original_notional", "final_notional",
+            ] {
+                table = table.column(key, key, Align::Left);
+            }
+            for p in &sized {
+                table.row(vec![
+                    Cell::text(p.asset.as_str()),
+                    Cell::text(p.config.as_str()),
+                    Cell::text(p.original_amount.to_string()),
+                    Cell::new(p.adjustment_factor.to_string(), p.adjustment_factor),
+                    Cell::text(p.final_amount.to_string()),
+                    Cell::text(p.final_amount_units.as_str()),
+                    Cell::new(p.decimals.to_string(), p.decimals),
+                    Cell::new(p.price.to_string(), p.price),
+                    Cell::new(p.original_notional.to_string(), p.original_notional),
+                    Cell::new(p.final_notional.to_string(), p.final_notional),
+                ]);
+            }
+            println!("{}", table.format(format));
+        }
+        OutputFormat::Table => {
+            println!("{} {}", "🧮 Calculating volatility adjustment for portfolio:".cyan(), file.yellow());
+            if let Some(rounding) = rounding {
+                println!("  • Final amounts rounded: {}, within the config limits", rounding.describe());
+            }
+            println!();
+            let mut table = Table::new()
+                .column("asset", "Asset", Align::Left)
+                .column("original_amount", "Amount", Align::Right)
+                .column("adjustment_factor", "Factor", Align::Right)
+                .column("final_amount", "Final", Align::Right)
+                .column("original_notional", format!("Notional ({})", portfolio.quote_symbol), Align::Right)
+                .column("final_notional", format!("Final ({})", portfolio.quote_symbol), Align::Right);
+            for p in &sized {
+                table.row(vec![
+                    Cell::text(p.asset.as_str()),
+                    Cell::new(p.original_amount.format_eth(precision), p.original_amount.as_eth_f64()),
+                    Cell::new(format!("{}%", p.adjustment_factor), p.adjustment_factor),
+                    Cell::new(p.final_amount.format_eth(precision), p.final_amount.as_eth_f64()),
+                    Cell::new(format_amount(p.original_notional, precision), p.original_notional),
+                    Cell::new(format_amount(p.final_notional, precision), p.final_notional),
+                ]);
+            }
+            println!("{}", table.render());
+            println!();
+            println!("📊 Portfolio Summary:");
+            println!("  • Positions: {}", sized.len());
+            println!("  • Total original notional: {} {}", format_amount(total_original, precision), portfolio.quote_symbol);
+            println!("  • Total adjusted notional: {} {}", format_amount(total_final, precision).yellow(), portfolio.quote_symbol);
+            if total_original > 0.0 {
+                println!("  • Change: {:+.2}%", (total_final / total_original - 1.0) * 100.0);
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tests::config_json;
+
+    #[test]
+    fn limits_are_base_units_of_the_position_asset() {
+        // 5 and 0.1 tokens of a 6-decimal asset such as USDC
+        let config: VolatilityConfig = serde_json::from_str(&config_json("5000000", "100000")).unwrap();
+        let amount = |text: &str| text.parse::<Amount>().unwrap();
+        assert_eq!(position_limits(&config, 6), (amount("0.1"), amount("5")));
+        assert_eq!(size_position(&config, amount("8"), 6, None), (amount("8"), amount("5")));
+        assert_eq!(size_position(&config, amount("0.05"), 6, None).1, amount("0.1"));
+        assert_eq!(size_position(&config, amount("2"), 6, None).1, amount("2"));
+
+        // At 18 decimals the same limits are a few millionths of a token
+        assert_eq!(size_position(&config, amount("2"), 18, None).1, Amount::from_wei(5_000_000));
+    }
+
+    #[tokio::test]
+    async fn portfolio_requires_approval_of_each_position_config() {
+        let dir = std::env::temp_dir().join(format!("vector-plus-portfolio-approval-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let position_config = dir.join("eth-volatility.json");
+        std::fs::write(&position_config, config_json("5000000000000000000", "100000000000000000")).unwrap();
+        let portfolio = dir.join("portfolio.json");
+        let positions = serde_json::json!({ "positions": [
+            { "asset": "ETH", "amount": 1, "price": 3000.0, "config": position_config.to_str().unwrap() }
+        ]});
+        std::fs::write(&portfolio, positions.to_string()).unwrap();
+        let options = PortfolioOptions { rounding: None, unit_warnings: false, require_approval: true, precision: 4 };
+
+        let err = calculate_portfolio(portfolio.to_str().unwrap(), "unused.json", OutputFormat::Json, &options).await.unwrap_err();
+        assert!(err.to_string().contains("eth-volatility.json has not been approved"), "{}", err);
+        let options = PortfolioOptions { require_approval: false, ..options };
+        calculate_portfolio(portfolio.to_str().unwrap(), "unused.json", OutputFormat::Json, &options).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,20 +1,167 @@
+use colored::*;
+use eyre::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
 
+use crate::bps::Bps;
+use crate::error::VectorPlusError;
+
+/// Returned by [`load`] when the config file does not exist, so callers can
+/// tell a missing file apart from an unreadable or malformed one.
+#[derive(Debug)]
+pub struct ConfigNotFound {
+    pub path: String,
+}
+
+impl fmt::Display for ConfigNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Config file not found: {} (run `vector-plus config init` to create one)", self.path)
+    }
+}
+
+impl std::error::Error for ConfigNotFound {}
+
+/// Loads the config at `path`. A missing file is reported as [`ConfigNotFound`],
+/// invalid `custom_networks` entries as a validation error.
+pub fn load(path: &str) -> Result<VectorPlusConfig> {
+    let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => eyre::Report::new(ConfigNotFound { path: path.to_string() }),
+        _ => eyre::eyre!("Could not read file: {}", path),
+    })?;
+    let config: VectorPlusConfig = serde_json::from_str(&content)
+        .map_err(|e| crate::utils::json_error(path, &content, &e, &format!("vector-plus config validate {}", path)))?;
+    let errors = check_custom_networks(&config.custom_networks);
+    if !errors.is_empty() {
+        return Err(VectorPlusError::Validation(format!("Invalid custom_networks in {}:\n  {}", path, errors.join("\n  "))).into());
+    }
+    if config.price_command.as_deref().is_some_and(|command| command.trim().is_empty()) {
+        return Err(VectorPlusError::Validation(format!("Invalid price_command in {}: it must name a program", path)).into());
+    }
+    Ok(config)
+}
+
+/// Problems with user-defined networks, one message per problem.
+pub fn check_custom_networks(networks: &BTreeMap<String, CustomNetwork>) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (name, network) in networks {
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_') {
+            errors.push(format!("{:?}: names use lowercase letters, digits, '-' and '_'", name));
+        }
+        if crate::networks::builtin(name).is_some() {
+            errors.push(format!("{}: shadows the built-in network of the same name", name));
+        }
+        if network.chain_id == 0 {
+            errors.push(format!("{}: chain_id must be greater than zero", name));
+        }
+        if network.gas_token.trim().is_empty() {
+            errors.push(format!("{}: gas_token must not be empty", name));
+        }
+        if network.block_time_ms == 0 {
+            errors.push(format!("{}: block_time_ms must be greater than zero", name));
+        }
+        if let Some(url) = &network.rpc_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                errors.push(format!("{}: rpc_url must be an http(s) URL, got {}", name, url));
+            }
+        }
+        for (contract, address) in [
+            ("volatility_calculator", &network.contracts.volatility_calculator),
+            ("twap_executor", &network.contracts.twap_executor),
+            ("options_calculator", &network.contracts.options_calculator),
+        ] {
+            if let Some(address) = address {
+                if crate::eip712::parse_address(address).is_err() {
+                    errors.push(format!("{}: contracts.{} is not a 0x-prefixed 20-byte address: {}", name, contract, address));
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Loads the config at `path`, falling back to the built-in defaults with a
+/// warning when the file does not exist. Unreadable or malformed files are
+/// still errors, so a typo never silently turns into defaults.
+pub fn load_or_default(path: &str) -> Result<VectorPlusConfig> {
+    match load(path) {
+        Err(e) if e.is::<ConfigNotFound>() => {
+            eprintln!("{} {}; using built-in defaults", "⚠️ ".yellow(), e);
+            Ok(VectorPlusConfig::default())
+        }
+        result => result,
+    }
+}
+
+/// Where an effective setting came from, from lowest to highest precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    Default,
+    File,
+    /// A variable loaded from `.env` (or `--env-file`)
+    EnvFile,
+    /// A variable set in the real environment
+    Env,
+    Flag,
+}
+
+impl Source {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Source::Default => "default",
+            Source::File => "file",
+            Source::EnvFile => "env-file",
+            Source::Env => "env",
+            Source::Flag => "flag",
+        }
+    }
+}
+
+#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VectorPlusConfig {
     pub network: String,
     pub rpc_url: Option<String>,
     pub contracts: ContractConfig,
     pub defaults: DefaultConfig,
+    /// Chains beyond the built-in ones, selectable with `--network <name>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_networks: BTreeMap<String, CustomNetwork>,
+    /// External program `volatility watch` polls for prices, run with the
+    /// asset symbol as its last argument
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_command: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A user-defined chain from the config's `custom_networks`.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomNetwork {
+    pub chain_id: u64,
+    /// Used for RPC commands on this network when `--rpc-url` is not given
+    pub rpc_url: Option<String>,
+    #[serde(default)]
+    pub contracts: ContractConfig,
+    pub gas_token: String,
+    #[serde(default = "default_block_time_ms")]
+    pub block_time_ms: u64,
+}
+
+fn default_block_time_ms() -> u64 {
+    12_000
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ContractConfig {
     pub volatility_calculator: Option<String>,
     pub twap_executor: Option<String>,
     pub options_calculator: Option<String>,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DefaultConfig {
     pub volatility: VolatilityDefaults,
@@ -22,14 +169,16 @@ pub struct DefaultConfig {
     pub options: OptionsDefaults,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VolatilityDefaults {
-    pub baseline_volatility: u64,
+    pub baseline_volatility: Bps,
     pub max_execution_size: String,
     pub min_execution_size: String,
     pub conservative_mode: bool,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TwapDefaults {
     pub duration: u64,
@@ -38,6 +187,7 @@ pub struct TwapDefaults {
     pub adaptive_intervals: bool,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OptionsDefaults {
     pub default_expiration_hours: u64,
@@ -57,7 +207,7 @@ impl Default for VectorPlusConfig {
             },
             defaults: DefaultConfig {
                 volatility: VolatilityDefaults {
-                    baseline_volatility: 300,
+                    baseline_volatility: Bps::from_bps(300),
                     max_execution_size: "5000000000000000000".to_string(), // 5 ETH
                     min_execution_size: "100000000000000000".to_string(),   // 0.1 ETH
                     conservative_mode: false,
@@ -74,6 +224,34 @@ impl Default for VectorPlusConfig {
                     risk_free_rate: 300,           // 3%
                 },
             },
+            custom_networks: BTreeMap::new(),
+            price_command: None,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(json: &str) -> CustomNetwork {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn checks_custom_networks() {
+        let mut networks = BTreeMap::new();
+        networks.insert("mybase".to_string(), network(r#"{"chain_id": 84532, "rpc_url": "https://sepolia.base.org", "gas_token": "ETH"}"#));
+        assert!(check_custom_networks(&networks).is_empty());
+        assert_eq!(networks["mybase"].block_time_ms, 12_000);
+
+        networks.insert("polygon".to_string(), network(r#"{"chain_id": 137, "gas_token": "POL"}"#));
+        networks.insert("Bad Name".to_string(), network(
+            r#"{"chain_id": 0, "rpc_url": "localhost:8545", "gas_token": " ", "contracts": {"twap_executor": "0x1234"}}"#,
+        ));
+        let errors = check_custom_networks(&networks);
+        assert_eq!(errors.len(), 6, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("polygon: shadows")));
+        assert!(errors.iter().any(|e| e.contains("contracts.twap_executor")));
+    }
+}
@@ -0,0 +1,219 @@
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+
+/// 1inch Limit Order Protocol v4 router, deployed at the same address on every supported chain.
+pub const LIMIT_ORDER_PROTOCOL: &str = "0x111111125421cA6dc452d289314280a0f8842A65";
+
+const DOMAIN_NAME: &str = "1inch Aggregation Router";
+const DOMAIN_VERSION: &str = "6";
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address receiver,address makerAsset,address takerAsset,uint256 makingAmount,uint256 takingAmount,uint256 makerTraits)";
+
+/// Order as serialized in JSON, mirroring `IOrderMixin.Order`. Integers may be
+/// decimal or `0x` hex strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderJson {
+    pub salt: String,
+    pub maker: String,
+    pub receiver: String,
+    pub maker_asset: String,
+    pub taker_asset: String,
+    pub making_amount: String,
+    pub taking_amount: String,
+    pub maker_traits: String,
+}
+
+impl OrderJson {
+    /// EIP-712 struct hash of the order, before domain separation.
+    pub fn struct_hash(&self) -> Result<[u8; 32]> {
+        let mut encoded = Vec::with_capacity(32 * 9);
+        encoded.extend_from_slice(&keccak256(ORDER_TYPE.as_bytes()));
+        encoded.extend_from_slice(&parse_uint256(&self.salt).map_err(|e| field_error("salt", e))?);
+        encoded.extend_from_slice(&parse_address(&self.maker).map_err(|e| field_error("maker", e))?);
+        encoded.extend_from_slice(&parse_address(&self.receiver).map_err(|e| field_error("receiver", e))?);
+        encoded.extend_from_slice(&parse_address(&self.maker_asset).map_err(|e| field_error("makerAsset", e))?);
+        encoded.extend_from_slice(&parse_address(&self.taker_asset).map_err(|e| field_error("takerAsset", e))?);
+        encoded.extend_from_slice(&parse_uint256(&self.making_amount).map_err(|e| field_error("makingAmount", e))?);
+        encoded.extend_from_slice(&parse_uint256(&self.taking_amount).map_err(|e| field_error("takingAmount", e))?);
+        encoded.extend_from_slice(&parse_uint256(&self.maker_traits).map_err(|e| field_error("makerTraits", e))?);
+        Ok(keccak256(&encoded))
+    }
+
+    /// Order hash as computed by `OrderMixin.hashOrder` on `chain_id`.
+    pub fn hash(&self, chain_id: u64) -> Result<[u8; 32]> {
+        let mut encoded = Vec::with_capacity(66);
+        encoded.extend_from_slice(b"\x19\x01");
+        encoded.extend_from_slice(&domain_separator(chain_id, LIMIT_ORDER_PROTOCOL)?);
+        encoded.extend_from_slice(&self.struct_hash()?);
+        Ok(keccak256(&encoded))
+    }
+}
+
+pub fn domain_separator(chain_id: u64, verifying_contract: &str) -> Result<[u8; 32]> {
+    let mut chain = [0u8; 32];
+    chain[24..].copy_from_slice(&chain_id.to_be_bytes());
+
+    let mut encoded = Vec::with_capacity(32 * 5);
+    encoded.extend_from_slice(&keccak256(DOMAIN_TYPE.as_bytes()));
+    encoded.extend_from_slice(&keccak256(DOMAIN_NAME.as_bytes()));
+    encoded.extend_from_slice(&keccak256(DOMAIN_VERSION.as_bytes()));
+    encoded.extend_from_slice(&chain);
+    encoded.extend_from_slice(&parse_address(verifying_contract)?);
+    Ok(keccak256(&encoded))
+}
+
+/// Chain id for the networks accepted by `--network`.
+pub fn chain_id(network: &str) -> Result<u64> {
+    crate::networks::lookup(network).map(|network| network.chain_id)
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut out = [0u8; 32];
+    hasher.finalize(&mut out);
+    out
+}
+
+/// Function or error selector: the first 4 bytes of the signature's keccak256,
+/// e.g. `decimals()` is `0x313ce567`.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Parses a decimal or `0x` hex string into a big-endian 32-byte word.
+pub fn parse_uint256(value: &str) -> Result<[u8; 32]> {
+    let mut word = [0u8; 32];
+
+    if let Some(hex_digits) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        if hex_digits.is_empty() || hex_digits.len() > 64 {
+            return Err(eyre::eyre!("Invalid uint256: {}", value));
+        }
+        let padded = format!("{:0>64}", hex_digits);
+        hex::decode_to_slice(&padded, &mut word)
+            .map_err(|_| eyre::eyre!("Invalid uint256: {}", value))?;
+        return Ok(word);
+    }
+
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(eyre::eyre!("Invalid uint256: {}", value));
+    }
+    for digit in value.bytes().map(|b| b - b'0') {
+        // word = word * 10 + digit, big-endian
+        let mut carry = digit as u16;
+        for byte in word.iter_mut().rev() {
+            let next = *byte as u16 * 10 + carry;
+            *byte = next as u8;
+            carry = next >> 8;
+        }
+        if carry != 0 {
+            return Err(eyre::eyre!("uint256 overflow: {}", value));
+        }
+    }
+    Ok(word)
+}
+
+/// Parses a `0x`-prefixed 20-byte address into a left-padded 32-byte word.
+pub fn parse_address(value: &str) -> Result<[u8; 32]> {
+    let hex_digits = value.strip_prefix("0x")
+        .filter(|h| h.len() == 40)
+        .ok_or_else(|| eyre::eyre!("Invalid address: {}", value))?;
+    let mut word = [0u8; 32];
+    hex::decode_to_slice(hex_digits, &mut word[12..])
+        .map_err(|_| eyre::eyre!("Invalid address: {}", value))?;
+    Ok(word)
+}
+
+/// Formats a big-endian 32-byte word as a decimal string, the inverse of
+/// [`parse_uint256`] for decimal input.
+pub fn format_uint256(word: &[u8; 32]) -> String {
+    let mut remaining = *word;
+    let mut digits = Vec::new();
+    while remaining.iter().any(|&b| b != 0) {
+        // remaining = remaining / 10, collecting the remainder
+        let mut rem = 0u16;
+        for byte in remaining.iter_mut() {
+            let next = (rem << 8) | *byte as u16;
+            *byte = (next / 10) as u8;
+            rem = next % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("decimal digits are ASCII")
+}
+
+/// EIP-55 checksummed address from the low 20 bytes of a word; the upper
+/// bytes are ignored, as by the protocol's `Address` type.
+pub fn format_address(word: &[u8; 32]) -> String {
+    let lower = hex::encode(&word[12..]);
+    let hash = keccak256(lower.as_bytes());
+    let checksummed: String = lower.chars().enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+fn field_error(field: &str, error: eyre::Report) -> eyre::Report {
+    eyre::eyre!("Invalid order field {}: {}", field, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_domain_type() {
+        assert_eq!(
+            hex::encode(keccak256(DOMAIN_TYPE.as_bytes())),
+            "8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f"
+        );
+    }
+
+    #[test]
+    fn selects_the_first_four_hash_bytes() {
+        assert_eq!(hex::encode(selector("decimals()")), "313ce567");
+        assert_eq!(hex::encode(selector("approve(address,uint256)")), "095ea7b3");
+    }
+
+    #[test]
+    fn parses_uint256_in_both_bases() {
+        let decimal = parse_uint256("1234567890").unwrap();
+        assert_eq!(decimal, parse_uint256("0x499602d2").unwrap());
+        assert_eq!(hex::encode(&decimal[28..]), "499602d2");
+        assert_eq!(
+            parse_uint256("115792089237316195423570985008687907853269984665640564039457584007913129639935").unwrap(),
+            [0xff; 32]
+        );
+        assert!(parse_uint256("115792089237316195423570985008687907853269984665640564039457584007913129639936").is_err());
+        assert!(parse_uint256("-1").is_err());
+        assert!(parse_uint256("0x").is_err());
+    }
+
+    #[test]
+    fn formats_words() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(format_uint256(&parse_uint256(max).unwrap()), max);
+        assert_eq!(format_uint256(&parse_uint256("0x499602d2").unwrap()), "1234567890");
+        assert_eq!(format_uint256(&[0; 32]), "0");
+        assert_eq!(format_address(&parse_address(&LIMIT_ORDER_PROTOCOL.to_lowercase()).unwrap()), LIMIT_ORDER_PROTOCOL);
+    }
+
+    #[test]
+    fn parses_addresses() {
+        let word = parse_address(LIMIT_ORDER_PROTOCOL).unwrap();
+        assert_eq!(&word[..12], &[0u8; 12]);
+        assert_eq!(hex::encode(&word[12..]), "111111125421ca6dc452d289314280a0f8842a65");
+        assert!(parse_address("0x1234").is_err());
+        assert!(parse_address("111111125421cA6dc452d289314280a0f8842A65").is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+use crate::config::ConfigNotFound;
+
+/// Exit code for errors without a more specific category.
+pub const EXIT_FAILURE: i32 = 1;
+/// Also what clap exits with on invalid arguments.
+pub const EXIT_VALIDATION: i32 = 2;
+pub const EXIT_CONFIG_NOT_FOUND: i32 = 3;
+pub const EXIT_NETWORK: i32 = 4;
+pub const EXIT_PRICING: i32 = 5;
+
+/// Failures scripts may want to tell apart; each maps to its own exit code
+/// in [`exit_code`]. Anything else exits with [`EXIT_FAILURE`].
+#[derive(Debug)]
+pub enum VectorPlusError {
+    /// A config, strategy or order failed its checks
+    Validation(String),
+    /// A config or input file does not exist
+    FileNotFound(String),
+    /// An HTTP or JSON-RPC request failed
+    Network(String),
+    /// Pricing produced no finite result
+    Pricing(String),
+}
+
+impl fmt::Display for VectorPlusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VectorPlusError::FileNotFound(path) => write!(f, "File not found: {}", path),
+            VectorPlusError::Validation(message)
+            | VectorPlusError::Network(message)
+            | VectorPlusError::Pricing(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for VectorPlusError {}
+
+/// The process exit code for a command's error.
+pub fn exit_code(error: &eyre::Report) -> i32 {
+    match error.downcast_ref::<VectorPlusError>() {
+        Some(VectorPlusError::Validation(_)) => EXIT_VALIDATION,
+        Some(VectorPlusError::FileNotFound(_)) => EXIT_CONFIG_NOT_FOUND,
+        Some(VectorPlusError::Network(_)) => EXIT_NETWORK,
+        Some(VectorPlusError::Pricing(_)) => EXIT_PRICING,
+        None if error.is::<ConfigNotFound>() => EXIT_CONFIG_NOT_FOUND,
+        None => EXIT_FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_error_kinds_to_exit_codes() {
+        let report = |e: VectorPlusError| eyre::Report::new(e);
+        assert_eq!(exit_code(&report(VectorPlusError::Validation("bad".into()))), 2);
+        assert_eq!(exit_code(&report(VectorPlusError::FileNotFound("a.json".into()))), 3);
+        assert_eq!(exit_code(&report(VectorPlusError::Network("down".into()))), 4);
+        assert_eq!(exit_code(&report(VectorPlusError::Pricing("nan".into()))), 5);
+        assert_eq!(exit_code(&eyre::Report::new(ConfigNotFound { path: "x".into() })), 3);
+        assert_eq!(exit_code(&eyre::eyre!("anything else")), 1);
+
+        // Context added on the way up keeps the code
+        let wrapped = report(VectorPlusError::Network("down".into())).wrap_err("Could not submit");
+        assert_eq!(exit_code(&wrapped), 4);
+    }
+}
@@ -0,0 +1,128 @@
+use colored::*;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// One config or order the tool produced, a line of the history log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Subcommand that produced it, e.g. `volatility create-config`
+    pub command: String,
+    /// Path written, or `-` for stdout
+    pub output: String,
+    /// Same fingerprint as `config fingerprint`
+    pub fingerprint: String,
+}
+
+struct Recorder {
+    path: PathBuf,
+    command: String,
+}
+
+/// Set once at startup unless `--no-history` is given; until then nothing is recorded.
+static RECORDER: OnceLock<Recorder> = OnceLock::new();
+
+/// `$XDG_CONFIG_HOME/vector-plus/history.jsonl`, falling back to
+/// `~/.config/vector-plus/history.jsonl`.
+pub fn default_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("vector-plus").join("history.jsonl"))
+}
+
+/// Records what `command` creates for the rest of the run in the log at `path`.
+pub fn enable(path: PathBuf, command: String) {
+    let _ = RECORDER.set(Recorder { path, command });
+}
+
+/// Appends an entry for a config or order written to `output`. A log that
+/// cannot be written only warns, since the file itself was created.
+pub fn record<T: Serialize>(output: &str, created: &T) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+    let entry = serde_json::to_value(created).map(|value| HistoryEntry {
+        timestamp: chrono::Utc::now(),
+        command: recorder.command.clone(),
+        output: absolute(output),
+        fingerprint: crate::commands::config::fingerprint(&value),
+    });
+    if let Err(e) = entry.map_err(eyre::Report::from).and_then(|entry| append(&recorder.path, &entry)) {
+        eprintln!("{} Could not record history in {}: {}", "⚠️ ".yellow(), recorder.path.display(), e);
+    }
+}
+
+/// The log outlives the working directory, so written files are recorded by
+/// absolute path; stdout stays `-`.
+fn absolute(output: &str) -> String {
+    if output == crate::utils::STDOUT_PATH {
+        return output.to_string();
+    }
+    fs::canonicalize(output).map_or_else(|_| output.to_string(), |path| path.display().to_string())
+}
+
+fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new().create(true).append(true).open(path)?.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Entries in the order they were recorded, and how many lines could not be
+/// read, e.g. one cut short by a crash mid-write. A missing log is empty.
+pub fn read(path: &Path) -> Result<(Vec<HistoryEntry>, usize)> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(e) => return Err(eyre::eyre!("Could not read history {}: {}", path.display(), e)),
+    };
+    let mut entries = Vec::new();
+    let mut unreadable = 0;
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => unreadable += 1,
+        }
+    }
+    Ok((entries, unreadable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_entries_and_skips_unreadable_lines() {
+        let dir = std::env::temp_dir().join(format!("vector-plus-history-{}", std::process::id()));
+        let path = dir.join("nested").join("history.jsonl");
+        let _ = fs::remove_dir_all(&dir);
+        assert!(read(&path).unwrap().0.is_empty());
+
+        for output in ["a.json", "b.json"] {
+            let entry = HistoryEntry {
+                timestamp: chrono::Utc::now(),
+                command: "twap create-config".to_string(),
+                output: output.to_string(),
+                fingerprint: crate::commands::config::fingerprint(&serde_json::json!({ "intervals": 4 })),
+            };
+            append(&path, &entry).unwrap();
+        }
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"timestamp\":").unwrap();
+
+        let (entries, unreadable) = read(&path).unwrap();
+        let outputs: Vec<&str> = entries.iter().map(|entry| entry.output.as_str()).collect();
+        assert_eq!(outputs, ["a.json", "b.json"]);
+        assert_eq!(entries[0].fingerprint, entries[1].fingerprint);
+        assert_eq!(unreadable, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+//! Signing on a Ledger's Ethereum app over USB HID, behind the `ledger` feature.
+
+use eyre::Result;
+
+use crate::eip712::{format_address, parse_address};
+use crate::error::VectorPlusError;
+
+/// Deepest path the Ledger Ethereum app accepts.
+const MAX_HD_DEPTH: usize = 10;
+
+const HARDENED: u32 = 0x8000_0000;
+
+#[cfg(feature = "ledger")]
+const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+/// HID usage page of the interface Ledger apps exchange APDUs on.
+#[cfg(feature = "ledger")]
+const LEDGER_USAGE_PAGE: u16 = 0xffa0;
+
+/// Long enough for the user to read and approve on the device.
+#[cfg(feature = "ledger")]
+const READ_TIMEOUT_MS: i32 = 120_000;
+
+// HID transport framing
+const CHANNEL: u16 = 0x0101;
+const TAG_APDU: u8 = 0x05;
+const PACKET_SIZE: usize = 64;
+const HEADER_SIZE: usize = 5;
+
+// Ethereum app commands
+const CLA: u8 = 0xe0;
+const INS_GET_ADDRESS: u8 = 0x02;
+const INS_SIGN_EIP712_HASHED: u8 = 0x0c;
+const P1_SHOW_ON_DEVICE: u8 = 0x01;
+const SW_OK: u16 = 0x9000;
+
+/// Parses a BIP-32 path such as `m/44'/60'/0'/0/0` into indexes, with the
+/// hardened bit set on those marked `'` or `h`.
+pub fn parse_hd_path(path: &str) -> Result<Vec<u32>> {
+    let invalid = |reason: String| VectorPlusError::Validation(format!("Invalid --hd-path {}: {}", path, reason));
+    let Some(rest) = path.strip_prefix("m/") else {
+        return Err(invalid("must start with m/".to_string()).into());
+    };
+    let indexes = rest.split('/').map(|part| {
+        let (digits, hardened) = match part.strip_suffix(['\'', 'h']) {
+            Some(digits) => (digits, true),
+            None => (part, false),
+        };
+        match digits.parse::<u32>() {
+            Ok(index) if index < HARDENED => Ok(if hardened { index | HARDENED } else { index }),
+            _ => Err(invalid(format!("{:?} is not an index below 2^31", part))),
+        }
+    }).collect::<std::result::Result<Vec<u32>, _>>()?;
+    if indexes.len() > MAX_HD_DEPTH {
+        return Err(invalid(format!("at most {} levels are supported", MAX_HD_DEPTH)).into());
+    }
+    Ok(indexes)
+}
+
+/// A path as the app reads it: the level count, then each index big-endian.
+fn encode_path(path: &[u32]) -> Vec<u8> {
+    let mut data = vec![path.len() as u8];
+    for index in path {
+        data.extend_from_slice(&index.to_be_bytes());
+    }
+    data
+}
+
+/// Ethereum app APDU; every payload here is well under the 255-byte limit.
+fn apdu(ins: u8, p1: u8, data: &[u8]) -> Vec<u8> {
+    let mut apdu = vec![CLA, ins, p1, 0x00, data.len() as u8];
+    apdu.extend_from_slice(data);
+    apdu
+}
+
+/// Splits an APDU into HID packets of channel, tag and sequence number, with
+/// the APDU length ahead of the first packet's payload.
+fn frame(apdu: &[u8]) -> Vec<[u8; PACKET_SIZE]> {
+    let mut payload = (apdu.len() as u16).to_be_bytes().to_vec();
+    payload.extend_from_slice(apdu);
+    payload.chunks(PACKET_SIZE - HEADER_SIZE).enumerate().map(|(seq, chunk)| {
+        let mut packet = [0u8; PACKET_SIZE];
+        packet[..2].copy_from_slice(&CHANNEL.to_be_bytes());
+        packet[2] = TAG_APDU;
+        packet[3..HEADER_SIZE].copy_from_slice(&(seq as u16).to_be_bytes());
+        packet[HEADER_SIZE..HEADER_SIZE + chunk.len()].copy_from_slice(chunk);
+        packet
+    }).collect()
+}
+
+/// Reassembles a response from the packets `next` reads, returning the data
+/// and the trailing status word.
+fn unframe(mut next: impl FnMut() -> Result<[u8; PACKET_SIZE]>) -> Result<(Vec<u8>, u16)> {
+    let mut payload = Vec::new();
+    let mut len = usize::MAX;
+    let mut seq: u16 = 0;
+    while payload.len() < len {
+        let packet = next()?;
+        if packet[..2] != CHANNEL.to_be_bytes() || packet[2] != TAG_APDU || packet[3..HEADER_SIZE] != seq.to_be_bytes() {
+            return Err(eyre::eyre!("Unexpected packet {} from the Ledger", seq));
+        }
+        if seq == 0 {
+            len = usize::from(u16::from_be_bytes([packet[HEADER_SIZE], packet[HEADER_SIZE + 1]]));
+            payload.extend_from_slice(&packet[HEADER_SIZE + 2..]);
+        } else {
+            payload.extend_from_slice(&packet[HEADER_SIZE..]);
+        }
+        seq += 1;
+    }
+    if len < 2 {
+        return Err(eyre::eyre!("Ledger response has no status word"));
+    }
+    payload.truncate(len);
+    let status = u16::from_be_bytes([payload[len - 2], payload[len - 1]]);
+    payload.truncate(len - 2);
+    Ok((payload, status))
+}
+
+fn check_status(status: u16) -> Result<()> {
+    match status {
+        SW_OK => Ok(()),
+        0x6985 => Err(eyre::eyre!("Rejected on the Ledger")),
+        0x5515 | 0x6511 | 0x6d00 | 0x6e00 | 0x6e01 => {
+            Err(eyre::eyre!("Unlock the Ledger and open its Ethereum app (status 0x{:04x})", status))
+        }
+        0x6a80 => Err(eyre::eyre!("The Ethereum app refused the request; update it, or enable blind signing in its settings (status 0x6a80)")),
+        _ => Err(eyre::eyre!("Ledger returned status 0x{:04x}", status)),
+    }
+}
+
+/// Address from a GET_ADDRESS response: the public key, then the address as
+/// ASCII hex, each with a length byte.
+fn parse_address_response(data: &[u8]) -> Result<String> {
+    let invalid = || eyre::eyre!("Malformed address response from the Ledger");
+    let key_len = usize::from(*data.first().ok_or_else(invalid)?);
+    let address_len = usize::from(*data.get(1 + key_len).ok_or_else(invalid)?);
+    let address = data.get(2 + key_len..2 + key_len + address_len).ok_or_else(invalid)?;
+    let address = std::str::from_utf8(address).map_err(|_| invalid())?;
+    Ok(format_address(&parse_address(&format!("0x{}", address))?))
+}
+
+/// Reorders the app's `v || r || s` into the `r || s || v` `order submit` takes.
+fn parse_signature_response(data: &[u8]) -> Result<String> {
+    if data.len() != 65 {
+        return Err(eyre::eyre!("Ledger returned a {}-byte signature, expected 65", data.len()));
+    }
+    let mut signature = data[1..].to_vec();
+    signature.push(data[0]);
+    Ok(format!("0x{}", hex::encode(signature)))
+}
+
+/// A connected Ledger with the Ethereum app open.
+#[cfg(feature = "ledger")]
+pub struct Ledger {
+    device: hidapi::HidDevice,
+}
+
+#[cfg(feature = "ledger")]
+impl Ledger {
+    /// Opens the first connected Ledger.
+    pub fn open() -> Result<Self> {
+        let api = hidapi::HidApi::new().map_err(|e| eyre::eyre!("Could not start USB HID: {}", e))?;
+        let info = api.device_list()
+            .find(|info| {
+                info.vendor_id() == LEDGER_VENDOR_ID
+                    && (info.usage_page() == LEDGER_USAGE_PAGE || info.interface_number() == 0)
+            })
+            .ok_or_else(|| eyre::eyre!("No Ledger found; connect and unlock it, then open the Ethereum app"))?;
+        let device = info.open_device(&api).map_err(|e| eyre::eyre!("Could not open the Ledger: {}", e))?;
+        Ok(Self { device })
+    }
+
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+        for packet in frame(apdu) {
+            // hidapi takes the report id, always 0, ahead of the packet
+            let mut report = vec![0u8];
+            report.extend_from_slice(&packet);
+            self.device.write(&report).map_err(|e| eyre::eyre!("Could not write to the Ledger: {}", e))?;
+        }
+        let (data, status) = unframe(|| {
+            let mut packet = [0u8; PACKET_SIZE];
+            let read = self.device.read_timeout(&mut packet, READ_TIMEOUT_MS)
+                .map_err(|e| eyre::eyre!("Could not read from the Ledger: {}", e))?;
+            if read == 0 {
+                return Err(eyre::eyre!("Timed out waiting for the Ledger"));
+            }
+            Ok(packet)
+        })?;
+        check_status(status)?;
+        Ok(data)
+    }
+
+    /// Address of the account at `path`; with `show`, the device displays it
+    /// and waits for the user to approve.
+    pub fn address(&self, path: &[u32], show: bool) -> Result<String> {
+        let p1 = if show { P1_SHOW_ON_DEVICE } else { 0 };
+        parse_address_response(&self.exchange(&apdu(INS_GET_ADDRESS, p1, &encode_path(path)))?)
+    }
+
+    /// Signs an EIP-712 message given its domain separator and struct hash,
+    /// which the device shows for approval, returning `r || s || v` as hex.
+    pub fn sign_eip712_hashed(&self, path: &[u32], domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> Result<String> {
+        let mut data = encode_path(path);
+        data.extend_from_slice(domain_separator);
+        data.extend_from_slice(struct_hash);
+        parse_signature_response(&self.exchange(&apdu(INS_SIGN_EIP712_HASHED, 0, &data))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::order::DEFAULT_HD_PATH;
+
+    #[test]
+    fn parses_hardened_derivation_paths() {
+        assert_eq!(parse_hd_path(DEFAULT_HD_PATH).unwrap(), [44 | HARDENED, 60 | HARDENED, HARDENED, 0, 0]);
+        assert_eq!(parse_hd_path("m/44h/60h/1h").unwrap(), [44 | HARDENED, 60 | HARDENED, 1 | HARDENED]);
+        for bad in ["44'/60'/0'", "m/", "m/44'/x", "m/2147483648", "m/0/0/0/0/0/0/0/0/0/0/0"] {
+            assert!(parse_hd_path(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn frames_apdus_across_packets_and_back() {
+        let path = parse_hd_path(DEFAULT_HD_PATH).unwrap();
+        let mut data = encode_path(&path);
+        data.extend_from_slice(&[0x11; 32]);
+        data.extend_from_slice(&[0x22; 32]);
+        let request = apdu(INS_SIGN_EIP712_HASHED, 0, &data);
+        assert_eq!(request[..6], [0xe0, 0x0c, 0x00, 0x00, 85, 5]);
+        assert_eq!(request[6..10], [0x80, 0x00, 0x00, 0x2c]);
+
+        // 2 length bytes + 90 APDU bytes need two 59-byte payloads
+        let packets = frame(&request);
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0][..7], [0x01, 0x01, 0x05, 0x00, 0x00, 0x00, 90]);
+        assert_eq!(packets[1][..5], [0x01, 0x01, 0x05, 0x00, 0x01]);
+
+        // A response frames the same way, with the status word at the end
+        let mut response = vec![0xab; 70];
+        response.extend_from_slice(&SW_OK.to_be_bytes());
+        let mut packets = frame(&response).into_iter();
+        let (body, status) = unframe(|| packets.next().ok_or_else(|| eyre::eyre!("no more packets"))).unwrap();
+        assert_eq!(body, vec![0xab; 70]);
+        check_status(status).unwrap();
+        assert!(check_status(0x6985).unwrap_err().to_string().contains("Rejected"));
+
+        let mut wrong_channel = frame(&response)[0];
+        wrong_channel[1] = 0x02;
+        assert!(unframe(|| Ok(wrong_channel)).is_err());
+    }
+
+    #[test]
+    fn reads_address_and_signature_responses() {
+        let mut address = vec![65];
+        address.extend_from_slice(&[0x04; 65]);
+        address.push(40);
+        address.extend_from_slice(b"00000000000000000000000000000000000000aa");
+        assert_eq!(parse_address_response(&address).unwrap(), "0x00000000000000000000000000000000000000AA");
+        assert!(parse_address_response(&address[..70]).is_err());
+        assert_eq!(apdu(INS_GET_ADDRESS, P1_SHOW_ON_DEVICE, &[])[..4], [0xe0, 0x02, 0x01, 0x00]);
+
+        let mut signature = vec![28];
+        signature.extend_from_slice(&[0x11; 32]);
+        signature.extend_from_slice(&[0x22; 32]);
+        let hex = parse_signature_response(&signature).unwrap();
+        assert_eq!(hex, format!("0x{}{}1c", "11".repeat(32), "22".repeat(32)));
+        assert!(parse_signature_response(&signature[1..]).is_err());
+    }
+}
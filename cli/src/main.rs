@@ -1,9 +1,25 @@
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use colored::*;
 use eyre::Result;
+use std::collections::BTreeMap;
 
+mod amount;
+mod bps;
+mod build_info;
 mod commands;
 mod config;
+mod eip712;
+mod error;
+mod history;
+#[cfg(any(feature = "ledger", test))]
+mod ledger;
+mod networks;
+mod price_stream;
+mod rng;
+mod rounding;
+mod rpc;
+mod shutdown;
 mod utils;
 
 use commands::*;
@@ -11,23 +27,149 @@ use commands::*;
 #[derive(Parser)]
 #[command(name = "vector-plus")]
 #[command(about = "Vector Plus - Advanced Trading Strategies for 1inch Limit Order Protocol")]
-#[command(version = "0.1.0")]
+#[command(version)]
 #[command(author = "1inch Team")]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
 
     /// Network to use (mainnet, polygon, arbitrum)
-    #[arg(long, default_value = "mainnet")]
+    #[arg(long, env = "VECTOR_PLUS_NETWORK", default_value = "mainnet")]
     network: String,
 
     /// Configuration file path
-    #[arg(long, default_value = "vector-plus.json")]
+    #[arg(long, env = "VECTOR_PLUS_CONFIG", default_value = "vector-plus.json")]
     config: String,
 
-    /// Verbose output
-    #[arg(short, long)]
+    /// Print resolved settings, intermediate values and written file paths
+    #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Answer yes to confirmation prompts such as overwrites, for non-interactive use
+    #[arg(short = 'y', long, global = true)]
+    assume_yes: bool,
+
+    /// Decimal places for displayed ETH and quote amounts (max 18)
+    #[arg(long, global = true, default_value = "6", value_parser = clap::value_parser!(u8).range(0..=18))]
+    precision: u8,
+
+    /// How displayed times such as option expiry and config age are written
+    #[arg(long, global = true, value_enum, default_value = "relative")]
+    time_format: utils::TimeFormat,
+
+    /// JSON-RPC endpoint for commands that send transactions, e.g. a local Anvil fork
+    #[arg(long, global = true, env = "VECTOR_PLUS_RPC_URL")]
+    rpc_url: Option<String>,
+
+    /// Load environment variables from this file instead of ./.env
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "no_env_file")]
+    env_file: Option<String>,
+
+    /// Do not load a .env file at startup
+    #[arg(long, global = true)]
+    no_env_file: bool,
+
+    /// When to use ANSI colors; auto honors NO_COLOR, then whether stdout is a terminal
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    color: ColorChoice,
+
+    /// Never use ANSI colors, same as --color never
+    #[arg(long, global = true, conflicts_with = "color")]
+    no_color: bool,
+
+    /// Log of created configs and orders (default: ~/.config/vector-plus/history.jsonl)
+    #[arg(long, global = true, env = "VECTOR_PLUS_HISTORY_FILE", value_name = "PATH")]
+    history_file: Option<String>,
+
+    /// Do not record created configs and orders in the history log
+    #[arg(long, global = true)]
+    no_history: bool,
+
+    /// Where each global setting came from, filled in by [`Cli::resolve`]
+    #[arg(skip)]
+    sources: BTreeMap<&'static str, config::Source>,
+
+    /// Subcommand names, e.g. `volatility create-config`, for the history log
+    #[arg(skip)]
+    command_path: String,
+}
+
+/// Global settings whose source `config show --effective` reports, with the
+/// environment variable each can be read from.
+const RESOLVED_SETTINGS: [(&str, Option<&str>); 9] = [
+    ("network", Some("VECTOR_PLUS_NETWORK")),
+    ("config", Some("VECTOR_PLUS_CONFIG")),
+    ("rpc_url", Some("VECTOR_PLUS_RPC_URL")),
+    ("precision", None),
+    ("time_format", None),
+    ("verbose", None),
+    ("assume_yes", None),
+    ("color", None),
+    ("no_color", None),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl Cli {
+    /// Parses the arguments like `Cli::parse`, recording whether each global
+    /// setting came from a flag, the environment, a variable loaded from
+    /// `env_file_vars`, or clap's default. Precedence is clap's: a flag beats
+    /// the environment, which beats the default; `.env` never overrides a
+    /// variable that is already set.
+    fn resolve(env_file_vars: &[String]) -> Self {
+        let mut command = Cli::command();
+        let matches = command.clone().get_matches();
+        Self::from_matches(&matches, env_file_vars).unwrap_or_else(|e| e.format(&mut command).exit())
+    }
+
+    fn from_matches(matches: &clap::ArgMatches, env_file_vars: &[String]) -> Result<Self, clap::Error> {
+        let mut cli = Cli::from_arg_matches(matches)?;
+        for (id, var) in RESOLVED_SETTINGS {
+            let source = match matches.value_source(id) {
+                Some(ValueSource::CommandLine) => config::Source::Flag,
+                Some(ValueSource::EnvVariable) if var.is_some_and(|var| env_file_vars.iter().any(|v| v == var)) => {
+                    config::Source::EnvFile
+                }
+                Some(ValueSource::EnvVariable) => config::Source::Env,
+                _ => config::Source::Default,
+            };
+            cli.sources.insert(id, source);
+        }
+        let mut names = Vec::new();
+        let mut current = matches;
+        while let Some((name, sub)) = current.subcommand() {
+            names.push(name);
+            current = sub;
+        }
+        cli.command_path = names.join(" ");
+        Ok(cli)
+    }
+
+    /// The history log `--history-file` names, else the default location.
+    fn history_path(&self) -> Option<std::path::PathBuf> {
+        self.history_file.as_deref().map(std::path::PathBuf::from).or_else(history::default_path)
+    }
+
+    /// Where a global setting came from; see [`RESOLVED_SETTINGS`].
+    fn source(&self, id: &str) -> config::Source {
+        self.sources.get(id).copied().unwrap_or(config::Source::Default)
+    }
+
+    /// Applies --color/--no-color. An explicit choice overrides NO_COLOR and
+    /// terminal detection, which `colored` applies on its own under auto.
+    fn apply_color_choice(&self) {
+        let choice = if self.no_color { ColorChoice::Never } else { self.color };
+        match choice {
+            ColorChoice::Auto => {}
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -52,48 +194,327 @@ enum Commands {
         #[command(subcommand)]
         command: CombinedCommands,
     },
+    /// Inspect any strategy config file
+    Strategy {
+        #[command(subcommand)]
+        command: StrategyCommands,
+    },
+    /// Limit order file utilities
+    Order {
+        #[command(subcommand)]
+        command: OrderCommands,
+    },
+    /// Summarize risk across every strategy config in a directory
+    Report {
+        /// Directory of strategy config files
+        #[arg(long, default_value = ".")]
+        dir: String,
+
+        /// Count options expiring within this many hours as expiring soon
+        #[arg(long, default_value_t = 24)]
+        expiring_within_hours: u64,
+
+        /// Print the report as JSON, the same as --format json
+        #[arg(long)]
+        json: bool,
+
+        /// Output format of the report
+        #[arg(long, value_enum, conflicts_with = "json")]
+        format: Option<utils::table::OutputFormat>,
+    },
+    /// Preview one order end to end: volatility, sizing, amounts, order JSON and EIP-712 hash
+    Simulate {
+        /// Volatility config that sizes the order
+        #[arg(long, default_value = "volatility-config.json")]
+        volatility_config: String,
+
+        /// Requested amount of the maker asset
+        #[arg(long)]
+        amount: amount::Amount,
+
+        /// Taker asset received per unit of the maker asset
+        #[arg(long, value_parser = crate::utils::parse_positive_f64)]
+        price: f64,
+
+        /// Asset sold: an address or a token symbol known on --network, e.g. WETH
+        #[arg(long)]
+        maker_asset: String,
+
+        /// Asset bought: an address or a token symbol known on --network, e.g. USDC
+        #[arg(long)]
+        taker_asset: String,
+
+        /// Maker address (default: the zero address, for a preview)
+        #[arg(long, default_value = "0x0000000000000000000000000000000000000000")]
+        maker: String,
+
+        /// Decimals of the maker asset, required when it is not a known token
+        #[arg(long)]
+        maker_decimals: Option<u32>,
+
+        /// Decimals of the taker asset, required when it is not a known token
+        #[arg(long)]
+        taker_decimals: Option<u32>,
+
+        /// Order salt, decimal or 0x hex (default: the current time in milliseconds)
+        #[arg(long)]
+        salt: Option<String>,
+
+        /// Expire the order this many seconds from now (default: never)
+        #[arg(long)]
+        expires_in_secs: Option<u64>,
+
+        /// Also write the order JSON to this file
+        #[arg(long)]
+        out: Option<String>,
+    },
     /// Configuration management
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
     },
     /// Show examples and documentation
-    Examples,
+    Examples {
+        #[command(subcommand)]
+        command: Option<ExamplesCommands>,
+    },
     /// Interactive strategy builder
     Interactive,
+    /// Print version and build details for bug reports
+    Version,
+    /// Show the configs and orders created so far
+    History {
+        #[command(subcommand)]
+        command: Option<HistoryCommands>,
+
+        /// Show only the most recent entries
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Print a shell completion script, e.g. `vector-plus completions bash > /etc/bash_completion.d/vector-plus`
+    Completions {
+        /// Shell to generate the script for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+impl Commands {
+    /// Whether the command prints JSON, CSV or build details that must not be
+    /// mixed with decorative output.
+    fn is_machine_readable(&self) -> bool {
+        matches!(
+            self,
+            Commands::Version
+                | Commands::Completions { .. }
+                | Commands::Volatility { command: VolatilityCommands::Calculate { json: true, .. } }
+                | Commands::Volatility { command: VolatilityCommands::Calculate { explain_json: true, .. } }
+                | Commands::Volatility { command: VolatilityCommands::Calculate { csv: true, .. } }
+                | Commands::Volatility { command: VolatilityCommands::Calculate { output_schema: true, .. } }
+                | Commands::Volatility { command: VolatilityCommands::Calculate { min_factor_change: Some(_), .. } }
+                | Commands::Volatility { command: VolatilityCommands::Stress { json: true, .. } }
+                | Commands::Volatility { command: VolatilityCommands::Heatmap { json: true, .. } }
+                | Commands::Volatility { command: VolatilityCommands::Var { json: true, .. } }
+                | Commands::Options { command: OptionsCommands::Chain { csv: true, .. } }
+                | Commands::Options { command: OptionsCommands::Chain { json: true, .. } }
+                | Commands::Options { command: OptionsCommands::Premium { json: true, .. } }
+                | Commands::Config { command: ConfigCommands::Show { json: true, .. } }
+                | Commands::Config { command: ConfigCommands::Diff { json: true, .. } }
+                | Commands::Config { command: ConfigCommands::Lint { json: true, .. } }
+                | Commands::Report { json: true, .. }
+        ) || self.output_format().is_some_and(utils::table::OutputFormat::is_machine)
+    }
+
+    /// The `--format` given to a tabular command.
+    fn output_format(&self) -> Option<utils::table::OutputFormat> {
+        match self {
+            Commands::Volatility {
+                command: VolatilityCommands::Calculate { format, .. } | VolatilityCommands::Stress { format, .. },
+            }
+            | Commands::Options {
+                command: OptionsCommands::Chain { format, .. } | OptionsCommands::Premium { format, .. },
+            }
+            | Commands::Report { format, .. } => *format,
+            _ => None,
+        }
+    }
+
+    /// Whether a create command was given `--output -`, so stdout must carry
+    /// only the config JSON.
+    fn writes_config_to_stdout(&self) -> bool {
+        let output = match self {
+            Commands::Volatility { command: VolatilityCommands::CreateConfig { output, .. } }
+            | Commands::Twap { command: TwapCommands::CreateConfig { output, .. } }
+            | Commands::Combined { command: CombinedCommands::Create { output, .. } }
+            | Commands::Options {
+                command: OptionsCommands::CreateCall { output, .. } | OptionsCommands::CreatePut { output, .. },
+            }
+            | Commands::Strategy { command: StrategyCommands::Convert { output: Some(output), .. } } => output,
+            _ => return false,
+        };
+        output == utils::STDOUT_PATH
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    // The .env file must be loaded before clap reads env-backed arguments,
+    // so a first, lenient parse only picks out its own flags
+    let env_file_vars = load_env_file()?;
+
+    // clap handles --version on its own and exits, so the verbose form is
+    // picked out of the raw arguments first; plain --version stays minimal
+    let args: Vec<String> = std::env::args().collect();
+    let has = |flags: &[&str]| args.iter().skip(1).any(|arg| flags.contains(&arg.as_str()));
+    if has(&["--version", "-V"]) && has(&["--verbose", "-v"]) {
+        println!("{}", build_info::describe());
+        return Ok(());
+    }
+
+    let cli = Cli::resolve(&env_file_vars);
+    cli.apply_color_choice();
+    if !cli.no_history {
+        if let Some(path) = cli.history_path() {
+            history::enable(path, cli.command_path.clone());
+        }
+    }
     
-    // Print Vector Plus banner
-    print_banner();
+    if cli.command.writes_config_to_stdout() {
+        utils::reserve_stdout();
+    }
+
+    // Print Vector Plus banner, unless stdout carries machine-readable output
+    if !cli.command.is_machine_readable() && !utils::stdout_reserved() {
+        print_banner();
+    }
+    utils::verbose!(
+        cli.verbose,
+        "network: {}, config: {}, precision: {}",
+        cli.network, cli.config, cli.precision
+    );
+
+    // Report the error as eyre would, but with an exit code per error kind
+    if let Err(e) = run(&cli).await {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(error::exit_code(&e));
+    }
+    Ok(())
+}
 
-    // Execute command
+async fn run(cli: &Cli) -> Result<()> {
+    // Networks defined in the config resolve like the built-in ones
+    if networks::builtin(&cli.network).is_none() {
+        match config::load(&cli.config) {
+            Ok(config) => networks::register(&config.custom_networks),
+            Err(e) if e.is::<config::ConfigNotFound>() => {}
+            Err(e) => return Err(e),
+        }
+    }
     match cli.command {
         Commands::Volatility { ref command } => {
-            commands::volatility::handle_command(command, &cli).await
+            commands::volatility::handle_command(command, cli).await
         }
         Commands::Twap { ref command } => {
-            commands::twap::handle_command(command, &cli).await
+            commands::twap::handle_command(command, cli).await
         }
         Commands::Options { ref command } => {
-            commands::options::handle_command(command, &cli).await
+            commands::options::handle_command(command, cli).await
         }
         Commands::Combined { ref command } => {
-            commands::combined::handle_command(command, &cli).await
+            commands::combined::handle_command(command, cli).await
+        }
+        Commands::Strategy { ref command } => {
+            commands::strategy::handle_command(command, cli).await
+        }
+        Commands::Order { ref command } => {
+            commands::order::handle_command(command, cli).await
+        }
+        Commands::Report { ref dir, expiring_within_hours, json, format } => {
+            let format = utils::table::OutputFormat::resolve(format, json, false);
+            commands::report::generate_report(dir, expiring_within_hours, format, cli.precision, cli.time_format).await
+        }
+        Commands::Simulate {
+            ref volatility_config,
+            amount,
+            price,
+            ref maker_asset,
+            ref taker_asset,
+            ref maker,
+            maker_decimals,
+            taker_decimals,
+            ref salt,
+            expires_in_secs,
+            ref out,
+        } => {
+            let inputs = commands::simulate::PipelineInputs {
+                volatility_config,
+                amount,
+                price,
+                maker_asset,
+                taker_asset,
+                maker,
+                maker_decimals,
+                taker_decimals,
+                salt: salt.as_deref(),
+                expires_in_secs,
+                out: out.as_deref(),
+            };
+            commands::simulate::run_pipeline(&inputs, cli).await
         }
         Commands::Config { ref command } => {
-            commands::config::handle_command(command, &cli).await
+            commands::config::handle_command(command, cli).await
         }
-        Commands::Examples => {
-            commands::examples::show_examples().await
+        Commands::Examples { ref command } => {
+            commands::examples::handle_command(command.as_ref(), cli).await
         }
         Commands::Interactive => {
-            commands::interactive::run_interactive_mode(&cli).await
+            commands::interactive::run_interactive_mode(cli).await
+        }
+        Commands::Version => {
+            println!("{}", build_info::describe());
+            Ok(())
+        }
+        Commands::History { ref command, limit } => {
+            commands::history::handle_command(command.as_ref(), limit, cli)
         }
+        Commands::Completions { shell } => commands::completions::print_completions(shell),
+    }
+}
+
+/// Loads `.env` (or `--env-file <path>`) unless `--no-env-file` is given,
+/// returning the names of the variables it set. Variables already set in the
+/// real environment always take precedence.
+fn load_env_file() -> Result<Vec<String>> {
+    // Errors, such as a required argument only the .env file supplies, wait
+    // for the real parse in Cli::resolve
+    let matches = Cli::command().ignore_errors(true).try_get_matches().unwrap_or_default();
+    if matches.try_get_one::<bool>("no_env_file").ok().flatten() == Some(&true) {
+        return Ok(Vec::new());
     }
+    let explicit = matches.try_get_one::<String>("env_file").ok().flatten().cloned();
+    let before: std::collections::BTreeSet<String> = std::env::vars_os()
+        .filter_map(|(key, _)| key.into_string().ok())
+        .collect();
+
+    match explicit {
+        Some(path) => {
+            dotenvy::from_path(&path)
+                .map_err(|e| eyre::eyre!("Could not load env file {}: {}", path, e))?;
+        }
+        None => {
+            // A missing ./.env is not an error
+            if let Err(e) = dotenvy::dotenv() {
+                if !e.not_found() {
+                    return Err(eyre::eyre!("Could not load .env: {}", e));
+                }
+            }
+        }
+    }
+
+    Ok(std::env::vars_os()
+        .filter_map(|(key, _)| key.into_string().ok())
+        .filter(|key| !before.contains(key))
+        .collect())
 }
 
 fn print_banner() {
@@ -0,0 +1,122 @@
+use eyre::Result;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::config::CustomNetwork;
+
+/// A chain accepted by `--network`.
+#[derive(Debug)]
+pub struct Network {
+    pub name: &'static str,
+    pub chain_id: u64,
+    /// Symbol of the native token gas is paid in
+    pub gas_token: &'static str,
+    /// Typical time between blocks, in milliseconds
+    pub block_time_ms: u64,
+    /// Well-known ERC-20s, for labelling order assets
+    pub tokens: &'static [Token],
+}
+
+/// An ERC-20 recognized by address when displaying orders.
+#[derive(Debug)]
+pub struct Token {
+    pub symbol: &'static str,
+    pub address: &'static str,
+    pub decimals: u32,
+}
+
+impl Network {
+    /// The known token at `address`, compared case-insensitively.
+    pub fn token(&self, address: &str) -> Option<&'static Token> {
+        self.tokens.iter().find(|token| token.address.eq_ignore_ascii_case(address))
+    }
+
+    /// The wrapped gas token, e.g. WETH or WPOL, if it is among the known tokens.
+    pub fn wrapped_native(&self) -> Option<&'static Token> {
+        self.tokens.iter().find(|token| token.symbol.strip_prefix('W') == Some(self.gas_token))
+    }
+}
+
+const MAINNET_TOKENS: &[Token] = &[
+    Token { symbol: "WETH", address: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", decimals: 18 },
+    Token { symbol: "USDC", address: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", decimals: 6 },
+    Token { symbol: "USDT", address: "0xdAC17F958D2ee523a2206206994597C13D831ec7", decimals: 6 },
+    Token { symbol: "DAI", address: "0x6B175474E89094C44Da98b954EedeAC495271d0F", decimals: 18 },
+    Token { symbol: "WBTC", address: "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599", decimals: 8 },
+];
+
+const OPTIMISM_TOKENS: &[Token] = &[
+    Token { symbol: "WETH", address: "0x4200000000000000000000000000000000000006", decimals: 18 },
+    Token { symbol: "USDC", address: "0x0b2C639c533813f4Aa9D7837Caf62653d097Ff85", decimals: 6 },
+];
+
+const POLYGON_TOKENS: &[Token] = &[
+    Token { symbol: "WPOL", address: "0x0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270", decimals: 18 },
+    Token { symbol: "USDC", address: "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359", decimals: 6 },
+];
+
+const BASE_TOKENS: &[Token] = &[
+    Token { symbol: "WETH", address: "0x4200000000000000000000000000000000000006", decimals: 18 },
+    Token { symbol: "USDC", address: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", decimals: 6 },
+];
+
+const ARBITRUM_TOKENS: &[Token] = &[
+    Token { symbol: "WETH", address: "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1", decimals: 18 },
+    Token { symbol: "USDC", address: "0xaf88d065e77c8cC0eAa45E7E2eD0D0f7d31A1F3F", decimals: 6 },
+];
+
+pub const NETWORKS: [Network; 5] = [
+    Network { name: "mainnet", chain_id: 1, gas_token: "ETH", block_time_ms: 12000, tokens: MAINNET_TOKENS },
+    Network { name: "optimism", chain_id: 10, gas_token: "ETH", block_time_ms: 2000, tokens: OPTIMISM_TOKENS },
+    // Polygon's gas token was renamed from MATIC to POL in 2024
+    Network { name: "polygon", chain_id: 137, gas_token: "POL", block_time_ms: 2000, tokens: POLYGON_TOKENS },
+    Network { name: "base", chain_id: 8453, gas_token: "ETH", block_time_ms: 2000, tokens: BASE_TOKENS },
+    Network { name: "arbitrum", chain_id: 42161, gas_token: "ETH", block_time_ms: 250, tokens: ARBITRUM_TOKENS },
+];
+
+/// Networks registered from the config's `custom_networks`.
+static CUSTOM_NETWORKS: Mutex<Vec<&'static Network>> = Mutex::new(Vec::new());
+
+pub fn builtin(name: &str) -> Option<&'static Network> {
+    NETWORKS.iter().find(|network| network.name == name)
+}
+
+/// The built-in production network with `chain_id`, if any; config-defined
+/// networks are never production ones.
+pub fn production(chain_id: u64) -> Option<&'static Network> {
+    NETWORKS.iter().find(|network| network.chain_id == chain_id)
+}
+
+/// Makes config-defined networks resolvable by [`lookup`]. They are leaked,
+/// living for the rest of the process just like the built-in ones; a name
+/// registered earlier keeps its first definition.
+pub fn register(custom: &BTreeMap<String, CustomNetwork>) {
+    let mut registered = CUSTOM_NETWORKS.lock().unwrap_or_else(|e| e.into_inner());
+    for (name, network) in custom {
+        if builtin(name).is_some() || registered.iter().any(|known| known.name == name) {
+            continue;
+        }
+        registered.push(Box::leak(Box::new(Network {
+            name: Box::leak(name.clone().into_boxed_str()),
+            chain_id: network.chain_id,
+            gas_token: Box::leak(network.gas_token.clone().into_boxed_str()),
+            block_time_ms: network.block_time_ms,
+            tokens: &[],
+        })));
+    }
+}
+
+pub fn lookup(name: &str) -> Result<&'static Network> {
+    if let Some(network) = builtin(name) {
+        return Ok(network);
+    }
+    let registered = CUSTOM_NETWORKS.lock().unwrap_or_else(|e| e.into_inner());
+    registered.iter().copied().find(|network| network.name == name).ok_or_else(|| {
+        let names: Vec<&str> = NETWORKS.iter().map(|network| network.name).collect();
+        eyre::eyre!(
+            "Unknown network: {} (built-in: {}; define others under custom_networks in the config)",
+            name,
+            names.join(", ")
+        )
+    })
+}
@@ -0,0 +1,226 @@
+use eyre::Result;
+use futures_util::StreamExt;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// What the next read from a price stream produced.
+pub enum StreamEvent {
+    Price(f64),
+    /// A message that did not hold a price; the socket stays open
+    Invalid(eyre::Report),
+    /// The socket closed, errored or went quiet and has been dropped
+    Dropped(String),
+}
+
+/// WebSocket price feed for `volatility watch --ws-url`. Messages are JSON
+/// (or bare numbers), one per frame or one per line. After a drop the caller
+/// polls over HTTP until `retry_due`, and each failed reconnect doubles the
+/// wait up to a minute.
+pub struct PriceStream {
+    url: String,
+    socket: Option<WebSocketStream<MaybeTlsStream<TcpStream>>>,
+    /// Prices from a multi-line message not yet handed out
+    pending: VecDeque<f64>,
+    backoff: Duration,
+    retry_at: Instant,
+    pub reconnects: u64,
+}
+
+impl PriceStream {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            socket: None,
+            pending: VecDeque::new(),
+            backoff: INITIAL_BACKOFF,
+            retry_at: Instant::now(),
+            reconnects: 0,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.socket.is_some()
+    }
+
+    pub fn retry_due(&self) -> bool {
+        Instant::now() >= self.retry_at
+    }
+
+    /// Time left before the next reconnect attempt.
+    pub fn until_retry(&self) -> Duration {
+        self.retry_at.saturating_duration_since(Instant::now())
+    }
+
+    /// `until_retry` in whole seconds, rounded up for display.
+    pub fn retry_secs(&self) -> u64 {
+        self.until_retry().as_secs_f64().ceil() as u64
+    }
+
+    /// Opens the socket, scheduling the next attempt with a longer backoff on failure.
+    pub async fn connect(&mut self) -> Result<()> {
+        match tokio_tungstenite::connect_async(self.url.as_str()).await {
+            Ok((socket, _)) => {
+                self.socket = Some(socket);
+                self.backoff = INITIAL_BACKOFF;
+                Ok(())
+            }
+            Err(e) => {
+                self.schedule_retry();
+                Err(eyre::eyre!("{} (retrying in {}s)", e, self.retry_secs()))
+            }
+        }
+    }
+
+    /// Waits for the next price. A socket with no message for `idle_timeout`
+    /// is treated as dropped, since a stalled feed would otherwise freeze the watch.
+    pub async fn next(&mut self, pointer: Option<&str>, idle_timeout: Duration) -> StreamEvent {
+        loop {
+            if let Some(price) = self.pending.pop_front() {
+                return StreamEvent::Price(price);
+            }
+            let Some(socket) = self.socket.as_mut() else {
+                return StreamEvent::Dropped("not connected".to_string());
+            };
+            let text = match tokio::time::timeout(idle_timeout, socket.next()).await {
+                Err(_) => return self.drop_socket(format!("no message for {}s", idle_timeout.as_secs())),
+                Ok(None) => return self.drop_socket("connection ended".to_string()),
+                Ok(Some(Err(e))) => return self.drop_socket(e.to_string()),
+                Ok(Some(Ok(Message::Close(frame)))) => {
+                    let reason = frame.map(|frame| frame.reason.to_string()).filter(|reason| !reason.is_empty());
+                    return self.drop_socket(reason.unwrap_or_else(|| "closed by server".to_string()));
+                }
+                Ok(Some(Ok(Message::Text(text)))) => text.to_string(),
+                Ok(Some(Ok(Message::Binary(bytes)))) => match String::from_utf8(bytes.to_vec()) {
+                    Ok(text) => text,
+                    Err(_) => return StreamEvent::Invalid(eyre::eyre!("binary message is not UTF-8")),
+                },
+                // Pings are answered by the socket itself
+                Ok(Some(Ok(_))) => continue,
+            };
+            match prices_from_message(&text, pointer) {
+                Ok(prices) => self.pending.extend(prices),
+                Err(e) => return StreamEvent::Invalid(e),
+            }
+        }
+    }
+
+    fn drop_socket(&mut self, reason: String) -> StreamEvent {
+        self.socket = None;
+        self.pending.clear();
+        self.reconnects += 1;
+        self.schedule_retry();
+        StreamEvent::Dropped(reason)
+    }
+
+    fn schedule_retry(&mut self) {
+        self.retry_at = Instant::now() + self.backoff;
+        self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Prices in one stream message, one JSON value per non-empty line.
+pub fn prices_from_message(text: &str, pointer: Option<&str>) -> Result<Vec<f64>> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let body: Value = serde_json::from_str(line)
+                .map_err(|e| eyre::eyre!("Stream message is not JSON ({}): {}", e, line))?;
+            price_from_json(&body, pointer)
+        })
+        .collect()
+}
+
+/// The positive price at `pointer` in a price response, accepting numbers
+/// and numeric strings.
+pub fn price_from_json(body: &Value, pointer: Option<&str>) -> Result<f64> {
+    let value = match pointer {
+        Some(pointer) => body.pointer(pointer)
+            .ok_or_else(|| eyre::eyre!("No value at {} in price response", pointer))?,
+        None => body,
+    };
+    let price = value.as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| eyre::eyre!("Price response is not a number: {}", value))?;
+    if !price.is_finite() || price <= 0.0 {
+        return Err(eyre::eyre!("Invalid price: {}", price));
+    }
+    Ok(price)
+}
+
+/// Runs the config's `price_command` with `symbol` appended as its last
+/// argument and reads the price it prints, JSON or a bare number, as an HTTP
+/// response would be read. The command is split on whitespace and run without
+/// a shell; a non-zero exit or no output within `timeout` is an error.
+pub async fn price_from_command(command: &str, symbol: &str, pointer: Option<&str>, timeout: Duration) -> Result<f64> {
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(|| eyre::eyre!("price_command is empty"))?;
+    let run = tokio::process::Command::new(program)
+        .args(words)
+        .arg(symbol)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(timeout, run)
+        .await
+        .map_err(|_| eyre::eyre!("`{}` printed no price within {}s", command, timeout.as_secs()))?
+        .map_err(|e| eyre::eyre!("Could not run `{}`: {}", command, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().find(|line| !line.trim().is_empty()).unwrap_or("no error output");
+        return Err(eyre::eyre!("`{}` failed ({}): {}", command, output.status, reason.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let body: Value = serde_json::from_str(stdout.trim())
+        .map_err(|_| eyre::eyre!("`{}` printed {:?}, not a price", command, stdout.trim()))?;
+    price_from_json(&body, pointer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_one_price_per_line() {
+        let message = "{\"ethereum\":{\"usd\":2500.5}}\n\n{\"ethereum\":{\"usd\":\"2501\"}}\n";
+        assert_eq!(prices_from_message(message, Some("/ethereum/usd")).unwrap(), vec![2500.5, 2501.0]);
+        assert_eq!(prices_from_message("2499.75", None).unwrap(), vec![2499.75]);
+        assert!(prices_from_message("{\"usd\":-1}", Some("/usd")).is_err());
+        assert!(prices_from_message("not json", None).is_err());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_a_minute() {
+        let mut stream = PriceStream::new("ws://127.0.0.1:1");
+        for _ in 0..10 {
+            stream.schedule_retry();
+        }
+        assert_eq!(stream.backoff, MAX_BACKOFF);
+        assert!(!stream.retry_due());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_the_price_command_with_the_symbol() {
+        let timeout = Duration::from_secs(5);
+        // printf ignores the appended symbol, which has no format slot
+        assert_eq!(price_from_command("printf 2500.5", "ETH", None, timeout).await.unwrap(), 2500.5);
+        assert_eq!(price_from_command("printf {\"usd\":7}", "ETH", Some("/usd"), timeout).await.unwrap(), 7.0);
+        let failed = price_from_command("false", "ETH", None, timeout).await.unwrap_err();
+        assert!(failed.to_string().contains("failed"), "{}", failed);
+        assert!(price_from_command("printf -5", "ETH", None, timeout).await.is_err());
+        assert!(price_from_command("true", "ETH", None, timeout).await.is_err());
+        assert!(price_from_command("no-such-price-program", "ETH", None, timeout).await.is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use clap::{Args, ValueEnum};
+use eyre::Result;
+
+use crate::amount::{Amount, DECIMALS};
+use crate::error::VectorPlusError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RoundingMode {
+    /// Round down; the final TWAP chunk collects the remainder
+    Floor,
+    /// Round up; the final TWAP chunk is whatever is left
+    Ceil,
+    /// Round to the nearest step, tracking the running total so errors don't build up
+    Nearest,
+}
+
+/// Flags for rounding sized amounts, shared by TWAP and portfolio sizing.
+#[derive(Args)]
+pub struct RoundingArgs {
+    /// How sized amounts are rounded (default: nearest, when --round-decimals or --lot-size is set)
+    #[arg(long, value_enum)]
+    rounding: Option<RoundingMode>,
+
+    /// Round amounts to this many decimal places
+    #[arg(long, conflicts_with = "lot_size")]
+    round_decimals: Option<u8>,
+
+    /// Round amounts to multiples of this lot size
+    #[arg(long)]
+    lot_size: Option<Amount>,
+}
+
+impl RoundingArgs {
+    /// The rounding to apply, or `None` to keep amounts unrounded.
+    pub fn resolve(&self) -> Result<Option<Rounding>> {
+        let grid = match (self.round_decimals, self.lot_size) {
+            (Some(decimals), _) if decimals as usize > DECIMALS => {
+                return Err(VectorPlusError::Validation(format!("--round-decimals must be at most {}, got {}", DECIMALS, decimals)).into());
+            }
+            (Some(decimals), _) => Grid::Decimals(decimals),
+            (None, Some(lot)) if !lot.is_zero() => Grid::Lot(lot),
+            (None, Some(lot)) => return Err(VectorPlusError::Validation(format!("--lot-size must be positive, got {}", lot)).into()),
+            (None, None) if self.rounding.is_some() => {
+                return Err(VectorPlusError::Validation("--rounding needs --round-decimals or --lot-size".to_string()).into());
+            }
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(Rounding { mode: self.rounding.unwrap_or(RoundingMode::Nearest), grid }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grid {
+    Decimals(u8),
+    Lot(Amount),
+}
+
+/// A rounding mode applied to a grid of decimal places or lot sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rounding {
+    mode: RoundingMode,
+    grid: Grid,
+}
+
+impl Rounding {
+    /// Wei in one grid step.
+    fn step(&self) -> u128 {
+        match self.grid {
+            Grid::Decimals(decimals) => 10u128.pow((DECIMALS - decimals as usize) as u32),
+            Grid::Lot(lot) => lot.wei(),
+        }
+    }
+
+    fn round_with(&self, amount: Amount, mode: RoundingMode) -> Amount {
+        let step = self.step();
+        let (steps, remainder) = (amount.wei() / step, amount.wei() % step);
+        let steps = match mode {
+            RoundingMode::Ceil if remainder > 0 => steps + 1,
+            RoundingMode::Nearest if remainder >= step - step / 2 => steps + 1,
+            _ => steps,
+        };
+        Amount::from_wei(steps.saturating_mul(step))
+    }
+
+    /// e.g. "floor to 2 decimals" or "nearest lot of 0.5".
+    pub fn describe(&self) -> String {
+        let mode = match self.mode {
+            RoundingMode::Floor => "floor",
+            RoundingMode::Ceil => "ceil",
+            RoundingMode::Nearest => "nearest",
+        };
+        match self.grid {
+            Grid::Decimals(decimals) => format!("{} to {} decimals", mode, decimals),
+            Grid::Lot(lot) => format!("{} lot of {}", mode, lot),
+        }
+    }
+
+    pub fn round(&self, amount: Amount) -> Amount {
+        self.round_with(amount, self.mode)
+    }
+
+    /// Rounds `amount`, then steps back onto the grid inside `[min, max]` if
+    /// rounding pushed it out.
+    pub fn round_within(&self, amount: Amount, min: Amount, max: Amount) -> Amount {
+        let rounded = self.round(amount);
+        if rounded > max {
+            self.round_with(max, RoundingMode::Floor)
+        } else if rounded < min {
+            self.round_with(min, RoundingMode::Ceil)
+        } else {
+            rounded
+        }
+    }
+
+    /// Rounds every chunk but the last, which takes `total` minus the others so
+    /// the sum is conserved. Floor leaves the last chunk the accumulated
+    /// remainder; ceil stops once `total` is used up, leaving later chunks
+    /// empty; nearest rounds the running total, so no chunk is off by more than
+    /// a step. The last chunk is off the grid only if `total` is.
+    pub fn split(&self, total: Amount, chunks: &[Amount]) -> Vec<Amount> {
+        let Some(last) = chunks.len().checked_sub(1) else {
+            return Vec::new();
+        };
+        let mut rounded = Vec::with_capacity(chunks.len());
+        let mut allocated = Amount::ZERO;
+        let mut cumulative = Amount::ZERO;
+        for chunk in &chunks[..last] {
+            cumulative = cumulative + *chunk;
+            let size = match self.mode {
+                RoundingMode::Nearest => self.round(cumulative).saturating_sub(allocated),
+                RoundingMode::Floor | RoundingMode::Ceil => self.round(*chunk),
+            };
+            // Never hand out more than the order holds
+            let size = size.min(self.round_with(total.saturating_sub(allocated), RoundingMode::Floor));
+            allocated = allocated + size;
+            rounded.push(size);
+        }
+        rounded.push(total.saturating_sub(allocated));
+        rounded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounding(mode: RoundingMode, grid: Grid) -> Rounding {
+        Rounding { mode, grid }
+    }
+
+    fn eth(text: &str) -> Amount {
+        text.parse().unwrap()
+    }
+
+    fn amounts(texts: &[&str]) -> Vec<Amount> {
+        texts.iter().map(|text| eth(text)).collect()
+    }
+
+    #[test]
+    fn rejects_out_of_range_flags_as_validation_errors() {
+        for (round_decimals, lot_size, rounding) in [
+            (Some(30), None, None),
+            (None, Some(Amount::ZERO), None),
+            (None, None, Some(RoundingMode::Floor)),
+        ] {
+            let err = RoundingArgs { rounding, round_decimals, lot_size }.resolve().unwrap_err();
+            assert_eq!(crate::error::exit_code(&err), crate::error::EXIT_VALIDATION, "{}", err);
+        }
+    }
+
+    #[test]
+    fn splits_conserve_the_total() {
+        let chunks = eth("10").split(&[1.0; 3]);
+        let floor = rounding(RoundingMode::Floor, Grid::Decimals(1)).split(eth("10"), &chunks);
+        assert_eq!(floor, amounts(&["3.3", "3.3", "3.4"]));
+
+        let ceil = rounding(RoundingMode::Ceil, Grid::Lot(eth("4"))).split(eth("10"), &chunks);
+        assert_eq!(ceil, amounts(&["4", "4", "2"]));
+
+        let nearest = rounding(RoundingMode::Nearest, Grid::Lot(eth("1"))).split(eth("10"), &[eth("2.5"); 4]);
+        assert_eq!(nearest, amounts(&["3", "2", "3", "2"]));
+
+        // Ceil runs out before the last chunk, which takes what is left
+        let short = rounding(RoundingMode::Ceil, Grid::Lot(eth("3"))).split(eth("5"), &[eth("1"); 5]);
+        assert_eq!(short, amounts(&["3", "0", "0", "0", "2"]));
+        for (split, total) in [(floor, "10"), (ceil, "10"), (nearest, "10"), (short, "5")] {
+            assert_eq!(split.iter().sum::<Amount>(), eth(total));
+        }
+    }
+
+    #[test]
+    fn rounds_onto_the_grid_within_limits() {
+        let floor = rounding(RoundingMode::Floor, Grid::Decimals(2));
+        assert_eq!(floor.round(eth("0.3")), eth("0.3"));
+        assert_eq!(floor.round(eth("1.239")), eth("1.23"));
+        let ceil = rounding(RoundingMode::Ceil, Grid::Lot(eth("0.5")));
+        assert_eq!(ceil.round(eth("1.1")), eth("1.5"));
+        assert_eq!(ceil.round_within(eth("4.9"), eth("0.1"), eth("4.9")), eth("4.5"));
+        assert_eq!(floor.round_within(eth("0.105"), eth("0.105"), eth("5")), eth("0.11"));
+        let nearest = rounding(RoundingMode::Nearest, Grid::Decimals(0));
+        assert_eq!(nearest.round(eth("2.5")), eth("3"));
+        assert_eq!(nearest.round(eth("2.499999999999999999")), eth("2"));
+    }
+}
@@ -0,0 +1,142 @@
+use eyre::Result;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::error::VectorPlusError;
+
+/// Result of a simulated call that may revert.
+pub enum CallOutcome {
+    Success(Vec<u8>),
+    /// The node's error message and the revert data, when the node returns it
+    Reverted { message: String, data: Option<Vec<u8>> },
+}
+
+/// Minimal JSON-RPC client for an Ethereum node, such as a local Anvil or
+/// Hardhat fork.
+pub struct RpcClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl RpcClient {
+    /// Client for `--rpc-url`, falling back to the `rpc_url` of a custom
+    /// `--network`, then to the config file's `rpc_url`.
+    pub fn from_cli(cli: &crate::Cli, command: &str) -> Result<Self> {
+        let url = match &cli.rpc_url {
+            Some(url) => url.clone(),
+            None => {
+                let mut config = crate::config::load_or_default(&cli.config)?;
+                config.custom_networks.remove(&cli.network)
+                    .and_then(|network| network.rpc_url)
+                    .or(config.rpc_url)
+                    .ok_or_else(|| {
+                        eyre::eyre!("{} needs --rpc-url (or rpc_url in {}), e.g. http://127.0.0.1:8545 for a local Anvil fork", command, cli.config)
+                    })?
+            }
+        };
+        Self::new(&url)
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn new(url: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        Ok(Self { client, url: url.to_string() })
+    }
+
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        self.request(method, params).await?.map_err(|error| {
+            let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+            VectorPlusError::Network(format!("{} failed: {}", method, message)).into()
+        })
+    }
+
+    /// Sends a request, returning the node's `error` object rather than failing on it.
+    async fn request(&self, method: &str, params: Value) -> Result<std::result::Result<Value, Value>> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self.client.post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| VectorPlusError::Network(format!("Could not reach RPC {}: {}", self.url, e)))?
+            .json()
+            .await
+            .map_err(|e| VectorPlusError::Network(format!("Invalid response from RPC {}: {}", self.url, e)))?;
+
+        if let Some(error) = response.get("error") {
+            return Ok(Err(error.clone()));
+        }
+        response.get("result")
+            .cloned()
+            .map(Ok)
+            .ok_or_else(|| eyre::eyre!("{} returned no result", method))
+    }
+
+    pub async fn chain_id(&self) -> Result<u64> {
+        let result = self.call("eth_chainId", json!([])).await?;
+        parse_quantity(&result).ok_or_else(|| eyre::eyre!("eth_chainId returned {}", result))
+    }
+
+    /// Calls `to` with `data` at the latest block, returning the raw result bytes.
+    pub async fn eth_call(&self, to: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let call = json!({ "to": to, "data": format!("0x{}", hex::encode(data)) });
+        let result = self.call("eth_call", json!([call, "latest"])).await?;
+        let result = result.as_str().ok_or_else(|| eyre::eyre!("eth_call returned {}", result))?;
+        hex::decode(result.strip_prefix("0x").unwrap_or(result))
+            .map_err(|e| eyre::eyre!("eth_call returned invalid hex: {}", e))
+    }
+
+    /// Runs `tx` with `eth_call` at the latest block, keeping the revert data a
+    /// failed [`RpcClient::call`] would drop.
+    pub async fn simulate(&self, tx: &Value) -> Result<CallOutcome> {
+        match self.request("eth_call", json!([tx, "latest"])).await? {
+            Ok(result) => {
+                let result = result.as_str().ok_or_else(|| eyre::eyre!("eth_call returned {}", result))?;
+                let bytes = hex::decode(result.strip_prefix("0x").unwrap_or(result))
+                    .map_err(|e| eyre::eyre!("eth_call returned invalid hex: {}", e))?;
+                Ok(CallOutcome::Success(bytes))
+            }
+            Err(error) => revert_outcome(&error)
+                .ok_or_else(|| VectorPlusError::Network(format!("eth_call failed: {}", error)).into()),
+        }
+    }
+
+    pub async fn estimate_gas(&self, tx: &Value) -> Result<u64> {
+        let result = self.call("eth_estimateGas", json!([tx])).await?;
+        parse_quantity(&result).ok_or_else(|| eyre::eyre!("eth_estimateGas returned {}", result))
+    }
+
+    /// Accounts the node can sign for; forks unlock a set of funded test accounts.
+    pub async fn accounts(&self) -> Result<Vec<String>> {
+        let result = self.call("eth_accounts", json!([])).await?;
+        Ok(result.as_array()
+            .map(|accounts| accounts.iter().filter_map(|a| a.as_str().map(str::to_string)).collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Reads a JSON-RPC error as a revert. Geth and Anvil report code 3 with the
+/// data as hex; some nodes nest it one level deeper or only say so in the message.
+fn revert_outcome(error: &Value) -> Option<CallOutcome> {
+    let message = error.get("message").and_then(Value::as_str).unwrap_or("execution reverted").to_string();
+    let reverted = error.get("code").and_then(Value::as_i64) == Some(3) || message.contains("revert");
+    if !reverted {
+        return None;
+    }
+    let data = error.get("data")
+        .and_then(|data| data.as_str().or_else(|| data.get("data").and_then(Value::as_str)))
+        .and_then(|hex| hex::decode(hex.strip_prefix("0x").unwrap_or(hex)).ok());
+    Some(CallOutcome::Reverted { message, data })
+}
+
+/// Parses a `0x` hex quantity such as `0x7a69`.
+pub fn parse_quantity(value: &Value) -> Option<u64> {
+    value.as_str()
+        .and_then(|hex| hex.strip_prefix("0x"))
+        .and_then(|digits| u64::from_str_radix(digits, 16).ok())
+}
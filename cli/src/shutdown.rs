@@ -0,0 +1,69 @@
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Cancellation token shared by long-running commands. Triggered once by
+/// SIGINT (Ctrl+C) or SIGTERM; loops check it at iteration boundaries so an
+/// in-flight iteration always completes before the command exits.
+#[derive(Clone)]
+pub struct Shutdown {
+    receiver: watch::Receiver<bool>,
+}
+
+impl Shutdown {
+    /// Installs the signal handlers and returns a token observing them.
+    pub fn install() -> Self {
+        let (sender, receiver) = watch::channel(false);
+        tokio::spawn(async move {
+            wait_for_signal().await;
+            let _ = sender.send(true);
+        });
+        Self { receiver }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// Sleeps for `duration`, returning early with `true` if shutdown is requested.
+    pub async fn sleep(&mut self, duration: Duration) -> bool {
+        if self.is_triggered() {
+            return true;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => self.is_triggered(),
+            _ = self.receiver.changed() => true,
+        }
+    }
+
+    /// Resolves once shutdown is requested, for `select!` against other waits.
+    pub async fn triggered(&mut self) {
+        if self.is_triggered() {
+            return;
+        }
+        if self.receiver.changed().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -1,22 +1,208 @@
+use dialoguer::{theme::ColorfulTheme, Confirm};
 use eyre::Result;
 use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-#[allow(dead_code)]
-pub fn parse_eth_amount(amount_str: &str) -> Result<f64> {
-    amount_str.parse::<f64>()
-        .map_err(|_| eyre::eyre!("Invalid ETH amount: {}", amount_str))
+use crate::error::VectorPlusError;
+
+pub mod table;
+pub mod timeline;
+
+/// Prints extra detail when `--verbose` is set. It goes to stderr so JSON and
+/// CSV on stdout stay machine-readable.
+macro_rules! verbose {
+    ($enabled:expr, $($arg:tt)*) => {
+        if $enabled {
+            eprintln!("{}", colored::Colorize::dimmed(format!("  ↳ {}", format!($($arg)*)).as_str()));
+        }
+    };
+}
+pub(crate) use verbose;
+
+/// `--output` value that sends a created config to stdout instead of a file.
+pub const STDOUT_PATH: &str = "-";
+
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Marks stdout as carrying only machine-readable output for the rest of the
+/// run, so [`status!`] lines go to stderr instead.
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+pub fn stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// `println!` for progress and summary lines, which move to stderr when
+/// stdout is reserved for a config written with `--output -`.
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if $crate::utils::stdout_reserved() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use status;
+
+/// Asks a yes/no confirmation, answering yes without prompting when
+/// `assume_yes` is set (`--assume-yes`). Only use for confirmations, not for
+/// choices whose answer changes what gets built.
+pub fn confirm(prompt: &str, default: bool, assume_yes: bool) -> Result<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default)
+        .interact()
+        .map_err(|e| eyre::eyre!("{}: {} (use --assume-yes in scripts)", prompt, e))
+}
+
+/// Confirms overwriting `path` if it exists, failing if the user declines.
+pub fn confirm_overwrite(path: &str, assume_yes: bool) -> Result<()> {
+    if std::path::Path::new(path).exists() && !confirm(&format!("{} exists. Overwrite?", path), false, assume_yes)? {
+        return Err(eyre::eyre!("Not overwriting {}", path));
+    }
+    Ok(())
 }
 
+const WEI_DECIMALS: usize = 18;
+
+/// Converts a wei integer string to an exact ETH decimal string, without
+/// trailing zeros (e.g. `"1500000000000000000"` -> `"1.5"`).
 #[allow(dead_code)]
-pub fn format_wei_to_eth(wei_str: &str) -> Result<f64> {
-    let wei: f64 = wei_str.parse()
-        .map_err(|_| eyre::eyre!("Invalid wei amount: {}", wei_str))?;
-    Ok(wei / 1e18)
+pub fn format_wei_to_eth(wei_str: &str) -> Result<String> {
+    format_units(wei_str, WEI_DECIMALS)
+        .map_err(|_| eyre::eyre!("Invalid wei amount: {}", wei_str))
 }
 
+/// Converts an integer string of base units to an exact decimal string with
+/// `decimals` decimals, without trailing zeros (e.g. `"1500000"`, 6 -> `"1.5"`).
+pub fn format_units(units_str: &str, decimals: usize) -> Result<String> {
+    if units_str.is_empty() || !units_str.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(eyre::eyre!("Invalid amount: {}", units_str));
+    }
+
+    let digits = units_str.trim_start_matches('0');
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        Ok(whole.to_string())
+    } else {
+        Ok(format!("{}.{}", whole, fraction))
+    }
+}
+
+/// Converts an ETH decimal string with at most 18 decimals to an exact wei
+/// integer string (e.g. `"1.5"` -> `"1500000000000000000"`).
 #[allow(dead_code)]
-pub fn format_eth_to_wei(eth: f64) -> String {
-    format!("{:.0}", eth * 1e18)
+pub fn parse_eth_to_wei(eth_str: &str) -> Result<String> {
+    parse_base_units(eth_str, WEI_DECIMALS)
+}
+
+/// Converts a decimal string with at most `decimals` decimals to an exact
+/// integer string in the token's smallest unit.
+pub fn parse_base_units(amount_str: &str, decimals: usize) -> Result<String> {
+    let invalid = || eyre::eyre!("Invalid amount: {}", amount_str);
+
+    let (whole, fraction) = amount_str.split_once('.').unwrap_or((amount_str, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if fraction.len() > decimals {
+        return Err(eyre::eyre!("Amount has more than {} decimals: {}", decimals, amount_str));
+    }
+
+    let units = format!("{}{:0<width$}", whole, fraction, width = decimals);
+    let units = units.trim_start_matches('0');
+    Ok(if units.is_empty() { "0".to_string() } else { units.to_string() })
+}
+
+/// Formats an ETH or quote-asset amount for display with `precision` decimals.
+pub fn format_amount(amount: f64, precision: u8) -> String {
+    format!("{:.*}", precision as usize, amount)
+}
+
+/// How displayed timestamps are written, chosen with the global `--time-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum TimeFormat {
+    /// ISO 8601 in UTC, e.g. 2025-01-01T00:00:00Z
+    Rfc3339,
+    /// Seconds since the Unix epoch
+    Unix,
+    /// Distance from now, e.g. "3h ago" or "in 2d"
+    #[default]
+    Relative,
+}
+
+/// Formats a Unix timestamp in seconds for display.
+pub fn format_time(ts: u64, format: TimeFormat) -> String {
+    format_time_at(ts, format, chrono::Utc::now().timestamp() as u64)
+}
+
+/// [`format_time`] as seen at `now`, so relative times are testable.
+pub fn format_time_at(ts: u64, format: TimeFormat, now: u64) -> String {
+    match format {
+        TimeFormat::Unix => ts.to_string(),
+        TimeFormat::Rfc3339 => i64::try_from(ts)
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_else(|| ts.to_string()),
+        TimeFormat::Relative => {
+            let span = ts.abs_diff(now);
+            let span = match span {
+                0 => return "now".to_string(),
+                1..=59 => format!("{}s", span),
+                60..=3599 => format!("{}m", span / 60),
+                3600..=86_399 => format!("{}h", span / 3600),
+                _ => format!("{}d", span / 86_400),
+            };
+            if ts < now { format!("{} ago", span) } else { format!("in {}", span) }
+        }
+    }
+}
+
+/// Clap value parser for float arguments, rejecting NaN and infinities.
+pub fn parse_finite_f64(text: &str) -> std::result::Result<f64, String> {
+    let value: f64 = text.trim().parse().map_err(|_| format!("`{}` is not a number", text))?;
+    if !value.is_finite() {
+        return Err(format!("`{}` must be a finite number", text));
+    }
+    Ok(value)
+}
+
+/// Clap value parser for prices, sizes and other amounts that must be above zero.
+pub fn parse_positive_f64(text: &str) -> std::result::Result<f64, String> {
+    match parse_finite_f64(text)? {
+        value if value > 0.0 => Ok(value),
+        _ => Err(format!("`{}` must be greater than zero", text)),
+    }
+}
+
+/// Clap value parser for ETH amounts that must be above zero, such as an order to split.
+pub fn parse_positive_amount(text: &str) -> std::result::Result<crate::amount::Amount, String> {
+    match text.parse::<crate::amount::Amount>()? {
+        amount if amount.is_zero() => Err(format!("`{}` must be greater than zero", text)),
+        amount => Ok(amount),
+    }
+}
+
+/// Clap value parser for amounts that may be zero but not negative, such as a premium or gas price.
+pub fn parse_non_negative_f64(text: &str) -> std::result::Result<f64, String> {
+    match parse_finite_f64(text)? {
+        value if value >= 0.0 => Ok(value),
+        _ => Err(format!("`{}` must not be negative", text)),
+    }
 }
 
 #[allow(dead_code)]
@@ -27,16 +213,430 @@ pub fn ensure_file_exists(path: &str) -> Result<()> {
     Ok(())
 }
 
-#[allow(dead_code)]
 pub fn write_json_file<T: serde::Serialize>(path: &str, data: &T) -> Result<()> {
     let json = serde_json::to_string_pretty(data)?;
     fs::write(path, json)?;
     Ok(())
 }
 
-#[allow(dead_code)]
+/// Reads and parses a JSON config, pointing at the offending line on a
+/// parse error and suggesting how to recover.
+pub fn read_config<T: serde::de::DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => VectorPlusError::FileNotFound(path.display().to_string()).into(),
+        _ => eyre::eyre!("Could not read file: {}", path.display()),
+    })?;
+    serde_json::from_str(&content).map_err(|e| {
+        let path = path.display().to_string();
+        json_error(&path, &content, &e, &format!("vector-plus strategy inspect {}", path))
+    })
+}
+
+/// Keys in a JSON config that serde accepts silently: fields `T` does not
+/// know, which it ignores, and keys repeated within a map or an unknown
+/// object, where the last value wins (a repeated struct field is already a
+/// parse error). Each finding names the key by its dotted path.
+pub fn check_config_keys<T: serde::de::DeserializeOwned>(path: &str) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path).map_err(|_| eyre::eyre!("Could not read file: {}", path))?;
+
+    let mut duplicates = Vec::new();
+    serde::de::DeserializeSeed::deserialize(
+        DuplicateKeys { path: String::new(), found: &mut duplicates },
+        &mut serde_json::Deserializer::from_str(&content),
+    )?;
+    let mut unknown = Vec::new();
+    serde_ignored::deserialize::<_, _, T>(&mut serde_json::Deserializer::from_str(&content), |field| {
+        unknown.push(field.to_string())
+    })?;
+
+    Ok(unknown.iter().map(|field| format!("Unknown field `{}`", field))
+        .chain(duplicates.iter().map(|field| format!("Duplicate key `{}` (the last value wins)", field)))
+        .collect())
+}
+
+/// Adds [`check_config_keys`] findings to a validator's warnings, or to its
+/// errors under `--strict`. A file the key check cannot read becomes an
+/// error, so the validator's other findings are still reported.
+pub fn add_key_findings<T: serde::de::DeserializeOwned>(
+    path: &str,
+    strict: bool,
+    warnings: &mut Vec<String>,
+    errors: &mut Vec<String>,
+) {
+    let findings = match check_config_keys::<T>(path) {
+        Ok(findings) => findings,
+        Err(e) => {
+            errors.push(format!("❌ Could not check keys: {}", e));
+            return;
+        }
+    };
+    for finding in findings {
+        match strict {
+            true => errors.push(format!("❌ {}", finding)),
+            false => warnings.push(format!("⚠️  {}", finding)),
+        }
+    }
+}
+
+/// Walks a JSON document, collecting the paths of keys repeated within one object.
+struct DuplicateKeys<'a> {
+    path: String,
+    found: &'a mut Vec<String>,
+}
+
+impl DuplicateKeys<'_> {
+    fn child(&mut self, key: &str) -> DuplicateKeys<'_> {
+        let path = if self.path.is_empty() { key.to_string() } else { format!("{}.{}", self.path, key) };
+        DuplicateKeys { path, found: self.found }
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for DuplicateKeys<'_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for DuplicateKeys<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> { Ok(()) }
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> { Ok(()) }
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> { Ok(()) }
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> { Ok(()) }
+    fn visit_str<E>(self, _: &str) -> Result<(), E> { Ok(()) }
+    fn visit_unit<E>(self) -> Result<(), E> { Ok(()) }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0;
+        while seq.next_element_seed(self.child(&index.to_string()))?.is_some() {
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        let mut seen = std::collections::HashSet::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let child = self.child(&key);
+            if !seen.insert(key) {
+                child.found.push(child.path.clone());
+            }
+            map.next_value_seed(child)?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes a JSON error in `path` with its line and column, the offending
+/// line, and recovery steps: `check_command` and, if a backup exists, `config restore`.
+pub fn json_error(path: &str, content: &str, error: &serde_json::Error, check_command: &str) -> eyre::Report {
+    let mut message = format!("Invalid JSON format in {}", path);
+    if error.line() > 0 {
+        message.push_str(&format!(" at line {}, column {}", error.line(), error.column()));
+    }
+    // serde_json appends its own position; it is already in the message
+    let reason = error.to_string();
+    let reason = reason.split(" at line ").next().unwrap_or(&reason);
+    message.push_str(&format!(": {}", reason));
+
+    if let Some(line) = content.lines().nth(error.line().saturating_sub(1)).filter(|_| error.line() > 0) {
+        // Keep long lines readable by showing a window around the column
+        const WINDOW: usize = 60;
+        let column = error.column().saturating_sub(1);
+        let chars: Vec<char> = line.chars().collect();
+        let start = column.saturating_sub(WINDOW / 2).min(chars.len());
+        let end = (start + WINDOW).min(chars.len());
+        let prefix = if start > 0 { "..." } else { "" };
+        let snippet: String = chars[start..end].iter().collect();
+        let gutter = error.line().to_string();
+        message.push_str(&format!(
+            "\n  {} | {}{}\n  {} | {}^",
+            gutter, prefix, snippet,
+            " ".repeat(gutter.len()), " ".repeat(prefix.len() + column - start)
+        ));
+    }
+
+    message.push_str(&format!("\n  Fix the file and check it with `{}`", check_command));
+    if Path::new(&backup_path(path)).exists() {
+        message.push_str(&format!(", or run `vector-plus config restore {}` to swap its backup back in", path));
+    }
+    eyre::eyre!(message)
+}
+
+/// Files matching `pattern`, sorted. `*` and `?` match within the file name;
+/// the directory part is taken literally, e.g. `configs/*.json`.
+pub fn glob_files(pattern: &str) -> Result<Vec<String>> {
+    let path = Path::new(pattern);
+    let name = path.file_name().and_then(|name| name.to_str())
+        .ok_or_else(|| eyre::eyre!("Invalid glob pattern: {}", pattern))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut files: Vec<String> = fs::read_dir(dir)
+        .map_err(|_| eyre::eyre!("Could not read directory: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|file| file.is_file())
+        .filter(|file| file.file_name().and_then(|n| n.to_str()).is_some_and(|n| wildcard_match(name, n)))
+        .map(|file| match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => file.display().to_string(),
+            _ => file.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters
+/// and `?` exactly one.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    // Where the last `*` was, and how much of the name it has swallowed
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Where [`backup_existing`] keeps the previous version of `path`.
+pub fn backup_path(path: &str) -> String {
+    format!("{}.bak", path)
+}
+
+/// Copies `path` to its `.bak` before it is overwritten, returning the backup
+/// path, or `None` when there is nothing to back up.
+pub fn backup_existing(path: &str) -> Result<Option<String>> {
+    if !Path::new(path).is_file() {
+        return Ok(None);
+    }
+    let backup = backup_path(path);
+    fs::copy(path, &backup).map_err(|e| eyre::eyre!("Could not back up {} to {}: {}", path, backup, e))?;
+    Ok(Some(backup))
+}
+
+/// How messages name a create command's `--output`.
+pub fn output_name(output: &str) -> &str {
+    if output == STDOUT_PATH { "stdout" } else { output }
+}
+
+/// Writes a created config as pretty JSON to `output`, or to stdout when it
+/// is [`STDOUT_PATH`], and records it in the history log. A replaced file is
+/// backed up first.
+pub fn write_created_config<T: serde::Serialize>(output: &str, config: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(config)?;
+    if output == STDOUT_PATH {
+        println!("{}", json);
+    } else {
+        if let Some(backup) = backup_existing(output)? {
+            status!("  • Kept the previous {} as {}", output, backup);
+        }
+        fs::write(output, json)?;
+    }
+    crate::history::record(output, config);
+    Ok(())
+}
+
 pub fn read_json_file<T: serde::de::DeserializeOwned>(path: &str) -> Result<T> {
     let content = fs::read_to_string(path)?;
     let data = serde_json::from_str(&content)?;
     Ok(data)
-}
\ No newline at end of file
+}
+
+/// Writes JSON via a temporary file and rename, so readers never observe a
+/// partially written file even if the process is interrupted mid-write.
+pub fn write_json_file_atomic<T: serde::Serialize>(path: &str, data: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(data)?;
+    let tmp = format!("{}.tmp", path);
+    fs::write(&tmp, json)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_parsers_reject_nonsense() {
+        assert_eq!(parse_finite_f64("-1.5"), Ok(-1.5));
+        assert_eq!(parse_positive_f64("2500"), Ok(2500.0));
+        assert_eq!(parse_non_negative_f64("0"), Ok(0.0));
+        for text in ["NaN", "nan", "inf", "-infinity", "1e999", "abc", ""] {
+            assert!(parse_finite_f64(text).is_err(), "{}", text);
+        }
+        assert!(parse_positive_f64("0").is_err());
+        assert!(parse_positive_f64("-2").unwrap_err().contains("greater than zero"));
+        assert!(parse_non_negative_f64("-0.01").unwrap_err().contains("must not be negative"));
+    }
+
+    #[test]
+    fn matches_wildcards_in_file_names() {
+        assert!(wildcard_match("*.json", "twap-config.json"));
+        assert!(wildcard_match("vol*-config.json", "volatility-config.json"));
+        assert!(wildcard_match("option-?.json", "option-1.json"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("*.json", "twap-config.json.bak"));
+        assert!(!wildcard_match("option-?.json", "option-10.json"));
+        assert!(!wildcard_match("a*b*c", "abca-b"));
+    }
+
+    #[test]
+    fn finds_unknown_fields_and_duplicate_keys() {
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Inner {
+            size: u64,
+        }
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Config {
+            name: String,
+            inner: Inner,
+            labels: std::collections::BTreeMap<String, u64>,
+        }
+
+        let path = std::env::temp_dir().join(format!("vector-plus-keys-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, r#"{"name":"a","inner":{"size":1,"sise":2},"labels":{"a":1,"a":2},"extra":[{"x":1,"x":2}]}"#).unwrap();
+        let findings = check_config_keys::<Config>(path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(findings, vec![
+            "Unknown field `inner.sise`",
+            "Unknown field `extra`",
+            "Duplicate key `labels.a` (the last value wins)",
+            "Duplicate key `extra.0.x` (the last value wins)",
+        ]);
+    }
+    use proptest::prelude::*;
+
+    #[test]
+    fn created_configs_replace_files_with_a_backup() {
+        let path = std::env::temp_dir().join(format!("vector-plus-created-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        write_created_config(path, &serde_json::json!({ "intervals": 4 })).unwrap();
+        write_created_config(path, &serde_json::json!({ "intervals": 8 })).unwrap();
+        let backup = backup_path(path);
+        assert_eq!(read_json_file::<serde_json::Value>(path).unwrap()["intervals"], 8);
+        assert_eq!(read_json_file::<serde_json::Value>(&backup).unwrap()["intervals"], 4);
+        fs::remove_file(path).unwrap();
+        fs::remove_file(&backup).unwrap();
+
+        assert_eq!(output_name(STDOUT_PATH), "stdout");
+        assert_eq!(output_name("twap.json"), "twap.json");
+    }
+
+    #[test]
+    fn json_errors_point_at_the_offending_line() {
+        let content = "{\n  \"baseline_volatility\": 300,\n  \"current_volatility\": 35 0\n}";
+        let error = serde_json::from_str::<serde_json::Value>(content).unwrap_err();
+        let message = json_error("vol.json", content, &error, "vector-plus strategy inspect vol.json").to_string();
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines[0], "Invalid JSON format in vol.json at line 3, column 28: expected `,` or `}`");
+        assert_eq!(lines[1], "  3 |   \"current_volatility\": 35 0");
+        assert_eq!(lines[2], format!("    | {}^", " ".repeat(27)));
+        assert!(lines[3].contains("vector-plus strategy inspect vol.json"));
+    }
+
+    #[test]
+    fn round_trips_edge_cases() {
+        for (wei, eth) in [
+            ("0", "0"),
+            ("1", "0.000000000000000001"),
+            ("1000000000000000000", "1"),
+            ("1500000000000000000", "1.5"),
+            ("100000000000000000000", "100"),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+            ),
+        ] {
+            assert_eq!(format_wei_to_eth(wei).unwrap(), eth);
+            assert_eq!(parse_eth_to_wei(eth).unwrap(), wei);
+        }
+    }
+
+    #[test]
+    fn normalizes_zeros() {
+        assert_eq!(format_wei_to_eth("000").unwrap(), "0");
+        assert_eq!(parse_eth_to_wei("0.0").unwrap(), "0");
+        assert_eq!(parse_eth_to_wei("2.500").unwrap(), "2500000000000000000");
+        assert_eq!(parse_eth_to_wei(".5").unwrap(), "500000000000000000");
+        assert_eq!(parse_eth_to_wei("007.").unwrap(), "7000000000000000000");
+    }
+
+    #[test]
+    fn rejects_malformed_amounts() {
+        for input in ["", ".", "-1", "1e18", "1.2.3", "0.0000000000000000001", " 1"] {
+            assert!(parse_eth_to_wei(input).is_err(), "accepted {:?}", input);
+        }
+        for input in ["", "-1", "1.0", "0x10"] {
+            assert!(format_wei_to_eth(input).is_err(), "accepted {:?}", input);
+        }
+    }
+
+    #[test]
+    fn formats_times_in_each_style() {
+        let now = 1_735_689_600; // 2025-01-01T00:00:00Z
+        assert_eq!(format_time_at(now, TimeFormat::Rfc3339, 0), "2025-01-01T00:00:00Z");
+        assert_eq!(format_time_at(now, TimeFormat::Unix, 0), "1735689600");
+        assert_eq!(format_time_at(now, TimeFormat::Relative, now), "now");
+        assert_eq!(format_time_at(now - 3 * 3600 - 59, TimeFormat::Relative, now), "3h ago");
+        assert_eq!(format_time_at(now + 2 * 86_400, TimeFormat::Relative, now), "in 2d");
+        assert_eq!(format_time_at(now + 90, TimeFormat::Relative, now), "in 1m");
+        assert_eq!(format_time_at(now - 5, TimeFormat::Relative, now), "5s ago");
+        assert_eq!(format_time_at(u64::MAX, TimeFormat::Rfc3339, now), u64::MAX.to_string());
+    }
+
+    proptest! {
+        #[test]
+        fn wei_round_trips_through_eth(wei in "[1-9][0-9]{0,77}|0") {
+            let eth = format_wei_to_eth(&wei).unwrap();
+            prop_assert_eq!(parse_eth_to_wei(&eth).unwrap(), wei);
+        }
+
+        #[test]
+        fn eth_round_trips_through_wei(whole in "[1-9][0-9]{0,59}|0", fraction in "[0-9]{0,17}[1-9]") {
+            let eth = format!("{}.{}", whole, fraction);
+            let wei = parse_eth_to_wei(&eth).unwrap();
+            prop_assert_eq!(format_wei_to_eth(&wei).unwrap(), eth);
+        }
+
+        #[test]
+        fn trailing_zeros_do_not_change_wei(
+            whole in "[1-9][0-9]{0,59}|0",
+            fraction in "[0-9]{0,9}",
+            zeros in 0usize..=9,
+        ) {
+            let eth = format!("{}.{}", whole, fraction);
+            let padded = format!("{}{}", eth, "0".repeat(zeros));
+            prop_assert_eq!(parse_eth_to_wei(&padded).unwrap(), parse_eth_to_wei(&eth).unwrap());
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use colored::*;
+use serde_json::{Map, Value};
+
+/// Output of tabular commands, chosen with `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned text with the command's headings and summary
+    Table,
+    /// Rows only, as a JSON array of objects
+    Json,
+    /// Rows only, with a header row of field names
+    Csv,
+}
+
+impl OutputFormat {
+    /// The `--format` a command was given, or the one its older `--json`
+    /// and `--csv` shorthands select.
+    pub fn resolve(format: Option<Self>, json: bool, csv: bool) -> Self {
+        match format {
+            Some(format) => format,
+            None if json => OutputFormat::Json,
+            None if csv => OutputFormat::Csv,
+            None => OutputFormat::Table,
+        }
+    }
+
+    pub fn is_machine(self) -> bool {
+        self != OutputFormat::Table
+    }
+}
+
+/// Horizontal alignment of a column in text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+struct Column {
+    /// Field name in JSON and CSV output
+    key: &'static str,
+    /// Heading in text output
+    header: String,
+    align: Align,
+}
+
+/// One table cell: the text shown in a terminal and the raw value used for
+/// JSON and CSV, so machine output never carries display rounding.
+pub struct Cell {
+    text: String,
+    value: Value,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self { text: text.into(), value: value.into() }
+    }
+
+    /// A cell whose raw value is its text.
+    pub fn text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self { value: Value::String(text.clone()), text }
+    }
+}
+
+struct Row {
+    cells: Vec<Cell>,
+    color: Option<Color>,
+}
+
+/// Tabular output shared by commands that print rows: aligned text for
+/// terminals, an array of objects for JSON and a header row plus values for CSV.
+#[derive(Default)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn column(mut self, key: &'static str, header: impl Into<String>, align: Align) -> Self {
+        self.columns.push(Column { key, header: header.into(), align });
+        self
+    }
+
+    /// Appends a row; it must have one cell per column.
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        debug_assert_eq!(cells.len(), self.columns.len());
+        self.rows.push(Row { cells, color: None });
+    }
+
+    /// Appends a row drawn in `color` in text output, e.g. to highlight it.
+    pub fn colored_row(&mut self, cells: Vec<Cell>, color: Color) {
+        debug_assert_eq!(cells.len(), self.columns.len());
+        self.rows.push(Row { cells, color: Some(color) });
+    }
+
+    pub fn render(&self) -> String {
+        let widths: Vec<usize> = self.columns.iter().enumerate()
+            .map(|(i, column)| {
+                self.rows.iter()
+                    .map(|row| row.cells[i].text.chars().count())
+                    .chain([column.header.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+        let header: Vec<String> = self.columns.iter().zip(&widths)
+            .map(|(column, &width)| pad(&column.header, width, column.align).bold().to_string())
+            .collect();
+        lines.push(header.join("  "));
+        lines.push(widths.iter().map(|&width| "─".repeat(width)).collect::<Vec<_>>().join("  "));
+
+        for row in &self.rows {
+            let cells: Vec<String> = row.cells.iter().zip(self.columns.iter().zip(&widths))
+                .map(|(cell, (column, &width))| {
+                    let padded = pad(&cell.text, width, column.align);
+                    match row.color {
+                        Some(color) => padded.color(color).to_string(),
+                        None => padded,
+                    }
+                })
+                .collect();
+            lines.push(cells.join("  "));
+        }
+        lines.join("\n")
+    }
+
+    /// The rows in `format`; only `Table` output carries colors.
+    pub fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Table => self.render(),
+            OutputFormat::Json => serde_json::to_string_pretty(&self.to_json()).expect("table cells serialize"),
+            OutputFormat::Csv => self.to_csv(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        Value::Array(
+            self.rows.iter()
+                .map(|row| {
+                    let object: Map<String, Value> = self.columns.iter().zip(&row.cells)
+                        .map(|(column, cell)| (column.key.to_string(), cell.value.clone()))
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut lines = Vec::with_capacity(self.rows.len() + 1);
+        lines.push(self.columns.iter().map(|column| column.key).collect::<Vec<_>>().join(","));
+        for row in &self.rows {
+            lines.push(row.cells.iter().map(|cell| csv_field(&cell.value)).collect::<Vec<_>>().join(","));
+        }
+        lines.join("\n")
+    }
+}
+
+fn pad(text: &str, width: usize, align: Align) -> String {
+    match align {
+        Align::Left => format!("{:<width$}", text),
+        Align::Right => format!("{:>width$}", text),
+    }
+}
+
+fn csv_field(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        // Shortest round-trip form, so 1900.0 prints as 1900
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() => f.to_string(),
+            _ => n.to_string(),
+        },
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Table {
+        let mut table = Table::new()
+            .column("asset", "Asset", Align::Left)
+            .column("amount", "Amount", Align::Right);
+        table.row(vec![Cell::text("ETH"), Cell::new("2.50", 2.5)]);
+        table.row(vec![Cell::text("WBTC, wrapped"), Cell::new("10.00", 10.0)]);
+        table
+    }
+
+    #[test]
+    fn renders_aligned_text() {
+        colored::control::set_override(false);
+        let text = sample().render();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Asset          Amount");
+        assert_eq!(lines[2], "ETH              2.50");
+        assert_eq!(lines[3], "WBTC, wrapped   10.00");
+    }
+
+    #[test]
+    fn renders_raw_values_for_machines() {
+        let table = sample();
+        assert_eq!(table.to_json()[0], serde_json::json!({ "asset": "ETH", "amount": 2.5 }));
+        assert_eq!(table.to_csv(), "asset,amount\nETH,2.5\n\"WBTC, wrapped\",10");
+
+        let mut quoted = Table::new().column("name", "Name", Align::Left);
+        quoted.row(vec![Cell::text("say \"hi\"\nbye")]);
+        assert_eq!(quoted.format(OutputFormat::Csv), "name\n\"say \"\"hi\"\"\nbye\"");
+        assert_eq!(OutputFormat::resolve(None, true, false), OutputFormat::Json);
+        assert_eq!(OutputFormat::resolve(Some(OutputFormat::Csv), false, false), OutputFormat::Csv);
+        assert_eq!(OutputFormat::resolve(None, false, false), OutputFormat::Table);
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::IsTerminal;
+
+/// Narrowest bar area drawn, however narrow the terminal.
+const MIN_TRACK: usize = 10;
+/// Width assumed when a terminal does not report its own.
+const DEFAULT_WIDTH: usize = 80;
+
+/// What happened in one interval of a schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// A fill, drawn with a bar proportional to its size
+    Fill,
+    /// The schedule waited out this interval
+    Paused,
+    /// The interval had no fill, e.g. an empty chunk
+    Skipped,
+    /// The order was already filled
+    Idle,
+}
+
+impl Mark {
+    fn symbol(self) -> char {
+        match self {
+            Mark::Fill => '█',
+            Mark::Paused => '~',
+            Mark::Skipped => 'x',
+            Mark::Idle => ' ',
+        }
+    }
+
+    fn note(self) -> &'static str {
+        match self {
+            Mark::Fill => "",
+            Mark::Paused => " paused",
+            Mark::Skipped => " skipped",
+            Mark::Idle => " done",
+        }
+    }
+}
+
+struct Span {
+    label: String,
+    start_secs: u64,
+    end_secs: u64,
+    size: f64,
+    size_text: String,
+    mark: Mark,
+}
+
+/// Gantt-style chart of a simulated schedule: one row per interval, placed
+/// on a shared time axis, with each fill's bar proportional to its size.
+#[derive(Default)]
+pub struct Timeline {
+    spans: Vec<Span>,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an interval running from `start_secs` to `end_secs`.
+    pub fn span(&mut self, label: impl Into<String>, start_secs: u64, end_secs: u64, size: f64, size_text: impl Into<String>, mark: Mark) {
+        self.spans.push(Span { label: label.into(), start_secs, end_secs, size, size_text: size_text.into(), mark });
+    }
+
+    /// The chart scaled to the terminal's width, or a plain list when stdout
+    /// is not a terminal, e.g. when piped to a file.
+    pub fn render_for_stdout(&self) -> String {
+        if !std::io::stdout().is_terminal() {
+            return self.render_list();
+        }
+        let width = dialoguer::console::Term::stdout()
+            .size_checked()
+            .map_or(DEFAULT_WIDTH, |(_, columns)| columns as usize);
+        self.render(width)
+    }
+
+    /// The chart fitted to `width` columns where it can be.
+    pub fn render(&self, width: usize) -> String {
+        let label_width = self.spans.iter().map(|span| span.label.chars().count()).max().unwrap_or(0);
+        let suffix_width = self.spans.iter()
+            .map(|span| span.size_text.chars().count() + span.mark.note().len())
+            .max()
+            .unwrap_or(0);
+        // "label │track│ size note"
+        let track = width.saturating_sub(label_width + suffix_width + 4).max(MIN_TRACK);
+        let total = self.spans.iter().map(|span| span.end_secs).max().unwrap_or(0).max(1);
+        let largest = self.spans.iter()
+            .filter(|span| span.mark == Mark::Fill)
+            .map(|span| span.size)
+            .fold(0.0, f64::max);
+        let column = |secs: u64| (secs as u128 * track as u128 / total as u128) as usize;
+
+        let mut lines = Vec::with_capacity(self.spans.len() + 2);
+        for span in &self.spans {
+            let start = column(span.start_secs).min(track - 1);
+            let end = column(span.end_secs).clamp(start + 1, track);
+            let window = end - start;
+            let filled = match span.mark {
+                Mark::Fill if largest > 0.0 && span.size > 0.0 => {
+                    ((window as f64 * span.size / largest).round() as usize).clamp(1, window)
+                }
+                Mark::Fill => 0,
+                Mark::Skipped => 1,
+                Mark::Paused => window,
+                Mark::Idle => 0,
+            };
+            let mut bar = " ".repeat(start);
+            bar.extend(std::iter::repeat_n(span.mark.symbol(), filled));
+            bar.extend(std::iter::repeat_n('·', window - filled));
+            bar.push_str(&" ".repeat(track - end));
+            lines.push(format!("{:>label_width$} │{}│ {}{}", span.label, bar, span.size_text, span.mark.note()));
+        }
+
+        let end_label = format!("+{}s", total);
+        lines.push(format!(
+            "{:>label_width$}  +0s{:>pad$}",
+            "",
+            end_label,
+            pad = (track + 1).saturating_sub(3),
+        ));
+        let mut legend = vec!["█ fill, length by size", "· rest of interval"];
+        for (mark, text) in [(Mark::Paused, "~ paused"), (Mark::Skipped, "x skipped")] {
+            if self.spans.iter().any(|span| span.mark == mark) {
+                legend.push(text);
+            }
+        }
+        lines.push(format!("{:>label_width$}  {}", "", legend.join("  ")));
+        lines.join("\n")
+    }
+
+    /// One line per interval, for output that is not a terminal.
+    pub fn render_list(&self) -> String {
+        self.spans.iter()
+            .map(|span| {
+                format!("  {}: +{}s to +{}s, {}{}", span.label, span.start_secs, span.end_secs, span.size_text, span.mark.note())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Timeline {
+        let mut timeline = Timeline::new();
+        timeline.span("1", 0, 60, 2.0, "2.0 ETH", Mark::Fill);
+        timeline.span("2", 60, 120, 0.0, "0.0 ETH", Mark::Paused);
+        timeline.span("3", 120, 180, 1.0, "1.0 ETH", Mark::Fill);
+        timeline.span("4", 180, 240, 0.0, "0.0 ETH", Mark::Skipped);
+        timeline
+    }
+
+    #[test]
+    fn scales_bars_to_the_width_and_chunk_size() {
+        let text = sample().render(40);
+        let lines: Vec<&str> = text.lines().collect();
+        // 40 columns leave a 20-column track: 5 per interval
+        assert_eq!(lines[0], "1 │█████               │ 2.0 ETH");
+        assert_eq!(lines[1], "2 │     ~~~~~          │ 0.0 ETH paused");
+        assert_eq!(lines[2], "3 │          ███··     │ 1.0 ETH");
+        assert_eq!(lines[3], "4 │               x····│ 0.0 ETH skipped");
+        assert_eq!(lines[4], "   +0s             +240s");
+        assert!(lines[5].contains("~ paused") && lines[5].contains("x skipped"));
+        // Too narrow a terminal still gets the minimum track
+        assert_eq!(sample().render(0).lines().next().unwrap().chars().count(), 3 + MIN_TRACK + 9);
+    }
+
+    #[test]
+    fn lists_intervals_without_a_terminal() {
+        let text = sample().render_list();
+        assert_eq!(text.lines().nth(1), Some("  2: +60s to +120s, 0.0 ETH paused"));
+    }
+}